@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+pub trait TensorAllocator: Send + Sync {
+    fn allocate(&self, len: usize) -> Vec<f32>;
+
+    fn release(&self, buffer: Vec<f32>);
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemAllocator;
+
+impl TensorAllocator for SystemAllocator {
+    fn allocate(&self, len: usize) -> Vec<f32> {
+        return Vec::with_capacity(len);
+    }
+
+    fn release(&self, _buffer: Vec<f32>) {}
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PoolStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub cached_buffers: usize,
+    pub cached_bytes: usize,
+}
+
+#[derive(Debug, Default)]
+struct PoolState {
+    buckets: HashMap<usize, Vec<Vec<f32>>>,
+    stats: PoolStats,
+}
+
+#[derive(Debug)]
+pub struct PoolAllocator {
+    max_per_size: usize,
+    state: Mutex<PoolState>,
+}
+
+impl PoolAllocator {
+    pub fn new(max_per_size: usize) -> Self {
+        return PoolAllocator {
+            max_per_size: max_per_size,
+            state: Mutex::new(PoolState::default()),
+        };
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        return self.state.lock().unwrap().stats;
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.buckets.clear();
+        state.stats.cached_buffers = 0;
+        state.stats.cached_bytes = 0;
+    }
+}
+
+impl TensorAllocator for PoolAllocator {
+    fn allocate(&self, len: usize) -> Vec<f32> {
+        let mut guard = self.state.lock().unwrap();
+        let PoolState { buckets, stats } = &mut *guard;
+        if let Some(buffer) = buckets.get_mut(&len).and_then(|bucket| bucket.pop()) {
+            stats.hits += 1;
+            stats.cached_buffers -= 1;
+            stats.cached_bytes -= len * size_of::<f32>();
+            return buffer;
+        }
+
+        stats.misses += 1;
+        return Vec::with_capacity(len);
+    }
+
+    fn release(&self, mut buffer: Vec<f32>) {
+        let capacity = buffer.capacity();
+        if capacity == 0 {
+            return;
+        }
+
+        let mut guard = self.state.lock().unwrap();
+        let PoolState { buckets, stats } = &mut *guard;
+        let bucket = buckets.entry(capacity).or_default();
+        if bucket.len() < self.max_per_size {
+            buffer.clear();
+            bucket.push(buffer);
+            stats.cached_buffers += 1;
+            stats.cached_bytes += capacity * size_of::<f32>();
+        }
+    }
+}
+
+static CUSTOM: AtomicBool = AtomicBool::new(false);
+static ALLOCATOR: RwLock<Option<Arc<dyn TensorAllocator>>> = RwLock::new(None);
+
+pub fn set_allocator(allocator: Arc<dyn TensorAllocator>) {
+    *ALLOCATOR.write().unwrap() = Some(allocator);
+    CUSTOM.store(true, Ordering::Release);
+}
+
+pub fn reset_allocator() {
+    CUSTOM.store(false, Ordering::Release);
+    *ALLOCATOR.write().unwrap() = None;
+}
+
+pub fn allocate(len: usize) -> Vec<f32> {
+    crate::profile::record_alloc(len * size_of::<f32>());
+    if CUSTOM.load(Ordering::Acquire)
+        && let Some(allocator) = ALLOCATOR.read().unwrap().as_ref()
+    {
+        return allocator.allocate(len);
+    }
+
+    return Vec::with_capacity(len);
+}
+
+pub fn release(buffer: Vec<f32>) {
+    if CUSTOM.load(Ordering::Acquire)
+        && let Some(allocator) = ALLOCATOR.read().unwrap().as_ref()
+    {
+        allocator.release(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 只测试独立的分配器实例，全局分配器由其他测试并行共享
+    #[test]
+    fn pool_reuses_buffers_by_capacity() {
+        let pool = PoolAllocator::new(2);
+        let mut a = pool.allocate(16);
+        assert!(a.capacity() >= 16);
+        a.extend([1.0; 16]);
+        let capacity = a.capacity();
+        let ptr = a.as_ptr();
+        pool.release(a);
+        assert_eq!(pool.stats().cached_buffers, 1);
+        assert_eq!(pool.stats().cached_bytes, capacity * 4);
+
+        let b = pool.allocate(capacity);
+        assert_eq!(b.as_ptr(), ptr);
+        assert!(b.is_empty());
+        assert_eq!(
+            pool.stats(),
+            PoolStats {
+                hits: 1,
+                misses: 1,
+                cached_buffers: 0,
+                cached_bytes: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn pool_respects_limits_and_clear() {
+        let pool = PoolAllocator::new(1);
+        pool.release(Vec::with_capacity(8));
+        pool.release(Vec::with_capacity(8));
+        pool.release(Vec::new());
+        assert_eq!(pool.stats().cached_buffers, 1);
+
+        pool.clear();
+        assert_eq!(pool.stats().cached_bytes, 0);
+        pool.allocate(8);
+        pool.allocate(0);
+        assert_eq!(pool.stats().misses, 2);
+        assert_eq!(pool.stats().hits, 0);
+
+        let disabled = PoolAllocator::new(0);
+        disabled.release(Vec::with_capacity(4));
+        assert_eq!(disabled.stats().cached_buffers, 0);
+    }
+
+    #[test]
+    fn system_allocator_is_plain_vec() {
+        let buffer = SystemAllocator.allocate(5);
+        assert!(buffer.is_empty() && buffer.capacity() >= 5);
+        SystemAllocator.release(buffer);
+    }
+}
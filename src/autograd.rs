@@ -0,0 +1,809 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::Tensor;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static GRAD_ENABLED: Cell<bool> = const { Cell::new(true) };
+}
+
+pub struct NoGradGuard {
+    previous: bool,
+}
+
+pub fn no_grad() -> NoGradGuard {
+    let previous = GRAD_ENABLED.with(|e| e.replace(false));
+
+    return NoGradGuard { previous: previous };
+}
+
+pub fn is_grad_enabled() -> bool {
+    return GRAD_ENABLED.with(|e| e.get());
+}
+
+impl Drop for NoGradGuard {
+    fn drop(&mut self) {
+        GRAD_ENABLED.with(|e| e.set(self.previous));
+    }
+}
+
+type BackwardFn = Box<dyn Fn(&Tensor) -> Result<Vec<Tensor>, String>>;
+
+struct Node {
+    id: usize,
+    op: &'static str,
+    value: Tensor,
+    requires_grad: bool,
+    parents: Vec<Var>,
+    backward: Option<BackwardFn>,
+    grad: RefCell<Option<Tensor>>,
+}
+
+#[derive(Clone)]
+pub struct Var(Rc<Node>);
+
+pub trait CustomOp {
+    fn name(&self) -> &'static str;
+
+    fn forward(&self, inputs: &[&Tensor]) -> Result<Tensor, String>;
+
+    fn backward(
+        &self,
+        inputs: &[&Tensor],
+        output: &Tensor,
+        grad: &Tensor,
+    ) -> Result<Vec<Tensor>, String>;
+}
+
+impl std::fmt::Debug for Var {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Var")
+            .field("op", &self.0.op)
+            .field("shape", &self.0.value.shape)
+            .field("requires_grad", &self.0.requires_grad)
+            .finish()
+    }
+}
+
+fn reduce_to_shape(grad: Tensor, shape: &[usize]) -> Result<Tensor, String> {
+    if grad.shape == shape {
+        return Ok(grad);
+    }
+
+    let mut grad = grad;
+    while grad.shape.len() > shape.len() {
+        grad = grad.sum_axis(0)?;
+    }
+    for d in 0..shape.len() {
+        if shape[d] == 1 && grad.shape[d] != 1 {
+            let mut keep = grad.shape.clone();
+            keep[d] = 1;
+            grad = grad.sum_axis(d)?.reshaped(keep)?;
+        }
+    }
+
+    return grad.reshaped(shape.to_vec());
+}
+
+fn swap_last(t: &Tensor) -> Result<Tensor, String> {
+    let rank = t.shape.len();
+
+    return t.transpose(rank - 2, rank - 1);
+}
+
+impl Var {
+    pub fn new(value: Tensor, requires_grad: bool) -> Self {
+        return Var(Rc::new(Node {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            op: "leaf",
+            value: value,
+            requires_grad: requires_grad,
+            parents: Vec::new(),
+            backward: None,
+            grad: RefCell::new(None),
+        }));
+    }
+
+    pub fn parameter(value: Tensor) -> Self {
+        return Var::new(value, true);
+    }
+
+    pub fn constant(value: Tensor) -> Self {
+        return Var::new(value, false);
+    }
+
+    pub(crate) fn from_op(
+        op: &'static str,
+        value: Tensor,
+        parents: Vec<Var>,
+        backward: BackwardFn,
+    ) -> Self {
+        let requires_grad = is_grad_enabled() && parents.iter().any(|p| p.0.requires_grad);
+
+        return Var(Rc::new(Node {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            op: op,
+            value: value,
+            requires_grad: requires_grad,
+            parents: if requires_grad { parents } else { Vec::new() },
+            backward: if requires_grad { Some(backward) } else { None },
+            grad: RefCell::new(None),
+        }));
+    }
+
+    pub fn value(&self) -> &Tensor {
+        return &self.0.value;
+    }
+
+    pub fn op(&self) -> &'static str {
+        return self.0.op;
+    }
+
+    pub fn requires_grad(&self) -> bool {
+        return self.0.requires_grad;
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        return self.0.backward.is_none();
+    }
+
+    pub fn grad(&self) -> Option<Tensor> {
+        return self.0.grad.borrow().clone();
+    }
+
+    pub fn zero_grad(&self) {
+        *self.0.grad.borrow_mut() = None;
+    }
+
+    pub fn detach(&self) -> Var {
+        return Var::constant(self.0.value.clone());
+    }
+
+    pub fn add(&self, other: &Var) -> Result<Var, String> {
+        let (sa, sb) = (self.0.value.shape.clone(), other.0.value.shape.clone());
+        let value = self.0.value.add(&other.0.value)?;
+
+        return Ok(Var::from_op(
+            "add",
+            value,
+            vec![self.clone(), other.clone()],
+            Box::new(move |g| {
+                Ok(vec![
+                    reduce_to_shape(g.clone(), &sa)?,
+                    reduce_to_shape(g.clone(), &sb)?,
+                ])
+            }),
+        ));
+    }
+
+    pub fn sub(&self, other: &Var) -> Result<Var, String> {
+        let (sa, sb) = (self.0.value.shape.clone(), other.0.value.shape.clone());
+        let value = self.0.value.sub(&other.0.value)?;
+
+        return Ok(Var::from_op(
+            "sub",
+            value,
+            vec![self.clone(), other.clone()],
+            Box::new(move |g| {
+                Ok(vec![
+                    reduce_to_shape(g.clone(), &sa)?,
+                    reduce_to_shape(g.neg()?, &sb)?,
+                ])
+            }),
+        ));
+    }
+
+    pub fn mul(&self, other: &Var) -> Result<Var, String> {
+        let (a, b) = (self.0.value.clone(), other.0.value.clone());
+        let value = a.mul(&b)?;
+
+        return Ok(Var::from_op(
+            "mul",
+            value,
+            vec![self.clone(), other.clone()],
+            Box::new(move |g| {
+                Ok(vec![
+                    reduce_to_shape(g.mul(&b)?, &a.shape)?,
+                    reduce_to_shape(g.mul(&a)?, &b.shape)?,
+                ])
+            }),
+        ));
+    }
+
+    pub fn div(&self, other: &Var) -> Result<Var, String> {
+        let (a, b) = (self.0.value.clone(), other.0.value.clone());
+        let value = a.div(&b)?;
+
+        return Ok(Var::from_op(
+            "div",
+            value,
+            vec![self.clone(), other.clone()],
+            Box::new(move |g| {
+                let ga = g.div(&b)?;
+                let gb = g.mul(&a)?.div(&b.mul(&b)?)?.neg()?;
+                Ok(vec![
+                    reduce_to_shape(ga, &a.shape)?,
+                    reduce_to_shape(gb, &b.shape)?,
+                ])
+            }),
+        ));
+    }
+
+    pub fn matmul(&self, other: &Var) -> Result<Var, String> {
+        let (a, b) = (self.0.value.clone(), other.0.value.clone());
+        let value = a.matmul(&b)?;
+
+        return Ok(Var::from_op(
+            "matmul",
+            value,
+            vec![self.clone(), other.clone()],
+            Box::new(move |g| {
+                let ga = g.matmul(&swap_last(&b)?)?;
+                let gb = swap_last(&a)?.matmul(g)?;
+                Ok(vec![
+                    reduce_to_shape(ga, &a.shape)?,
+                    reduce_to_shape(gb, &b.shape)?,
+                ])
+            }),
+        ));
+    }
+
+    pub fn mul_scalar(&self, value: f32) -> Result<Var, String> {
+        let out = self.0.value.mul_scalar(value)?;
+
+        return Ok(Var::from_op(
+            "mul_scalar",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| Ok(vec![g.mul_scalar(value)?])),
+        ));
+    }
+
+    pub fn add_scalar(&self, value: f32) -> Result<Var, String> {
+        let out = self.0.value.add_scalar(value)?;
+
+        return Ok(Var::from_op(
+            "add_scalar",
+            out,
+            vec![self.clone()],
+            Box::new(|g| Ok(vec![g.clone()])),
+        ));
+    }
+
+    pub fn neg(&self) -> Result<Var, String> {
+        let out = self.0.value.neg()?;
+
+        return Ok(Var::from_op(
+            "neg",
+            out,
+            vec![self.clone()],
+            Box::new(|g| Ok(vec![g.neg()?])),
+        ));
+    }
+
+    pub fn exp(&self) -> Result<Var, String> {
+        let out = self.0.value.exp()?;
+        let saved = out.clone();
+
+        return Ok(Var::from_op(
+            "exp",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| Ok(vec![g.mul(&saved)?])),
+        ));
+    }
+
+    pub fn log(&self) -> Result<Var, String> {
+        let a = self.0.value.clone();
+        let out = a.log()?;
+
+        return Ok(Var::from_op(
+            "log",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| Ok(vec![g.div(&a)?])),
+        ));
+    }
+
+    pub fn relu(&self) -> Result<Var, String> {
+        let a = self.0.value.clone();
+        let out = a.relu()?;
+
+        return Ok(Var::from_op(
+            "relu",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| {
+                Ok(vec![g.zip_with("relu_backward", &a, |g, x| {
+                    if x > 0.0 { g } else { 0.0 }
+                })?])
+            }),
+        ));
+    }
+
+    pub fn sigmoid(&self) -> Result<Var, String> {
+        let out = self.0.value.sigmoid()?;
+        let saved = out.clone();
+
+        return Ok(Var::from_op(
+            "sigmoid",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| {
+                Ok(vec![g.zip_with("sigmoid_backward", &saved, |g, s| {
+                    g * s * (1.0 - s)
+                })?])
+            }),
+        ));
+    }
+
+    pub fn tanh(&self) -> Result<Var, String> {
+        let out = self.0.value.tanh()?;
+        let saved = out.clone();
+
+        return Ok(Var::from_op(
+            "tanh",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| {
+                Ok(vec![g.zip_with("tanh_backward", &saved, |g, t| {
+                    g * (1.0 - t * t)
+                })?])
+            }),
+        ));
+    }
+
+    pub fn sum(&self) -> Result<Var, String> {
+        let shape = self.0.value.shape.clone();
+        let out = Tensor::new(vec![self.0.value.sum()?], vec![])?;
+
+        return Ok(Var::from_op(
+            "sum",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| Ok(vec![Tensor::full(shape.clone(), g.data[0])?])),
+        ));
+    }
+
+    pub fn mean(&self) -> Result<Var, String> {
+        let shape = self.0.value.shape.clone();
+        let n = self.0.value.data.len() as f32;
+        let out = Tensor::new(vec![self.0.value.mean()?], vec![])?;
+
+        return Ok(Var::from_op(
+            "mean",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| Ok(vec![Tensor::full(shape.clone(), g.data[0] / n)?])),
+        ));
+    }
+
+    pub fn sum_axis(&self, axis: usize) -> Result<Var, String> {
+        let shape = self.0.value.shape.clone();
+        let out = self.0.value.sum_axis(axis)?;
+
+        return Ok(Var::from_op(
+            "sum_axis",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| {
+                let mut keep = shape.clone();
+                keep[axis] = 1;
+                Ok(vec![Tensor::zeros(shape.clone())?.add(&g.reshaped(keep)?)?])
+            }),
+        ));
+    }
+
+    pub fn embedding(&self, indices: &Tensor, padding_idx: Option<usize>) -> Result<Var, String> {
+        let out = Tensor::embedding(&self.0.value, indices, padding_idx)?;
+        let indices = indices.clone();
+        let vocab = self.0.value.shape[0];
+
+        return Ok(Var::from_op(
+            "embedding",
+            out,
+            vec![self.clone()],
+            Box::new(move |g| {
+                Ok(vec![Tensor::embedding_backward(
+                    g,
+                    &indices,
+                    vocab,
+                    padding_idx,
+                )?])
+            }),
+        ));
+    }
+
+    pub fn apply_custom(op: Rc<dyn CustomOp>, inputs: &[Var]) -> Result<Var, String> {
+        let values: Vec<Tensor> = inputs.iter().map(|v| v.0.value.clone()).collect();
+        let refs: Vec<&Tensor> = values.iter().collect();
+        let output = op.forward(&refs)?;
+        let saved = output.clone();
+        let name = op.name();
+
+        return Ok(Var::from_op(
+            name,
+            output,
+            inputs.to_vec(),
+            Box::new(move |g| {
+                let refs: Vec<&Tensor> = values.iter().collect();
+                let grads = op.backward(&refs, &saved, g)?;
+                if grads.len() != values.len() {
+                    return Err(format!(
+                        "自定义算子 {} 返回了 {} 个梯度，但有 {} 个输入",
+                        name,
+                        grads.len(),
+                        values.len()
+                    ));
+                }
+                for (i, (grad, value)) in grads.iter().zip(values.iter()).enumerate() {
+                    if grad.shape != value.shape {
+                        return Err(format!(
+                            "自定义算子 {} 第 {} 个梯度形状 {:?} 与输入形状 {:?} 不匹配",
+                            name, i, grad.shape, value.shape
+                        ));
+                    }
+                }
+                Ok(grads)
+            }),
+        ));
+    }
+
+    fn topo_order(&self) -> Vec<Var> {
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![(self.clone(), false)];
+        while let Some((var, expanded)) = stack.pop() {
+            if expanded {
+                order.push(var);
+                continue;
+            }
+            if !visited.insert(var.0.id) {
+                continue;
+            }
+            stack.push((var.clone(), true));
+            for parent in &var.0.parents {
+                if !visited.contains(&parent.0.id) {
+                    stack.push((parent.clone(), false));
+                }
+            }
+        }
+
+        return order;
+    }
+
+    pub fn graph_to_dot(&self) -> String {
+        let mut out = String::from("digraph autograd {\n    node [shape=box];\n");
+        for var in self.topo_order() {
+            let node = &var.0;
+            let style = if node.backward.is_none() && node.requires_grad {
+                ", style=filled, fillcolor=lightblue"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    n{} [label=\"{}\\n{:?}\"{}];\n",
+                node.id, node.op, node.value.shape, style
+            ));
+            for parent in &node.parents {
+                let elementwise = matches!(node.op, "add" | "sub" | "mul" | "div");
+                let attrs = if elementwise && parent.0.value.shape != node.value.shape {
+                    " [label=\"broadcast\", color=red]"
+                } else {
+                    ""
+                };
+                out.push_str(&format!("    n{} -> n{}{};\n", parent.0.id, node.id, attrs));
+            }
+        }
+        out.push_str("}\n");
+
+        return out;
+    }
+
+    pub fn backward(&self) -> Result<(), String> {
+        if self.0.value.data.len() != 1 {
+            return Err(format!(
+                "backward 需要标量输出，实际形状为 {:?}",
+                self.0.value.shape
+            ));
+        }
+
+        return self.backward_with(Tensor::ones(self.0.value.shape.clone())?);
+    }
+
+    pub fn backward_with(&self, grad: Tensor) -> Result<(), String> {
+        if grad.shape != self.0.value.shape {
+            return Err(format!(
+                "梯度形状 {:?} 与输出形状 {:?} 不匹配",
+                grad.shape, self.0.value.shape
+            ));
+        }
+        if !self.0.requires_grad {
+            return Err("输出不依赖任何需要梯度的变量".to_string());
+        }
+
+        let order = self.topo_order();
+        let mut grads: HashMap<usize, Tensor> = HashMap::new();
+        grads.insert(self.0.id, grad);
+        for var in order.iter().rev() {
+            let Some(g) = grads.remove(&var.0.id) else {
+                continue;
+            };
+            match &var.0.backward {
+                Some(backward) => {
+                    let parent_grads = backward(&g)?;
+                    for (parent, pg) in var.0.parents.iter().zip(parent_grads) {
+                        if !parent.0.requires_grad {
+                            continue;
+                        }
+                        let merged = match grads.remove(&parent.0.id) {
+                            Some(existing) => existing.add(&pg)?,
+                            None => pg,
+                        };
+                        grads.insert(parent.0.id, merged);
+                    }
+                }
+                None => {
+                    if var.0.requires_grad {
+                        let mut slot = var.0.grad.borrow_mut();
+                        *slot = match slot.take() {
+                            Some(existing) => Some(existing.add(&g)?),
+                            None => Some(g),
+                        };
+                    }
+                }
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradMismatch {
+    pub input: usize,
+    pub index: usize,
+    pub analytic: f32,
+    pub numeric: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradCheckReport {
+    pub passed: bool,
+    pub max_abs_error: f32,
+    pub mismatches: Vec<GradMismatch>,
+}
+
+pub fn grad_check<F>(f: F, inputs: &[Tensor], eps: f32, tol: f32) -> Result<GradCheckReport, String>
+where
+    F: Fn(&[Var]) -> Result<Var, String>,
+{
+    let evaluate = |values: &[Tensor]| -> Result<f32, String> {
+        let vars: Vec<Var> = values.iter().map(|v| Var::constant(v.clone())).collect();
+        let out = f(&vars)?;
+        if out.value().data.len() != 1 {
+            return Err(format!(
+                "grad_check 需要标量输出，实际形状为 {:?}",
+                out.value().shape
+            ));
+        }
+        Ok(out.value().data[0])
+    };
+
+    let vars: Vec<Var> = inputs.iter().map(|v| Var::parameter(v.clone())).collect();
+    f(&vars)?.backward()?;
+
+    let mut report = GradCheckReport {
+        passed: true,
+        max_abs_error: 0.0,
+        mismatches: Vec::new(),
+    };
+    // 在可写的独立副本上扰动，输入冻结时也不会被改写
+    let mut values = inputs
+        .iter()
+        .map(|v| v.deep_copy())
+        .collect::<Result<Vec<Tensor>, String>>()?;
+    for (input, var) in vars.iter().enumerate() {
+        let analytic = var
+            .grad()
+            .unwrap_or(Tensor::zeros(inputs[input].shape.clone())?);
+        for index in 0..inputs[input].data.len() {
+            let original = values[input].data[index];
+            values[input].data_mut()?[index] = original + eps;
+            let plus = evaluate(&values)?;
+            values[input].data_mut()?[index] = original - eps;
+            let minus = evaluate(&values)?;
+            values[input].data_mut()?[index] = original;
+
+            let numeric = (plus - minus) / (2.0 * eps);
+            let a = analytic.data[index];
+            let error = (a - numeric).abs();
+            report.max_abs_error = report.max_abs_error.max(error);
+            if error > tol * (1.0 + numeric.abs()) || error.is_nan() {
+                report.passed = false;
+                report.mismatches.push(GradMismatch {
+                    input: input,
+                    index: index,
+                    analytic: a,
+                    numeric: numeric,
+                });
+            }
+        }
+    }
+
+    return Ok(report);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_flags_only_broadcasting_elementwise_edges() {
+        let a = Var::parameter(Tensor::ones(vec![2, 3]).unwrap());
+        let w = Var::parameter(Tensor::ones(vec![3, 4]).unwrap());
+        let bias = Var::parameter(Tensor::ones(vec![4]).unwrap());
+        let y = a.matmul(&w).unwrap().add(&bias).unwrap();
+        let dot = y.graph_to_dot();
+
+        let flagged: Vec<&str> = dot.lines().filter(|l| l.contains("broadcast")).collect();
+        assert_eq!(flagged.len(), 1);
+        assert!(flagged[0].starts_with(&format!("    n{} ->", bias.0.id)));
+    }
+
+    #[test]
+    fn broadcast_add_reduces_gradient_to_input_shape() {
+        let a = Var::parameter(Tensor::ones(vec![2, 3]).unwrap());
+        let bias = Var::parameter(Tensor::ones(vec![3]).unwrap());
+        a.add(&bias).unwrap().sum().unwrap().backward().unwrap();
+
+        let grad = bias.grad().unwrap();
+        assert_eq!(grad.shape, vec![3]);
+        assert_eq!(grad.data.to_vec(), vec![2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn grad_check_passes_for_composite_expression() {
+        let x = Tensor::new(vec![0.5, -1.0, 2.0, 0.25], vec![2, 2]).unwrap();
+        let w = Tensor::new(vec![1.0, -0.5, 0.75, 2.0], vec![2, 2]).unwrap();
+        let report = grad_check(
+            |v| v[0].matmul(&v[1])?.tanh()?.mul(&v[0])?.sum(),
+            &[x, w],
+            1e-2,
+            1e-2,
+        )
+        .unwrap();
+
+        assert!(report.passed, "{:?}", report);
+
+        // 冻结的参数同样可以检查，原张量不被改写
+        let mut frozen = Tensor::new(vec![0.5, -1.0], vec![2]).unwrap();
+        frozen.freeze();
+        let report = grad_check(
+            |v| v[0].tanh()?.sum(),
+            std::slice::from_ref(&frozen),
+            1e-2,
+            1e-2,
+        )
+        .unwrap();
+        assert!(report.passed, "{:?}", report);
+        assert_eq!(frozen.data.to_vec(), vec![0.5, -1.0]);
+    }
+
+    #[test]
+    fn embedding_gradient_skips_padding_row() {
+        let w = Tensor::new(vec![0.5, -1.0, 2.0, 0.25, 1.5, -0.75], vec![3, 2]).unwrap();
+        let ids = Tensor::new(vec![2.0, 1.0, 2.0], vec![3]).unwrap();
+        let report = grad_check(
+            |v| v[0].embedding(&ids, None)?.tanh()?.sum(),
+            std::slice::from_ref(&w),
+            1e-2,
+            1e-2,
+        )
+        .unwrap();
+        assert!(report.passed, "{:?}", report);
+
+        let weight = Var::parameter(w);
+        let padded = Tensor::new(vec![0.0, 1.0, 0.0], vec![3]).unwrap();
+        weight
+            .embedding(&padded, Some(0))
+            .unwrap()
+            .sum()
+            .unwrap()
+            .backward()
+            .unwrap();
+        assert_eq!(
+            weight.grad().unwrap().data.to_vec(),
+            vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn grad_check_rejects_non_scalar_output() {
+        let x = Tensor::ones(vec![2]).unwrap();
+        assert!(grad_check(|v| v[0].exp(), &[x], 1e-2, 1e-2).is_err());
+    }
+
+    #[test]
+    fn backward_rejects_non_scalar_and_constant_outputs() {
+        let x = Var::parameter(Tensor::ones(vec![2]).unwrap());
+        assert!(x.exp().unwrap().backward().is_err());
+        let c = Var::constant(Tensor::ones(vec![1]).unwrap());
+        assert!(c.exp().unwrap().backward().is_err());
+    }
+
+    struct Product;
+
+    impl CustomOp for Product {
+        fn name(&self) -> &'static str {
+            return "product";
+        }
+
+        fn forward(&self, inputs: &[&Tensor]) -> Result<Tensor, String> {
+            return inputs[0].mul(inputs[1]);
+        }
+
+        fn backward(
+            &self,
+            inputs: &[&Tensor],
+            _output: &Tensor,
+            grad: &Tensor,
+        ) -> Result<Vec<Tensor>, String> {
+            return Ok(vec![grad.mul(inputs[1])?, grad.mul(inputs[0])?]);
+        }
+    }
+
+    #[test]
+    fn custom_op_gradient_flows_into_leaves() {
+        let a = Tensor::new(vec![0.5, -1.0, 2.0], vec![3]).unwrap();
+        let b = Tensor::new(vec![3.0, 0.25, -2.0], vec![3]).unwrap();
+        let report = grad_check(
+            |v| Var::apply_custom(Rc::new(Product), v)?.sum(),
+            &[a.clone(), b.clone()],
+            1e-2,
+            1e-2,
+        )
+        .unwrap();
+        assert!(report.passed, "{:?}", report);
+
+        let x = Var::parameter(a.clone());
+        let y = Var::parameter(b.clone());
+        let out = Var::apply_custom(Rc::new(Product), &[x.clone(), y.clone()]).unwrap();
+        assert_eq!(out.op(), "product");
+        out.sum().unwrap().backward().unwrap();
+        assert_eq!(x.grad().unwrap().data.to_vec(), b.data.to_vec());
+        assert_eq!(y.grad().unwrap().data.to_vec(), a.data.to_vec());
+    }
+
+    #[test]
+    fn no_grad_records_no_graph() {
+        let x = Var::parameter(Tensor::ones(vec![2]).unwrap());
+        {
+            let _guard = no_grad();
+            let y = x.exp().unwrap();
+            assert!(!y.requires_grad());
+            assert!(y.is_leaf());
+            assert!(y.sum().unwrap().backward().is_err());
+        }
+        assert!(is_grad_enabled());
+        assert!(x.exp().unwrap().requires_grad());
+    }
+
+    #[test]
+    fn gradients_accumulate_until_zero_grad() {
+        let x = Var::parameter(Tensor::new(vec![1.0, -2.0], vec![2]).unwrap());
+        x.mul(&x).unwrap().sum().unwrap().backward().unwrap();
+        assert_eq!(x.grad().unwrap().data.to_vec(), vec![2.0, -4.0]);
+        x.mul(&x).unwrap().sum().unwrap().backward().unwrap();
+        assert_eq!(x.grad().unwrap().data.to_vec(), vec![4.0, -8.0]);
+
+        x.zero_grad();
+        assert!(x.grad().is_none());
+        x.sum().unwrap().backward().unwrap();
+        assert_eq!(x.grad().unwrap().data.to_vec(), vec![1.0, 1.0]);
+    }
+}
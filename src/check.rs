@@ -0,0 +1,136 @@
+use std::cell::Cell;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::Tensor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckMode {
+    Off,
+    Log,
+    Error,
+}
+
+type LogHook = Box<dyn Fn(&str) + Send + Sync>;
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+static LOG_HOOK: RwLock<Option<LogHook>> = RwLock::new(None);
+
+thread_local! {
+    static SCOPED_MODE: Cell<Option<CheckMode>> = const { Cell::new(None) };
+}
+
+pub fn set_mode(mode: CheckMode) {
+    let value = match mode {
+        CheckMode::Off => 0,
+        CheckMode::Log => 1,
+        CheckMode::Error => 2,
+    };
+    MODE.store(value, Ordering::Relaxed);
+}
+
+// 只对当前线程发起的运算使用 mode，f 返回（或 panic）后恢复，不改动全局设置
+pub fn with_mode<T>(mode: CheckMode, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<CheckMode>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_MODE.with(|s| s.set(self.0));
+        }
+    }
+
+    let _restore = Restore(SCOPED_MODE.with(|s| s.replace(Some(mode))));
+    return f();
+}
+
+pub fn mode() -> CheckMode {
+    if let Some(mode) = SCOPED_MODE.with(|s| s.get()) {
+        return mode;
+    }
+
+    return match MODE.load(Ordering::Relaxed) {
+        1 => CheckMode::Log,
+        2 => CheckMode::Error,
+        _ => CheckMode::Off,
+    };
+}
+
+// Log 模式下的报告交给 hook 处理（可转发到日志框架或收集起来）；传 None 恢复默认的 stderr 输出
+pub fn set_log_hook(hook: Option<LogHook>) {
+    *LOG_HOOK.write().unwrap_or_else(|e| e.into_inner()) = hook;
+}
+
+fn report(message: &str) {
+    match LOG_HOOK.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(hook) => hook(message),
+        None => eprintln!("[tensor check] {}", message),
+    }
+}
+
+pub(crate) fn inspect(op: &str, inputs: &[&[usize]], out: &Tensor) -> Result<(), String> {
+    let mode = mode();
+    if mode == CheckMode::Off {
+        return Ok(());
+    }
+
+    let nan = out.data.iter().filter(|x| x.is_nan()).count();
+    let inf = out.data.iter().filter(|x| x.is_infinite()).count();
+    if nan == 0 && inf == 0 {
+        return Ok(());
+    }
+
+    let message = format!(
+        "操作 {} 的输出包含 {} 个 NaN 和 {} 个 Inf（输入形状 {:?}，输出形状 {:?}）",
+        op, nan, inf, inputs, out.shape
+    );
+    if mode == CheckMode::Error {
+        return Err(message);
+    }
+    report(&message);
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn modes_control_how_non_finite_outputs_are_reported() {
+        let x = Tensor::new(vec![0.0, 1.0, -1.0], vec![3]).unwrap();
+        let finite = Tensor::new(vec![1.0, 2.0], vec![2]).unwrap();
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let sink = logged.clone();
+        set_log_hook(Some(Box::new(move |m: &str| {
+            sink.lock().unwrap().push(m.to_string())
+        })));
+
+        with_mode(CheckMode::Off, || {
+            assert_eq!(mode(), CheckMode::Off);
+            assert!(x.log().is_ok());
+        });
+        with_mode(CheckMode::Log, || {
+            let out = x.log().unwrap();
+            assert!(out.data[2].is_nan());
+            assert!(finite.log().is_ok());
+        });
+        let errors = with_mode(CheckMode::Error, || {
+            assert!(finite.log().is_ok());
+            let inf = Tensor::new(vec![1.0, 0.0], vec![2]).unwrap();
+            return [
+                x.log().unwrap_err(),
+                inf.div(&Tensor::zeros(vec![2]).unwrap()).unwrap_err(),
+            ];
+        });
+        set_log_hook(None);
+        assert_eq!(mode(), CheckMode::Off);
+
+        // 其他测试线程的日志不会落到这里：它们没有开启检查
+        let logged = logged.lock().unwrap();
+        assert_eq!(logged.len(), 1);
+        assert!(logged[0].contains("1 个 NaN 和 1 个 Inf"), "{}", logged[0]);
+        assert!(errors[0].contains("1 个 NaN 和 1 个 Inf"), "{}", errors[0]);
+        assert!(errors[1].contains("1 个 NaN 和 1 个 Inf"), "{}", errors[1]);
+    }
+}
@@ -0,0 +1,344 @@
+use std::path::Path;
+
+use crate::json::Json;
+use crate::state_dict::StateDict;
+use crate::{DType, Endianness, Tensor};
+
+// 目录布局：
+//   manifest.json   格式名、版本、元数据，以及每个张量的文件名、类型、形状与校验和
+//   00000.bin ...   按保存顺序编号的张量数据，小端序 f32
+// 清单最后写入，保存中途失败时目录里不会出现指向残缺数据的清单。
+const FORMAT: &str = "tensor-checkpoint";
+const VERSION: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    version: u32,
+    metadata: Vec<(String, String)>,
+    tensors: Vec<(String, Tensor)>,
+}
+
+struct Manifest {
+    version: u32,
+    metadata: Vec<(String, String)>,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    file: String,
+    shape: Vec<usize>,
+    checksum: u64,
+}
+
+impl Checkpoint {
+    pub fn version(&self) -> u32 {
+        return self.version;
+    }
+
+    pub fn metadata(&self) -> &[(String, String)] {
+        return &self.metadata;
+    }
+
+    pub fn get_metadata(&self, key: &str) -> Option<&str> {
+        return self
+            .metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str());
+    }
+
+    pub fn tensors(&self) -> &[(String, Tensor)] {
+        return &self.tensors;
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tensor> {
+        return self.tensors.iter().find(|(n, _)| n == name).map(|(_, t)| t);
+    }
+
+    pub fn into_tensors(self) -> Vec<(String, Tensor)> {
+        return self.tensors;
+    }
+
+    pub fn into_state_dict(self) -> StateDict {
+        return StateDict::from(self.tensors);
+    }
+}
+
+pub fn save<P: AsRef<Path>>(
+    dir: P,
+    tensors: &[(String, Tensor)],
+    metadata: &[(String, String)],
+) -> Result<(), String> {
+    let dir = dir.as_ref();
+    for (i, (name, _)) in tensors.iter().enumerate() {
+        if tensors[..i].iter().any(|(n, _)| n == name) {
+            return Err(format!("检查点中的张量名 {} 重复", name));
+        }
+    }
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("无法创建检查点目录 {}：{}", dir.display(), e))?;
+
+    let mut entries = Vec::with_capacity(tensors.len());
+    for (i, (name, tensor)) in tensors.iter().enumerate() {
+        let file = format!("{:05}.bin", i);
+        let path = dir.join(&file);
+        std::fs::write(&path, tensor.to_bytes(Endianness::Little))
+            .map_err(|e| format!("无法写入张量 {} 到 {}：{}", name, path.display(), e))?;
+        entries.push(Json::Object(vec![
+            ("name".to_string(), Json::String(name.clone())),
+            ("file".to_string(), Json::String(file)),
+            (
+                "dtype".to_string(),
+                Json::String(DType::F32.name().to_string()),
+            ),
+            (
+                "shape".to_string(),
+                Json::Array(
+                    tensor
+                        .shape
+                        .iter()
+                        .map(|&d| Json::Number(d as f64))
+                        .collect(),
+                ),
+            ),
+            (
+                "checksum".to_string(),
+                Json::String(format!("{:016x}", tensor.content_hash())),
+            ),
+        ]));
+    }
+
+    let manifest = Json::Object(vec![
+        ("format".to_string(), Json::String(FORMAT.to_string())),
+        ("version".to_string(), Json::Number(VERSION as f64)),
+        (
+            "metadata".to_string(),
+            Json::Object(
+                metadata
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Json::String(v.clone())))
+                    .collect(),
+            ),
+        ),
+        ("tensors".to_string(), Json::Array(entries)),
+    ]);
+    let path = dir.join(MANIFEST);
+    std::fs::write(&path, manifest.to_pretty())
+        .map_err(|e| format!("无法写入检查点清单 {}：{}", path.display(), e))?;
+
+    return Ok(());
+}
+
+fn parse_entry(value: &Json) -> Result<Entry, String> {
+    let name = value
+        .get("name")
+        .and_then(Json::as_str)
+        .ok_or("检查点清单中的张量缺少 name")?;
+    let field = |key: &str| {
+        return value
+            .get(key)
+            .ok_or(format!("检查点清单中的张量 {} 缺少 {}", name, key));
+    };
+
+    let file = field("file")?.as_str().unwrap_or_default();
+    // 只允许目录内的普通文件名，防止清单指向目录之外
+    if file.is_empty() || file.contains(['/', '\\']) || file == ".." {
+        return Err(format!("张量 {} 的数据文件名 {:?} 无效", name, file));
+    }
+    let dtype = field("dtype")?.as_str().unwrap_or_default();
+    if dtype != DType::F32.name() {
+        return Err(format!("张量 {} 的数据类型 {} 暂不支持", name, dtype));
+    }
+    let mut shape = Vec::new();
+    for d in field("shape")?.as_array().unwrap_or_default() {
+        match d.as_f64() {
+            Some(v) if v >= 0.0 && v.fract() == 0.0 => shape.push(v as usize),
+            _ => return Err(format!("张量 {} 的形状含无效维度 {:?}", name, d)),
+        }
+    }
+    let checksum = field("checksum")?.as_str().unwrap_or_default();
+    let checksum = u64::from_str_radix(checksum, 16)
+        .map_err(|_| format!("张量 {} 的校验和 {:?} 无效", name, checksum))?;
+
+    return Ok(Entry {
+        name: name.to_string(),
+        file: file.to_string(),
+        shape: shape,
+        checksum: checksum,
+    });
+}
+
+fn read_manifest(dir: &Path) -> Result<Manifest, String> {
+    let path = dir.join(MANIFEST);
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("无法读取检查点清单 {}：{}", path.display(), e))?;
+    let manifest = Json::parse(&text).map_err(|e| format!("检查点清单无效：{}", e))?;
+
+    if manifest.get("format").and_then(Json::as_str) != Some(FORMAT) {
+        return Err(format!("{} 不是检查点清单", path.display()));
+    }
+    let version = manifest
+        .get("version")
+        .and_then(Json::as_f64)
+        .ok_or("检查点清单缺少版本号")? as u32;
+    if version == 0 || version > VERSION {
+        return Err(format!(
+            "检查点版本 {} 不受支持（最高支持 {}）",
+            version, VERSION
+        ));
+    }
+
+    let mut metadata = Vec::new();
+    for (key, value) in manifest
+        .get("metadata")
+        .and_then(Json::as_object)
+        .unwrap_or_default()
+    {
+        let Some(value) = value.as_str() else {
+            return Err(format!("检查点元数据 {} 应为字符串", key));
+        };
+        metadata.push((key.clone(), value.to_string()));
+    }
+    let entries = manifest
+        .get("tensors")
+        .and_then(Json::as_array)
+        .ok_or("检查点清单缺少 tensors 列表")?
+        .iter()
+        .map(parse_entry)
+        .collect::<Result<Vec<_>, String>>()?;
+
+    return Ok(Manifest {
+        version: version,
+        metadata: metadata,
+        entries: entries,
+    });
+}
+
+fn load_entry(dir: &Path, entry: &Entry) -> Result<Tensor, String> {
+    let path = dir.join(&entry.file);
+    let bytes = std::fs::read(&path).map_err(|e| {
+        format!(
+            "无法读取张量 {} 的数据 {}：{}",
+            entry.name,
+            path.display(),
+            e
+        )
+    })?;
+    let tensor = Tensor::from_bytes(&bytes, entry.shape.clone(), DType::F32, Endianness::Little)
+        .map_err(|e| format!("张量 {} 数据无效：{}", entry.name, e))?;
+    if tensor.content_hash() != entry.checksum {
+        return Err(format!(
+            "张量 {} 校验和不匹配：清单为 {:016x}，数据为 {:016x}",
+            entry.name,
+            entry.checksum,
+            tensor.content_hash()
+        ));
+    }
+
+    return Ok(tensor);
+}
+
+pub fn load<P: AsRef<Path>>(dir: P) -> Result<Checkpoint, String> {
+    let dir = dir.as_ref();
+    let manifest = read_manifest(dir)?;
+    let tensors = manifest
+        .entries
+        .iter()
+        .map(|e| Ok((e.name.clone(), load_entry(dir, e)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    return Ok(Checkpoint {
+        version: manifest.version,
+        metadata: manifest.metadata,
+        tensors: tensors,
+    });
+}
+
+// 只读取指定张量的数据文件，结果按 names 的顺序排列
+pub fn load_only<P: AsRef<Path>>(dir: P, names: &[&str]) -> Result<Checkpoint, String> {
+    let dir = dir.as_ref();
+    let manifest = read_manifest(dir)?;
+    let mut tensors = Vec::with_capacity(names.len());
+    for &name in names {
+        let Some(entry) = manifest.entries.iter().find(|e| e.name == name) else {
+            return Err(format!("检查点 {} 中没有张量 {}", dir.display(), name));
+        };
+        tensors.push((name.to_string(), load_entry(dir, entry)?));
+    }
+
+    return Ok(Checkpoint {
+        version: manifest.version,
+        metadata: manifest.metadata,
+        tensors: tensors,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("tensor-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        return dir;
+    }
+
+    #[test]
+    fn save_load_round_trip_with_partial_loading() {
+        let dir = scratch_dir("checkpoint");
+        let tensors = vec![
+            (
+                "encoder.weight".to_string(),
+                Tensor::new(vec![1.0, -2.0, 3.5, 0.0, 5.0, 6.0], vec![2, 3]).unwrap(),
+            ),
+            ("encoder.bias".to_string(), Tensor::zeros(vec![3]).unwrap()),
+            ("step".to_string(), Tensor::new(vec![42.0], vec![]).unwrap()),
+        ];
+        let metadata = vec![("epoch".to_string(), "7".to_string())];
+        save(&dir, &tensors, &metadata).unwrap();
+
+        let ckpt = load(&dir).unwrap();
+        assert_eq!(ckpt.version(), 1);
+        assert_eq!(ckpt.get_metadata("epoch"), Some("7"));
+        assert_eq!(ckpt.tensors(), &tensors[..]);
+        let dict = ckpt.into_state_dict();
+        assert_eq!(dict.as_slice(), &tensors[..]);
+
+        let partial = load_only(&dir, &["step", "encoder.weight"]).unwrap();
+        assert_eq!(partial.tensors().len(), 2);
+        assert_eq!(partial.tensors()[0].0, "step");
+        assert_eq!(partial.get("encoder.weight"), Some(&tensors[0].1));
+        assert!(load_only(&dir, &["decoder.weight"]).is_err());
+
+        // 未被选中的张量损坏不影响部分加载，全量加载则报校验和错误
+        std::fs::write(dir.join("00001.bin"), [1u8; 12]).unwrap();
+        assert!(load_only(&dir, &["step"]).is_ok());
+        assert!(load(&dir).unwrap_err().contains("校验和"));
+
+        let dup = vec![tensors[0].clone(), tensors[0].clone()];
+        assert!(save(&dir, &dup, &[]).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_versions_and_foreign_manifests() {
+        let dir = scratch_dir("checkpoint-version");
+        save(&dir, &[], &[]).unwrap();
+        let manifest = std::fs::read_to_string(dir.join(MANIFEST)).unwrap();
+        assert!(load(&dir).unwrap().tensors().is_empty());
+
+        std::fs::write(
+            dir.join(MANIFEST),
+            manifest.replace("\"version\": 1", "\"version\": 2"),
+        )
+        .unwrap();
+        assert!(load(&dir).unwrap_err().contains("版本"));
+        std::fs::write(dir.join(MANIFEST), "{\"format\": \"other\"}").unwrap();
+        assert!(load(&dir).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,169 @@
+use crate::Tensor;
+use crate::random::Rng;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KMeans {
+    pub centroids: Tensor,
+    pub assignments: Tensor,
+    pub inertia: f32,
+    pub n_iter: usize,
+}
+
+fn squared_distances(data: &Tensor, centroids: &Tensor) -> Result<Tensor, String> {
+    let k = centroids.shape[0];
+    let data_sq = data
+        .mul(data)?
+        .sum_axis(1)?
+        .reshaped(vec![data.shape[0], 1])?;
+    let cent_sq = centroids
+        .mul(centroids)?
+        .sum_axis(1)?
+        .reshaped(vec![1, k])?;
+    let cross = data.matmul(&centroids.transpose(0, 1)?)?.mul_scalar(-2.0)?;
+
+    return data_sq
+        .add(&cross)?
+        .add(&cent_sq)?
+        .map("kmeans", |d| d.max(0.0));
+}
+
+pub fn kmeans(data: &Tensor, k: usize, max_iter: usize, seed: u64) -> Result<KMeans, String> {
+    if data.shape.len() != 2 {
+        return Err(format!(
+            "kmeans 需要二维数据矩阵，实际形状为 {:?}",
+            data.shape
+        ));
+    }
+    let rows = data.shape[0];
+    if k == 0 || k > rows {
+        return Err(format!("簇数量 {} 不在 [1, {}] 范围内", k, rows));
+    }
+
+    // k-means++ 初始化
+    let mut rng = Rng::new(seed);
+    let mut chosen = vec![rng.below(rows)];
+    while chosen.len() < k {
+        let centers = data.index_select(
+            0,
+            &Tensor::new(
+                chosen.iter().map(|&c| c as f32).collect(),
+                vec![chosen.len()],
+            )?,
+        )?;
+        let nearest = squared_distances(data, &centers)?.min_axis(1)?;
+        let total: f32 = nearest.data.iter().sum();
+        let next = if total > 0.0 {
+            let mut target = rng.next_f32() * total;
+            let mut pick = rows - 1;
+            for (i, &d) in nearest.data.iter().enumerate() {
+                if target < d {
+                    pick = i;
+                    break;
+                }
+                target -= d;
+            }
+            pick
+        } else {
+            rng.below(rows)
+        };
+        chosen.push(next);
+    }
+    let indices = Tensor::new(chosen.iter().map(|&c| c as f32).collect(), vec![k])?;
+    let mut centroids = data.index_select(0, &indices)?;
+
+    let mut assignments = Tensor::zeros(vec![rows])?;
+    let mut n_iter = 0;
+    for _ in 0..max_iter {
+        n_iter += 1;
+        let new_assignments = squared_distances(data, &centroids)?.argmin_axis(1)?;
+        let changed = new_assignments != assignments;
+        assignments = new_assignments;
+
+        let sums = data.segment_sum(&assignments, k)?;
+        let counts = Tensor::ones(vec![rows, 1])?.segment_sum(&assignments, k)?;
+        let mut updated = sums.div(&counts)?;
+        for c in 0..k {
+            if counts.data[c] == 0.0 {
+                let cols = data.shape[1];
+                updated.data[c * cols..(c + 1) * cols]
+                    .copy_from_slice(&centroids.data[c * cols..(c + 1) * cols]);
+            }
+        }
+        centroids = updated;
+
+        if !changed && n_iter > 1 {
+            break;
+        }
+    }
+
+    let distances = squared_distances(data, &centroids)?;
+    assignments = distances.argmin_axis(1)?;
+    let inertia = distances.min_axis(1)?.sum()?;
+
+    return Ok(KMeans {
+        centroids: centroids,
+        assignments: assignments,
+        inertia: inertia,
+        n_iter: n_iter,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 两团相距很远的点，任何初始化都应收敛到同一划分
+    fn blobs() -> Tensor {
+        return Tensor::new(
+            vec![
+                0.0, 0.0, 0.1, 0.0, 0.0, 0.1, 10.0, 10.0, 10.1, 10.0, 10.0, 10.1,
+            ],
+            vec![6, 2],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn kmeans_separates_blobs() {
+        let data = blobs();
+        for seed in 0..8 {
+            let result = kmeans(&data, 2, 50, seed).unwrap();
+            let a = &result.assignments.data;
+            assert!(a[0] == a[1] && a[1] == a[2]);
+            assert!(a[3] == a[4] && a[4] == a[5]);
+            assert_ne!(a[0], a[3]);
+            let c = a[0] as usize;
+            assert!((result.centroids.data[c * 2] - 0.1 / 3.0).abs() < 1e-5);
+            assert!((result.inertia - 8.0 * 0.01 / 3.0).abs() < 1e-3);
+        }
+        assert_eq!(kmeans(&data, 2, 50, 3), kmeans(&data, 2, 50, 3));
+    }
+
+    #[test]
+    fn kmeans_edge_cases() {
+        let data = blobs();
+        let all = kmeans(&data, 6, 10, 0).unwrap();
+        assert!(all.inertia.abs() < 1e-5);
+        let one = kmeans(&data, 1, 10, 0).unwrap();
+        assert!(one.assignments.data.iter().all(|&a| a == 0.0));
+
+        // 重复点：k-means++ 的权重全为 0 时退化为均匀抽样
+        let same = Tensor::ones(vec![4, 3]).unwrap();
+        let result = kmeans(&same, 2, 10, 0).unwrap();
+        assert_eq!(result.inertia, 0.0);
+        assert!(result.centroids.data.iter().all(|&v| v == 1.0));
+
+        let zero_iter = kmeans(&data, 2, 0, 0).unwrap();
+        assert_eq!(zero_iter.n_iter, 0);
+        assert_eq!(zero_iter.assignments.shape, vec![6]);
+    }
+
+    #[test]
+    fn kmeans_rejects_bad_input() {
+        let data = blobs();
+        assert!(kmeans(&data, 0, 10, 0).is_err());
+        assert!(kmeans(&data, 7, 10, 0).is_err());
+        assert!(kmeans(&Tensor::zeros(vec![6]).unwrap(), 1, 10, 0).is_err());
+        assert!(kmeans(&Tensor::zeros(vec![0, 2]).unwrap(), 1, 10, 0).is_err());
+    }
+}
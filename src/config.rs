@@ -0,0 +1,117 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::thread;
+
+use crate::Summation;
+
+static NUM_THREADS: AtomicUsize = AtomicUsize::new(0);
+static PARALLEL_THRESHOLD: AtomicUsize = AtomicUsize::new(1 << 15);
+static DETERMINISTIC: AtomicBool = AtomicBool::new(false);
+static STRICT_DTYPES: AtomicBool = AtomicBool::new(false);
+static SUMMATION: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    static SCOPED_THREADS: Cell<usize> = const { Cell::new(0) };
+}
+
+pub fn set_num_threads(threads: usize) {
+    NUM_THREADS.store(threads, Ordering::Relaxed);
+}
+
+// 只在当前线程发起的运算中使用 threads 个线程，f 返回（或 panic）后恢复；
+// 不改动全局设置，其他线程上的运算不受影响
+pub fn with_num_threads<T>(threads: usize, f: impl FnOnce() -> T) -> T {
+    struct Restore(usize);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_THREADS.with(|s| s.set(self.0));
+        }
+    }
+
+    let _restore = Restore(SCOPED_THREADS.with(|s| s.replace(threads)));
+    return f();
+}
+
+pub fn num_threads() -> usize {
+    let scoped = SCOPED_THREADS.with(|s| s.get());
+    if scoped > 0 {
+        return scoped;
+    }
+    let threads = NUM_THREADS.load(Ordering::Relaxed);
+    if threads > 0 {
+        return threads;
+    }
+
+    return thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+}
+
+pub fn set_parallel_threshold(elements: usize) {
+    PARALLEL_THRESHOLD.store(elements, Ordering::Relaxed);
+}
+
+pub fn parallel_threshold() -> usize {
+    return PARALLEL_THRESHOLD.load(Ordering::Relaxed);
+}
+
+// 确定性模式：全局随机数发生器立即以固定种子重置，之后懒初始化也使用该种子；
+// Batcher 与 transforms::Pipeline 在创建时从全局发生器取种子，随之可复现，
+// kmeans 只使用调用方传入的种子。归约本来就按与线程数无关的固定块与固定合并树
+// 进行，因此开启后同一输入、同一调用序列在多次运行之间逐位一致（线程数不同也一致）
+pub fn set_deterministic(enabled: bool) {
+    DETERMINISTIC.store(enabled, Ordering::Relaxed);
+    if enabled {
+        crate::random::manual_seed(crate::random::DETERMINISTIC_SEED);
+    }
+}
+
+pub fn is_deterministic() -> bool {
+    return DETERMINISTIC.load(Ordering::Relaxed);
+}
+
+// 严格模式下，dtype 不同的张量做二元运算会报错，而不是按提升表自动提升
+pub fn strict_dtypes(enabled: bool) {
+    STRICT_DTYPES.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_strict_dtypes() -> bool {
+    return STRICT_DTYPES.load(Ordering::Relaxed);
+}
+
+// sum、mean 及其按轴版本默认使用的求和方式；单次调用可用 sum_with/mean_with 覆盖
+pub fn set_summation(mode: Summation) {
+    SUMMATION.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn summation() -> Summation {
+    return match SUMMATION.load(Ordering::Relaxed) {
+        1 => Summation::Pairwise,
+        2 => Summation::Kahan,
+        _ => Summation::Naive,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tensor;
+
+    #[test]
+    fn reductions_do_not_depend_on_thread_count() {
+        let n = 300_000;
+        let data: Vec<f32> = (0..n)
+            .map(|i| ((i * 7919) % 1000) as f32 * 1e-3 - 0.37)
+            .collect();
+        let t = Tensor::new(data, vec![n]).unwrap();
+        let run = || (t.sum().unwrap(), t.argmax().unwrap(), t.min().unwrap());
+        let serial = with_num_threads(1, run);
+        let threaded = with_num_threads(7, || {
+            assert_eq!(num_threads(), 7);
+            run()
+        });
+        assert_eq!(serial.0.to_bits(), threaded.0.to_bits());
+        assert_eq!(serial.1, threaded.1);
+        assert_eq!(serial.2.to_bits(), threaded.2.to_bits());
+    }
+}
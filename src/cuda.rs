@@ -0,0 +1,430 @@
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use cudarc::cublas::sys::cublasOperation_t;
+use cudarc::cublas::{CudaBlas, Gemm, GemmConfig, StridedBatchedConfig};
+use cudarc::driver::{CudaContext, CudaModule, CudaSlice, CudaStream, LaunchConfig, PushKernelArg};
+
+use crate::Tensor;
+use crate::lazy::{BinaryOp, UnaryOp};
+use crate::tensor::{Backend, DeviceBuffer, ReduceOp};
+
+// CUDA 后端：逐元素算子与归约由 NVRTC 在首次使用时编译，matmul 走 cuBLAS。
+// 所有工作都排在该设备专属的流上异步执行：上传经页锁定内存，算子结果留在设备的
+// 常驻缓冲区里直接作为下一个算子的输入，只有主机按切片读取（或归约出标量）时
+// 才在流上排队拷回并等待。
+// 需要广播的二元运算、批维需要广播的 matmul 与空张量返回 None，退回主机实现
+
+const KERNELS: &str = r#"
+#define BINARY(name, expr)                                                        \
+    extern "C" __global__ void name(const float* a, const float* b, float* out,   \
+                                    unsigned int n) {                             \
+        unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;                   \
+        if (i < n) { float x = a[i]; float y = b[i]; out[i] = expr; }             \
+    }
+
+#define UNARY(name, expr)                                                         \
+    extern "C" __global__ void name(const float* a, float* out, unsigned int n) { \
+        unsigned int i = blockIdx.x * blockDim.x + threadIdx.x;                   \
+        if (i < n) { float x = a[i]; out[i] = expr; }                             \
+    }
+
+// 每个块把网格跨步区间归约成一个部分结果，部分结果在主机上合并
+#define REDUCE(name, identity, combine)                                           \
+    extern "C" __global__ void name(const float* a, float* partial,               \
+                                    unsigned int n) {                             \
+        __shared__ float cache[256];                                              \
+        unsigned int tid = threadIdx.x;                                           \
+        float x = identity;                                                       \
+        for (unsigned int i = blockIdx.x * blockDim.x + tid; i < n;               \
+             i += blockDim.x * gridDim.x) {                                       \
+            float y = a[i]; x = combine;                                          \
+        }                                                                         \
+        cache[tid] = x;                                                           \
+        __syncthreads();                                                          \
+        for (unsigned int s = blockDim.x / 2; s > 0; s >>= 1) {                   \
+            if (tid < s) { x = cache[tid]; float y = cache[tid + s];              \
+                           cache[tid] = combine; }                                \
+            __syncthreads();                                                      \
+        }                                                                         \
+        if (tid == 0) { partial[blockIdx.x] = cache[0]; }                         \
+    }
+
+BINARY(binary_add, x + y)
+BINARY(binary_sub, x - y)
+BINARY(binary_mul, x * y)
+BINARY(binary_div, x / y)
+BINARY(binary_max, fmaxf(x, y))
+BINARY(binary_min, fminf(x, y))
+
+UNARY(unary_neg, -x)
+UNARY(unary_abs, fabsf(x))
+UNARY(unary_sqrt, sqrtf(x))
+UNARY(unary_exp, expf(x))
+UNARY(unary_log, logf(x))
+UNARY(unary_relu, fmaxf(x, 0.0f))
+UNARY(unary_sigmoid, 1.0f / (1.0f + expf(-x)))
+UNARY(unary_tanh, tanhf(x))
+
+REDUCE(reduce_sum, 0.0f, x + y)
+// max/min 遇到 NaN 时结果为 NaN，与主机实现一致
+REDUCE(reduce_max, __int_as_float(0xff800000), (isnan(x) || x > y) ? x : y)
+REDUCE(reduce_min, __int_as_float(0x7f800000), (isnan(x) || x < y) ? x : y)
+"#;
+
+const REDUCE_BLOCK: u32 = 256;
+const REDUCE_GRID: u32 = 1024;
+
+fn cuda_error(e: impl std::fmt::Debug) -> String {
+    return format!("CUDA 错误：{:?}", e);
+}
+
+// 设备上的 f32 缓冲区。释放与读回都排在分配它的流上，因此读回会等到写入它的算子完成
+struct CudaBuffer {
+    slice: CudaSlice<f32>,
+}
+
+impl DeviceBuffer for CudaBuffer {
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn len(&self) -> usize {
+        return self.slice.len();
+    }
+
+    // memcpy_dtov 在流上排队拷贝并等待完成
+    fn read(&self) -> Result<Vec<f32>, String> {
+        return self
+            .slice
+            .stream()
+            .memcpy_dtov(&self.slice)
+            .map_err(cuda_error);
+    }
+}
+
+fn resident(slice: CudaSlice<f32>) -> Arc<dyn DeviceBuffer> {
+    return Arc::new(CudaBuffer { slice: slice });
+}
+
+pub(crate) struct CudaBackend {
+    ctx: Arc<CudaContext>,
+    stream: Arc<CudaStream>,
+    module: Arc<CudaModule>,
+    blas: CudaBlas,
+}
+
+impl CudaBackend {
+    fn new(ordinal: usize) -> Result<CudaBackend, String> {
+        if ordinal >= device_count()? {
+            return Err(format!("CUDA 设备 {} 不存在", ordinal));
+        }
+        let ctx = CudaContext::new(ordinal).map_err(cuda_error)?;
+        // 独立的非默认流，与其他库在默认流上的工作互不阻塞
+        let stream = ctx.new_stream().map_err(cuda_error)?;
+        let ptx = cudarc::nvrtc::compile_ptx(KERNELS).map_err(cuda_error)?;
+        let module = ctx.load_module(ptx).map_err(cuda_error)?;
+        let blas = CudaBlas::new(stream.clone()).map_err(cuda_error)?;
+
+        return Ok(CudaBackend {
+            ctx: ctx,
+            stream: stream,
+            module: module,
+            blas: blas,
+        });
+    }
+
+    fn slice<'a>(&self, buffer: &'a Arc<dyn DeviceBuffer>) -> Result<&'a CudaSlice<f32>, String> {
+        return match buffer.as_any().downcast_ref::<CudaBuffer>() {
+            Some(buffer) => Ok(&buffer.slice),
+            None => Err("CUDA 后端收到了其他设备的缓冲区".to_string()),
+        };
+    }
+
+    fn launch_elementwise(
+        &self,
+        kernel: &str,
+        inputs: &[&CudaSlice<f32>],
+        n: usize,
+    ) -> Result<CudaSlice<f32>, String> {
+        let function = self.module.load_function(kernel).map_err(cuda_error)?;
+        let mut out = self.stream.alloc_zeros::<f32>(n).map_err(cuda_error)?;
+        let len = n as u32;
+        let mut args = self.stream.launch_builder(&function);
+        for input in inputs {
+            args.arg(*input);
+        }
+        args.arg(&mut out).arg(&len);
+        unsafe { args.launch(LaunchConfig::for_num_elems(len)) }.map_err(cuda_error)?;
+
+        return Ok(out);
+    }
+}
+
+impl Backend for CudaBackend {
+    // 先拷进页锁定的暂存区，再异步传到设备；写合并内存只适合主机写、设备读
+    fn upload(&self, data: &[f32]) -> Result<Arc<dyn DeviceBuffer>, String> {
+        let mut pinned = unsafe { self.ctx.alloc_pinned::<f32>(data.len()) }.map_err(cuda_error)?;
+        pinned
+            .as_mut_slice()
+            .map_err(cuda_error)?
+            .copy_from_slice(data);
+
+        return Ok(resident(
+            self.stream.memcpy_stod(&pinned).map_err(cuda_error)?,
+        ));
+    }
+
+    fn binary(&self, op: BinaryOp, a: &Tensor, b: &Tensor) -> Result<Option<Tensor>, String> {
+        if a.shape != b.shape || a.data.is_empty() || a.data.len() > u32::MAX as usize {
+            return Ok(None);
+        }
+        let (da, db) = (a.device_buffer(self)?, b.device_buffer(self)?);
+        let kernel = format!("binary_{}", op.name());
+        let inputs = [self.slice(&da)?, self.slice(&db)?];
+        let out = self.launch_elementwise(&kernel, &inputs, a.data.len())?;
+
+        return Ok(Some(Tensor::from_resident(resident(out), a.shape.clone())?));
+    }
+
+    fn unary(&self, op: UnaryOp, x: &Tensor) -> Result<Option<Tensor>, String> {
+        if x.data.is_empty() || x.data.len() > u32::MAX as usize {
+            return Ok(None);
+        }
+        let dx = x.device_buffer(self)?;
+        let kernel = format!("unary_{}", op.name());
+        let out = self.launch_elementwise(&kernel, &[self.slice(&dx)?], x.data.len())?;
+
+        return Ok(Some(Tensor::from_resident(resident(out), x.shape.clone())?));
+    }
+
+    fn reduce(&self, op: ReduceOp, x: &Tensor) -> Result<Option<f32>, String> {
+        if x.data.is_empty() || x.data.len() > u32::MAX as usize {
+            return Ok(None);
+        }
+        let kernel = match op {
+            ReduceOp::Sum => "reduce_sum",
+            ReduceOp::Max => "reduce_max",
+            ReduceOp::Min => "reduce_min",
+        };
+
+        let n = x.data.len() as u32;
+        let blocks = n.div_ceil(REDUCE_BLOCK).min(REDUCE_GRID);
+        let dx = x.device_buffer(self)?;
+        let mut partial = self
+            .stream
+            .alloc_zeros::<f32>(blocks as usize)
+            .map_err(cuda_error)?;
+        let function = self.module.load_function(kernel).map_err(cuda_error)?;
+        let config = LaunchConfig {
+            grid_dim: (blocks, 1, 1),
+            block_dim: (REDUCE_BLOCK, 1, 1),
+            shared_mem_bytes: 0,
+        };
+        let mut args = self.stream.launch_builder(&function);
+        args.arg(self.slice(&dx)?).arg(&mut partial).arg(&n);
+        unsafe { args.launch(config) }.map_err(cuda_error)?;
+
+        let partial = self.stream.memcpy_dtov(&partial).map_err(cuda_error)?;
+        return Ok(Some(
+            partial
+                .into_iter()
+                .fold(op.identity(), |a, b| op.combine(a, b)),
+        ));
+    }
+
+    // 行主序的 C = A·B 等价于列主序的 Cᵀ = Bᵀ·Aᵀ，因此交换操作数后直接调用 sgemm
+    fn matmul(&self, a: &Tensor, b: &Tensor) -> Result<Option<Tensor>, String> {
+        let (ra, rb) = (a.shape.len(), b.shape.len());
+        if a.shape[..ra - 2] != b.shape[..rb - 2] {
+            return Ok(None);
+        }
+        let (m, k, n) = (a.shape[ra - 2], a.shape[ra - 1], b.shape[rb - 1]);
+        let batch: usize = a.shape[..ra - 2].iter().product();
+        if m == 0 || k == 0 || n == 0 || batch == 0 {
+            return Ok(None);
+        }
+        if [m, k, n, batch].iter().any(|&d| d > i32::MAX as usize) {
+            return Ok(None);
+        }
+
+        let (da, db) = (a.device_buffer(self)?, b.device_buffer(self)?);
+        let mut dc = self
+            .stream
+            .alloc_zeros::<f32>(batch * m * n)
+            .map_err(cuda_error)?;
+        let config = StridedBatchedConfig {
+            gemm: GemmConfig {
+                transa: cublasOperation_t::CUBLAS_OP_N,
+                transb: cublasOperation_t::CUBLAS_OP_N,
+                m: n as i32,
+                n: m as i32,
+                k: k as i32,
+                alpha: 1.0f32,
+                lda: n as i32,
+                ldb: k as i32,
+                beta: 0.0f32,
+                ldc: n as i32,
+            },
+            batch_size: batch as i32,
+            stride_a: (k * n) as i64,
+            stride_b: (m * k) as i64,
+            stride_c: (m * n) as i64,
+        };
+        unsafe {
+            self.blas
+                .gemm_strided_batched(config, self.slice(&db)?, self.slice(&da)?, &mut dc)
+        }
+        .map_err(cuda_error)?;
+
+        let mut shape = a.shape[..ra - 2].to_vec();
+        shape.push(m);
+        shape.push(n);
+        return Ok(Some(Tensor::from_resident(resident(dc), shape)?));
+    }
+}
+
+// 每个设备的后端在首次使用时创建并常驻，后续分派直接复用同一个流与模块
+static BACKENDS: Mutex<Vec<(usize, &'static CudaBackend)>> = Mutex::new(Vec::new());
+
+pub(crate) fn backend(ordinal: usize) -> Result<&'static dyn Backend, String> {
+    let mut backends = BACKENDS.lock().unwrap();
+    if let Some(&(_, backend)) = backends.iter().find(|(o, _)| *o == ordinal) {
+        return Ok(backend);
+    }
+    let backend: &'static CudaBackend = Box::leak(Box::new(CudaBackend::new(ordinal)?));
+    backends.push((ordinal, backend));
+
+    return Ok(backend);
+}
+
+// 没有安装驱动时返回 0，而不是在动态加载时 panic
+pub fn device_count() -> Result<usize, String> {
+    if !unsafe { cudarc::driver::sys::is_culib_present() } {
+        return Ok(0);
+    }
+    if !unsafe { cudarc::nvrtc::sys::is_culib_present() }
+        || !unsafe { cudarc::cublas::sys::is_culib_present() }
+    {
+        return Err("找到了 CUDA 驱动，但缺少 NVRTC 或 cuBLAS 运行库".to_string());
+    }
+
+    return Ok(CudaContext::device_count().map_err(cuda_error)? as usize);
+}
+
+// 等待设备上已排队的工作全部完成
+pub fn synchronize(ordinal: usize) -> Result<(), String> {
+    let backends = BACKENDS.lock().unwrap();
+    if let Some((_, backend)) = backends.iter().find(|(o, _)| *o == ordinal) {
+        backend.stream.synchronize().map_err(cuda_error)?;
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Device, Tensor};
+
+    fn gpu() -> bool {
+        return super::device_count().unwrap_or(0) > 0;
+    }
+
+    // 只在有 GPU 的机器上检验数值；没有设备时确认分派报错而不是 panic
+    #[test]
+    fn dispatched_ops_match_host_results() {
+        let a = Tensor::new((0..12).map(|i| i as f32 - 5.0).collect(), vec![2, 2, 3]).unwrap();
+        let b = Tensor::new((0..18).map(|i| i as f32 * 0.5).collect(), vec![2, 3, 3]).unwrap();
+        if !gpu() {
+            assert!(!Device::Cuda(0).is_available());
+            assert!(a.to(Device::Cuda(0)).is_err());
+            return;
+        }
+
+        let (ga, gb) = (
+            a.to(Device::Cuda(0)).unwrap(),
+            b.to(Device::Cuda(0)).unwrap(),
+        );
+        let product = ga.matmul(&gb).unwrap();
+        assert_eq!(product.device(), Device::Cuda(0));
+        assert_eq!(product.data, a.matmul(&b).unwrap().data);
+        assert_eq!(ga.add(&ga).unwrap().data, a.add(&a).unwrap().data);
+        assert_eq!(ga.relu().unwrap().data, a.relu().unwrap().data);
+        assert_eq!(ga.sum().unwrap(), a.sum().unwrap());
+        assert_eq!(ga.max().unwrap(), 6.0);
+        super::synchronize(0).unwrap();
+    }
+
+    // 算子链的中间结果留在设备上，不产生主机副本
+    #[test]
+    fn chained_ops_stay_resident_until_read() {
+        if !gpu() {
+            return;
+        }
+        let a = Tensor::new(
+            (0..1000).map(|i| (i as f32 - 500.0) / 100.0).collect(),
+            vec![10, 100],
+        )
+        .unwrap();
+        let w = Tensor::new(
+            (0..100 * 8).map(|i| (i % 7) as f32 - 3.0).collect(),
+            vec![100, 8],
+        )
+        .unwrap();
+        let (ga, gw) = (
+            a.to(Device::Cuda(0)).unwrap(),
+            w.to(Device::Cuda(0)).unwrap(),
+        );
+
+        let hidden = ga.mul(&ga).unwrap().sub(&ga).unwrap().tanh().unwrap();
+        let out = hidden.matmul(&gw).unwrap().relu().unwrap();
+        for t in [&hidden, &out] {
+            assert_eq!(t.device(), Device::Cuda(0));
+            assert!(t.data.resident().is_some());
+            assert!(!t.data.is_on_host());
+        }
+
+        let expected = a
+            .mul(&a)
+            .unwrap()
+            .sub(&a)
+            .unwrap()
+            .tanh()
+            .unwrap()
+            .matmul(&w)
+            .unwrap()
+            .relu()
+            .unwrap();
+        for (x, y) in out.data.iter().zip(expected.data.iter()) {
+            assert!((x - y).abs() < 1e-3, "{} 与 {}", x, y);
+        }
+        assert!(out.data.is_on_host());
+    }
+
+    #[test]
+    fn host_readback_and_fallbacks_keep_the_device() {
+        if !gpu() {
+            return;
+        }
+        let a = Tensor::new((0..6).map(|i| i as f32).collect(), vec![2, 3]).unwrap();
+        let ga = a.to(Device::Cuda(0)).unwrap();
+        let doubled = ga.add(&ga).unwrap();
+
+        // 主机实现的算子读取设备结果，结果仍在同一设备上，可以继续参与分派
+        let column = doubled.sum_axis(0).unwrap();
+        assert_eq!(column.device(), Device::Cuda(0));
+        let shifted = doubled.add(&column.reshaped(vec![1, 3]).unwrap());
+        assert!(shifted.is_ok());
+
+        let back = doubled.to(Device::Cpu).unwrap();
+        assert_eq!(back.device(), Device::Cpu);
+        assert!(back.data.resident().is_none());
+        assert_eq!(back.data.to_vec(), vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+
+        // 主机端写入后设备副本失效，下一次分派重新上传
+        let mut edited = doubled.clone();
+        edited.data_mut().unwrap()[0] = 100.0;
+        assert!(edited.data.resident().is_none());
+        assert_eq!(edited.relu().unwrap().data[0], 100.0);
+    }
+}
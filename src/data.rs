@@ -0,0 +1,272 @@
+use crate::Tensor;
+use crate::random::{self, Rng};
+
+#[derive(Debug, Clone)]
+pub struct Batcher {
+    features: Tensor,
+    labels: Option<Tensor>,
+    batch_size: usize,
+    shuffle: bool,
+    drop_last: bool,
+    rng: Rng,
+}
+
+pub struct Batches<'a> {
+    batcher: &'a Batcher,
+    order: Vec<usize>,
+    position: usize,
+}
+
+impl Batcher {
+    pub fn new(
+        features: Tensor,
+        labels: Option<Tensor>,
+        batch_size: usize,
+    ) -> Result<Self, String> {
+        if features.shape.is_empty() {
+            return Err("Batcher 不支持零维特征张量".to_string());
+        }
+        if batch_size == 0 {
+            return Err("批大小必须为正".to_string());
+        }
+        if let Some(labels) = &labels
+            && labels.shape.first() != Some(&features.shape[0])
+        {
+            return Err(format!(
+                "标签形状 {:?} 与特征样本数 {} 不匹配",
+                labels.shape, features.shape[0]
+            ));
+        }
+        let seed = random::derive_seed();
+
+        return Ok(Batcher {
+            features: features,
+            labels: labels,
+            batch_size: batch_size,
+            shuffle: true,
+            drop_last: false,
+            rng: Rng::new(seed),
+        });
+    }
+
+    pub fn shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        return self;
+    }
+
+    pub fn drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        return self;
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        return self;
+    }
+
+    pub fn num_samples(&self) -> usize {
+        return self.features.shape[0];
+    }
+
+    pub fn num_batches(&self) -> usize {
+        if self.drop_last {
+            return self.num_samples() / self.batch_size;
+        }
+
+        return self.num_samples().div_ceil(self.batch_size);
+    }
+
+    pub fn epoch(&mut self) -> Batches<'_> {
+        let mut order: Vec<usize> = (0..self.num_samples()).collect();
+        if self.shuffle {
+            self.rng.shuffle(&mut order);
+        }
+
+        return Batches {
+            batcher: self,
+            order: order,
+            position: 0,
+        };
+    }
+}
+
+impl Iterator for Batches<'_> {
+    type Item = (Tensor, Option<Tensor>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batcher = self.batcher;
+        let left = self.order.len() - self.position;
+        if left == 0 || (batcher.drop_last && left < batcher.batch_size) {
+            return None;
+        }
+
+        let end = self.position + batcher.batch_size.min(left);
+        let picks: Vec<f32> = self.order[self.position..end]
+            .iter()
+            .map(|&i| i as f32)
+            .collect();
+        let len = picks.len();
+        self.position = end;
+        let index = Tensor::new(picks, vec![len]).ok()?;
+
+        let features = batcher.features.index_select(0, &index).ok()?;
+        let labels = match &batcher.labels {
+            Some(labels) => Some(labels.index_select(0, &index).ok()?),
+            None => None,
+        };
+
+        return Some((features, labels));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncrementalTensor {
+    row_shape: Vec<usize>,
+    data: Vec<f32>,
+    rows: usize,
+}
+
+impl IncrementalTensor {
+    pub fn new(row_shape: Vec<usize>) -> Self {
+        return IncrementalTensor::with_capacity(row_shape, 0);
+    }
+
+    pub fn with_capacity(row_shape: Vec<usize>, rows: usize) -> Self {
+        let row_len: usize = row_shape.iter().product();
+
+        return IncrementalTensor {
+            row_shape: row_shape,
+            data: Vec::with_capacity(rows * row_len),
+            rows: 0,
+        };
+    }
+
+    pub fn row_shape(&self) -> &[usize] {
+        return &self.row_shape;
+    }
+
+    pub fn rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn push_row(&mut self, row: &[f32]) -> Result<(), String> {
+        let row_len: usize = self.row_shape.iter().product();
+        if row.len() != row_len {
+            return Err(format!(
+                "行长度 {} 与行形状 {:?} 不匹配",
+                row.len(),
+                self.row_shape
+            ));
+        }
+        self.data.extend_from_slice(row);
+        self.rows += 1;
+
+        return Ok(());
+    }
+
+    pub fn append(&mut self, block: &Tensor) -> Result<(), String> {
+        if block.shape.len() != self.row_shape.len() + 1 || block.shape[1..] != self.row_shape {
+            return Err(format!(
+                "块形状 {:?} 与行形状 {:?} 不匹配，应为 [k, {:?}...]",
+                block.shape, self.row_shape, self.row_shape
+            ));
+        }
+        self.data.extend_from_slice(&block.data);
+        self.rows += block.shape[0];
+
+        return Ok(());
+    }
+
+    pub fn finish(self) -> Result<Tensor, String> {
+        let mut shape = Vec::with_capacity(self.row_shape.len() + 1);
+        shape.push(self.rows);
+        shape.extend_from_slice(&self.row_shape);
+
+        return Tensor::new(self.data, shape);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(n: usize) -> (Tensor, Tensor) {
+        let x = Tensor::new((0..n * 2).map(|i| i as f32).collect(), vec![n, 2]).unwrap();
+        let y = Tensor::new((0..n).map(|i| i as f32).collect(), vec![n]).unwrap();
+
+        return (x, y);
+    }
+
+    #[test]
+    fn epochs_visit_every_sample_once_with_aligned_labels() {
+        let (x, y) = rows(10);
+        let mut batcher = Batcher::new(x, Some(y), 4).unwrap().seed(7);
+        assert_eq!(batcher.num_batches(), 3);
+
+        let mut seen = Vec::new();
+        let mut sizes = Vec::new();
+        for (features, labels) in batcher.epoch() {
+            let labels = labels.unwrap();
+            sizes.push(labels.shape[0]);
+            for (r, &label) in labels.data.iter().enumerate() {
+                assert_eq!(features.data[r * 2], label * 2.0);
+                seen.push(label as usize);
+            }
+        }
+        assert_eq!(sizes, vec![4, 4, 2]);
+        let first: Vec<usize> = seen.clone();
+        seen.sort();
+        assert_eq!(seen, (0..10).collect::<Vec<_>>());
+        assert_ne!(first, (0..10).collect::<Vec<_>>());
+
+        // 每个 epoch 重新打乱，同一种子可复现
+        let second: Vec<f32> = batcher
+            .epoch()
+            .flat_map(|(_, l)| l.unwrap().data.to_vec())
+            .collect();
+        let (x, y) = rows(10);
+        let mut replay = Batcher::new(x, Some(y), 4).unwrap().seed(7);
+        replay.epoch().count();
+        let replayed: Vec<f32> = replay
+            .epoch()
+            .flat_map(|(_, l)| l.unwrap().data.to_vec())
+            .collect();
+        assert_eq!(second, replayed);
+    }
+
+    #[test]
+    fn drop_last_and_sequential_order() {
+        let (x, _) = rows(10);
+        let mut batcher = Batcher::new(x, None, 4)
+            .unwrap()
+            .shuffle(false)
+            .drop_last(true);
+        let batches: Vec<_> = batcher.epoch().collect();
+        assert_eq!(batches.len(), 2);
+        assert!(batches[0].1.is_none());
+        assert_eq!(batches[1].0.data[0], 8.0);
+
+        let (x, y) = rows(3);
+        assert!(Batcher::new(x.clone(), Some(y), 0).is_err());
+        assert!(Batcher::new(x, Some(Tensor::zeros(vec![2]).unwrap()), 2).is_err());
+    }
+
+    #[test]
+    fn incremental_tensor_accepts_rows_and_blocks() {
+        let mut builder = IncrementalTensor::with_capacity(vec![2], 4);
+        builder.push_row(&[1.0, 2.0]).unwrap();
+        let (block, _) = rows(2);
+        builder.append(&block).unwrap();
+        assert_eq!(builder.rows(), 3);
+        assert!(builder.push_row(&[1.0]).is_err());
+        assert!(builder.append(&Tensor::zeros(vec![2, 3]).unwrap()).is_err());
+
+        let out = builder.finish().unwrap();
+        assert_eq!(out.shape, [3, 2]);
+        assert_eq!(out.data.to_vec(), vec![1.0, 2.0, 0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(
+            IncrementalTensor::new(vec![4]).finish().unwrap().shape,
+            [0, 4]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::Tensor;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pca {
+    pub components: Tensor,
+    pub explained_variance: Tensor,
+    pub explained_variance_ratio: Tensor,
+    pub mean: Tensor,
+}
+
+impl Pca {
+    pub fn transform(&self, data: &Tensor) -> Result<Tensor, String> {
+        return data
+            .sub(&self.mean)?
+            .matmul(&self.components.transpose(0, 1)?);
+    }
+
+    pub fn inverse_transform(&self, projected: &Tensor) -> Result<Tensor, String> {
+        return projected.matmul(&self.components)?.add(&self.mean);
+    }
+}
+
+pub fn pca(data: &Tensor, n_components: usize) -> Result<Pca, String> {
+    if data.shape.len() != 2 {
+        return Err(format!("PCA 需要二维数据矩阵，实际形状为 {:?}", data.shape));
+    }
+    let (rows, features) = (data.shape[0], data.shape[1]);
+    if rows < 2 {
+        return Err(format!("PCA 至少需要 2 个样本，实际为 {}", rows));
+    }
+    if n_components == 0 || n_components > features {
+        return Err(format!(
+            "主成分数量 {} 不在 [1, {}] 范围内",
+            n_components, features
+        ));
+    }
+
+    let mean = data.mean_axis(0)?;
+    let centered = data.sub(&mean)?;
+    let covariance = centered
+        .transpose(0, 1)?
+        .matmul(&centered)?
+        .mul_scalar(1.0 / (rows - 1) as f32)?;
+    let (values, vectors) = covariance.eigh()?;
+
+    let total: f32 = values.data.iter().map(|v| v.max(0.0)).sum();
+    let mut components = Vec::with_capacity(n_components * features);
+    let mut variance = Vec::with_capacity(n_components);
+    for c in (features - n_components..features).rev() {
+        let mut column: Vec<f32> = (0..features)
+            .map(|r| vectors.data[r * features + c])
+            .collect();
+        // 固定符号：令绝对值最大的分量为正，保证结果可复现
+        let pivot = column
+            .iter()
+            .cloned()
+            .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+            .unwrap_or(0.0);
+        if pivot < 0.0 {
+            column.iter_mut().for_each(|v| *v = -*v);
+        }
+        components.extend(column);
+        variance.push(values.data[c].max(0.0));
+    }
+    let ratio = variance
+        .iter()
+        .map(|v| if total > 0.0 { v / total } else { 0.0 })
+        .collect();
+
+    return Ok(Pca {
+        components: Tensor::new(components, vec![n_components, features])?,
+        explained_variance: Tensor::new(variance, vec![n_components])?,
+        explained_variance_ratio: Tensor::new(ratio, vec![n_components])?,
+        mean: mean,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 沿 (3, 4)/5 方向分布、垂直方向有小扰动的点；扰动均值为 0 且与主方向不相关
+    fn line() -> Tensor {
+        let mut values = Vec::new();
+        let noise = [0.01f32, -0.02, 0.0, 0.02, -0.01];
+        for (t, e) in [-2.0f32, -1.0, 0.0, 1.0, 2.0].iter().zip(noise) {
+            values.push(1.0 + 0.6 * t - 0.8 * e);
+            values.push(2.0 + 0.8 * t + 0.6 * e);
+        }
+        return Tensor::new(values, vec![5, 2]).unwrap();
+    }
+
+    #[test]
+    fn pca_finds_principal_direction() {
+        let result = pca(&line(), 2).unwrap();
+        let c = &result.components.data;
+        assert!((c[0] - 0.6).abs() < 1e-3 && (c[1] - 0.8).abs() < 1e-3);
+        assert!((c[0] * c[2] + c[1] * c[3]).abs() < 1e-4);
+        assert!((result.explained_variance.data[0] - 2.5).abs() < 1e-3);
+        assert!(result.explained_variance.data[0] >= result.explained_variance.data[1]);
+        let ratio: f32 = result.explained_variance_ratio.data.iter().sum();
+        assert!((ratio - 1.0).abs() < 1e-5);
+        assert!((result.mean.data[0] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn transform_round_trip() {
+        let data = line();
+        let full = pca(&data, 2).unwrap();
+        let back = full
+            .inverse_transform(&full.transform(&data).unwrap())
+            .unwrap();
+        for (a, b) in back.data.iter().zip(data.data.iter()) {
+            assert!((a - b).abs() < 1e-4);
+        }
+
+        // 只保留一个主成分时，重建误差等于丢弃方向上的扰动
+        let one = pca(&data, 1).unwrap();
+        let projected = one.transform(&data).unwrap();
+        assert_eq!(projected.shape, vec![5, 1]);
+        let back = one.inverse_transform(&projected).unwrap();
+        for (a, b) in back.data.iter().zip(data.data.iter()) {
+            assert!((a - b).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn constant_data_has_zero_ratio() {
+        let result = pca(&Tensor::ones(vec![3, 2]).unwrap(), 1).unwrap();
+        assert_eq!(result.explained_variance.data[0], 0.0);
+        assert_eq!(result.explained_variance_ratio.data[0], 0.0);
+    }
+
+    #[test]
+    fn pca_rejects_bad_input() {
+        let data = line();
+        assert!(pca(&data, 0).is_err());
+        assert!(pca(&data, 3).is_err());
+        assert!(pca(&Tensor::zeros(vec![1, 2]).unwrap(), 1).is_err());
+        assert!(pca(&Tensor::zeros(vec![0, 2]).unwrap(), 1).is_err());
+        assert!(pca(&Tensor::zeros(vec![4]).unwrap(), 1).is_err());
+    }
+}
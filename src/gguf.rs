@@ -0,0 +1,392 @@
+use std::path::Path;
+
+use crate::Tensor;
+use crate::half::{bf16_to_f32, f16_to_f32};
+
+const MAGIC: &[u8; 4] = b"GGUF";
+const DEFAULT_ALIGNMENT: u64 = 32;
+const QK: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataValue {
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Array(Vec<MetadataValue>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GgmlType {
+    F32,
+    F16,
+    Q4_0,
+    Q8_0,
+    Bf16,
+    Other(u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TensorInfo {
+    pub name: String,
+    pub shape: Vec<usize>,
+    pub ggml_type: GgmlType,
+    offset: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct GgufFile {
+    version: u32,
+    metadata: Vec<(String, MetadataValue)>,
+    tensors: Vec<TensorInfo>,
+    bytes: Vec<u8>,
+    data_start: usize,
+}
+
+impl GgmlType {
+    fn from_code(code: u32) -> GgmlType {
+        return match code {
+            0 => GgmlType::F32,
+            1 => GgmlType::F16,
+            2 => GgmlType::Q4_0,
+            8 => GgmlType::Q8_0,
+            30 => GgmlType::Bf16,
+            other => GgmlType::Other(other),
+        };
+    }
+
+    // 返回 (每块元素数, 每块字节数)
+    fn block(self) -> Option<(usize, usize)> {
+        return match self {
+            GgmlType::F32 => Some((1, 4)),
+            GgmlType::F16 | GgmlType::Bf16 => Some((1, 2)),
+            GgmlType::Q4_0 => Some((QK, 2 + QK / 2)),
+            GgmlType::Q8_0 => Some((QK, 2 + QK)),
+            GgmlType::Other(_) => None,
+        };
+    }
+}
+
+impl MetadataValue {
+    pub fn as_u64(&self) -> Option<u64> {
+        return match self {
+            MetadataValue::UInt(v) => Some(*v),
+            MetadataValue::Int(v) if *v >= 0 => Some(*v as u64),
+            _ => None,
+        };
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        return match self {
+            MetadataValue::String(s) => Some(s),
+            _ => None,
+        };
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() - self.pos < len {
+            return Err(format!(
+                "GGUF 文件在偏移 {} 处被截断（需要 {} 字节）",
+                self.pos, len
+            ));
+        }
+        let out = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+
+        return Ok(out);
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        return Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()));
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        return Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()));
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u64()? as usize;
+        return String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|e| format!("GGUF 字符串不是有效的 UTF-8：{}", e));
+    }
+
+    fn value(&mut self, kind: u32) -> Result<MetadataValue, String> {
+        return Ok(match kind {
+            0 => MetadataValue::UInt(self.take(1)?[0] as u64),
+            1 => MetadataValue::Int(self.take(1)?[0] as i8 as i64),
+            2 => MetadataValue::UInt(u16::from_le_bytes(self.take(2)?.try_into().unwrap()) as u64),
+            3 => MetadataValue::Int(i16::from_le_bytes(self.take(2)?.try_into().unwrap()) as i64),
+            4 => MetadataValue::UInt(self.u32()? as u64),
+            5 => MetadataValue::Int(self.u32()? as i32 as i64),
+            6 => MetadataValue::Float(f32::from_bits(self.u32()?) as f64),
+            7 => MetadataValue::Bool(self.take(1)?[0] != 0),
+            8 => MetadataValue::String(self.string()?),
+            9 => {
+                let inner = self.u32()?;
+                let len = self.u64()? as usize;
+                let mut items = Vec::with_capacity(len.min(1 << 16));
+                for _ in 0..len {
+                    items.push(self.value(inner)?);
+                }
+                MetadataValue::Array(items)
+            }
+            10 => MetadataValue::UInt(self.u64()?),
+            11 => MetadataValue::Int(self.u64()? as i64),
+            12 => MetadataValue::Float(f64::from_bits(self.u64()?)),
+            other => return Err(format!("未知的 GGUF 元数据类型 {}", other)),
+        });
+    }
+}
+
+fn dequantize(ggml_type: GgmlType, raw: &[u8], numel: usize) -> Vec<f32> {
+    let mut out = crate::alloc::allocate(numel);
+    match ggml_type {
+        GgmlType::F32 => out.extend(
+            raw.chunks_exact(4)
+                .map(|c| f32::from_le_bytes(c.try_into().unwrap())),
+        ),
+        GgmlType::F16 => out.extend(
+            raw.chunks_exact(2)
+                .map(|c| f16_to_f32(u16::from_le_bytes([c[0], c[1]]))),
+        ),
+        GgmlType::Bf16 => out.extend(
+            raw.chunks_exact(2)
+                .map(|c| bf16_to_f32(u16::from_le_bytes([c[0], c[1]]))),
+        ),
+        GgmlType::Q8_0 => {
+            for block in raw.chunks_exact(2 + QK) {
+                let d = f16_to_f32(u16::from_le_bytes([block[0], block[1]]));
+                out.extend(block[2..].iter().map(|&q| q as i8 as f32 * d));
+            }
+        }
+        GgmlType::Q4_0 => {
+            // 低 4 位依次给出前 16 个元素，高 4 位给出后 16 个
+            for block in raw.chunks_exact(2 + QK / 2) {
+                let d = f16_to_f32(u16::from_le_bytes([block[0], block[1]]));
+                let qs = &block[2..];
+                out.extend(qs.iter().map(|&q| ((q & 0x0f) as i32 - 8) as f32 * d));
+                out.extend(qs.iter().map(|&q| ((q >> 4) as i32 - 8) as f32 * d));
+            }
+        }
+        GgmlType::Other(_) => unreachable!(),
+    }
+
+    return out;
+}
+
+impl GgufFile {
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<GgufFile, String> {
+        let mut cursor = Cursor {
+            bytes: &bytes,
+            pos: 0,
+        };
+        if cursor.take(4)? != MAGIC {
+            return Err("不是 GGUF 文件（魔数不匹配）".to_string());
+        }
+        let version = cursor.u32()?;
+        if !(2..=3).contains(&version) {
+            return Err(format!("不支持的 GGUF 版本 {}", version));
+        }
+        let tensor_count = cursor.u64()?;
+        let kv_count = cursor.u64()?;
+
+        let mut metadata = Vec::new();
+        for _ in 0..kv_count {
+            let key = cursor.string()?;
+            let kind = cursor.u32()?;
+            let value = cursor.value(kind)?;
+            metadata.push((key, value));
+        }
+
+        let mut tensors = Vec::new();
+        for _ in 0..tensor_count {
+            let name = cursor.string()?;
+            let n_dims = cursor.u32()?;
+            if n_dims > 8 {
+                return Err(format!("张量 {} 的维度数 {} 超出 GGUF 上限", name, n_dims));
+            }
+            let mut shape = Vec::with_capacity(n_dims as usize);
+            for _ in 0..n_dims {
+                shape.push(cursor.u64()? as usize);
+            }
+            // GGUF 维度从最快变化的维度开始，这里转为行主序
+            shape.reverse();
+            let ggml_type = GgmlType::from_code(cursor.u32()?);
+            let offset = cursor.u64()?;
+            tensors.push(TensorInfo {
+                name: name,
+                shape: shape,
+                ggml_type: ggml_type,
+                offset: offset,
+            });
+        }
+
+        let alignment = metadata
+            .iter()
+            .find(|(k, _)| k == "general.alignment")
+            .and_then(|(_, v)| v.as_u64())
+            .unwrap_or(DEFAULT_ALIGNMENT);
+        if alignment == 0 {
+            return Err("GGUF 对齐值不能为 0".to_string());
+        }
+        let data_start = (cursor.pos as u64).div_ceil(alignment) * alignment;
+
+        return Ok(GgufFile {
+            version: version,
+            metadata: metadata,
+            tensors: tensors,
+            data_start: data_start as usize,
+            bytes: bytes,
+        });
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<GgufFile, String> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| format!("无法读取 GGUF 文件 {}：{}", path.display(), e))?;
+
+        return GgufFile::from_bytes(bytes);
+    }
+
+    pub fn version(&self) -> u32 {
+        return self.version;
+    }
+
+    pub fn metadata(&self) -> &[(String, MetadataValue)] {
+        return &self.metadata;
+    }
+
+    pub fn get_metadata(&self, key: &str) -> Option<&MetadataValue> {
+        return self.metadata.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    }
+
+    pub fn tensor_infos(&self) -> &[TensorInfo] {
+        return &self.tensors;
+    }
+
+    pub fn tensor(&self, name: &str) -> Result<Tensor, String> {
+        let Some(info) = self.tensors.iter().find(|t| t.name == name) else {
+            return Err(format!("GGUF 文件中没有名为 {} 的张量", name));
+        };
+        let Some((block_len, block_bytes)) = info.ggml_type.block() else {
+            return Err(format!(
+                "张量 {} 的 ggml 类型 {:?} 暂不支持",
+                name, info.ggml_type
+            ));
+        };
+        let numel: usize = info.shape.iter().product();
+        if !numel.is_multiple_of(block_len) {
+            return Err(format!(
+                "张量 {} 元素数 {} 不是块大小 {} 的倍数",
+                name, numel, block_len
+            ));
+        }
+
+        let size = numel / block_len * block_bytes;
+        let start = self.data_start + info.offset as usize;
+        let Some(raw) = self.bytes.get(start..start + size) else {
+            return Err(format!(
+                "张量 {} 的数据区 [{}, {}) 超出文件长度 {}",
+                name,
+                start,
+                start + size,
+                self.bytes.len()
+            ));
+        };
+
+        return Tensor::new(dequantize(info.ggml_type, raw, numel), info.shape.clone());
+    }
+
+    pub fn tensors(&self) -> Result<Vec<(String, Tensor)>, String> {
+        return self
+            .tensors
+            .iter()
+            .map(|info| Ok((info.name.clone(), self.tensor(&info.name)?)))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(s: &str, out: &mut Vec<u8>) {
+        out.extend((s.len() as u64).to_le_bytes());
+        out.extend(s.as_bytes());
+    }
+
+    fn tensor_info(name: &str, dims: &[u64], kind: u32, offset: u64, out: &mut Vec<u8>) {
+        string(name, out);
+        out.extend((dims.len() as u32).to_le_bytes());
+        for d in dims {
+            out.extend(d.to_le_bytes());
+        }
+        out.extend(kind.to_le_bytes());
+        out.extend(offset.to_le_bytes());
+    }
+
+    #[test]
+    fn loads_f32_and_q8_0_tensors_with_metadata() {
+        let mut file = Vec::new();
+        file.extend(MAGIC);
+        file.extend(3u32.to_le_bytes());
+        file.extend(2u64.to_le_bytes());
+        file.extend(2u64.to_le_bytes());
+        string("general.architecture", &mut file);
+        file.extend(8u32.to_le_bytes());
+        string("llama", &mut file);
+        string("llama.context_length", &mut file);
+        file.extend(4u32.to_le_bytes());
+        file.extend(2048u32.to_le_bytes());
+        // GGUF 维度 [3, 2] 对应行主序形状 [2, 3]
+        tensor_info("norm.weight", &[3, 2], 0, 0, &mut file);
+        tensor_info("attn.q", &[32], 8, 32, &mut file);
+        file.resize(file.len().div_ceil(32) * 32, 0);
+
+        let mut data = Vec::new();
+        for v in [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            data.extend(v.to_le_bytes());
+        }
+        data.resize(32, 0);
+        data.extend(0x3800u16.to_le_bytes()); // f16 0.5
+        data.extend((0..32).map(|i| (i as i8 - 16) as u8));
+        file.extend(data);
+
+        let gguf = GgufFile::from_bytes(file.clone()).unwrap();
+        assert_eq!(gguf.version(), 3);
+        assert_eq!(
+            gguf.get_metadata("general.architecture").unwrap().as_str(),
+            Some("llama")
+        );
+        assert_eq!(
+            gguf.get_metadata("llama.context_length").unwrap().as_u64(),
+            Some(2048)
+        );
+
+        let norm = gguf.tensor("norm.weight").unwrap();
+        assert_eq!(norm.shape, [2, 3]);
+        assert_eq!(norm.data.to_vec(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let q = gguf.tensor("attn.q").unwrap();
+        assert_eq!(q.data[0], -8.0);
+        assert_eq!(q.data[31], 7.5);
+        assert_eq!(gguf.tensors().unwrap().len(), 2);
+        assert!(gguf.tensor("missing").is_err());
+
+        file.truncate(file.len() - 1);
+        assert!(
+            GgufFile::from_bytes(file)
+                .unwrap()
+                .tensor("attn.q")
+                .is_err()
+        );
+        assert!(GgufFile::from_bytes(b"GGML".to_vec()).is_err());
+    }
+}
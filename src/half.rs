@@ -0,0 +1,53 @@
+pub(crate) fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exp = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+    let magnitude = match exp {
+        0 => (mantissa as f32) * 2f32.powi(-24),
+        0x1f if mantissa == 0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => f32::from_bits(((exp + 112) << 23) | (mantissa << 13)),
+    };
+
+    return f32::from_bits(magnitude.to_bits() | sign);
+}
+
+pub(crate) fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let abs = value.abs();
+    if abs.is_nan() {
+        return sign | 0x7e00;
+    }
+    if abs >= 65520.0 {
+        return sign | 0x7c00;
+    }
+    if abs < 2f32.powi(-14) {
+        // 非规格化数：以 2^-24 为单位就近取偶
+        return sign | (abs * 2f32.powi(24)).round_ties_even() as u16;
+    }
+
+    let exp = ((abs.to_bits() >> 23) as i32 - 127 + 15) as u32;
+    let mantissa = abs.to_bits() & 0x7f_ffff;
+    let mut half = (exp << 10) | (mantissa >> 13);
+    let rest = mantissa & 0x1fff;
+    if rest > 0x1000 || (rest == 0x1000 && half & 1 == 1) {
+        half += 1;
+    }
+
+    return sign | half as u16;
+}
+
+pub(crate) fn bf16_to_f32(bits: u16) -> f32 {
+    return f32::from_bits((bits as u32) << 16);
+}
+
+pub(crate) fn f32_to_bf16(value: f32) -> u16 {
+    if value.is_nan() {
+        return ((value.to_bits() >> 16) | 0x40) as u16;
+    }
+    let bits = value.to_bits();
+    let rounded = bits + 0x7fff + ((bits >> 16) & 1);
+
+    return (rounded >> 16) as u16;
+}
@@ -0,0 +1,702 @@
+use std::path::Path;
+
+use crate::{DType, Endianness, Tensor};
+
+// 只实现 HDF5 的一个子集，覆盖 h5py/libhdf5 默认（libver="earliest"）写出的文件：
+// 0/1 版超级块、1 版对象头、符号表组，连续或紧凑存储且不带过滤器的数值数据集。
+// 写出时使用同样的结构，数据集都放在根组下，元素为小端 f32。
+const SIGNATURE: &[u8; 8] = b"\x89HDF\r\n\x1a\n";
+const UNDEFINED: u64 = u64::MAX;
+const SUPERBLOCK_SIZE: usize = 96;
+const INTERNAL_K: usize = 16;
+const MAX_DEPTH: usize = 32;
+
+const MSG_DATASPACE: u16 = 0x0001;
+const MSG_DATATYPE: u16 = 0x0003;
+const MSG_FILL_VALUE: u16 = 0x0005;
+const MSG_LAYOUT: u16 = 0x0008;
+const MSG_FILTERS: u16 = 0x000b;
+const MSG_CONTINUATION: u16 = 0x0010;
+const MSG_SYMBOL_TABLE: u16 = 0x0011;
+
+fn le_uint(bytes: &[u8]) -> u64 {
+    return bytes.iter().rev().fold(0, |acc, &b| (acc << 8) | b as u64);
+}
+
+fn field(msg: &[u8], pos: usize, len: usize) -> Result<&[u8], String> {
+    return msg.get(pos..pos + len).ok_or_else(|| {
+        format!(
+            "HDF5 消息长度 {} 不足（需要 {} 字节）",
+            msg.len(),
+            pos + len
+        )
+    });
+}
+
+struct File<'a> {
+    bytes: &'a [u8],
+    base: u64,
+    offset_size: usize,
+    length_size: usize,
+}
+
+impl<'a> File<'a> {
+    fn open(bytes: &'a [u8]) -> Result<(File<'a>, u64), String> {
+        if bytes.len() < 24 || &bytes[..8] != SIGNATURE {
+            return Err("不是 HDF5 文件（签名不匹配）".to_string());
+        }
+        if bytes[8] > 1 {
+            return Err(format!(
+                "HDF5 超级块版本 {} 暂不支持（仅支持 0 与 1）",
+                bytes[8]
+            ));
+        }
+        let (offset_size, length_size) = (bytes[13] as usize, bytes[14] as usize);
+        if ![2, 4, 8].contains(&offset_size) || ![2, 4, 8].contains(&length_size) {
+            return Err(format!(
+                "HDF5 地址/长度字节数 {}/{} 无效",
+                offset_size, length_size
+            ));
+        }
+        let mut file = File {
+            bytes: bytes,
+            base: 0,
+            offset_size: offset_size,
+            length_size: length_size,
+        };
+
+        // 1 版超级块多出 4 字节（索引存储 K 值与保留字段）
+        let pos = if bytes[8] == 1 { 28 } else { 24 };
+        file.base = file.uint(pos, offset_size)?;
+        let root_entry = pos + 4 * offset_size as u64;
+        let root = file.uint(root_entry + length_size as u64, offset_size)?;
+
+        return Ok((file, root));
+    }
+
+    // pos 为相对基地址的文件地址
+    fn slice(&self, pos: u64, len: usize) -> Result<&'a [u8], String> {
+        let start = self.base.saturating_add(pos);
+        if start > self.bytes.len() as u64 || self.bytes.len() - (start as usize) < len {
+            return Err(format!(
+                "HDF5 文件在偏移 {} 处被截断（需要 {} 字节）",
+                start, len
+            ));
+        }
+
+        return Ok(&self.bytes[start as usize..start as usize + len]);
+    }
+
+    fn uint(&self, pos: u64, size: usize) -> Result<u64, String> {
+        return Ok(le_uint(self.slice(pos, size)?));
+    }
+
+    fn is_undefined(&self, addr: u64) -> bool {
+        return addr == UNDEFINED >> (64 - 8 * self.offset_size);
+    }
+
+    fn c_string(&self, pos: u64) -> Result<String, String> {
+        let rest = self
+            .slice(pos, 0)
+            .map(|_| &self.bytes[(self.base + pos) as usize..])?;
+        let Some(end) = rest.iter().position(|&b| b == 0) else {
+            return Err(format!("HDF5 名称在偏移 {} 处没有结尾", pos));
+        };
+
+        return String::from_utf8(rest[..end].to_vec())
+            .map_err(|e| format!("HDF5 名称不是有效的 UTF-8：{}", e));
+    }
+
+    // 依次展开续块，返回 (消息类型, 消息数据)
+    fn messages(&self, header: u64) -> Result<Vec<(u16, &'a [u8])>, String> {
+        let prefix = self.slice(header, 16)?;
+        if prefix[0] != 1 {
+            return Err(format!(
+                "偏移 {} 处的对象头版本 {} 暂不支持（仅支持 1 版）",
+                header, prefix[0]
+            ));
+        }
+        let (o, l) = (self.offset_size, self.length_size);
+        let mut blocks = vec![(header + 16, le_uint(&prefix[8..12]))];
+        let mut out = Vec::new();
+        let mut next = 0;
+        while next < blocks.len() {
+            let (start, len) = blocks[next];
+            next += 1;
+            let mut pos = start;
+            while pos + 8 <= start + len {
+                let head = self.slice(pos, 8)?;
+                let kind = le_uint(&head[..2]) as u16;
+                let data = self.slice(pos + 8, le_uint(&head[2..4]) as usize)?;
+                if kind == MSG_CONTINUATION {
+                    if blocks.len() > 1024 {
+                        return Err(format!("偏移 {} 处的对象头续块过多", header));
+                    }
+                    blocks.push((le_uint(field(data, 0, o)?), le_uint(field(data, o, l)?)));
+                } else {
+                    out.push((kind, data));
+                }
+                pos += 8 + data.len() as u64;
+            }
+        }
+
+        return Ok(out);
+    }
+
+    fn collect_symbol_nodes(
+        &self,
+        node: u64,
+        depth: usize,
+        out: &mut Vec<u64>,
+    ) -> Result<(), String> {
+        let (o, l) = (self.offset_size, self.length_size);
+        let head = self.slice(node, 8 + 2 * o)?;
+        if &head[..4] != b"TREE" || head[4] != 0 {
+            return Err(format!("偏移 {} 处不是组的 B 树节点", node));
+        }
+        if depth > MAX_DEPTH {
+            return Err("HDF5 组 B 树层数过多".to_string());
+        }
+        let (level, entries) = (head[5], le_uint(&head[6..8]) as usize);
+        for i in 0..entries {
+            let child = self.uint(node + (8 + 2 * o + l + i * (l + o)) as u64, o)?;
+            if level == 0 {
+                out.push(child);
+            } else {
+                self.collect_symbol_nodes(child, depth + 1, out)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    // 组内的 (名称, 对象头地址)；不是组时返回 None
+    fn children(&self, header: u64) -> Result<Option<Vec<(String, u64)>>, String> {
+        let (o, l) = (self.offset_size, self.length_size);
+        let messages = self.messages(header)?;
+        let Some(&(_, table)) = messages.iter().find(|(k, _)| *k == MSG_SYMBOL_TABLE) else {
+            return Ok(None);
+        };
+        let btree = le_uint(field(table, 0, o)?);
+        let heap = le_uint(field(table, o, o)?);
+
+        let heap_head = self.slice(heap, 8 + 2 * l + o)?;
+        if &heap_head[..4] != b"HEAP" {
+            return Err(format!("偏移 {} 处不是局部堆", heap));
+        }
+        let names = le_uint(&heap_head[8 + 2 * l..]);
+        let mut nodes = Vec::new();
+        self.collect_symbol_nodes(btree, 0, &mut nodes)?;
+
+        let entry_size = l + o + 24;
+        let mut out = Vec::new();
+        for node in nodes {
+            let head = self.slice(node, 8)?;
+            if &head[..4] != b"SNOD" {
+                return Err(format!("偏移 {} 处不是符号表节点", node));
+            }
+            for i in 0..le_uint(&head[6..8]) as usize {
+                let entry = self.slice(node + (8 + i * entry_size) as u64, entry_size)?;
+                let name = self.c_string(names + le_uint(&entry[..l]))?;
+                out.push((name, le_uint(&entry[l..l + o])));
+            }
+        }
+
+        return Ok(Some(out));
+    }
+
+    fn resolve(&self, root: u64, path: &str) -> Result<u64, String> {
+        let mut header = root;
+        for part in path.split('/').filter(|p| !p.is_empty()) {
+            let children = self
+                .children(header)?
+                .ok_or_else(|| format!("HDF5 路径 {} 中的 {} 的上级不是组", path, part))?;
+            header = children
+                .iter()
+                .find(|(name, _)| name == part)
+                .map(|&(_, addr)| addr)
+                .ok_or_else(|| format!("HDF5 文件中没有 {}", path))?;
+        }
+
+        return Ok(header);
+    }
+
+    fn dataspace(&self, msg: &[u8]) -> Result<Vec<usize>, String> {
+        let l = self.length_size;
+        let start = match field(msg, 0, 4)? {
+            [1, ..] => 8,
+            [2, _, _, 2] => {
+                return Err("HDF5 空数据空间（null dataspace）无法转换为张量".to_string());
+            }
+            [2, ..] => 4,
+            other => return Err(format!("HDF5 数据空间消息版本 {} 暂不支持", other[0])),
+        };
+        let rank = msg[1] as usize;
+
+        return (0..rank)
+            .map(|i| Ok(le_uint(field(msg, start + i * l, l)?) as usize))
+            .collect();
+    }
+
+    // 返回原始数据；连续存储尚未分配空间时返回 None（按填充值 0 处理）
+    fn layout(&self, msg: &'a [u8], nbytes: usize) -> Result<Option<&'a [u8]>, String> {
+        let (o, l) = (self.offset_size, self.length_size);
+        let (class, addr_pos, compact_pos) = match field(msg, 0, 3)? {
+            [3 | 4, class, _] => (*class, 2, 2),
+            [1 | 2, rank, class] => (*class, 8, 8 + *rank as usize * 4),
+            other => return Err(format!("HDF5 数据布局消息版本 {} 暂不支持", other[0])),
+        };
+
+        let data = match class {
+            0 => {
+                let size_len = if msg[0] >= 3 { 2 } else { 4 };
+                let size = le_uint(field(msg, compact_pos, size_len)?) as usize;
+                field(msg, compact_pos + size_len, size)?
+            }
+            1 => {
+                let addr = le_uint(field(msg, addr_pos, o)?);
+                if self.is_undefined(addr) {
+                    return Ok(None);
+                }
+                if msg[0] >= 3 && (le_uint(field(msg, addr_pos + o, l)?) as usize) < nbytes {
+                    return Err("HDF5 连续存储的大小小于数据集大小".to_string());
+                }
+                self.slice(addr, nbytes)?
+            }
+            2 => return Err("HDF5 分块存储（chunked）暂不支持".to_string()),
+            other => return Err(format!("HDF5 存储布局 {} 暂不支持", other)),
+        };
+        if data.len() < nbytes {
+            return Err(format!(
+                "HDF5 紧凑存储只有 {} 字节，数据集需要 {} 字节",
+                data.len(),
+                nbytes
+            ));
+        }
+
+        return Ok(Some(&data[..nbytes]));
+    }
+
+    fn read_dataset(&self, header: u64, path: &str) -> Result<Tensor, String> {
+        let messages = self.messages(header)?;
+        let find = |kind: u16| messages.iter().find(|(k, _)| *k == kind).map(|&(_, d)| d);
+        if find(MSG_FILTERS).is_some() {
+            return Err(format!("数据集 {} 使用了过滤器（如压缩），暂不支持", path));
+        }
+        let (Some(space), Some(kind), Some(layout)) =
+            (find(MSG_DATASPACE), find(MSG_DATATYPE), find(MSG_LAYOUT))
+        else {
+            return Err(format!("HDF5 对象 {} 不是数据集", path));
+        };
+
+        let shape = self.dataspace(space)?;
+        let (dtype, endianness) = datatype(kind).map_err(|e| format!("数据集 {}：{}", path, e))?;
+        let nbytes = shape.iter().product::<usize>() * dtype.size();
+
+        return match self.layout(layout, nbytes)? {
+            Some(raw) => Tensor::from_bytes(raw, shape, dtype, endianness),
+            None => Tensor::zeros(shape),
+        };
+    }
+
+    fn collect_datasets(
+        &self,
+        header: u64,
+        prefix: &str,
+        depth: usize,
+        out: &mut Vec<String>,
+    ) -> Result<(), String> {
+        if depth > MAX_DEPTH {
+            return Err("HDF5 组嵌套层数过多".to_string());
+        }
+        let Some(children) = self.children(header)? else {
+            return Ok(());
+        };
+        for (name, addr) in children {
+            let path = format!("{}{}", prefix, name);
+            let kinds: Vec<u16> = self.messages(addr)?.iter().map(|&(k, _)| k).collect();
+            if kinds.contains(&MSG_SYMBOL_TABLE) {
+                self.collect_datasets(addr, &format!("{}/", path), depth + 1, out)?;
+            } else if kinds.contains(&MSG_LAYOUT) {
+                out.push(path);
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+fn datatype(msg: &[u8]) -> Result<(DType, Endianness), String> {
+    let head = field(msg, 0, 8)?;
+    let (class, bits) = (head[0] & 0x0f, head[1]);
+    let size = le_uint(&head[4..8]);
+    if class == 1 && bits & 0x40 != 0 {
+        return Err("VAX 字节序的浮点数暂不支持".to_string());
+    }
+    let endianness = if bits & 1 == 1 {
+        Endianness::Big
+    } else {
+        Endianness::Little
+    };
+    let signed = bits & 0x08 != 0;
+
+    let dtype = match (class, size, signed) {
+        (0, 1, true) => DType::I8,
+        (0, 2, true) => DType::I16,
+        (0, 4, true) => DType::I32,
+        (0, 8, true) => DType::I64,
+        (0, 1, false) => DType::U8,
+        (0, 2, false) => DType::U16,
+        (0, 4, false) => DType::U32,
+        (0, 8, false) => DType::U64,
+        (1, 2, _) => DType::F16,
+        (1, 4, _) => DType::F32,
+        (1, 8, _) => DType::F64,
+        _ => {
+            return Err(format!(
+                "HDF5 数据类型（类别 {}，{} 字节）暂不支持",
+                class, size
+            ));
+        }
+    };
+
+    return Ok((dtype, endianness));
+}
+
+fn put(out: &mut [u8], pos: usize, value: u64, size: usize) {
+    out[pos..pos + size].copy_from_slice(&value.to_le_bytes()[..size]);
+}
+
+fn pad8(out: &mut Vec<u8>) {
+    out.resize(out.len().div_ceil(8) * 8, 0);
+}
+
+// 写出 1 版对象头，返回每条消息数据的起始偏移
+fn object_header(out: &mut Vec<u8>, messages: &[(u16, u8, Vec<u8>)]) -> Vec<usize> {
+    let body: usize = messages
+        .iter()
+        .map(|(_, _, d)| 8 + d.len().div_ceil(8) * 8)
+        .sum();
+    out.extend([1, 0]);
+    out.extend((messages.len() as u16).to_le_bytes());
+    out.extend(1u32.to_le_bytes());
+    out.extend((body as u32).to_le_bytes());
+    out.extend([0; 4]);
+
+    let mut positions = Vec::with_capacity(messages.len());
+    for (kind, flags, data) in messages {
+        out.extend(kind.to_le_bytes());
+        out.extend(((data.len().div_ceil(8) * 8) as u16).to_le_bytes());
+        out.extend([*flags, 0, 0, 0]);
+        positions.push(out.len());
+        out.extend(data);
+        pad8(out);
+    }
+
+    return positions;
+}
+
+fn encode(datasets: &[(String, Tensor)]) -> Result<Vec<u8>, String> {
+    for (i, (name, _)) in datasets.iter().enumerate() {
+        if name.is_empty() || name.contains(['/', '\0']) || name == "." {
+            return Err(format!("HDF5 数据集名 {:?} 无效（只能写到根组下）", name));
+        }
+        if datasets[..i].iter().any(|(n, _)| n == name) {
+            return Err(format!("HDF5 数据集名 {} 重复", name));
+        }
+    }
+    // 符号表节点最多容纳 2K 个条目，这里让一个节点装下全部数据集
+    let leaf_k = datasets.len().div_ceil(2).max(4);
+    if leaf_k > u16::MAX as usize {
+        return Err(format!("数据集数量 {} 超出单个组的上限", datasets.len()));
+    }
+    let mut order: Vec<usize> = (0..datasets.len()).collect();
+    order.sort_by(|&a, &b| datasets[a].0.as_bytes().cmp(datasets[b].0.as_bytes()));
+
+    // 局部堆：偏移 0 是空字符串，名称以 NUL 结尾并按 8 字节对齐
+    let mut names = vec![0u8; 8];
+    let mut name_offsets = vec![0; datasets.len()];
+    for &i in &order {
+        name_offsets[i] = names.len();
+        names.extend(datasets[i].0.as_bytes());
+        names.push(0);
+        pad8(&mut names);
+    }
+
+    let mut out = vec![0u8; SUPERBLOCK_SIZE];
+    let root = out.len();
+    let table = object_header(&mut out, &[(MSG_SYMBOL_TABLE, 0, vec![0; 16])])[0];
+
+    let heap = out.len();
+    out.extend(b"HEAP");
+    out.extend([0; 4]);
+    out.extend((names.len() as u64).to_le_bytes());
+    // 空闲链表为空；libhdf5 在磁盘上用 1 表示链表结束
+    out.extend(1u64.to_le_bytes());
+    out.extend(((heap + 32) as u64).to_le_bytes());
+    out.extend(&names);
+
+    // B 树节点按 2K 个子节点分配空间，libhdf5 读取时按完整大小读
+    let btree = out.len();
+    let entries = !datasets.is_empty() as u16;
+    out.extend(b"TREE");
+    out.extend([0, 0]);
+    out.extend(entries.to_le_bytes());
+    out.extend(UNDEFINED.to_le_bytes());
+    out.extend(UNDEFINED.to_le_bytes());
+    let keys = out.len();
+    out.resize(keys + (2 * INTERNAL_K + 1) * 8 + 2 * INTERNAL_K * 8, 0);
+    put(&mut out, table, btree as u64, 8);
+    put(&mut out, table + 8, heap as u64, 8);
+
+    if let Some(&last) = order.last() {
+        let node = out.len();
+        put(&mut out, keys + 8, node as u64, 8);
+        put(&mut out, keys + 16, name_offsets[last] as u64, 8);
+        out.extend(b"SNOD");
+        out.extend([1, 0]);
+        out.extend((datasets.len() as u16).to_le_bytes());
+        out.resize(node + 8 + 2 * leaf_k * 40, 0);
+
+        for (slot, &i) in order.iter().enumerate() {
+            let tensor = &datasets[i].1;
+            let mut space = vec![1, tensor.shape.len() as u8, 0, 0, 0, 0, 0, 0];
+            for &d in &tensor.shape {
+                space.extend((d as u64).to_le_bytes());
+            }
+            // 小端 IEEE f32：隐含最高位的尾数、符号位 31、指数 23..31、偏置 127
+            let float = vec![
+                0x11, 0x20, 0x1f, 0x00, 4, 0, 0, 0, 0, 0, 32, 0, 23, 8, 0, 23, 127, 0, 0, 0,
+            ];
+            let fill = vec![2, 2, 2, 0];
+            let mut layout = vec![3, 1];
+            layout.extend(UNDEFINED.to_le_bytes());
+            layout.extend(((tensor.data.len() * 4) as u64).to_le_bytes());
+
+            let header = out.len();
+            let positions = object_header(
+                &mut out,
+                &[
+                    (MSG_DATASPACE, 0, space),
+                    (MSG_DATATYPE, 1, float),
+                    (MSG_FILL_VALUE, 0, fill),
+                    (MSG_LAYOUT, 0, layout),
+                ],
+            );
+            if !tensor.data.is_empty() {
+                let data = out.len();
+                out.extend(tensor.to_bytes(Endianness::Little));
+                pad8(&mut out);
+                put(&mut out, positions[3] + 2, data as u64, 8);
+            }
+
+            let entry = node + 8 + slot * 40;
+            put(&mut out, entry, name_offsets[i] as u64, 8);
+            put(&mut out, entry + 8, header as u64, 8);
+        }
+    }
+
+    out[..8].copy_from_slice(SIGNATURE);
+    out[13] = 8;
+    out[14] = 8;
+    put(&mut out, 16, leaf_k as u64, 2);
+    put(&mut out, 18, INTERNAL_K as u64, 2);
+    put(&mut out, 32, UNDEFINED, 8);
+    let eof = out.len() as u64;
+    put(&mut out, 40, eof, 8);
+    put(&mut out, 48, UNDEFINED, 8);
+    // 根组的符号表条目缓存 B 树与局部堆地址
+    put(&mut out, 64, root as u64, 8);
+    put(&mut out, 72, 1, 4);
+    put(&mut out, 80, btree as u64, 8);
+    put(&mut out, 88, heap as u64, 8);
+
+    return Ok(out);
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    return std::fs::read(path)
+        .map_err(|e| format!("无法读取 HDF5 文件 {}：{}", path.display(), e));
+}
+
+// dataset 为组内路径，如 "train/images"
+pub fn read<P: AsRef<Path>>(path: P, dataset: &str) -> Result<Tensor, String> {
+    let bytes = read_file(path.as_ref())?;
+    let (file, root) = File::open(&bytes)?;
+    let header = file.resolve(root, dataset)?;
+
+    return file.read_dataset(header, dataset);
+}
+
+pub fn list<P: AsRef<Path>>(path: P) -> Result<Vec<String>, String> {
+    let bytes = read_file(path.as_ref())?;
+    let (file, root) = File::open(&bytes)?;
+    let mut out = Vec::new();
+    file.collect_datasets(root, "", 0, &mut out)?;
+
+    return Ok(out);
+}
+
+// 覆盖写出新文件，各数据集位于根组下
+pub fn write<P: AsRef<Path>>(path: P, datasets: &[(String, Tensor)]) -> Result<(), String> {
+    let path = path.as_ref();
+    let bytes = encode(datasets)?;
+
+    return std::fs::write(path, bytes)
+        .map_err(|e| format!("无法写入 HDF5 文件 {}：{}", path.display(), e));
+}
+
+impl Tensor {
+    pub fn from_hdf5<P: AsRef<Path>>(path: P, dataset: &str) -> Result<Tensor, String> {
+        return read(path, dataset);
+    }
+
+    // 写入根组下的一个数据集：文件已存在时保留其他数据集（按 f32 重新写出），同名的被替换
+    pub fn to_hdf5<P: AsRef<Path>>(&self, path: P, dataset: &str) -> Result<(), String> {
+        let path = path.as_ref();
+        let mut datasets = Vec::new();
+        if path.exists() {
+            let bytes = read_file(path)?;
+            let (file, root) = File::open(&bytes)?;
+            let mut names = Vec::new();
+            file.collect_datasets(root, "", 0, &mut names)?;
+            for name in names {
+                if name == dataset {
+                    continue;
+                }
+                if name.contains('/') {
+                    return Err(format!(
+                        "HDF5 文件 {} 含有子组中的数据集 {}，无法保留它后写回",
+                        path.display(),
+                        name
+                    ));
+                }
+                let header = file.resolve(root, &name)?;
+                datasets.push((name.clone(), file.read_dataset(header, &name)?));
+            }
+        }
+        datasets.push((dataset.to_string(), self.clone()));
+
+        return write(path, &datasets);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datasets_round_trip_through_a_file() {
+        let path = std::env::temp_dir().join(format!("tensor-{}.h5", std::process::id()));
+        let weight = Tensor::new(vec![1.0, -2.5, 3.0, 0.0, 5.0, 6.0], vec![2, 3]).unwrap();
+        let datasets = vec![
+            ("weight".to_string(), weight.clone()),
+            ("bias".to_string(), Tensor::ones(vec![3]).unwrap()),
+            ("step".to_string(), Tensor::new(vec![7.0], vec![]).unwrap()),
+            ("empty".to_string(), Tensor::zeros(vec![0, 4]).unwrap()),
+        ];
+        write(&path, &datasets).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(&bytes[..8], SIGNATURE);
+        assert_eq!(le_uint(&bytes[40..48]), bytes.len() as u64);
+        assert_eq!(list(&path).unwrap(), ["bias", "empty", "step", "weight"]);
+        for (name, tensor) in &datasets {
+            assert_eq!(&read(&path, name).unwrap(), tensor);
+        }
+        assert_eq!(Tensor::from_hdf5(&path, "/weight").unwrap(), weight);
+        assert!(read(&path, "missing").is_err());
+        assert!(read(&path, "weight/inner").is_err());
+
+        weight.to_hdf5(&path, "w").unwrap();
+        assert_eq!(
+            list(&path).unwrap(),
+            ["bias", "empty", "step", "w", "weight"]
+        );
+        let bias = Tensor::new(vec![0.5, -0.5, 2.0], vec![3]).unwrap();
+        bias.to_hdf5(&path, "bias").unwrap();
+        assert_eq!(read(&path, "bias").unwrap(), bias);
+        assert_eq!(read(&path, "w").unwrap(), weight);
+        assert_eq!(read(&path, "step").unwrap(), datasets[2].1);
+        assert!(weight.to_hdf5(&path, "a/b").is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        weight.to_hdf5(&path, "w").unwrap();
+        assert_eq!(list(&path).unwrap(), ["w"]);
+        // 子组中的数据集无法写回，文件保持原样
+        std::fs::write(&path, LIBHDF5_LAYOUT).unwrap();
+        assert!(weight.to_hdf5(&path, "w").is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), LIBHDF5_LAYOUT);
+        std::fs::remove_file(&path).unwrap();
+        assert!(File::open(b"not an hdf5 file at all").is_err());
+    }
+
+    // 沿用 libhdf5 1.10 默认（libver="earliest"）的磁盘结构，包含我们的写出端不会产生的部分：
+    // 子组、NIL 与修改时间消息、带最大维度的数据空间、对象头续块、局部堆空闲块、
+    // 未分配空间的连续存储以及大端整数。等价的 h5py 写法：
+    //   f["ints"] = np.array([[1, -2, 3], [-4, 5, 2**31 - 1]], "<i4")
+    //   f.create_dataset("unwritten", (2,), "<f4")
+    //   f["group/be16"] = np.array([-2, 300, 7], ">i2")
+    //   f.create_dataset("group/scalar", data=np.float32(2.5), layout=h5py.h5d.COMPACT)
+    //   f["group/temps"] = np.array([20.5, -3.25, 0.0, 1e-3], "<f8")
+    const LIBHDF5_LAYOUT: &[u8] = include_bytes!("../testdata/libhdf5_layout.h5");
+
+    #[test]
+    fn reads_the_libhdf5_file_layout() {
+        let (file, root) = File::open(LIBHDF5_LAYOUT).unwrap();
+        let mut names = Vec::new();
+        file.collect_datasets(root, "", 0, &mut names).unwrap();
+        assert_eq!(
+            names,
+            [
+                "group/be16",
+                "group/scalar",
+                "group/temps",
+                "ints",
+                "unwritten"
+            ]
+        );
+        let read = |path: &str| {
+            let header = file.resolve(root, path).unwrap();
+            file.read_dataset(header, path).unwrap()
+        };
+
+        let ints = read("ints");
+        assert_eq!(ints.shape, [2, 3]);
+        assert_eq!(
+            ints.data.to_vec(),
+            vec![1.0, -2.0, 3.0, -4.0, 5.0, 2147483647.0]
+        );
+        let be16 = read("/group/be16");
+        assert_eq!(be16.data.to_vec(), vec![-2.0, 300.0, 7.0]);
+        let scalar = read("group/scalar");
+        assert_eq!(scalar.shape, []);
+        assert_eq!(scalar.data.to_vec(), vec![2.5]);
+        let temps = read("group/temps");
+        assert_eq!(temps.data.to_vec(), vec![20.5, -3.25, 0.0, 1e-3]);
+        assert_eq!(read("unwritten"), Tensor::zeros(vec![2]).unwrap());
+        assert!(file.resolve(root, "group/missing").is_err());
+    }
+
+    #[test]
+    fn decodes_other_datatypes_and_compact_layouts() {
+        // 大端有符号 16 位整数
+        let be_i16 = [0x10, 0x09, 0, 0, 2, 0, 0, 0, 0, 0, 16, 0];
+        assert_eq!(datatype(&be_i16).unwrap(), (DType::I16, Endianness::Big));
+        let le_f64 = [0x11, 0x20, 0x3f, 0, 8, 0, 0, 0];
+        assert_eq!(datatype(&le_f64).unwrap(), (DType::F64, Endianness::Little));
+        assert!(datatype(&[0x13, 0, 0, 0, 4, 0, 0, 0]).is_err());
+
+        let bytes = encode(&[]).unwrap();
+        let (file, root) = File::open(&bytes).unwrap();
+        assert_eq!(file.children(root).unwrap(), Some(vec![]));
+        let compact = [3, 0, 4, 0, 0, 1, 0, 2];
+        assert_eq!(file.layout(&compact, 4).unwrap(), Some(&[0, 1, 0, 2][..]));
+        assert!(file.layout(&compact, 6).is_err());
+        assert!(file.layout(&[3, 2, 0, 0], 4).is_err());
+        let v1_space = [
+            1, 2, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(file.dataspace(&v1_space).unwrap(), vec![3, 5]);
+    }
+}
@@ -0,0 +1,329 @@
+// 只覆盖清单文件需要的 JSON 子集：数字按 f64 解析，输出时整数不带小数点
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub(crate) fn get(&self, key: &str) -> Option<&Json> {
+        let Json::Object(fields) = self else {
+            return None;
+        };
+
+        return fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        return match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        };
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        return match self {
+            Json::Number(v) => Some(*v),
+            _ => None,
+        };
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Json]> {
+        return match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        };
+    }
+
+    pub(crate) fn as_object(&self) -> Option<&[(String, Json)]> {
+        return match self {
+            Json::Object(fields) => Some(fields),
+            _ => None,
+        };
+    }
+
+    pub(crate) fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out.push('\n');
+
+        return out;
+    }
+
+    fn write(&self, out: &mut String, indent: usize) {
+        let pad = |out: &mut String, n: usize| out.extend(std::iter::repeat_n(' ', n * 2));
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Json::Number(v) if v.fract() == 0.0 && v.abs() < 1e15 => {
+                out.push_str(&format!("{}", *v as i64));
+            }
+            Json::Number(v) if v.is_finite() => out.push_str(&format!("{}", v)),
+            Json::Number(_) => out.push_str("null"),
+            Json::String(s) => write_string(out, s),
+            // 纯数字数组（形状）写在一行，便于阅读清单
+            Json::Array(items) if items.iter().all(|v| matches!(v, Json::Number(_))) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write(out, indent);
+                }
+                out.push(']');
+            }
+            Json::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    out.push_str(if i > 0 { ",\n" } else { "\n" });
+                    pad(out, indent + 1);
+                    item.write(out, indent + 1);
+                }
+                if !items.is_empty() {
+                    out.push('\n');
+                    pad(out, indent);
+                }
+                out.push(']');
+            }
+            Json::Object(fields) => {
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    out.push_str(if i > 0 { ",\n" } else { "\n" });
+                    pad(out, indent + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                }
+                if !fields.is_empty() {
+                    out.push('\n');
+                    pad(out, indent);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    pub(crate) fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.chars.len() {
+            return Err(format!("JSON 在位置 {} 之后有多余内容", parser.pos));
+        }
+
+        return Ok(value);
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.chars.len() && self.chars[self.pos].is_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.chars.get(self.pos) != Some(&c) {
+            return Err(format!("JSON 在位置 {} 处应为 '{}'", self.pos, c));
+        }
+        self.pos += 1;
+
+        return Ok(());
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        let end = self.pos + word.chars().count();
+        if end > self.chars.len() || self.chars[self.pos..end].iter().collect::<String>() != word {
+            return Err(format!("JSON 在位置 {} 处有无法识别的值", self.pos));
+        }
+        self.pos = end;
+
+        return Ok(value);
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        let Some(&c) = self.chars.get(self.pos) else {
+            return Err("JSON 意外结束".to_string());
+        };
+
+        return match c {
+            '{' => self.object(),
+            '[' => self.array(),
+            '"' => Ok(Json::String(self.string()?)),
+            't' => self.keyword("true", Json::Bool(true)),
+            'f' => self.keyword("false", Json::Bool(false)),
+            'n' => self.keyword("null", Json::Null),
+            _ => self.number(),
+        };
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.chars.get(self.pos) {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(format!("JSON 对象在位置 {} 处缺少 ',' 或 '}}'", self.pos)),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.chars.get(self.pos) == Some(&']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.get(self.pos) {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(format!("JSON 数组在位置 {} 处缺少 ',' 或 ']'", self.pos)),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if self.chars.get(self.pos) != Some(&'"') {
+            return Err(format!("JSON 在位置 {} 处应为字符串", self.pos));
+        }
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            let Some(&c) = self.chars.get(self.pos) else {
+                return Err("JSON 字符串未结束".to_string());
+            };
+            self.pos += 1;
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let Some(&e) = self.chars.get(self.pos) else {
+                        return Err("JSON 字符串未结束".to_string());
+                    };
+                    self.pos += 1;
+                    match e {
+                        '"' | '\\' | '/' => s.push(e),
+                        'n' => s.push('\n'),
+                        'r' => s.push('\r'),
+                        't' => s.push('\t'),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'u' => {
+                            let hex: String = self.chars.iter().skip(self.pos).take(4).collect();
+                            let code = u32::from_str_radix(&hex, 16)
+                                .map_err(|_| format!("JSON 转义 \\u{} 无效", hex))?;
+                            self.pos += 4;
+                            // 代理对不在清单的使用范围内，按替换字符处理
+                            s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        _ => return Err(format!("JSON 转义 \\{} 无效", e)),
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        while self.pos < self.chars.len()
+            && matches!(
+                self.chars[self.pos],
+                '0'..='9' | '-' | '+' | '.' | 'e' | 'E'
+            )
+        {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+
+        return text
+            .parse::<f64>()
+            .map(Json::Number)
+            .map_err(|_| format!("JSON 在位置 {} 处有无法识别的值", start));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pretty_output_parses_back() {
+        let doc = Json::Object(vec![
+            ("name".to_string(), Json::String("a \"b\"\n".to_string())),
+            (
+                "shape".to_string(),
+                Json::Array(vec![Json::Number(2.0), Json::Number(3.0)]),
+            ),
+            ("scale".to_string(), Json::Number(0.5)),
+            (
+                "items".to_string(),
+                Json::Array(vec![Json::Null, Json::Bool(true)]),
+            ),
+            ("empty".to_string(), Json::Object(vec![])),
+        ]);
+        let text = doc.to_pretty();
+        assert!(text.contains("\"shape\": [2, 3]"));
+        assert_eq!(Json::parse(&text).unwrap(), doc);
+        assert_eq!(doc.get("scale").and_then(Json::as_f64), Some(0.5));
+
+        assert_eq!(
+            Json::parse(" \"\\u4e2d\" ").unwrap(),
+            Json::String("中".to_string())
+        );
+        assert!(Json::parse("{\"a\": 1,}").is_err());
+        assert!(Json::parse("[1, 2] x").is_err());
+        assert!(Json::parse("tru").is_err());
+    }
+}
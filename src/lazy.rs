@@ -0,0 +1,507 @@
+use std::collections::HashMap;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::rc::Rc;
+
+use crate::tensor::saturate;
+use crate::{DType, Shape, Tensor, check, config, profile};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+    Neg,
+    Abs,
+    Sqrt,
+    Exp,
+    Log,
+    Relu,
+    Sigmoid,
+    Tanh,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Max,
+    Min,
+}
+
+impl UnaryOp {
+    pub fn apply(self, x: f32) -> f32 {
+        return match self {
+            UnaryOp::Neg => -x,
+            UnaryOp::Abs => x.abs(),
+            UnaryOp::Sqrt => x.sqrt(),
+            UnaryOp::Exp => x.exp(),
+            UnaryOp::Log => x.ln(),
+            UnaryOp::Relu => x.max(0.0),
+            UnaryOp::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            UnaryOp::Tanh => x.tanh(),
+        };
+    }
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            UnaryOp::Neg => "neg",
+            UnaryOp::Abs => "abs",
+            UnaryOp::Sqrt => "sqrt",
+            UnaryOp::Exp => "exp",
+            UnaryOp::Log => "log",
+            UnaryOp::Relu => "relu",
+            UnaryOp::Sigmoid => "sigmoid",
+            UnaryOp::Tanh => "tanh",
+        };
+    }
+
+    // 结果一般带小数，整数或布尔输入得到 f32
+    pub(crate) fn float_valued(self) -> bool {
+        return matches!(
+            self,
+            UnaryOp::Sqrt | UnaryOp::Exp | UnaryOp::Log | UnaryOp::Sigmoid | UnaryOp::Tanh
+        );
+    }
+}
+
+impl BinaryOp {
+    pub fn apply(self, a: f32, b: f32) -> f32 {
+        return match self {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mul => a * b,
+            BinaryOp::Div => a / b,
+            BinaryOp::Max => a.max(b),
+            BinaryOp::Min => a.min(b),
+        };
+    }
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            BinaryOp::Add => "add",
+            BinaryOp::Sub => "sub",
+            BinaryOp::Mul => "mul",
+            BinaryOp::Div => "div",
+            BinaryOp::Max => "max",
+            BinaryOp::Min => "min",
+        };
+    }
+
+    pub(crate) fn float_valued(self) -> bool {
+        return self == BinaryOp::Div;
+    }
+}
+
+#[derive(Debug)]
+pub enum Expr<'a> {
+    Leaf(&'a Tensor),
+    Scalar(f32),
+    Unary(UnaryOp, Rc<Expr<'a>>),
+    Binary(BinaryOp, Rc<Expr<'a>>, Rc<Expr<'a>>),
+}
+
+#[derive(Debug, Clone)]
+pub struct LazyTensor<'a> {
+    expr: Rc<Expr<'a>>,
+}
+
+// 寄存器式指令：第 i 条指令的结果存放在第 i 个值槽，操作数按槽号引用。
+// 每个值槽带有与立即求值相同规则推导出的 dtype，标量字面量没有 dtype（None），
+// 与张量运算时同 add_scalar 一样得到该张量 dtype 的浮点类型
+enum Instr {
+    Leaf(usize),
+    Scalar(f32),
+    Unary(UnaryOp, usize),
+    Binary(BinaryOp, usize, usize),
+}
+
+impl Tensor {
+    pub fn lazy(&self) -> LazyTensor<'_> {
+        return LazyTensor {
+            expr: Rc::new(Expr::Leaf(self)),
+        };
+    }
+}
+
+impl<'a> LazyTensor<'a> {
+    pub fn expr(&self) -> &Expr<'a> {
+        return &self.expr;
+    }
+
+    fn unary(&self, op: UnaryOp) -> LazyTensor<'a> {
+        return LazyTensor {
+            expr: Rc::new(Expr::Unary(op, self.expr.clone())),
+        };
+    }
+
+    fn binary(&self, op: BinaryOp, other: &LazyTensor<'a>) -> LazyTensor<'a> {
+        return LazyTensor {
+            expr: Rc::new(Expr::Binary(op, self.expr.clone(), other.expr.clone())),
+        };
+    }
+
+    fn scalar(value: f32) -> LazyTensor<'a> {
+        return LazyTensor {
+            expr: Rc::new(Expr::Scalar(value)),
+        };
+    }
+
+    pub fn abs(&self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Abs);
+    }
+
+    pub fn sqrt(&self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Sqrt);
+    }
+
+    pub fn exp(&self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Exp);
+    }
+
+    pub fn log(&self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Log);
+    }
+
+    pub fn relu(&self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Relu);
+    }
+
+    pub fn sigmoid(&self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Sigmoid);
+    }
+
+    pub fn tanh(&self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Tanh);
+    }
+
+    pub fn maximum(&self, other: &LazyTensor<'a>) -> LazyTensor<'a> {
+        return self.binary(BinaryOp::Max, other);
+    }
+
+    pub fn minimum(&self, other: &LazyTensor<'a>) -> LazyTensor<'a> {
+        return self.binary(BinaryOp::Min, other);
+    }
+
+    // 共享的子表达式按 Rc 指针只推导一次，否则 x = &x + &x 反复叠加时是指数级
+    pub fn shape(&self) -> Result<Shape, String> {
+        fn infer(expr: &Rc<Expr>, seen: &mut HashMap<*const (), Shape>) -> Result<Shape, String> {
+            let key = Rc::as_ptr(expr) as *const ();
+            if let Some(shape) = seen.get(&key) {
+                return Ok(shape.clone());
+            }
+            let shape = match &**expr {
+                Expr::Leaf(t) => t.shape.clone(),
+                Expr::Scalar(_) => Shape::scalar(),
+                Expr::Unary(_, a) => infer(a, seen)?,
+                Expr::Binary(_, a, b) => infer(a, seen)?.broadcast_with(&infer(b, seen)?)?,
+            };
+            seen.insert(key, shape.clone());
+
+            return Ok(shape);
+        }
+
+        return infer(&self.expr, &mut HashMap::new());
+    }
+
+    pub fn graph_to_dot(&self) -> Result<String, String> {
+        fn visit(
+            expr: &Rc<Expr>,
+            seen: &mut Vec<(*const (), Vec<usize>)>,
+            out: &mut String,
+        ) -> Result<(usize, Vec<usize>), String> {
+            let key = Rc::as_ptr(expr) as *const ();
+            if let Some(id) = seen.iter().position(|(k, _)| *k == key) {
+                return Ok((id, seen[id].1.clone()));
+            }
+
+            let (label, shape, children) = match &**expr {
+                Expr::Leaf(t) => ("leaf".to_string(), t.shape.to_vec(), vec![]),
+                Expr::Scalar(v) => (format!("scalar {}", v), vec![], vec![]),
+                Expr::Unary(op, a) => {
+                    let child = visit(a, seen, out)?;
+                    (op.name().to_string(), child.1.clone(), vec![child])
+                }
+                Expr::Binary(op, a, b) => {
+                    let left = visit(a, seen, out)?;
+                    let right = visit(b, seen, out)?;
+                    let shape = Tensor::broadcast_shapes(&left.1, &right.1)?;
+                    (op.name().to_string(), shape, vec![left, right])
+                }
+            };
+
+            let id = seen.len();
+            seen.push((key, shape.clone()));
+            out.push_str(&format!(
+                "    n{} [label=\"{}\\n{:?}\"];\n",
+                id, label, shape
+            ));
+            for (child, child_shape) in &children {
+                let attrs =
+                    if *child_shape != shape && !child_shape.is_empty() && children.len() > 1 {
+                        " [label=\"broadcast\", color=red]"
+                    } else {
+                        ""
+                    };
+                out.push_str(&format!("    n{} -> n{}{};\n", child, id, attrs));
+            }
+
+            return Ok((id, shape));
+        }
+
+        let mut out = String::from("digraph lazy {\n    node [shape=box];\n");
+        visit(&self.expr, &mut Vec::new(), &mut out)?;
+        out.push_str("}\n");
+
+        return Ok(out);
+    }
+
+    pub fn eval(&self) -> Result<Tensor, String> {
+        // 与 graph_to_dot 相同，按 Rc 指针去重：每个共享节点只编译一次，返回其值槽
+        fn compile<'a>(
+            expr: &Rc<Expr<'a>>,
+            seen: &mut HashMap<*const (), usize>,
+            leaves: &mut Vec<&'a Tensor>,
+            program: &mut Vec<(Instr, Option<DType>)>,
+        ) -> Result<usize, String> {
+            let key = Rc::as_ptr(expr) as *const ();
+            if let Some(&slot) = seen.get(&key) {
+                return Ok(slot);
+            }
+            let (instr, dtype) = match &**expr {
+                Expr::Leaf(t) => {
+                    let leaf = match leaves.iter().position(|l| std::ptr::eq(*l, *t)) {
+                        Some(leaf) => leaf,
+                        None => {
+                            if let Some(first) = leaves.first() {
+                                first.check_same_device("lazy_eval", t)?;
+                            }
+                            leaves.push(t);
+                            leaves.len() - 1
+                        }
+                    };
+                    (Instr::Leaf(leaf), Some(t.dtype()))
+                }
+                Expr::Scalar(v) => (Instr::Scalar(*v), None),
+                Expr::Unary(op, a) => {
+                    let a = compile(a, seen, leaves, program)?;
+                    let dtype = match program[a].1 {
+                        Some(d) if op.float_valued() => Some(d.to_float()),
+                        d => d,
+                    };
+                    (Instr::Unary(*op, a), dtype)
+                }
+                Expr::Binary(op, a, b) => {
+                    let a = compile(a, seen, leaves, program)?;
+                    let b = compile(b, seen, leaves, program)?;
+                    let dtype = match (program[a].1, program[b].1) {
+                        (Some(x), Some(y)) => {
+                            let strict = config::is_strict_dtypes();
+                            let d = Tensor::promote_dtypes(op.name(), x, y, strict)?;
+                            Some(if op.float_valued() { d.to_float() } else { d })
+                        }
+                        (Some(d), None) | (None, Some(d)) => Some(d.to_float()),
+                        (None, None) => None,
+                    };
+                    (Instr::Binary(*op, a, b), dtype)
+                }
+            };
+            program.push((instr, dtype));
+            seen.insert(key, program.len() - 1);
+
+            return Ok(program.len() - 1);
+        }
+
+        let shape = self.shape()?;
+        let mut leaves = Vec::new();
+        let mut program = Vec::new();
+        let result = compile(&self.expr, &mut HashMap::new(), &mut leaves, &mut program)?;
+        let dtype = program[result].1.unwrap_or(DType::F32);
+        // 叶子的值已经落在各自的 dtype 上，只有运算结果需要饱和
+        let saturating: Vec<Option<DType>> = program
+            .iter()
+            .map(|(instr, dtype)| match (instr, dtype) {
+                (Instr::Leaf(_), _) => None,
+                (_, Some(d)) if !d.is_native() => Some(*d),
+                _ => None,
+            })
+            .collect();
+
+        let strides: Vec<Vec<usize>> = leaves.iter().map(|l| l.broadcast_strides(&shape)).collect();
+        let total: usize = shape.iter().product();
+        let _scope = profile::scope("lazy_eval", total);
+
+        let mut data = crate::alloc::allocate(total);
+        let mut values = vec![0.0f32; program.len()];
+        let mut offsets = vec![0; leaves.len()];
+        let mut index = vec![0; shape.len()];
+        for _ in 0..total {
+            for (i, (instr, _)) in program.iter().enumerate() {
+                let v = match *instr {
+                    Instr::Leaf(leaf) => leaves[leaf].data[offsets[leaf]],
+                    Instr::Scalar(v) => v,
+                    Instr::Unary(op, a) => op.apply(values[a]),
+                    Instr::Binary(op, a, b) => op.apply(values[a], values[b]),
+                };
+                values[i] = match saturating[i] {
+                    Some(d) => saturate(v, d),
+                    None => v,
+                };
+            }
+            data.push(values[result]);
+
+            for d in (0..shape.len()).rev() {
+                index[d] += 1;
+                for (offset, s) in offsets.iter_mut().zip(strides.iter()) {
+                    *offset += s[d];
+                }
+                if index[d] < shape[d] {
+                    break;
+                }
+                for (offset, s) in offsets.iter_mut().zip(strides.iter()) {
+                    *offset -= s[d] * shape[d];
+                }
+                index[d] = 0;
+            }
+        }
+
+        let mut out = Tensor::new(data, shape)?.with_dtype(dtype);
+        if let Some(leaf) = leaves.first() {
+            out = leaf.placed(out);
+        }
+        let input_shapes: Vec<&[usize]> = leaves.iter().map(|l| l.shape.as_slice()).collect();
+        check::inspect("lazy_eval", &input_shapes, &out)?;
+
+        return Ok(out);
+    }
+}
+
+macro_rules! impl_lazy_binary {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl<'a> $trait<LazyTensor<'a>> for LazyTensor<'a> {
+            type Output = LazyTensor<'a>;
+
+            fn $method(self, other: LazyTensor<'a>) -> LazyTensor<'a> {
+                return self.binary($op, &other);
+            }
+        }
+
+        impl<'a> $trait<&LazyTensor<'a>> for &LazyTensor<'a> {
+            type Output = LazyTensor<'a>;
+
+            fn $method(self, other: &LazyTensor<'a>) -> LazyTensor<'a> {
+                return self.binary($op, other);
+            }
+        }
+
+        impl<'a> $trait<f32> for LazyTensor<'a> {
+            type Output = LazyTensor<'a>;
+
+            fn $method(self, other: f32) -> LazyTensor<'a> {
+                return self.binary($op, &LazyTensor::scalar(other));
+            }
+        }
+    };
+}
+
+impl_lazy_binary!(Add, add, BinaryOp::Add);
+impl_lazy_binary!(Sub, sub, BinaryOp::Sub);
+impl_lazy_binary!(Mul, mul, BinaryOp::Mul);
+impl_lazy_binary!(Div, div, BinaryOp::Div);
+
+impl<'a> Neg for LazyTensor<'a> {
+    type Output = LazyTensor<'a>;
+
+    fn neg(self) -> LazyTensor<'a> {
+        return self.unary(UnaryOp::Neg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_matches_eager_with_broadcast() {
+        let a = Tensor::new(vec![1.0, -2.0, 3.0, -4.0, 5.0, -6.0], vec![2, 3]).unwrap();
+        let b = Tensor::new(vec![0.5, 1.0, 2.0], vec![3]).unwrap();
+        let y = ((&a.lazy() * &b.lazy()) + 1.0).relu().eval().unwrap();
+        let expected = a.mul(&b).unwrap().add_scalar(1.0).unwrap().relu().unwrap();
+        assert_eq!(y, expected);
+        assert!(
+            (a.lazy() + Tensor::ones(vec![4]).unwrap().lazy())
+                .shape()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn dot_emits_each_shared_node_once() {
+        let a = Tensor::ones(vec![2, 3]).unwrap();
+        let b = Tensor::ones(vec![3]).unwrap();
+        let mut x = a.lazy();
+        for _ in 0..40 {
+            x = &x + &x;
+        }
+        let dot = (&x * &b.lazy()).graph_to_dot().unwrap();
+        assert_eq!(
+            dot.matches("[label=").count() - dot.matches("broadcast").count(),
+            43
+        );
+        assert_eq!(dot.matches("broadcast").count(), 1);
+        assert!(dot.contains("n41 -> n42 [label=\"broadcast\", color=red];"));
+    }
+
+    #[test]
+    fn eval_compiles_each_shared_node_once() {
+        let a = Tensor::new(vec![1.0, -2.0, 0.5], vec![3]).unwrap();
+        let mut x = a.lazy();
+        for _ in 0..40 {
+            x = &x + &x;
+        }
+        let y = (&x.tanh() * &x).eval().unwrap();
+        let scale = 2f32.powi(40);
+        for (&v, &s) in y.data.iter().zip(a.data.iter()) {
+            let expected = (s * scale).tanh() * s * scale;
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn eval_handles_scalars_and_empty_leaves() {
+        let s = Tensor::new(vec![3.0], Vec::<usize>::new()).unwrap();
+        assert_eq!(s.lazy().exp().eval().unwrap().shape, Vec::<usize>::new());
+        let e = Tensor::zeros(vec![0, 2]).unwrap();
+        assert_eq!((e.lazy() * 2.0).eval().unwrap().shape, [0, 2]);
+    }
+
+    #[test]
+    fn eval_matches_eager_for_integer_dtypes() {
+        let a = Tensor::new(vec![100.0, -100.0, 50.0, 7.0], vec![4])
+            .unwrap()
+            .cast_checked(DType::I8)
+            .unwrap();
+        let b = Tensor::new(vec![100.0, -100.0, 3.0, -9.0], vec![4])
+            .unwrap()
+            .cast_checked(DType::I8)
+            .unwrap();
+        let u = Tensor::new(vec![200.0, 0.0, 1.0, 255.0], vec![4])
+            .unwrap()
+            .cast_checked(DType::U8)
+            .unwrap();
+
+        // 每一步都按 i8 饱和：100 + 100 得到 127，而不是 200
+        let lazy = (&(&a.lazy() + &b.lazy()) * &b.lazy()).abs().eval().unwrap();
+        let eager = a.add(&b).unwrap().mul(&b).unwrap().abs().unwrap();
+        assert_eq!(lazy.dtype(), DType::I8);
+        assert_eq!(lazy, eager);
+        assert_eq!(lazy.data[0], 127.0);
+
+        let lazy = (&a.lazy() - &u.lazy()).eval().unwrap();
+        assert_eq!(lazy.dtype(), DType::I16);
+        assert_eq!(lazy, a.sub(&u).unwrap());
+
+        let lazy = ((a.lazy() + 0.5) / 2.0).eval().unwrap();
+        assert_eq!(lazy, a.add_scalar(0.5).unwrap().mul_scalar(0.5).unwrap());
+        assert_eq!((&a.lazy() / &b.lazy()).eval().unwrap(), a.div(&b).unwrap());
+        assert_eq!(a.lazy().sqrt().eval().unwrap().dtype(), DType::F32);
+    }
+}
@@ -1,3 +1,46 @@
+pub mod alloc;
+pub mod autograd;
+pub mod check;
+pub mod checkpoint;
+pub mod cluster;
+pub mod config;
+#[cfg(feature = "cuda")]
+pub mod cuda;
+pub mod data;
+pub mod decomposition;
+pub mod gguf;
+mod half;
+#[cfg(feature = "hdf5")]
+pub mod hdf5;
+mod json;
+pub mod lazy;
+#[cfg(all(feature = "metal", target_os = "macos"))]
+pub mod metal;
+pub mod metrics;
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+mod parallel;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "polars")]
+pub mod polars;
+pub mod profile;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(any(feature = "onnx", feature = "serving"))]
+mod proto;
+pub mod quantize;
+pub mod random;
+#[cfg(feature = "serving")]
+pub mod serving;
+pub mod state_dict;
 mod tensor;
+pub mod transforms;
 
-pub use tensor::Tensor;
+pub use tensor::{
+    Chunks, DType, Device, DistanceMetric, Endianness, Indices, IntoChunks, Layout, MinMaxStats,
+    RankMethod, RollingEdge, Shape, Spacing, Storage, Summation, Tensor, TensorView, UpsampleMode,
+    WindowFn, ZScoreStats, indices, indices_column_major,
+};
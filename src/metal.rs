@@ -0,0 +1,517 @@
+use std::any::Any;
+use std::ffi::c_void;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+
+use ::metal::{
+    Buffer, BufferRef, CommandBuffer, CommandBufferRef, CommandQueue, CompileOptions,
+    ComputePipelineState, Library, MTLResourceOptions, MTLSize,
+};
+use objc::runtime::{NO, Object};
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::Tensor;
+use crate::lazy::{BinaryOp, UnaryOp};
+use crate::tensor::{Backend, DeviceBuffer, ReduceOp};
+
+// Metal 后端（Apple Silicon）：逐元素算子与归约是运行时编译的 MSL 内核，
+// matmul 走 MPSMatrixMultiplication。统一内存下缓冲区使用共享存储模式，
+// 上传与取回都是一次 memcpy；每次分派提交一个命令缓冲区并等待完成。
+// 需要广播的二元运算、批维需要广播的 matmul 与空张量返回 None，退回主机实现
+
+const KERNELS: &str = r#"
+#include <metal_stdlib>
+using namespace metal;
+
+#define BINARY(name, expr)                                                      \
+    kernel void name(device const float* a [[buffer(0)]],                      \
+                     device const float* b [[buffer(1)]],                      \
+                     device float* out [[buffer(2)]],                          \
+                     constant uint& n [[buffer(3)]],                           \
+                     uint i [[thread_position_in_grid]]) {                     \
+        if (i < n) { float x = a[i]; float y = b[i]; out[i] = expr; }           \
+    }
+
+#define UNARY(name, expr)                                                       \
+    kernel void name(device const float* a [[buffer(0)]],                      \
+                     device float* out [[buffer(1)]],                          \
+                     constant uint& n [[buffer(2)]],                           \
+                     uint i [[thread_position_in_grid]]) {                     \
+        if (i < n) { float x = a[i]; out[i] = expr; }                           \
+    }
+
+// 每个线程组把网格跨步区间归约成一个部分结果，部分结果在主机上合并
+#define REDUCE(name, identity, combine)                                         \
+    kernel void name(device const float* a [[buffer(0)]],                      \
+                     device float* partial [[buffer(1)]],                      \
+                     constant uint& n [[buffer(2)]],                           \
+                     uint tid [[thread_index_in_threadgroup]],                 \
+                     uint group [[threadgroup_position_in_grid]],              \
+                     uint groups [[threadgroups_per_grid]]) {                  \
+        threadgroup float cache[256];                                           \
+        float x = identity;                                                     \
+        for (uint i = group * 256 + tid; i < n; i += groups * 256) {            \
+            float y = a[i]; x = combine;                                        \
+        }                                                                       \
+        cache[tid] = x;                                                         \
+        threadgroup_barrier(mem_flags::mem_threadgroup);                        \
+        for (uint s = 128; s > 0; s >>= 1) {                                    \
+            if (tid < s) { x = cache[tid]; float y = cache[tid + s];            \
+                           cache[tid] = combine; }                              \
+            threadgroup_barrier(mem_flags::mem_threadgroup);                    \
+        }                                                                       \
+        if (tid == 0) { partial[group] = cache[0]; }                            \
+    }
+
+BINARY(binary_add, x + y)
+BINARY(binary_sub, x - y)
+BINARY(binary_mul, x * y)
+BINARY(binary_div, x / y)
+BINARY(binary_max, fmax(x, y))
+BINARY(binary_min, fmin(x, y))
+
+UNARY(unary_neg, -x)
+UNARY(unary_abs, fabs(x))
+UNARY(unary_sqrt, sqrt(x))
+UNARY(unary_exp, exp(x))
+UNARY(unary_log, log(x))
+UNARY(unary_relu, fmax(x, 0.0f))
+UNARY(unary_sigmoid, 1.0f / (1.0f + exp(-x)))
+UNARY(unary_tanh, tanh(x))
+
+REDUCE(reduce_sum, 0.0f, x + y)
+// max/min 遇到 NaN 时结果为 NaN，与主机实现一致
+REDUCE(reduce_max, -INFINITY, (isnan(x) || x > y) ? x : y)
+REDUCE(reduce_min, INFINITY, (isnan(x) || x < y) ? x : y)
+"#;
+
+const REDUCE_GROUP: u64 = 256;
+const REDUCE_GROUPS: u64 = 1024;
+
+// MPSDataTypeFloat32 = MPSDataTypeFloatBit | 32
+const MPS_FLOAT32: u32 = 0x1000_0000 | 32;
+
+#[link(name = "MetalPerformanceShaders", kind = "framework")]
+unsafe extern "C" {}
+
+// 常驻缓冲区记下最后写入它的命令缓冲区，读取前等待其完成。
+// Buffer 是引用计数的 Objective-C 对象句柄，没有会被 panic 打断的 Rust 端不变量，
+// 用 AssertUnwindSafe 包装以满足 DeviceBuffer 的 UnwindSafe 约束
+struct MetalBuffer {
+    buffer: AssertUnwindSafe<Buffer>,
+    len: usize,
+    pending: Mutex<Option<CommandBuffer>>,
+}
+
+impl MetalBuffer {
+    fn wait(&self) {
+        if let Some(commands) = self.pending.lock().unwrap().take() {
+            commands.wait_until_completed();
+        }
+    }
+}
+
+impl DeviceBuffer for MetalBuffer {
+    fn as_any(&self) -> &dyn Any {
+        return self;
+    }
+
+    fn len(&self) -> usize {
+        return self.len;
+    }
+
+    fn read(&self) -> Result<Vec<f32>, String> {
+        self.wait();
+        let mut data = crate::alloc::allocate(self.len);
+        let contents = self.buffer.contents() as *const f32;
+        data.extend_from_slice(unsafe { std::slice::from_raw_parts(contents, self.len) });
+
+        return Ok(data);
+    }
+}
+
+fn resident(buffer: Buffer, len: usize, pending: CommandBuffer) -> Arc<dyn DeviceBuffer> {
+    return Arc::new(MetalBuffer {
+        buffer: AssertUnwindSafe(buffer),
+        len: len,
+        pending: Mutex::new(Some(pending)),
+    });
+}
+
+pub(crate) struct MetalBackend {
+    device: ::metal::Device,
+    queue: CommandQueue,
+    library: Library,
+}
+
+impl MetalBackend {
+    fn new(ordinal: usize) -> Result<MetalBackend, String> {
+        let device = ::metal::Device::all()
+            .into_iter()
+            .nth(ordinal)
+            .ok_or_else(|| format!("Metal 设备 {} 不存在", ordinal))?;
+        let options = CompileOptions::new();
+        // 与主机实现的 IEEE 语义保持一致
+        options.set_fast_math_enabled(false);
+        let library = device
+            .new_library_with_source(KERNELS, &options)
+            .map_err(|e| format!("Metal 内核编译失败：{}", e))?;
+        let queue = device.new_command_queue();
+
+        return Ok(MetalBackend {
+            device: device,
+            queue: queue,
+            library: library,
+        });
+    }
+
+    fn pipeline(&self, kernel: &str) -> Result<ComputePipelineState, String> {
+        let function = self
+            .library
+            .get_function(kernel, None)
+            .map_err(|e| format!("找不到 Metal 内核 {}：{}", kernel, e))?;
+
+        return self
+            .device
+            .new_compute_pipeline_state_with_function(&function)
+            .map_err(|e| format!("创建 Metal 管线失败：{}", e));
+    }
+
+    fn buffer<'a>(&self, buffer: &'a Arc<dyn DeviceBuffer>) -> Result<&'a BufferRef, String> {
+        return buffer
+            .as_any()
+            .downcast_ref::<MetalBuffer>()
+            .map(|b| &**b.buffer)
+            .ok_or_else(|| "常驻缓冲区不属于 Metal 后端".to_string());
+    }
+
+    fn output(&self, len: usize) -> Buffer {
+        return self.device.new_buffer(
+            (len * std::mem::size_of::<f32>()) as u64,
+            MTLResourceOptions::StorageModeShared,
+        );
+    }
+
+    // 提交后立即返回；命令缓冲区持有编码时引用的输入缓冲区，直到执行完成
+    fn run(&self, encode: impl FnOnce(&CommandBufferRef)) -> CommandBuffer {
+        let commands = self.queue.new_command_buffer().to_owned();
+        encode(&commands);
+        commands.commit();
+
+        return commands;
+    }
+
+    fn elementwise(
+        &self,
+        kernel: &str,
+        inputs: &[&BufferRef],
+        n: usize,
+    ) -> Result<Arc<dyn DeviceBuffer>, String> {
+        let pipeline = self.pipeline(kernel)?;
+        let out = self.output(n);
+        let len = n as u32;
+        let width = pipeline.max_total_threads_per_threadgroup().min(n as u64);
+        let commands = self.run(|commands| {
+            let encoder = commands.new_compute_command_encoder();
+            encoder.set_compute_pipeline_state(&pipeline);
+            for (i, input) in inputs.iter().enumerate() {
+                encoder.set_buffer(i as u64, Some(*input), 0);
+            }
+            encoder.set_buffer(inputs.len() as u64, Some(&out), 0);
+            encoder.set_bytes(
+                inputs.len() as u64 + 1,
+                std::mem::size_of::<u32>() as u64,
+                &len as *const u32 as *const c_void,
+            );
+            encoder.dispatch_threads(MTLSize::new(n as u64, 1, 1), MTLSize::new(width, 1, 1));
+            encoder.end_encoding();
+        });
+
+        return Ok(resident(out, n, commands));
+    }
+}
+
+// 以行主序、连续存放的 rows×columns 矩阵视图包装缓冲区的一段
+unsafe fn mps_matrix(buffer: &BufferRef, offset: u64, rows: u64, columns: u64) -> *mut Object {
+    unsafe {
+        let descriptor: *mut Object = msg_send![
+            class!(MPSMatrixDescriptor),
+            matrixDescriptorWithRows: rows
+            columns: columns
+            rowBytes: columns * 4
+            dataType: MPS_FLOAT32
+        ];
+        let matrix: *mut Object = msg_send![class!(MPSMatrix), alloc];
+        return msg_send![matrix, initWithBuffer: buffer offset: offset descriptor: descriptor];
+    }
+}
+
+impl Backend for MetalBackend {
+    fn upload(&self, data: &[f32]) -> Result<Arc<dyn DeviceBuffer>, String> {
+        // Metal 不能分配零长度的缓冲区，空张量也占一个元素的位置
+        let buffer = if data.is_empty() {
+            self.output(1)
+        } else {
+            self.device.new_buffer_with_data(
+                data.as_ptr() as *const c_void,
+                std::mem::size_of_val(data) as u64,
+                MTLResourceOptions::StorageModeShared,
+            )
+        };
+
+        return Ok(Arc::new(MetalBuffer {
+            buffer: AssertUnwindSafe(buffer),
+            len: data.len(),
+            pending: Mutex::new(None),
+        }));
+    }
+
+    fn binary(&self, op: BinaryOp, a: &Tensor, b: &Tensor) -> Result<Option<Tensor>, String> {
+        if a.shape != b.shape || a.data.is_empty() || a.data.len() > u32::MAX as usize {
+            return Ok(None);
+        }
+        let (da, db) = (a.device_buffer(self)?, b.device_buffer(self)?);
+        let kernel = format!("binary_{}", op.name());
+        let inputs = [self.buffer(&da)?, self.buffer(&db)?];
+        let out = self.elementwise(&kernel, &inputs, a.data.len())?;
+
+        return Ok(Some(Tensor::from_resident(out, a.shape.clone())?));
+    }
+
+    fn unary(&self, op: UnaryOp, x: &Tensor) -> Result<Option<Tensor>, String> {
+        if x.data.is_empty() || x.data.len() > u32::MAX as usize {
+            return Ok(None);
+        }
+        let dx = x.device_buffer(self)?;
+        let kernel = format!("unary_{}", op.name());
+        let out = self.elementwise(&kernel, &[self.buffer(&dx)?], x.data.len())?;
+
+        return Ok(Some(Tensor::from_resident(out, x.shape.clone())?));
+    }
+
+    fn reduce(&self, op: ReduceOp, x: &Tensor) -> Result<Option<f32>, String> {
+        if x.data.is_empty() || x.data.len() > u32::MAX as usize {
+            return Ok(None);
+        }
+        let kernel = match op {
+            ReduceOp::Sum => "reduce_sum",
+            ReduceOp::Max => "reduce_max",
+            ReduceOp::Min => "reduce_min",
+        };
+
+        let pipeline = self.pipeline(kernel)?;
+        let n = x.data.len() as u32;
+        let groups = (n as u64).div_ceil(REDUCE_GROUP).min(REDUCE_GROUPS);
+        let dx = x.device_buffer(self)?;
+        let input = self.buffer(&dx)?;
+        let partial = self.output(groups as usize);
+        let commands = self.run(|commands| {
+            let encoder = commands.new_compute_command_encoder();
+            encoder.set_compute_pipeline_state(&pipeline);
+            encoder.set_buffer(0, Some(input), 0);
+            encoder.set_buffer(1, Some(&partial), 0);
+            encoder.set_bytes(
+                2,
+                std::mem::size_of::<u32>() as u64,
+                &n as *const u32 as *const c_void,
+            );
+            encoder.dispatch_thread_groups(
+                MTLSize::new(groups, 1, 1),
+                MTLSize::new(REDUCE_GROUP, 1, 1),
+            );
+            encoder.end_encoding();
+        });
+
+        let partial = resident(partial, groups as usize, commands).read()?;
+        return Ok(Some(
+            partial
+                .into_iter()
+                .fold(op.identity(), |a, b| op.combine(a, b)),
+        ));
+    }
+
+    fn matmul(&self, a: &Tensor, b: &Tensor) -> Result<Option<Tensor>, String> {
+        let (ra, rb) = (a.shape.len(), b.shape.len());
+        if a.shape[..ra - 2] != b.shape[..rb - 2] {
+            return Ok(None);
+        }
+        let (m, k, n) = (a.shape[ra - 2], a.shape[ra - 1], b.shape[rb - 1]);
+        let batch: usize = a.shape[..ra - 2].iter().product();
+        if m == 0 || k == 0 || n == 0 || batch == 0 {
+            return Ok(None);
+        }
+
+        let (da, db) = (a.device_buffer(self)?, b.device_buffer(self)?);
+        let (left, right) = (self.buffer(&da)?, self.buffer(&db)?);
+        let dc = self.output(batch * m * n);
+        let (m, k, n) = (m as u64, k as u64, n as u64);
+        let commands = self.run(|commands| unsafe {
+            let kernel: *mut Object = msg_send![class!(MPSMatrixMultiplication), alloc];
+            let kernel: *mut Object = msg_send![
+                kernel,
+                initWithDevice: &*self.device
+                transposeLeft: NO
+                transposeRight: NO
+                resultRows: m
+                resultColumns: n
+                interiorColumns: k
+                alpha: 1.0f64
+                beta: 0.0f64
+            ];
+            // 同一个命令缓冲区里按批依次编码，每批是缓冲区中的一段偏移
+            for i in 0..batch as u64 {
+                let left = mps_matrix(left, i * m * k * 4, m, k);
+                let right = mps_matrix(right, i * k * n * 4, k, n);
+                let result = mps_matrix(&dc, i * m * n * 4, m, n);
+                let () = msg_send![
+                    kernel,
+                    encodeToCommandBuffer: commands
+                    leftMatrix: left
+                    rightMatrix: right
+                    resultMatrix: result
+                ];
+                let () = msg_send![left, release];
+                let () = msg_send![right, release];
+                let () = msg_send![result, release];
+            }
+            let () = msg_send![kernel, release];
+        });
+
+        let mut shape = a.shape[..ra - 2].to_vec();
+        shape.push(m as usize);
+        shape.push(n as usize);
+        let out = resident(dc, batch * (m * n) as usize, commands);
+        return Ok(Some(Tensor::from_resident(out, shape)?));
+    }
+}
+
+// 每个设备的后端在首次使用时创建并常驻，后续分派复用同一个命令队列与内核库
+static BACKENDS: Mutex<Vec<(usize, &'static MetalBackend)>> = Mutex::new(Vec::new());
+
+pub(crate) fn backend(ordinal: usize) -> Result<&'static dyn Backend, String> {
+    let mut backends = BACKENDS.lock().unwrap();
+    if let Some(&(_, backend)) = backends.iter().find(|(o, _)| *o == ordinal) {
+        return Ok(backend);
+    }
+    let backend: &'static MetalBackend = Box::leak(Box::new(MetalBackend::new(ordinal)?));
+    backends.push((ordinal, backend));
+
+    return Ok(backend);
+}
+
+pub fn device_count() -> usize {
+    return ::metal::Device::all().len();
+}
+
+pub fn device_name(ordinal: usize) -> Option<String> {
+    return ::metal::Device::all()
+        .get(ordinal)
+        .map(|device| device.name().to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Device, Tensor};
+
+    fn gpu() -> bool {
+        return super::device_count() > 0;
+    }
+
+    #[test]
+    fn dispatched_ops_match_host_results() {
+        let a = Tensor::new((0..12).map(|i| i as f32 - 5.0).collect(), vec![2, 2, 3]).unwrap();
+        let b = Tensor::new((0..18).map(|i| i as f32 * 0.5).collect(), vec![2, 3, 3]).unwrap();
+        if !gpu() {
+            assert!(a.to(Device::Metal(0)).is_err());
+            return;
+        }
+
+        let (ga, gb) = (
+            a.to(Device::Metal(0)).unwrap(),
+            b.to(Device::Metal(0)).unwrap(),
+        );
+        let product = ga.matmul(&gb).unwrap();
+        assert_eq!(product.device(), Device::Metal(0));
+        assert_eq!(product.data, a.matmul(&b).unwrap().data);
+        assert_eq!(ga.sub(&ga).unwrap().data, a.sub(&a).unwrap().data);
+        assert_eq!(ga.abs().unwrap().data, a.abs().unwrap().data);
+        assert_eq!(ga.sum().unwrap(), a.sum().unwrap());
+        assert_eq!(ga.min().unwrap(), -5.0);
+    }
+
+    // 算子链的中间结果留在设备上，不产生主机副本
+    #[test]
+    fn chained_ops_stay_resident_until_read() {
+        if !gpu() {
+            return;
+        }
+        let a = Tensor::new(
+            (0..1000).map(|i| (i as f32 - 500.0) / 100.0).collect(),
+            vec![10, 100],
+        )
+        .unwrap();
+        let w = Tensor::new(
+            (0..100 * 8).map(|i| (i % 7) as f32 - 3.0).collect(),
+            vec![100, 8],
+        )
+        .unwrap();
+        let (ga, gw) = (
+            a.to(Device::Metal(0)).unwrap(),
+            w.to(Device::Metal(0)).unwrap(),
+        );
+
+        let hidden = ga.mul(&ga).unwrap().sub(&ga).unwrap().tanh().unwrap();
+        let out = hidden.matmul(&gw).unwrap().relu().unwrap();
+        for t in [&hidden, &out] {
+            assert_eq!(t.device(), Device::Metal(0));
+            assert!(t.data.resident().is_some());
+            assert!(!t.data.is_on_host());
+        }
+
+        let expected = a
+            .mul(&a)
+            .unwrap()
+            .sub(&a)
+            .unwrap()
+            .tanh()
+            .unwrap()
+            .matmul(&w)
+            .unwrap()
+            .relu()
+            .unwrap();
+        for (x, y) in out.data.iter().zip(expected.data.iter()) {
+            assert!((x - y).abs() < 1e-3, "{} 与 {}", x, y);
+        }
+        assert!(out.data.is_on_host());
+    }
+
+    #[test]
+    fn host_readback_and_fallbacks_keep_the_device() {
+        if !gpu() {
+            return;
+        }
+        let a = Tensor::new((0..6).map(|i| i as f32).collect(), vec![2, 3]).unwrap();
+        let ga = a.to(Device::Metal(0)).unwrap();
+        let doubled = ga.add(&ga).unwrap();
+
+        // 主机实现的算子读取设备结果，结果仍在同一设备上，可以继续参与分派
+        let column = doubled.sum_axis(0).unwrap();
+        assert_eq!(column.device(), Device::Metal(0));
+        let shifted = doubled.add(&column.reshaped(vec![1, 3]).unwrap());
+        assert!(shifted.is_ok());
+
+        let back = doubled.to(Device::Cpu).unwrap();
+        assert_eq!(back.device(), Device::Cpu);
+        assert!(back.data.resident().is_none());
+        assert_eq!(back.data.to_vec(), vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+
+        // 主机端写入后设备副本失效，下一次分派重新上传
+        let mut edited = doubled.clone();
+        edited.data_mut().unwrap()[0] = 100.0;
+        assert!(edited.data.resident().is_none());
+        assert_eq!(edited.relu().unwrap().data[0], 100.0);
+
+        let empty = Tensor::new(vec![], vec![0, 3]).unwrap();
+        assert_eq!(empty.to(Device::Metal(0)).unwrap().data.len(), 0);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::Tensor;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationReport {
+    pub precision: Tensor,
+    pub recall: Tensor,
+    pub f1: Tensor,
+    pub support: Tensor,
+    pub macro_precision: f32,
+    pub macro_recall: f32,
+    pub macro_f1: f32,
+}
+
+fn check_pair(pred: &Tensor, labels: &Tensor) -> Result<(), String> {
+    if pred.shape != labels.shape {
+        return Err(format!(
+            "预测形状 {:?} 与标签形状 {:?} 不匹配",
+            pred.shape, labels.shape
+        ));
+    }
+
+    return Ok(());
+}
+
+pub fn confusion_matrix(
+    pred: &Tensor,
+    labels: &Tensor,
+    num_classes: usize,
+) -> Result<Tensor, String> {
+    check_pair(pred, labels)?;
+    let p = Tensor::to_indices(pred, num_classes)?;
+    let t = Tensor::to_indices(labels, num_classes)?;
+
+    let mut matrix = Tensor::zeros(vec![num_classes, num_classes])?;
+    for (&pi, &ti) in p.iter().zip(t.iter()) {
+        matrix.data[ti * num_classes + pi] += 1.0;
+    }
+
+    return Ok(matrix);
+}
+
+pub fn accuracy(pred: &Tensor, labels: &Tensor) -> Result<f32, String> {
+    check_pair(pred, labels)?;
+    if labels.data.is_empty() {
+        return Err("无法在空张量上计算准确率".to_string());
+    }
+
+    let correct = pred
+        .data
+        .iter()
+        .zip(labels.data.iter())
+        .filter(|(p, t)| p == t)
+        .count();
+
+    return Ok(correct as f32 / labels.data.len() as f32);
+}
+
+pub fn classification_report(
+    pred: &Tensor,
+    labels: &Tensor,
+    num_classes: usize,
+) -> Result<ClassificationReport, String> {
+    let matrix = confusion_matrix(pred, labels, num_classes)?;
+
+    let mut precision = vec![0.0; num_classes];
+    let mut recall = vec![0.0; num_classes];
+    let mut f1 = vec![0.0; num_classes];
+    let mut support = vec![0.0; num_classes];
+    for c in 0..num_classes {
+        let tp = matrix.data[c * num_classes + c];
+        let predicted: f32 = (0..num_classes)
+            .map(|r| matrix.data[r * num_classes + c])
+            .sum();
+        let actual: f32 = matrix.data[c * num_classes..(c + 1) * num_classes]
+            .iter()
+            .sum();
+
+        precision[c] = if predicted > 0.0 { tp / predicted } else { 0.0 };
+        recall[c] = if actual > 0.0 { tp / actual } else { 0.0 };
+        f1[c] = if precision[c] + recall[c] > 0.0 {
+            2.0 * precision[c] * recall[c] / (precision[c] + recall[c])
+        } else {
+            0.0
+        };
+        support[c] = actual;
+    }
+
+    let mean = |v: &[f32]| v.iter().sum::<f32>() / v.len().max(1) as f32;
+
+    return Ok(ClassificationReport {
+        macro_precision: mean(&precision),
+        macro_recall: mean(&recall),
+        macro_f1: mean(&f1),
+        precision: Tensor::new(precision, vec![num_classes])?,
+        recall: Tensor::new(recall, vec![num_classes])?,
+        f1: Tensor::new(f1, vec![num_classes])?,
+        support: Tensor::new(support, vec![num_classes])?,
+    });
+}
+
+pub fn roc_auc(scores: &Tensor, labels: &Tensor) -> Result<f32, String> {
+    check_pair(scores, labels)?;
+    let t = Tensor::to_indices(labels, 2)?;
+
+    let mut order: Vec<usize> = (0..scores.data.len()).collect();
+    order.sort_by(|&a, &b| scores.data[a].total_cmp(&scores.data[b]));
+
+    // 以平均秩处理并列分数（Mann-Whitney U 统计量）
+    let mut rank_sum_pos = 0.0f64;
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i;
+        while j + 1 < order.len() && scores.data[order[j + 1]] == scores.data[order[i]] {
+            j += 1;
+        }
+        let avg_rank = (i + j) as f64 / 2.0 + 1.0;
+        for &k in &order[i..=j] {
+            if t[k] == 1 {
+                rank_sum_pos += avg_rank;
+            }
+        }
+        i = j + 1;
+    }
+
+    let n_pos = t.iter().filter(|&&v| v == 1).count() as f64;
+    let n_neg = t.len() as f64 - n_pos;
+    if n_pos == 0.0 || n_neg == 0.0 {
+        return Err("ROC-AUC 需要同时包含正负样本".to_string());
+    }
+
+    let auc = (rank_sum_pos - n_pos * (n_pos + 1.0) / 2.0) / (n_pos * n_neg);
+
+    return Ok(auc as f32);
+}
+
+fn regression_inputs(
+    pred: &Tensor,
+    target: &Tensor,
+    axis: Option<usize>,
+) -> Result<(Tensor, Tensor, usize), String> {
+    check_pair(pred, target)?;
+
+    return match axis {
+        Some(axis) => {
+            pred.check_axis(axis)?;
+            Ok((pred.clone(), target.clone(), axis))
+        }
+        None => {
+            let n = pred.data.len();
+            Ok((pred.reshaped(vec![n])?, target.reshaped(vec![n])?, 0))
+        }
+    };
+}
+
+pub fn mae(pred: &Tensor, target: &Tensor, axis: Option<usize>) -> Result<Tensor, String> {
+    let (p, t, axis) = regression_inputs(pred, target, axis)?;
+
+    return p.sub(&t)?.abs()?.mean_axis(axis);
+}
+
+pub fn rmse(pred: &Tensor, target: &Tensor, axis: Option<usize>) -> Result<Tensor, String> {
+    let (p, t, axis) = regression_inputs(pred, target, axis)?;
+    let diff = p.sub(&t)?;
+
+    return diff.mul(&diff)?.mean_axis(axis)?.sqrt();
+}
+
+pub fn mape(pred: &Tensor, target: &Tensor, axis: Option<usize>) -> Result<Tensor, String> {
+    let (p, t, axis) = regression_inputs(pred, target, axis)?;
+    let denom = t.abs()?.map("mape", |v| v.max(f32::EPSILON))?;
+
+    return p.sub(&t)?.abs()?.div(&denom)?.mean_axis(axis);
+}
+
+pub fn r2_score(pred: &Tensor, target: &Tensor, axis: Option<usize>) -> Result<Tensor, String> {
+    let (p, t, axis) = regression_inputs(pred, target, axis)?;
+
+    let residual = p.sub(&t)?;
+    let ss_res = residual.mul(&residual)?.sum_axis(axis)?;
+
+    let mut keep = t.shape.clone();
+    keep[axis] = 1;
+    let centered = t.sub(&t.mean_axis(axis)?.reshaped(keep)?)?;
+    let ss_tot = centered.mul(&centered)?.sum_axis(axis)?;
+
+    return ss_res.zip_with("r2_score", &ss_tot, |res, tot| {
+        if tot > 0.0 {
+            1.0 - res / tot
+        } else if res == 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(data: Vec<f32>, shape: Vec<usize>) -> Tensor {
+        return Tensor::new(data, shape).unwrap();
+    }
+
+    #[test]
+    fn classification_metrics_match_hand_counts() {
+        let pred = t(vec![0.0, 1.0, 1.0, 2.0, 2.0, 0.0], vec![6]);
+        let labels = t(vec![0.0, 1.0, 2.0, 2.0, 1.0, 0.0], vec![6]);
+
+        let matrix = confusion_matrix(&pred, &labels, 3).unwrap();
+        assert_eq!(
+            matrix.data.to_vec(),
+            vec![2.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]
+        );
+        assert!((accuracy(&pred, &labels).unwrap() - 4.0 / 6.0).abs() < 1e-6);
+
+        let report = classification_report(&pred, &labels, 4).unwrap();
+        assert_eq!(report.precision.data.to_vec(), vec![1.0, 0.5, 0.5, 0.0]);
+        assert_eq!(report.recall.data.to_vec(), vec![1.0, 0.5, 0.5, 0.0]);
+        assert_eq!(report.support.data.to_vec(), vec![2.0, 2.0, 2.0, 0.0]);
+        assert!((report.macro_f1 - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn classification_metrics_reject_bad_labels() {
+        let pred = t(vec![0.0, 1.0], vec![2]);
+        assert!(confusion_matrix(&pred, &t(vec![0.0, 3.0], vec![2]), 3).is_err());
+        assert!(confusion_matrix(&pred, &t(vec![0.0, f32::NAN], vec![2]), 3).is_err());
+        assert!(confusion_matrix(&pred, &t(vec![0.5, 1.0], vec![2]), 3).is_err());
+        assert!(accuracy(&pred, &t(vec![0.0], vec![1])).is_err());
+        assert!(accuracy(&t(vec![], vec![0]), &t(vec![], vec![0])).is_err());
+
+        let empty = classification_report(&t(vec![], vec![0]), &t(vec![], vec![0]), 2).unwrap();
+        assert_eq!(empty.macro_precision, 0.0);
+    }
+
+    #[test]
+    fn regression_metrics_per_axis() {
+        let pred = t(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]);
+        let target = t(vec![1.0, 2.0, 5.0, 4.0, 4.0, 6.0], vec![2, 3]);
+
+        assert_eq!(mae(&pred, &target, None).unwrap().data.to_vec(), vec![0.5]);
+        assert_eq!(
+            mae(&pred, &target, Some(0)).unwrap().data.to_vec(),
+            vec![0.0, 0.5, 1.0]
+        );
+        assert_eq!(
+            rmse(&pred, &target, Some(1)).unwrap().data.to_vec(),
+            vec![(4.0f32 / 3.0).sqrt(), (1.0f32 / 3.0).sqrt()]
+        );
+        let ape = mape(&pred, &target, Some(1)).unwrap();
+        assert!((ape.data[0] - 0.4 / 3.0).abs() < 1e-6);
+        assert!(mae(&pred, &target, Some(2)).is_err());
+        assert!(mae(&pred, &target.reshaped(vec![6]).unwrap(), None).is_err());
+    }
+
+    #[test]
+    fn r2_score_edge_cases() {
+        let target = t(vec![1.0, 2.0, 3.0], vec![3]);
+        assert_eq!(r2_score(&target, &target, None).unwrap().data[0], 1.0);
+        let mean = t(vec![2.0; 3], vec![3]);
+        assert_eq!(r2_score(&mean, &target, None).unwrap().data[0], 0.0);
+
+        // 目标为常数时：完全预测为 1，否则为 0
+        let constant = t(vec![2.0; 3], vec![3]);
+        assert_eq!(r2_score(&constant, &constant, None).unwrap().data[0], 1.0);
+        assert_eq!(r2_score(&target, &constant, None).unwrap().data[0], 0.0);
+    }
+}
@@ -0,0 +1,109 @@
+use ::nalgebra::{DMatrix, DVector};
+
+use crate::Tensor;
+
+// nalgebra 按列主序存储，张量按行主序，转换时需要转置数据布局
+impl TryFrom<&Tensor> for DMatrix<f32> {
+    type Error = String;
+
+    fn try_from(tensor: &Tensor) -> Result<DMatrix<f32>, String> {
+        if tensor.shape.len() != 2 {
+            return Err(format!(
+                "只有二维张量可以转换为 DMatrix，实际形状为 {:?}",
+                tensor.shape
+            ));
+        }
+
+        return Ok(DMatrix::from_row_slice(
+            tensor.shape[0],
+            tensor.shape[1],
+            &tensor.data,
+        ));
+    }
+}
+
+impl TryFrom<&Tensor> for DVector<f32> {
+    type Error = String;
+
+    fn try_from(tensor: &Tensor) -> Result<DVector<f32>, String> {
+        if tensor.shape.len() != 1 {
+            return Err(format!(
+                "只有一维张量可以转换为 DVector，实际形状为 {:?}",
+                tensor.shape
+            ));
+        }
+
+        return Ok(DVector::from_column_slice(&tensor.data));
+    }
+}
+
+impl From<&DMatrix<f32>> for Tensor {
+    fn from(matrix: &DMatrix<f32>) -> Tensor {
+        let (rows, cols) = matrix.shape();
+        let mut data = crate::alloc::allocate(rows * cols);
+        for i in 0..rows {
+            data.extend(matrix.row(i).iter());
+        }
+
+        return Tensor::new(data, vec![rows, cols]).unwrap();
+    }
+}
+
+impl From<DMatrix<f32>> for Tensor {
+    fn from(matrix: DMatrix<f32>) -> Tensor {
+        return Tensor::from(&matrix);
+    }
+}
+
+impl From<&DVector<f32>> for Tensor {
+    fn from(vector: &DVector<f32>) -> Tensor {
+        return Tensor::new(vector.as_slice().to_vec(), vec![vector.len()]).unwrap();
+    }
+}
+
+impl From<DVector<f32>> for Tensor {
+    fn from(vector: DVector<f32>) -> Tensor {
+        let len = vector.len();
+        return Tensor::new(vector.data.into(), vec![len]).unwrap();
+    }
+}
+
+impl Tensor {
+    pub fn to_dmatrix(&self) -> Result<DMatrix<f32>, String> {
+        return DMatrix::try_from(self);
+    }
+
+    pub fn to_dvector(&self) -> Result<DVector<f32>, String> {
+        return DVector::try_from(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrices_and_vectors_keep_row_major_layout() {
+        let t = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]).unwrap();
+        let m = t.to_dmatrix().unwrap();
+        assert_eq!(m.shape(), (2, 3));
+        assert_eq!(m[(0, 2)], 3.0);
+        assert_eq!(m[(1, 0)], 4.0);
+        assert_eq!(Tensor::from(&m), t);
+
+        // 交给 nalgebra 求逆，再回到张量
+        let a = Tensor::new(vec![4.0, 7.0, 2.0, 6.0], vec![2, 2]).unwrap();
+        let inv = Tensor::from(a.to_dmatrix().unwrap().try_inverse().unwrap());
+        let eye = a.matmul(&inv).unwrap();
+        for (x, e) in eye.data.iter().zip([1.0, 0.0, 0.0, 1.0]) {
+            assert!((x - e).abs() < 1e-5);
+        }
+
+        let v = Tensor::new(vec![1.0, 2.0, 3.0], vec![3]).unwrap();
+        let dv = v.to_dvector().unwrap();
+        assert_eq!((&m * &dv)[1], 32.0);
+        assert_eq!(Tensor::from(dv), v);
+        assert!(t.to_dvector().is_err());
+        assert!(v.to_dmatrix().is_err());
+    }
+}
@@ -0,0 +1,819 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::Tensor;
+use crate::half::{bf16_to_f32, f16_to_f32, f32_to_bf16, f32_to_f16};
+use crate::proto::{Reader, Writer};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Attribute {
+    Float(f32),
+    Int(i64),
+    String(String),
+    Tensor(Tensor),
+    Floats(Vec<f32>),
+    Ints(Vec<i64>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub name: String,
+    pub op_type: String,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub attributes: HashMap<String, Attribute>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Model {
+    opset: i64,
+    nodes: Vec<Node>,
+    initializers: HashMap<String, Tensor>,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+// ONNX TensorProto.DataType
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    Float,
+    Uint8,
+    Int8,
+    Uint16,
+    Int16,
+    Int32,
+    Int64,
+    Bool,
+    Float16,
+    Double,
+    Uint32,
+    Uint64,
+    Bfloat16,
+}
+
+impl DataType {
+    pub fn from_code(code: i64) -> Result<DataType, String> {
+        return Ok(match code {
+            1 => DataType::Float,
+            2 => DataType::Uint8,
+            3 => DataType::Int8,
+            4 => DataType::Uint16,
+            5 => DataType::Int16,
+            6 => DataType::Int32,
+            7 => DataType::Int64,
+            9 => DataType::Bool,
+            10 => DataType::Float16,
+            11 => DataType::Double,
+            12 => DataType::Uint32,
+            13 => DataType::Uint64,
+            16 => DataType::Bfloat16,
+            _ => return Err(format!("ONNX 数据类型 {} 暂不支持", code)),
+        });
+    }
+
+    pub fn code(self) -> i64 {
+        return match self {
+            DataType::Float => 1,
+            DataType::Uint8 => 2,
+            DataType::Int8 => 3,
+            DataType::Uint16 => 4,
+            DataType::Int16 => 5,
+            DataType::Int32 => 6,
+            DataType::Int64 => 7,
+            DataType::Bool => 9,
+            DataType::Float16 => 10,
+            DataType::Double => 11,
+            DataType::Uint32 => 12,
+            DataType::Uint64 => 13,
+            DataType::Bfloat16 => 16,
+        };
+    }
+
+    pub fn size(self) -> usize {
+        return match self {
+            DataType::Uint8 | DataType::Int8 | DataType::Bool => 1,
+            DataType::Uint16 | DataType::Int16 | DataType::Float16 | DataType::Bfloat16 => 2,
+            DataType::Float | DataType::Int32 | DataType::Uint32 => 4,
+            DataType::Int64 | DataType::Double | DataType::Uint64 => 8,
+        };
+    }
+
+    fn range(self) -> Option<(f64, f64)> {
+        return match self {
+            DataType::Uint8 => Some((0.0, u8::MAX as f64)),
+            DataType::Int8 => Some((i8::MIN as f64, i8::MAX as f64)),
+            DataType::Uint16 => Some((0.0, u16::MAX as f64)),
+            DataType::Int16 => Some((i16::MIN as f64, i16::MAX as f64)),
+            DataType::Int32 => Some((i32::MIN as f64, i32::MAX as f64)),
+            DataType::Int64 => Some((i64::MIN as f64, i64::MAX as f64)),
+            DataType::Uint32 => Some((0.0, u32::MAX as f64)),
+            DataType::Uint64 => Some((0.0, u64::MAX as f64)),
+            _ => None,
+        };
+    }
+}
+
+fn decode_raw(dtype: DataType, raw: &[u8]) -> Result<Vec<f32>, String> {
+    if !raw.len().is_multiple_of(dtype.size()) {
+        return Err(format!(
+            "raw_data 长度 {} 不是 {:?} 元素大小 {} 的倍数",
+            raw.len(),
+            dtype,
+            dtype.size()
+        ));
+    }
+
+    let chunks = raw.chunks_exact(dtype.size());
+    return Ok(match dtype {
+        DataType::Float => chunks
+            .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+            .collect(),
+        DataType::Uint8 => chunks.map(|c| c[0] as f32).collect(),
+        DataType::Int8 => chunks.map(|c| c[0] as i8 as f32).collect(),
+        DataType::Bool => chunks.map(|c| (c[0] != 0) as u8 as f32).collect(),
+        DataType::Uint16 => chunks
+            .map(|c| u16::from_le_bytes(c.try_into().unwrap()) as f32)
+            .collect(),
+        DataType::Int16 => chunks
+            .map(|c| i16::from_le_bytes(c.try_into().unwrap()) as f32)
+            .collect(),
+        DataType::Float16 => chunks
+            .map(|c| f16_to_f32(u16::from_le_bytes(c.try_into().unwrap())))
+            .collect(),
+        DataType::Bfloat16 => chunks
+            .map(|c| bf16_to_f32(u16::from_le_bytes(c.try_into().unwrap())))
+            .collect(),
+        DataType::Int32 => chunks
+            .map(|c| i32::from_le_bytes(c.try_into().unwrap()) as f32)
+            .collect(),
+        DataType::Uint32 => chunks
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()) as f32)
+            .collect(),
+        DataType::Int64 => chunks
+            .map(|c| i64::from_le_bytes(c.try_into().unwrap()) as f32)
+            .collect(),
+        DataType::Uint64 => chunks
+            .map(|c| u64::from_le_bytes(c.try_into().unwrap()) as f32)
+            .collect(),
+        DataType::Double => chunks
+            .map(|c| f64::from_le_bytes(c.try_into().unwrap()) as f32)
+            .collect(),
+    });
+}
+
+fn encode_raw(dtype: DataType, data: &[f32]) -> Result<Vec<u8>, String> {
+    if let Some((min, max)) = dtype.range()
+        && let Some(&bad) = data
+            .iter()
+            .find(|&&v| v.fract() != 0.0 || (v as f64) < min || (v as f64) > max)
+    {
+        return Err(format!("值 {} 无法无损转换为 {:?}", bad, dtype));
+    }
+
+    let mut raw = Vec::with_capacity(data.len() * dtype.size());
+    for &v in data {
+        match dtype {
+            DataType::Float => raw.extend(v.to_le_bytes()),
+            DataType::Uint8 => raw.push(v as u8),
+            DataType::Int8 => raw.push(v as i8 as u8),
+            DataType::Bool => raw.push((v != 0.0) as u8),
+            DataType::Uint16 => raw.extend((v as u16).to_le_bytes()),
+            DataType::Int16 => raw.extend((v as i16).to_le_bytes()),
+            DataType::Float16 => raw.extend(f32_to_f16(v).to_le_bytes()),
+            DataType::Bfloat16 => raw.extend(f32_to_bf16(v).to_le_bytes()),
+            DataType::Int32 => raw.extend((v as i32).to_le_bytes()),
+            DataType::Uint32 => raw.extend((v as u32).to_le_bytes()),
+            DataType::Int64 => raw.extend((v as i64).to_le_bytes()),
+            DataType::Uint64 => raw.extend((v as u64).to_le_bytes()),
+            DataType::Double => raw.extend((v as f64).to_le_bytes()),
+        }
+    }
+
+    return Ok(raw);
+}
+
+pub fn tensor_from_proto(bytes: &[u8]) -> Result<(String, Tensor), String> {
+    let mut name = String::new();
+    let mut dims = Vec::new();
+    let mut data_type = 0;
+    let mut floats = Vec::new();
+    let mut ints = Vec::new();
+    let mut doubles = Vec::new();
+    let mut raw: Option<&[u8]> = None;
+
+    let mut reader = Reader::new(bytes);
+    while let Some((field, value)) = reader.next_field()? {
+        match field {
+            1 => value.push_ints(&mut dims)?,
+            2 => data_type = value.as_i64()?,
+            4 => value.push_f32s(&mut floats)?,
+            5 | 7 | 11 => value.push_ints(&mut ints)?,
+            8 => name = value.as_str()?,
+            9 => raw = Some(value.as_bytes()?),
+            10 => value.push_f64s(&mut doubles)?,
+            14 => return Err(format!("张量 {} 使用外部数据存储，暂不支持", name)),
+            _ => {}
+        }
+    }
+
+    if dims.iter().any(|&d| d < 0) {
+        return Err(format!("张量 {} 的维度 {:?} 含负数", name, dims));
+    }
+    let shape: Vec<usize> = dims.iter().map(|&d| d as usize).collect();
+    let dtype = DataType::from_code(data_type).map_err(|e| format!("张量 {}：{}", name, e))?;
+    // 未使用 raw_data 时，各类型存放在对应的 typed 字段中：
+    // float16/bfloat16 以位模式存于 int32_data，uint32/uint64 存于 uint64_data
+    let data: Vec<f32> = match (dtype, raw) {
+        (_, Some(raw)) => decode_raw(dtype, raw).map_err(|e| format!("张量 {}：{}", name, e))?,
+        (DataType::Float, None) => floats,
+        (DataType::Double, None) => doubles.iter().map(|&v| v as f32).collect(),
+        (DataType::Float16, None) => ints.iter().map(|&v| f16_to_f32(v as u16)).collect(),
+        (DataType::Bfloat16, None) => ints.iter().map(|&v| bf16_to_f32(v as u16)).collect(),
+        (DataType::Bool, None) => ints.iter().map(|&v| (v != 0) as u8 as f32).collect(),
+        (DataType::Uint64, None) => ints.iter().map(|&v| v as u64 as f32).collect(),
+        (_, None) => ints.iter().map(|&v| v as f32).collect(),
+    };
+    let tensor = Tensor::new(data, shape).map_err(|e| format!("张量 {} 数据无效：{}", name, e))?;
+
+    return Ok((name, tensor));
+}
+
+pub fn tensor_to_proto(name: &str, tensor: &Tensor, dtype: DataType) -> Result<Vec<u8>, String> {
+    let raw = encode_raw(dtype, &tensor.data).map_err(|e| format!("张量 {}：{}", name, e))?;
+
+    let mut w = Writer::new();
+    for &d in &tensor.shape {
+        w.varint(1, d as u64);
+    }
+    w.varint(2, dtype.code() as u64);
+    w.bytes(8, name.as_bytes());
+    w.bytes(9, &raw);
+
+    return Ok(w.finish());
+}
+
+fn decode_attribute(bytes: &[u8]) -> Result<(String, Attribute), String> {
+    let mut name = String::new();
+    let mut kind = 0;
+    let (mut f, mut i, mut s, mut t) = (None, None, None, None);
+    let (mut floats, mut ints) = (Vec::new(), Vec::new());
+
+    let mut reader = Reader::new(bytes);
+    while let Some((field, value)) = reader.next_field()? {
+        match field {
+            1 => name = value.as_str()?,
+            2 => f = Some(value.as_f32()?),
+            3 => i = Some(value.as_i64()?),
+            4 => s = Some(value.as_str()?),
+            5 => t = Some(tensor_from_proto(value.as_bytes()?)?.1),
+            7 => value.push_f32s(&mut floats)?,
+            8 => value.push_ints(&mut ints)?,
+            20 => kind = value.as_i64()?,
+            _ => {}
+        }
+    }
+
+    // AttributeProto.AttributeType：缺失时按出现的字段推断
+    let attribute = match (kind, f, i, s, t) {
+        (1, Some(f), ..) | (0, Some(f), None, None, None) => Attribute::Float(f),
+        (2, _, Some(i), ..) | (0, None, Some(i), None, None) => Attribute::Int(i),
+        (3, _, _, Some(s), _) | (0, None, None, Some(s), None) => Attribute::String(s),
+        (4, .., Some(t)) | (0, None, None, None, Some(t)) => Attribute::Tensor(t),
+        (6, ..) => Attribute::Floats(floats),
+        (7, ..) => Attribute::Ints(ints),
+        (0, ..) if !floats.is_empty() => Attribute::Floats(floats),
+        (0, ..) if !ints.is_empty() => Attribute::Ints(ints),
+        _ => return Err(format!("属性 {} 的类型 {} 暂不支持", name, kind)),
+    };
+
+    return Ok((name, attribute));
+}
+
+fn decode_node(bytes: &[u8]) -> Result<Node, String> {
+    let mut node = Node {
+        name: String::new(),
+        op_type: String::new(),
+        inputs: Vec::new(),
+        outputs: Vec::new(),
+        attributes: HashMap::new(),
+    };
+    let mut domain = String::new();
+
+    let mut reader = Reader::new(bytes);
+    while let Some((field, value)) = reader.next_field()? {
+        match field {
+            1 => node.inputs.push(value.as_str()?),
+            2 => node.outputs.push(value.as_str()?),
+            3 => node.name = value.as_str()?,
+            4 => node.op_type = value.as_str()?,
+            5 => {
+                let (name, attribute) = decode_attribute(value.as_bytes()?)?;
+                node.attributes.insert(name, attribute);
+            }
+            7 => domain = value.as_str()?,
+            _ => {}
+        }
+    }
+    if !domain.is_empty() && domain != "ai.onnx" {
+        return Err(format!(
+            "节点 {} 属于算子域 {}，仅支持默认域",
+            node.name, domain
+        ));
+    }
+
+    return Ok(node);
+}
+
+fn value_info_name(bytes: &[u8]) -> Result<String, String> {
+    let mut reader = Reader::new(bytes);
+    while let Some((field, value)) = reader.next_field()? {
+        if field == 1 {
+            return value.as_str();
+        }
+    }
+
+    return Err("ValueInfoProto 缺少名称".to_string());
+}
+
+fn axis_index(axis: i64, rank: usize) -> Result<usize, String> {
+    let resolved = if axis < 0 { axis + rank as i64 } else { axis };
+    if resolved < 0 || resolved >= rank as i64 {
+        return Err(format!("轴 {} 超出张量秩 {} 的范围", axis, rank));
+    }
+
+    return Ok(resolved as usize);
+}
+
+impl Node {
+    fn int(&self, name: &str, default: i64) -> Result<i64, String> {
+        return match self.attributes.get(name) {
+            None => Ok(default),
+            Some(Attribute::Int(v)) => Ok(*v),
+            Some(other) => Err(format!(
+                "节点 {} 的属性 {} 应为整数，实际为 {:?}",
+                self.name, name, other
+            )),
+        };
+    }
+
+    fn float(&self, name: &str, default: f32) -> Result<f32, String> {
+        return match self.attributes.get(name) {
+            None => Ok(default),
+            Some(Attribute::Float(v)) => Ok(*v),
+            Some(other) => Err(format!(
+                "节点 {} 的属性 {} 应为浮点数，实际为 {:?}",
+                self.name, name, other
+            )),
+        };
+    }
+
+    fn ints(&self, name: &str, default: &[i64]) -> Result<Vec<i64>, String> {
+        return match self.attributes.get(name) {
+            None => Ok(default.to_vec()),
+            Some(Attribute::Ints(v)) => Ok(v.clone()),
+            Some(other) => Err(format!(
+                "节点 {} 的属性 {} 应为整数列表，实际为 {:?}",
+                self.name, name, other
+            )),
+        };
+    }
+}
+
+impl Model {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Model, String> {
+        let mut graph = None;
+        let mut opset = None;
+
+        let mut reader = Reader::new(bytes);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                7 => graph = Some(value.as_bytes()?),
+                8 => {
+                    let (mut domain, mut version) = (String::new(), 0);
+                    let mut entry = Reader::new(value.as_bytes()?);
+                    while let Some((field, value)) = entry.next_field()? {
+                        match field {
+                            1 => domain = value.as_str()?,
+                            2 => version = value.as_i64()?,
+                            _ => {}
+                        }
+                    }
+                    if domain.is_empty() || domain == "ai.onnx" {
+                        opset = Some(version);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(graph) = graph else {
+            return Err("ONNX 模型缺少计算图".to_string());
+        };
+
+        let mut model = Model {
+            opset: opset.unwrap_or(13),
+            nodes: Vec::new(),
+            initializers: HashMap::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        };
+        let mut reader = Reader::new(graph);
+        while let Some((field, value)) = reader.next_field()? {
+            match field {
+                1 => model.nodes.push(decode_node(value.as_bytes()?)?),
+                5 => {
+                    let (name, tensor) = tensor_from_proto(value.as_bytes()?)?;
+                    model.initializers.insert(name, tensor);
+                }
+                11 => model.inputs.push(value_info_name(value.as_bytes()?)?),
+                12 => model.outputs.push(value_info_name(value.as_bytes()?)?),
+                _ => {}
+            }
+        }
+        // 旧版导出器会把初始化器也列为图输入
+        model
+            .inputs
+            .retain(|name| !model.initializers.contains_key(name));
+
+        return Ok(model);
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Model, String> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| format!("无法读取 ONNX 文件 {}：{}", path.display(), e))?;
+
+        return Model::from_bytes(&bytes);
+    }
+
+    pub fn opset(&self) -> i64 {
+        return self.opset;
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        return &self.nodes;
+    }
+
+    pub fn inputs(&self) -> &[String] {
+        return &self.inputs;
+    }
+
+    pub fn outputs(&self) -> &[String] {
+        return &self.outputs;
+    }
+
+    pub fn initializer(&self, name: &str) -> Option<&Tensor> {
+        return self.initializers.get(name);
+    }
+
+    pub fn run(&self, feeds: &[(&str, &Tensor)]) -> Result<Vec<Tensor>, String> {
+        let mut env: HashMap<String, Tensor> = HashMap::new();
+        for name in &self.inputs {
+            let Some((_, tensor)) = feeds.iter().find(|(n, _)| n == name) else {
+                return Err(format!("缺少模型输入 {}", name));
+            };
+            env.insert(name.clone(), (*tensor).clone());
+        }
+
+        for node in &self.nodes {
+            let mut args = Vec::with_capacity(node.inputs.len());
+            for name in &node.inputs {
+                if name.is_empty() {
+                    args.push(None);
+                    continue;
+                }
+                let Some(tensor) = env.get(name).or_else(|| self.initializers.get(name)) else {
+                    return Err(format!("节点 {} 的输入 {} 尚未计算", node.name, name));
+                };
+                args.push(Some(tensor));
+            }
+            let output = self
+                .execute(node, &args)
+                .map_err(|e| format!("执行节点 {}（{}）失败：{}", node.name, node.op_type, e))?;
+            let Some(name) = node.outputs.first() else {
+                return Err(format!("节点 {} 没有输出", node.name));
+            };
+            env.insert(name.clone(), output);
+        }
+
+        let mut outputs = Vec::with_capacity(self.outputs.len());
+        for name in &self.outputs {
+            let Some(tensor) = env
+                .remove(name)
+                .or_else(|| self.initializers.get(name).cloned())
+            else {
+                return Err(format!("模型输出 {} 未被任何节点产生", name));
+            };
+            outputs.push(tensor);
+        }
+
+        return Ok(outputs);
+    }
+
+    fn execute(&self, node: &Node, args: &[Option<&Tensor>]) -> Result<Tensor, String> {
+        let arg = |i: usize| -> Result<&Tensor, String> {
+            return args
+                .get(i)
+                .copied()
+                .flatten()
+                .ok_or_else(|| format!("缺少第 {} 个输入", i));
+        };
+
+        return match node.op_type.as_str() {
+            "Relu" => arg(0)?.relu(),
+            "Add" => arg(0)?.add(arg(1)?),
+            "Gemm" => {
+                let mut a = arg(0)?.clone();
+                let mut b = arg(1)?.clone();
+                if a.shape.len() != 2 || b.shape.len() != 2 {
+                    return Err(format!(
+                        "Gemm 需要二维输入，实际形状为 {:?} 与 {:?}",
+                        a.shape, b.shape
+                    ));
+                }
+                if node.int("transA", 0)? != 0 {
+                    a = a.transpose(0, 1)?;
+                }
+                if node.int("transB", 0)? != 0 {
+                    b = b.transpose(0, 1)?;
+                }
+                let alpha = node.float("alpha", 1.0)?;
+                let beta = node.float("beta", 1.0)?;
+                let mut y = a.matmul(&b)?;
+                if alpha != 1.0 {
+                    y = y.mul_scalar(alpha)?;
+                }
+                if let Some(c) = args.get(2).copied().flatten() {
+                    let shape = y.shape.clone();
+                    y = y.add(&c.mul_scalar(beta)?)?;
+                    if y.shape != shape {
+                        return Err(format!(
+                            "Gemm 偏置形状 {:?} 无法广播到 {:?}",
+                            c.shape, shape
+                        ));
+                    }
+                }
+                Ok(y)
+            }
+            "Conv" => {
+                let (x, w) = (arg(0)?, arg(1)?);
+                let group = node.int("group", 1)?;
+                if group < 1 {
+                    return Err(format!("Conv 的 group = {} 必须为正", group));
+                }
+                if node.ints("dilations", &[1, 1])?.iter().any(|&d| d != 1) {
+                    return Err("Conv 暂不支持空洞卷积".to_string());
+                }
+                match node.attributes.get("auto_pad") {
+                    None => {}
+                    Some(Attribute::String(s)) if s == "NOTSET" || s == "VALID" => {}
+                    Some(other) => return Err(format!("Conv 不支持 auto_pad = {:?}", other)),
+                }
+                let strides = node.ints("strides", &[1, 1])?;
+                let pads = node.ints("pads", &[0, 0, 0, 0])?;
+                if strides.len() != 2 || pads.len() != 4 || strides.iter().any(|&s| s < 1) {
+                    return Err(format!(
+                        "Conv 仅支持二维卷积，strides = {:?}，pads = {:?}",
+                        strides, pads
+                    ));
+                }
+                if pads[0] != pads[2] || pads[1] != pads[3] || pads.iter().any(|&p| p < 0) {
+                    return Err(format!("Conv 暂不支持非对称填充 {:?}", pads));
+                }
+                x.conv2d(
+                    w,
+                    args.get(2).copied().flatten(),
+                    (strides[0] as usize, strides[1] as usize),
+                    (pads[0] as usize, pads[1] as usize),
+                    group as usize,
+                )
+            }
+            "Reshape" => {
+                let (x, target) = (arg(0)?, arg(1)?);
+                let mut shape = Vec::with_capacity(target.data.len());
+                let mut infer = None;
+                for (i, &d) in target.data.iter().enumerate() {
+                    match d as i64 {
+                        -1 if infer.is_none() => {
+                            infer = Some(i);
+                            shape.push(1);
+                        }
+                        0 if node.int("allowzero", 0)? == 0 => {
+                            let Some(&dim) = x.shape.get(i) else {
+                                return Err(format!("Reshape 第 {} 维为 0 但输入秩不足", i));
+                            };
+                            shape.push(dim);
+                        }
+                        d if d >= 0 => shape.push(d as usize),
+                        _ => return Err(format!("Reshape 目标形状 {:?} 无效", target.data)),
+                    }
+                }
+                if let Some(i) = infer {
+                    let known: usize = shape.iter().product();
+                    if known == 0 || x.data.len() % known != 0 {
+                        return Err(format!(
+                            "Reshape 无法将 {:?} 推断为 {:?}",
+                            x.shape, target.data
+                        ));
+                    }
+                    shape[i] = x.data.len() / known;
+                }
+                x.reshaped(shape)
+            }
+            "Softmax" => {
+                let x = arg(0)?;
+                if self.opset >= 13 {
+                    x.softmax(axis_index(node.int("axis", -1)?, x.shape.len())?)
+                } else {
+                    // opset 13 之前 Softmax 先在 axis 处展平为二维
+                    let axis = axis_index(node.int("axis", 1)?, x.shape.len())?;
+                    let rows = x.shape[..axis].iter().product();
+                    let cols = x.shape[axis..].iter().product();
+                    x.reshaped(vec![rows, cols])?
+                        .softmax(1)?
+                        .reshaped(x.shape.clone())
+                }
+            }
+            other => Err(format!("不支持的 ONNX 算子 {}", other)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tensor(name: &str, shape: &[usize], data: &[f32], dtype: DataType, g: &mut Writer) {
+        let t = Tensor::new(data.to_vec(), shape.to_vec()).unwrap();
+        g.bytes(5, &tensor_to_proto(name, &t, dtype).unwrap());
+    }
+
+    fn node(op: &str, inputs: &[&str], output: &str, attrs: &[Vec<u8>], g: &mut Writer) {
+        let mut n = Writer::new();
+        for input in inputs {
+            n.bytes(1, input.as_bytes());
+        }
+        n.bytes(2, output.as_bytes());
+        n.bytes(3, output.as_bytes());
+        n.bytes(4, op.as_bytes());
+        for attr in attrs {
+            n.bytes(5, attr);
+        }
+        g.bytes(1, &n.finish());
+    }
+
+    fn int_attr(name: &str, v: i64) -> Vec<u8> {
+        let mut a = Writer::new();
+        a.bytes(1, name.as_bytes());
+        a.varint(3, v as u64);
+        a.varint(20, 2);
+        return a.finish();
+    }
+
+    fn ints_attr(name: &str, v: &[i64]) -> Vec<u8> {
+        let mut a = Writer::new();
+        a.bytes(1, name.as_bytes());
+        a.packed_varints(8, v);
+        a.varint(20, 7);
+        return a.finish();
+    }
+
+    fn value_info(field: u32, name: &str, g: &mut Writer) {
+        let mut v = Writer::new();
+        v.bytes(1, name.as_bytes());
+        g.bytes(field, &v.finish());
+    }
+
+    fn model_bytes(graph: Writer, opset: i64) -> Vec<u8> {
+        let mut m = Writer::new();
+        m.varint(1, 8);
+        m.bytes(7, &graph.finish());
+        let mut op = Writer::new();
+        op.varint(2, opset as u64);
+        m.bytes(8, &op.finish());
+        return m.finish();
+    }
+
+    #[test]
+    fn runs_gemm_relu_softmax_classifier() {
+        let mut g = Writer::new();
+        node(
+            "Gemm",
+            &["x", "w", "b"],
+            "h",
+            &[int_attr("transB", 1)],
+            &mut g,
+        );
+        node("Relu", &["h"], "r", &[], &mut g);
+        node("Softmax", &["r"], "y", &[int_attr("axis", 1)], &mut g);
+        // w 以 [out, in] 存储，与 PyTorch Linear 导出一致
+        let w = [1.0, 0.0, -1.0, 0.5, 0.5, 0.5];
+        tensor("w", &[2, 3], &w, DataType::Float, &mut g);
+        tensor("b", &[2], &[0.0, -10.0], DataType::Float, &mut g);
+        value_info(11, "x", &mut g);
+        value_info(11, "w", &mut g);
+        value_info(12, "y", &mut g);
+
+        let model = Model::from_bytes(&model_bytes(g, 11)).unwrap();
+        assert_eq!(model.inputs(), ["x"]);
+        let x = Tensor::new(vec![3.0, 1.0, 1.0, 0.0, 0.0, 4.0], vec![2, 3]).unwrap();
+        let y = model.run(&[("x", &x)]).unwrap().remove(0);
+
+        // 第一行 h = [2, -7.5] → relu [2, 0]；第二行 h = [-4, -8] → relu [0, 0]
+        let e = 2f32.exp();
+        assert_eq!(y.shape, [2, 2]);
+        assert!((y.data[0] - e / (e + 1.0)).abs() < 1e-6);
+        assert!((y.data[2] - 0.5).abs() < 1e-6);
+        assert!(model.run(&[]).is_err());
+    }
+
+    #[test]
+    fn runs_conv_reshape_add_and_rejects_unknown_ops() {
+        let mut g = Writer::new();
+        let conv_attrs = [
+            ints_attr("pads", &[1, 1, 1, 1]),
+            ints_attr("strides", &[2, 2]),
+        ];
+        node("Conv", &["x", "k"], "c", &conv_attrs, &mut g);
+        node("Reshape", &["c", "s"], "flat", &[], &mut g);
+        node("Add", &["flat", "bias"], "y", &[], &mut g);
+        tensor("k", &[1, 1, 3, 3], &[1.0; 9], DataType::Float, &mut g);
+        // 形状张量使用 int64_data 而非 raw_data 存储
+        let mut shape = Writer::new();
+        shape.varint(1, 2);
+        shape.varint(2, DataType::Int64.code() as u64);
+        shape.bytes(8, b"s");
+        shape.packed_varints(7, &[0, -1]);
+        g.bytes(5, &shape.finish());
+        tensor("bias", &[1], &[0.5], DataType::Float, &mut g);
+        value_info(11, "x", &mut g);
+        value_info(12, "y", &mut g);
+
+        let model = Model::from_bytes(&model_bytes(g, 13)).unwrap();
+        let x = Tensor::ones(vec![1, 1, 4, 4]).unwrap();
+        let y = model.run(&[("x", &x)]).unwrap().remove(0);
+        assert_eq!(y.shape, [1, 4]);
+        assert_eq!(y.data.to_vec(), vec![4.5, 6.5, 6.5, 9.5]);
+
+        let mut bad = Writer::new();
+        node("LSTM", &["x"], "y", &[], &mut bad);
+        value_info(11, "x", &mut bad);
+        value_info(12, "y", &mut bad);
+        let err = Model::from_bytes(&model_bytes(bad, 13))
+            .unwrap()
+            .run(&[("x", &x)])
+            .unwrap_err();
+        assert!(err.contains("LSTM"));
+    }
+
+    #[test]
+    fn tensor_proto_round_trips_each_dtype() {
+        let t = Tensor::new(vec![0.0, 1.0, -2.0, 100.0, 0.5, -0.25], vec![2, 3]).unwrap();
+        for dtype in [
+            DataType::Float,
+            DataType::Double,
+            DataType::Float16,
+            DataType::Bfloat16,
+        ] {
+            let bytes = tensor_to_proto("w", &t, dtype).unwrap();
+            let (name, back) = tensor_from_proto(&bytes).unwrap();
+            assert_eq!(name, "w");
+            assert_eq!(back, t);
+        }
+
+        let ints = Tensor::new(vec![-3.0, 0.0, 7.0], vec![3]).unwrap();
+        for dtype in [
+            DataType::Int8,
+            DataType::Int16,
+            DataType::Int32,
+            DataType::Int64,
+        ] {
+            let bytes = tensor_to_proto("i", &ints, dtype).unwrap();
+            assert_eq!(tensor_from_proto(&bytes).unwrap().1, ints);
+        }
+        assert!(tensor_to_proto("i", &ints, DataType::Uint8).is_err());
+        assert!(tensor_to_proto("w", &t, DataType::Int32).is_err());
+
+        // float16 舍入到最近可表示值，超出范围变为无穷
+        let wide = Tensor::new(vec![1.0 + 1e-4, 1e5, 1e-8], vec![3]).unwrap();
+        let back = tensor_from_proto(&tensor_to_proto("h", &wide, DataType::Float16).unwrap())
+            .unwrap()
+            .1;
+        assert_eq!(back.data.to_vec(), vec![1.0, f32::INFINITY, 0.0]);
+
+        // 旧式 float16 张量以位模式存放在 int32_data 中
+        let mut w = Writer::new();
+        w.varint(1, 2);
+        w.varint(2, DataType::Float16.code() as u64);
+        w.packed_varints(5, &[0x3c00, 0xc000]);
+        assert_eq!(
+            tensor_from_proto(&w.finish()).unwrap().1.data.to_vec(),
+            vec![1.0, -2.0]
+        );
+
+        let mut w = Writer::new();
+        w.varint(1, 2);
+        w.varint(2, DataType::Float.code() as u64);
+        w.bytes(9, &[0; 6]);
+        assert!(tensor_from_proto(&w.finish()).is_err());
+    }
+}
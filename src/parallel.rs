@@ -0,0 +1,139 @@
+use std::thread;
+
+use crate::config;
+
+pub(crate) fn workers_for(len: usize) -> usize {
+    if len < config::parallel_threshold() {
+        return 1;
+    }
+
+    return config::num_threads().min(len).max(1);
+}
+
+pub(crate) fn fill_chunks<F>(out: &mut [f32], f: F)
+where
+    F: Fn(usize, &mut [f32]) + Sync,
+{
+    let workers = workers_for(out.len());
+    if workers <= 1 {
+        f(0, out);
+        return;
+    }
+
+    let chunk = out.len().div_ceil(workers);
+    thread::scope(|scope| {
+        for (i, part) in out.chunks_mut(chunk).enumerate() {
+            let f = &f;
+            scope.spawn(move || f(i * chunk, part));
+        }
+    });
+}
+
+pub(crate) fn map_range<T, F>(count: usize, work: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+{
+    let workers = workers_for(work).min(count);
+    if workers <= 1 {
+        return (0..count).map(f).collect();
+    }
+
+    let per_worker = count.div_ceil(workers);
+    return thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|w| {
+                let f = &f;
+                let start = (w * per_worker).min(count);
+                let end = ((w + 1) * per_worker).min(count);
+                scope.spawn(move || (start..end).map(f).collect::<Vec<T>>())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    });
+}
+
+// 叶子块大小固定，与线程数无关
+const REDUCE_LEAF: usize = 4096;
+
+// 把 data 切成固定大小的叶子块并行归约，再按固定的二叉树两两合并。
+// 块边界与合并顺序只取决于长度，不受线程数与调度影响，因此结果逐位可复现；
+// 两两合并也让浮点求和的误差只随 log(n) 增长。空输入返回 None
+pub(crate) fn reduce_tree<T, L, C>(data: &[f32], leaf: L, combine: C) -> Option<T>
+where
+    T: Send,
+    L: Fn(usize, &[f32]) -> T + Sync,
+    C: Fn(T, T) -> T,
+{
+    let leaves = data.len().div_ceil(REDUCE_LEAF);
+    let mut level = map_range(leaves, data.len(), |i| {
+        let start = i * REDUCE_LEAF;
+        leaf(start, &data[start..(start + REDUCE_LEAF).min(data.len())])
+    });
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut items = level.into_iter();
+        while let Some(left) = items.next() {
+            next.push(match items.next() {
+                Some(right) => combine(left, right),
+                None => left,
+            });
+        }
+        level = next;
+    }
+
+    return level.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 工作量超过默认并行阈值，多核机器上会走多线程分支
+    const LARGE: usize = 1 << 20;
+
+    #[test]
+    fn map_range_keeps_order() {
+        for work in [0, LARGE] {
+            let out = map_range(1000, work, |i| i * 2);
+            assert_eq!(out, (0..1000).map(|i| i * 2).collect::<Vec<_>>());
+        }
+        assert!(map_range(0, LARGE, |i| i).is_empty());
+        assert_eq!(map_range(3, LARGE, |i| i), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn fill_chunks_passes_global_offsets() {
+        for len in [0, 10, LARGE + 3] {
+            let mut out = vec![0.0f32; len];
+            fill_chunks(&mut out, |start, part| {
+                for (k, o) in part.iter_mut().enumerate() {
+                    *o = (start + k) as f32;
+                }
+            });
+            assert!(out.iter().enumerate().all(|(i, &v)| v == i as f32));
+        }
+    }
+
+    #[test]
+    fn reduce_tree_combines_leaves_in_order() {
+        assert!(reduce_tree(&[], |_, c| c.len(), |a, b| a + b).is_none());
+        let data = vec![1.0f32; LARGE + 5];
+        let count = reduce_tree(&data, |_, c| c.len(), |a, b| a + b);
+        assert_eq!(count, Some(LARGE + 5));
+        // 合并时左边总是较早的块
+        let starts = reduce_tree(
+            &data,
+            |start, _| vec![start],
+            |mut a, b| {
+                a.extend(b);
+                a
+            },
+        )
+        .unwrap();
+        assert!(starts.windows(2).all(|w| w[1] == w[0] + REDUCE_LEAF));
+    }
+}
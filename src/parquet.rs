@@ -0,0 +1,650 @@
+use std::path::Path;
+
+use crate::Tensor;
+
+// 只读取扁平（无重复字段）的数值列：BOOLEAN、INT32、INT64、FLOAT、DOUBLE，
+// 支持 PLAIN 与字典编码、v1/v2 数据页、未压缩与 Snappy 压缩。
+// DECIMAL 按 scale 还原为小数，空值映射为 NaN。
+const MAGIC: &[u8; 4] = b"PAR1";
+
+// Thrift compact 协议解出的通用值，按字段编号取用
+#[derive(Debug, Clone, PartialEq)]
+enum Thrift<'a> {
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    Binary(&'a [u8]),
+    List(Vec<Thrift<'a>>),
+    Struct(Vec<(i16, Thrift<'a>)>),
+}
+
+impl<'a> Thrift<'a> {
+    fn field(&self, id: i16) -> Option<&Thrift<'a>> {
+        let Thrift::Struct(fields) = self else {
+            return None;
+        };
+
+        return fields.iter().find(|(i, _)| *i == id).map(|(_, v)| v);
+    }
+
+    fn int(&self, id: i16) -> Option<i64> {
+        return match self.field(id) {
+            Some(Thrift::Int(v)) => Some(*v),
+            _ => None,
+        };
+    }
+
+    fn bool(&self, id: i16) -> Option<bool> {
+        return match self.field(id) {
+            Some(Thrift::Bool(v)) => Some(*v),
+            _ => None,
+        };
+    }
+
+    fn string(&self, id: i16) -> Option<String> {
+        return match self.field(id) {
+            Some(Thrift::Binary(b)) => Some(String::from_utf8_lossy(b).into_owned()),
+            _ => None,
+        };
+    }
+
+    fn list(&self, id: i16) -> &[Thrift<'a>] {
+        return match self.field(id) {
+            Some(Thrift::List(items)) => items,
+            _ => &[],
+        };
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() - self.pos < len {
+            return Err(format!(
+                "Parquet 数据在偏移 {} 处被截断（需要 {} 字节）",
+                self.pos, len
+            ));
+        }
+        let out = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+
+        return Ok(out);
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        return Ok(self.take(1)?[0]);
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        return Err(format!("Parquet varint 在偏移 {} 处过长", self.pos));
+    }
+
+    fn zigzag(&mut self) -> Result<i64, String> {
+        let v = self.varint()?;
+        return Ok((v >> 1) as i64 ^ -((v & 1) as i64));
+    }
+
+    fn thrift_value(&mut self, kind: u8, depth: usize) -> Result<Thrift<'a>, String> {
+        if depth > 64 {
+            return Err("Parquet 元数据嵌套过深".to_string());
+        }
+
+        return Ok(match kind {
+            1 => Thrift::Bool(true),
+            2 => Thrift::Bool(false),
+            3 => Thrift::Int(self.byte()? as i8 as i64),
+            4..=6 => Thrift::Int(self.zigzag()?),
+            7 => Thrift::Double(f64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            8 => {
+                let len = self.varint()? as usize;
+                Thrift::Binary(self.take(len)?)
+            }
+            9 | 10 => {
+                let head = self.byte()?;
+                let len = match head >> 4 {
+                    15 => self.varint()? as usize,
+                    n => n as usize,
+                };
+                let inner = head & 0x0f;
+                let mut items = Vec::with_capacity(len.min(1 << 16));
+                for _ in 0..len {
+                    // 列表中的布尔值各占一个字节
+                    items.push(match inner {
+                        1 | 2 => Thrift::Bool(self.byte()? == 1),
+                        _ => self.thrift_value(inner, depth + 1)?,
+                    });
+                }
+                Thrift::List(items)
+            }
+            11 => {
+                let len = self.varint()? as usize;
+                if len > 0 {
+                    let types = self.byte()?;
+                    for _ in 0..len {
+                        self.thrift_value(types >> 4, depth + 1)?;
+                        self.thrift_value(types & 0x0f, depth + 1)?;
+                    }
+                }
+                // 用到的元数据里映射只出现在忽略的字段中
+                Thrift::List(Vec::new())
+            }
+            12 => self.thrift_struct(depth + 1)?,
+            other => return Err(format!("未知的 Thrift 类型 {}", other)),
+        });
+    }
+
+    fn thrift_struct(&mut self, depth: usize) -> Result<Thrift<'a>, String> {
+        let mut fields = Vec::new();
+        let mut last = 0i16;
+        loop {
+            let head = self.byte()?;
+            if head == 0 {
+                return Ok(Thrift::Struct(fields));
+            }
+            let id = match head >> 4 {
+                0 => self.zigzag()? as i16,
+                delta => last + delta as i16,
+            };
+            last = id;
+            fields.push((id, self.thrift_value(head & 0x0f, depth)?));
+        }
+    }
+}
+
+// Parquet 使用不带分帧的原始 Snappy 块
+fn snappy_decompress(input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut cursor = Cursor {
+        bytes: input,
+        pos: 0,
+    };
+    let len = cursor.varint()? as usize;
+    let mut out = Vec::with_capacity(len);
+    while cursor.pos < input.len() {
+        let tag = cursor.byte()?;
+        let (copy_len, offset) = match tag & 3 {
+            0 => {
+                let mut literal = (tag >> 2) as usize;
+                if literal >= 60 {
+                    literal = le_uint(cursor.take(literal - 59)?) as usize;
+                }
+                out.extend_from_slice(cursor.take(literal + 1)?);
+                continue;
+            }
+            1 => (
+                ((tag >> 2) & 7) as usize + 4,
+                ((tag as usize >> 5) << 8) | cursor.byte()? as usize,
+            ),
+            2 => ((tag >> 2) as usize + 1, le_uint(cursor.take(2)?) as usize),
+            _ => ((tag >> 2) as usize + 1, le_uint(cursor.take(4)?) as usize),
+        };
+        if offset == 0 || offset > out.len() {
+            return Err(format!("Snappy 回溯偏移 {} 无效", offset));
+        }
+        // 回溯区间可以与输出重叠，只能逐字节复制
+        let start = out.len() - offset;
+        for i in 0..copy_len {
+            out.push(out[start + i]);
+        }
+    }
+    if out.len() != len {
+        return Err(format!(
+            "Snappy 解压长度 {} 与声明的 {} 不符",
+            out.len(),
+            len
+        ));
+    }
+
+    return Ok(out);
+}
+
+fn le_uint(bytes: &[u8]) -> u64 {
+    return bytes.iter().rev().fold(0, |acc, &b| (acc << 8) | b as u64);
+}
+
+// RLE / 位打包混合编码，解出 count 个值
+fn rle_hybrid(bytes: &[u8], bit_width: u8, count: usize) -> Result<Vec<u32>, String> {
+    if bit_width > 32 {
+        return Err(format!("RLE 位宽 {} 无效", bit_width));
+    }
+    let mut cursor = Cursor {
+        bytes: bytes,
+        pos: 0,
+    };
+    let mut out = Vec::with_capacity(count);
+    while out.len() < count {
+        let header = cursor.varint()? as usize;
+        if header & 1 == 0 {
+            let value = le_uint(cursor.take((bit_width as usize).div_ceil(8))?) as u32;
+            let run = (header >> 1).min(count - out.len());
+            out.extend(std::iter::repeat_n(value, run));
+        } else {
+            let values = (header >> 1) * 8;
+            let packed = cursor.take(values * bit_width as usize / 8)?;
+            for i in 0..values.min(count - out.len()) {
+                let mut v = 0u32;
+                for b in 0..bit_width as usize {
+                    let bit = i * bit_width as usize + b;
+                    v |= (((packed[bit / 8] >> (bit % 8)) & 1) as u32) << b;
+                }
+                out.push(v);
+            }
+        }
+    }
+
+    return Ok(out);
+}
+
+fn bit_width(max: u32) -> u8 {
+    return (32 - max.leading_zeros()) as u8;
+}
+
+#[derive(Debug, Clone)]
+struct Column {
+    path: String,
+    physical: i64,
+    max_def: u32,
+    max_rep: u32,
+    scale: i32,
+}
+
+// 深度优先展开模式树，计算每个叶子列的最大定义/重复级别
+fn leaf_columns(schema: &[Thrift]) -> Result<Vec<Column>, String> {
+    fn walk(
+        schema: &[Thrift],
+        pos: &mut usize,
+        prefix: &str,
+        def: u32,
+        rep: u32,
+        out: &mut Vec<Column>,
+    ) -> Result<(), String> {
+        let Some(element) = schema.get(*pos) else {
+            return Err("Parquet 模式的子节点数量与元素数量不符".to_string());
+        };
+        *pos += 1;
+        let name = element.string(4).unwrap_or_default();
+        let path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        let (def, rep) = match element.int(3) {
+            Some(1) => (def + 1, rep),
+            Some(2) => (def + 1, rep + 1),
+            _ => (def, rep),
+        };
+        match element.int(5) {
+            Some(children) if children > 0 => {
+                for _ in 0..children {
+                    walk(schema, pos, &path, def, rep, out)?;
+                }
+            }
+            _ => out.push(Column {
+                path: path,
+                physical: element.int(1).unwrap_or(-1),
+                max_def: def,
+                max_rep: rep,
+                // converted_type 5 为 DECIMAL
+                scale: if element.int(6) == Some(5) {
+                    element.int(7).unwrap_or(0) as i32
+                } else {
+                    0
+                },
+            }),
+        }
+
+        return Ok(());
+    }
+
+    let Some(root) = schema.first() else {
+        return Err("Parquet 模式为空".to_string());
+    };
+    let mut out = Vec::new();
+    let mut pos = 1;
+    for _ in 0..root.int(5).unwrap_or(0) {
+        walk(schema, &mut pos, "", 0, 0, &mut out)?;
+    }
+
+    return Ok(out);
+}
+
+fn is_numeric(physical: i64) -> bool {
+    return matches!(physical, 0 | 1 | 2 | 4 | 5);
+}
+
+fn plain_values(physical: i64, bytes: &[u8], count: usize) -> Result<Vec<f64>, String> {
+    let size = match physical {
+        0 => {
+            if bytes.len() * 8 < count {
+                return Err("Parquet 布尔值数据不足".to_string());
+            }
+            return Ok((0..count)
+                .map(|i| ((bytes[i / 8] >> (i % 8)) & 1) as f64)
+                .collect());
+        }
+        1 | 4 => 4,
+        _ => 8,
+    };
+    if bytes.len() < count * size {
+        return Err(format!(
+            "Parquet PLAIN 数据只有 {} 字节，需要 {} 字节",
+            bytes.len(),
+            count * size
+        ));
+    }
+
+    return Ok(bytes
+        .chunks_exact(size)
+        .take(count)
+        .map(|c| match physical {
+            1 => i32::from_le_bytes(c.try_into().unwrap()) as f64,
+            2 => i64::from_le_bytes(c.try_into().unwrap()) as f64,
+            4 => f32::from_le_bytes(c.try_into().unwrap()) as f64,
+            _ => f64::from_le_bytes(c.try_into().unwrap()),
+        })
+        .collect());
+}
+
+fn decompress(codec: i64, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    return match codec {
+        0 => Ok(bytes.to_vec()),
+        1 => snappy_decompress(bytes),
+        other => Err(format!(
+            "Parquet 压缩格式 {} 暂不支持（仅支持未压缩与 Snappy）",
+            other
+        )),
+    };
+}
+
+// 读出整个列块，空值为 NaN
+fn read_column_chunk(bytes: &[u8], column: &Column, meta: &Thrift) -> Result<Vec<f64>, String> {
+    let codec = meta.int(4).unwrap_or(0);
+    let num_values = meta.int(5).unwrap_or(0) as usize;
+    let start = match meta.int(11) {
+        Some(dict) if dict > 0 => dict,
+        _ => meta.int(9).unwrap_or(0),
+    };
+    if start < 0 || start as usize >= bytes.len() {
+        return Err(format!("列 {} 的数据页偏移 {} 无效", column.path, start));
+    }
+
+    let mut cursor = Cursor {
+        bytes: bytes,
+        pos: start as usize,
+    };
+    let mut dictionary: Vec<f64> = Vec::new();
+    let mut out = Vec::with_capacity(num_values);
+    let def_width = bit_width(column.max_def);
+    while out.len() < num_values {
+        let header = cursor.thrift_struct(0)?;
+        let page_type = header.int(1).unwrap_or(-1);
+        let page = cursor.take(header.int(3).unwrap_or(0) as usize)?;
+
+        let (values, count, encoding, defs) = match page_type {
+            2 => {
+                let dict = header.field(7).ok_or("字典页缺少页头")?;
+                let count = dict.int(1).unwrap_or(0) as usize;
+                dictionary = plain_values(column.physical, &decompress(codec, page)?, count)?;
+                continue;
+            }
+            0 => {
+                let data = header.field(5).ok_or("数据页缺少页头")?;
+                let count = data.int(1).unwrap_or(0) as usize;
+                let page = decompress(codec, page)?;
+                let mut pos = 0;
+                let mut defs = None;
+                if column.max_def > 0 {
+                    let len = le_uint(page.get(..4).ok_or("定义级别长度被截断")?) as usize;
+                    let levels = page.get(4..4 + len).ok_or("定义级别数据被截断")?;
+                    defs = Some(rle_hybrid(levels, def_width, count)?);
+                    pos = 4 + len;
+                }
+                (page[pos..].to_vec(), count, data.int(2).unwrap_or(0), defs)
+            }
+            3 => {
+                let data = header.field(8).ok_or("v2 数据页缺少页头")?;
+                let count = data.int(1).unwrap_or(0) as usize;
+                let def_len = data.int(5).unwrap_or(0) as usize;
+                let rep_len = data.int(6).unwrap_or(0) as usize;
+                let levels = page
+                    .get(rep_len..rep_len + def_len)
+                    .ok_or("v2 数据页级别数据被截断")?;
+                let defs = if column.max_def > 0 {
+                    Some(rle_hybrid(levels, def_width, count)?)
+                } else {
+                    None
+                };
+                // v2 页的级别数据从不压缩，值部分按 is_compressed 决定
+                let rest = &page[rep_len + def_len..];
+                let values = if data.bool(7).unwrap_or(true) {
+                    decompress(codec, rest)?
+                } else {
+                    rest.to_vec()
+                };
+                (values, count, data.int(4).unwrap_or(0), defs)
+            }
+            // 索引页等与数值无关的页直接跳过
+            _ => continue,
+        };
+
+        let present = match &defs {
+            Some(defs) => defs.iter().filter(|&&d| d == column.max_def).count(),
+            None => count,
+        };
+        let decoded = match encoding {
+            0 => plain_values(column.physical, &values, present)?,
+            2 | 8 => {
+                let Some(&width) = values.first() else {
+                    return Err(format!("列 {} 的字典索引为空", column.path));
+                };
+                rle_hybrid(&values[1..], width, present)?
+                    .iter()
+                    .map(|&i| {
+                        dictionary
+                            .get(i as usize)
+                            .copied()
+                            .ok_or(format!("列 {} 的字典索引 {} 越界", column.path, i))
+                    })
+                    .collect::<Result<Vec<f64>, String>>()?
+            }
+            other => {
+                return Err(format!("列 {} 使用的编码 {} 暂不支持", column.path, other));
+            }
+        };
+
+        match defs {
+            Some(defs) => {
+                let mut values = decoded.into_iter();
+                for d in defs {
+                    out.push(if d == column.max_def {
+                        values.next().unwrap_or(f64::NAN)
+                    } else {
+                        f64::NAN
+                    });
+                }
+            }
+            None => out.extend(decoded),
+        }
+    }
+    out.truncate(num_values);
+
+    return Ok(out);
+}
+
+fn read_metadata(bytes: &[u8]) -> Result<Thrift<'_>, String> {
+    let n = bytes.len();
+    if n < 12 || &bytes[..4] != MAGIC || &bytes[n - 4..] != MAGIC {
+        return Err("不是 Parquet 文件（魔数不匹配）".to_string());
+    }
+    let len = le_uint(&bytes[n - 8..n - 4]) as usize;
+    if len > n - 12 {
+        return Err(format!("Parquet 元数据长度 {} 超出文件大小", len));
+    }
+    let mut cursor = Cursor {
+        bytes: &bytes[n - 8 - len..n - 8],
+        pos: 0,
+    };
+
+    return cursor.thrift_struct(0);
+}
+
+fn decode(bytes: &[u8], columns: &[&str]) -> Result<Tensor, String> {
+    let metadata = read_metadata(bytes)?;
+    let leaves = leaf_columns(metadata.list(2))?;
+
+    // 未指定列时读取全部数值列
+    let selected: Vec<&Column> = if columns.is_empty() {
+        leaves.iter().filter(|c| is_numeric(c.physical)).collect()
+    } else {
+        let mut selected = Vec::with_capacity(columns.len());
+        for &name in columns {
+            let Some(column) = leaves.iter().find(|c| c.path == name) else {
+                return Err(format!("Parquet 文件中没有列 {}", name));
+            };
+            if !is_numeric(column.physical) {
+                return Err(format!("列 {} 不是数值类型", name));
+            }
+            selected.push(column);
+        }
+        selected
+    };
+    if let Some(column) = selected.iter().find(|c| c.max_rep > 0) {
+        return Err(format!("列 {} 是重复（列表）字段，暂不支持", column.path));
+    }
+
+    let rows = metadata.int(3).unwrap_or(0) as usize;
+    let cols = selected.len();
+    let mut data = vec![f32::NAN; rows * cols];
+    let mut row_start = 0;
+    for group in metadata.list(4) {
+        let group_rows = group.int(3).unwrap_or(0) as usize;
+        if row_start + group_rows > rows {
+            return Err("Parquet 行组的行数之和超过文件总行数".to_string());
+        }
+        for (j, column) in selected.iter().enumerate() {
+            let Some(meta) = group.list(1).iter().filter_map(|c| c.field(3)).find(|m| {
+                let path: Vec<String> = m
+                    .list(3)
+                    .iter()
+                    .map(|p| match p {
+                        Thrift::Binary(b) => String::from_utf8_lossy(b).into_owned(),
+                        _ => String::new(),
+                    })
+                    .collect();
+                path.join(".") == column.path
+            }) else {
+                return Err(format!("行组中缺少列 {} 的数据", column.path));
+            };
+            let values = read_column_chunk(bytes, column, meta)?;
+            if values.len() != group_rows {
+                return Err(format!(
+                    "列 {} 在行组中有 {} 个值，应为 {}",
+                    column.path,
+                    values.len(),
+                    group_rows
+                ));
+            }
+            let scale = 10f64.powi(column.scale);
+            for (i, v) in values.into_iter().enumerate() {
+                data[(row_start + i) * cols + j] = (v / scale) as f32;
+            }
+        }
+        row_start += group_rows;
+    }
+
+    return Tensor::new(data, vec![rows, cols]);
+}
+
+// 读出 [行数, 列数] 的特征矩阵，列按 columns 的顺序排列；columns 为空时读取全部数值列
+pub fn read<P: AsRef<Path>>(path: P, columns: &[&str]) -> Result<Tensor, String> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("无法读取 Parquet 文件 {}：{}", path.display(), e))?;
+
+    return decode(&bytes, columns);
+}
+
+pub fn column_names<P: AsRef<Path>>(path: P) -> Result<Vec<String>, String> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("无法读取 Parquet 文件 {}：{}", path.display(), e))?;
+    let metadata = read_metadata(&bytes)?;
+
+    return Ok(leaf_columns(metadata.list(2))?
+        .into_iter()
+        .map(|c| c.path)
+        .collect());
+}
+
+impl Tensor {
+    pub fn from_parquet<P: AsRef<Path>>(path: P, columns: &[&str]) -> Result<Tensor, String> {
+        return read(path, columns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 由 parquet-rs 写出：7 列、5 行、两个行组；分别为 Snappy + 字典 + v1 数据页，
+    // 以及未压缩 + PLAIN + v2 数据页
+    const SNAPPY_DICT_V1: &[u8] = include_bytes!("../testdata/snappy_dict_v1.parquet");
+    const PLAIN_V2: &[u8] = include_bytes!("../testdata/plain_v2.parquet");
+
+    #[test]
+    fn reads_selected_columns_with_nulls_as_nan() {
+        for file in [SNAPPY_DICT_V1, PLAIN_V2] {
+            let t = decode(file, &["score", "id", "price", "big"]).unwrap();
+            assert_eq!(t.shape, [5, 4]);
+            let score: Vec<f32> = (0..5).map(|i| t.data[i * 4]).collect();
+            assert_eq!(score[0], 0.0);
+            assert!(score[1].is_nan() && score[4].is_nan());
+            assert_eq!(&score[2..4], &[3.0, 4.5]);
+            assert_eq!(
+                (0..5).map(|i| t.data[i * 4 + 1]).collect::<Vec<_>>(),
+                [0.0, 1.0, 2.0, 3.0, 4.0]
+            );
+            // DECIMAL(9,2) 按 scale 还原
+            assert_eq!(t.data[4 * 4 + 2], 23.99);
+            assert_eq!(t.data[3], 1e12);
+
+            let all = decode(file, &[]).unwrap();
+            assert_eq!(all.shape, [5, 6]);
+            // flag 列：行 0 为空，其余奇数行为 true
+            let flag: Vec<f32> = (0..5).map(|i| all.data[i * 6 + 4]).collect();
+            assert!(flag[0].is_nan());
+            assert_eq!(&flag[1..], &[1.0, 0.0, 1.0, 0.0]);
+            assert!(all.data[2 * 6 + 2].is_nan());
+
+            assert!(decode(file, &["name"]).is_err());
+            assert!(decode(file, &["missing"]).is_err());
+        }
+        assert!(decode(b"PAR1 not really PAR1", &[]).is_err());
+    }
+
+    #[test]
+    fn snappy_and_rle_decoders_handle_copies_and_bit_packing() {
+        // 字面量 "ab" 后接长度 6、偏移 2 的重叠回溯
+        let compressed = [8, 0x04, b'a', b'b', 0x09, 0x02];
+        assert_eq!(snappy_decompress(&compressed).unwrap(), b"abababab");
+        assert!(snappy_decompress(&[4, 0x09, 0x02]).is_err());
+
+        // RLE 段：3 个 5；位打包段：8 个 3 位值 0..8
+        let mut encoded = vec![0x06, 0x05, 0x03];
+        encoded.extend([0b1000_1000, 0b1100_0110, 0b1111_1010]);
+        let values = rle_hybrid(&encoded, 3, 11).unwrap();
+        assert_eq!(values, [5, 5, 5, 0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,173 @@
+use ::polars::prelude::{Column, DataFrame, DataType, NamedFrom, PlSmallStr, Series};
+
+use crate::Tensor;
+
+// 与 Polars 互转：Series ↔ 一维张量，DataFrame ↔ [行数, 列数] 的二维张量。
+// 数值与布尔列统一转为 f32，空值映射为 NaN
+fn series_values(series: &Series) -> Result<Vec<f32>, String> {
+    let dtype = series.dtype();
+    if !dtype.is_numeric() && !dtype.is_bool() {
+        return Err(format!(
+            "列 {} 的类型 {} 不是数值类型",
+            series.name(),
+            dtype
+        ));
+    }
+    let cast = series
+        .cast(&DataType::Float32)
+        .map_err(|e| format!("列 {} 无法转换为 f32：{}", series.name(), e))?;
+    let values = cast
+        .f32()
+        .map_err(|e| format!("列 {} 无法转换为 f32：{}", series.name(), e))?;
+
+    return Ok(values.iter().map(|v| v.unwrap_or(f32::NAN)).collect());
+}
+
+impl TryFrom<&Series> for Tensor {
+    type Error = String;
+
+    fn try_from(series: &Series) -> Result<Tensor, String> {
+        let data = series_values(series)?;
+        let len = data.len();
+
+        return Tensor::new(data, vec![len]);
+    }
+}
+
+impl TryFrom<Series> for Tensor {
+    type Error = String;
+
+    fn try_from(series: Series) -> Result<Tensor, String> {
+        return Tensor::try_from(&series);
+    }
+}
+
+impl TryFrom<&DataFrame> for Tensor {
+    type Error = String;
+
+    fn try_from(frame: &DataFrame) -> Result<Tensor, String> {
+        let (rows, cols) = (frame.height(), frame.width());
+        let mut data = crate::alloc::allocate(rows * cols);
+        data.resize(rows * cols, 0.0);
+        for (j, column) in frame.get_columns().iter().enumerate() {
+            let values = series_values(column.as_materialized_series())?;
+            for (i, v) in values.into_iter().enumerate() {
+                data[i * cols + j] = v;
+            }
+        }
+
+        return Tensor::new(data, vec![rows, cols]);
+    }
+}
+
+impl TryFrom<DataFrame> for Tensor {
+    type Error = String;
+
+    fn try_from(frame: DataFrame) -> Result<Tensor, String> {
+        return Tensor::try_from(&frame);
+    }
+}
+
+impl TryFrom<&Tensor> for Series {
+    type Error = String;
+
+    fn try_from(tensor: &Tensor) -> Result<Series, String> {
+        return tensor.to_series("");
+    }
+}
+
+impl TryFrom<&Tensor> for DataFrame {
+    type Error = String;
+
+    fn try_from(tensor: &Tensor) -> Result<DataFrame, String> {
+        let cols = tensor.shape.get(1).copied().unwrap_or(0);
+        let names: Vec<String> = (0..cols).map(|j| format!("column_{}", j)).collect();
+        let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+
+        return tensor.to_dataframe(&names);
+    }
+}
+
+impl Tensor {
+    pub fn to_series(&self, name: &str) -> Result<Series, String> {
+        if self.shape.len() != 1 {
+            return Err(format!(
+                "只有一维张量可以转换为 Series，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+
+        return Ok(Series::new(PlSmallStr::from(name), self.data.to_vec()));
+    }
+
+    // 每列一个 Series，列名按 names 的顺序
+    pub fn to_dataframe(&self, names: &[&str]) -> Result<DataFrame, String> {
+        if self.shape.len() != 2 {
+            return Err(format!(
+                "只有二维张量可以转换为 DataFrame，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+        if names.len() != cols {
+            return Err(format!("列名数量 {} 与列数 {} 不匹配", names.len(), cols));
+        }
+
+        let columns: Vec<Column> = names
+            .iter()
+            .enumerate()
+            .map(|(j, &name)| {
+                let values: Vec<f32> = (0..rows).map(|i| self.data[i * cols + j]).collect();
+                Column::new(PlSmallStr::from(name), values)
+            })
+            .collect();
+
+        return DataFrame::new_with_height(rows, columns)
+            .map_err(|e| format!("无法构造 DataFrame：{}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_frames_round_trip_through_row_major_tensors() {
+        let frame = DataFrame::new(vec![
+            Column::new("a".into(), [1i64, 2, 3]),
+            Column::new("b".into(), [Some(0.5f64), None, Some(2.5)]),
+            Column::new("c".into(), [true, false, true]),
+        ])
+        .unwrap();
+        let t = Tensor::try_from(&frame).unwrap();
+        assert_eq!(t.shape, [3, 3]);
+        assert_eq!(&t.data[..3], &[1.0, 0.5, 1.0]);
+        assert!(t.data[4].is_nan());
+        assert_eq!(&t.data[6..], &[3.0, 2.5, 1.0]);
+
+        let back = t.to_dataframe(&["x", "y", "z"]).unwrap();
+        assert_eq!(back.get_column_names(), ["x", "y", "z"]);
+        assert_eq!(Tensor::try_from(&back).unwrap().shape, [3, 3]);
+        assert_eq!(
+            DataFrame::try_from(&t).unwrap().get_column_names(),
+            ["column_0", "column_1", "column_2"]
+        );
+        assert!(t.to_dataframe(&["x"]).is_err());
+        assert!(t.to_series("x").is_err());
+
+        let strings = DataFrame::new(vec![Column::new("s".into(), ["a", "b"])]).unwrap();
+        assert!(Tensor::try_from(&strings).is_err());
+    }
+
+    #[test]
+    fn series_convert_to_one_dimensional_tensors() {
+        let series = Series::new("v".into(), [Some(1i32), None, Some(3)]);
+        let t = Tensor::try_from(&series).unwrap();
+        assert_eq!(t.shape, [3]);
+        assert!(t.data[1].is_nan());
+
+        let out = Series::try_from(&Tensor::zeros(vec![4]).unwrap()).unwrap();
+        assert_eq!(out.len(), 4);
+        assert_eq!(out.dtype(), &DataType::Float32);
+    }
+}
@@ -0,0 +1,144 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDS: Mutex<BTreeMap<&'static str, OpStats>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    static ALLOC_BYTES: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct OpStats {
+    pub calls: usize,
+    pub total_time: Duration,
+    pub elements: usize,
+    pub alloc_bytes: usize,
+}
+
+pub struct Scope {
+    op: &'static str,
+    elements: usize,
+    start: Option<(Instant, usize)>,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    return ENABLED.load(Ordering::Relaxed);
+}
+
+pub fn reset() {
+    RECORDS.lock().unwrap().clear();
+}
+
+pub fn scope(op: &'static str, elements: usize) -> Scope {
+    let start = if is_enabled() {
+        Some((Instant::now(), ALLOC_BYTES.with(|b| b.get())))
+    } else {
+        None
+    };
+
+    return Scope {
+        op: op,
+        elements: elements,
+        start: start,
+    };
+}
+
+pub(crate) fn record_alloc(bytes: usize) {
+    if is_enabled() {
+        ALLOC_BYTES.with(|b| b.set(b.get() + bytes));
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if let Some((start, alloc_start)) = self.start {
+            let elapsed = start.elapsed();
+            let allocated = ALLOC_BYTES.with(|b| b.get()) - alloc_start;
+
+            let mut records = RECORDS.lock().unwrap();
+            let stats = records.entry(self.op).or_default();
+            stats.calls += 1;
+            stats.total_time += elapsed;
+            stats.elements += self.elements;
+            stats.alloc_bytes += allocated;
+        }
+    }
+}
+
+pub fn snapshot() -> Vec<(&'static str, OpStats)> {
+    let mut stats: Vec<(&'static str, OpStats)> = RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(op, s)| (*op, *s))
+        .collect();
+    stats.sort_by_key(|s| std::cmp::Reverse(s.1.total_time));
+
+    return stats;
+}
+
+pub fn summary() -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<24} {:>8} {:>12} {:>12} {:>14} {:>12}",
+        "op", "calls", "total(ms)", "avg(us)", "elements", "alloc(KiB)"
+    );
+    for (op, s) in snapshot() {
+        let total_ms = s.total_time.as_secs_f64() * 1e3;
+        let avg_us = s.total_time.as_secs_f64() * 1e6 / s.calls.max(1) as f64;
+        let _ = writeln!(
+            out,
+            "{:<24} {:>8} {:>12.3} {:>12.2} {:>14} {:>12.1}",
+            op,
+            s.calls,
+            total_ms,
+            avg_us,
+            s.elements,
+            s.alloc_bytes as f64 / 1024.0
+        );
+    }
+
+    return out;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_record_calls_and_elements_only_when_enabled() {
+        drop(scope("profile_test_disabled", 5));
+        enable();
+        drop(scope("profile_test_op", 7));
+        drop(scope("profile_test_op", 3));
+        disable();
+
+        let stats = snapshot();
+        let (_, op) = stats
+            .iter()
+            .find(|(name, _)| *name == "profile_test_op")
+            .unwrap();
+        assert_eq!(op.calls, 2);
+        assert_eq!(op.elements, 10);
+        assert!(
+            stats
+                .iter()
+                .all(|(name, _)| *name != "profile_test_disabled")
+        );
+        assert!(summary().contains("profile_test_op"));
+    }
+}
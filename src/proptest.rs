@@ -0,0 +1,128 @@
+use ::proptest::collection::vec;
+use ::proptest::prelude::*;
+use ::proptest::sample::select;
+
+use crate::{Shape, Tensor};
+
+// 供下游做属性测试的生成策略。默认的值分布刻意偏向边界情况：
+// NaN、±inf、±0、次正规数与极大极小量级都会以可观的概率出现
+
+// 每一维取 0..=max_dim，因此会生成含零长度维的空张量
+pub fn shape(max_rank: usize, max_dim: usize) -> impl Strategy<Value = Shape> {
+    return vec(0..=max_dim, 0..=max_rank).prop_map(Shape::from);
+}
+
+pub fn nonempty_shape(max_rank: usize, max_dim: usize) -> impl Strategy<Value = Shape> {
+    return vec(1..=max_dim.max(1), 0..=max_rank).prop_map(Shape::from);
+}
+
+pub fn extreme_f32() -> impl Strategy<Value = f32> + Clone {
+    return select(vec![
+        0.0,
+        -0.0,
+        f32::MAX,
+        f32::MIN,
+        f32::MIN_POSITIVE,
+        -f32::MIN_POSITIVE,
+        1e-40,
+        f32::EPSILON,
+        1e30,
+        -1e30,
+    ]);
+}
+
+pub fn finite_f32() -> impl Strategy<Value = f32> + Clone {
+    return prop_oneof![
+        6 => -1e3f32..1e3,
+        2 => extreme_f32(),
+        1 => ::proptest::num::f32::NORMAL | ::proptest::num::f32::SUBNORMAL | ::proptest::num::f32::ZERO,
+    ];
+}
+
+pub fn any_f32() -> impl Strategy<Value = f32> + Clone {
+    return prop_oneof![
+        8 => finite_f32(),
+        1 => Just(f32::NAN),
+        1 => select(vec![f32::INFINITY, f32::NEG_INFINITY]),
+    ];
+}
+
+pub fn tensor_with<S>(
+    shape: impl Strategy<Value = Shape>,
+    values: S,
+) -> impl Strategy<Value = Tensor>
+where
+    S: Strategy<Value = f32> + Clone,
+{
+    return shape.prop_flat_map(move |shape| {
+        vec(values.clone(), shape.numel())
+            .prop_map(move |data| Tensor::new(data, shape.clone()).unwrap())
+    });
+}
+
+pub fn tensor(max_rank: usize, max_dim: usize) -> impl Strategy<Value = Tensor> {
+    return tensor_with(shape(max_rank, max_dim), any_f32());
+}
+
+pub fn finite_tensor(max_rank: usize, max_dim: usize) -> impl Strategy<Value = Tensor> {
+    return tensor_with(shape(max_rank, max_dim), finite_f32());
+}
+
+// 同形状的一对张量，适合检验逐元素二元运算
+pub fn tensor_pair(max_rank: usize, max_dim: usize) -> impl Strategy<Value = (Tensor, Tensor)> {
+    return shape(max_rank, max_dim).prop_flat_map(|shape| {
+        let fixed = Just(shape);
+        (
+            tensor_with(fixed.clone(), any_f32()),
+            tensor_with(fixed, any_f32()),
+        )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Endianness;
+
+    fn same_bits(a: &Tensor, b: &Tensor) -> bool {
+        return a.shape == b.shape
+            && a.data
+                .iter()
+                .zip(b.data.iter())
+                .all(|(x, y)| x.to_bits() == y.to_bits());
+    }
+
+    proptest! {
+        #[test]
+        fn generated_tensors_are_consistent(t in tensor(4, 4)) {
+            prop_assert_eq!(t.data.len(), t.shape.numel());
+            prop_assert!(t.shape.len() <= 4);
+        }
+
+        #[test]
+        fn reshape_and_bytes_round_trip(t in tensor(4, 4)) {
+            let flat = t.reshaped(vec![t.data.len()]).unwrap();
+            prop_assert!(same_bits(&flat.reshaped(t.shape.clone()).unwrap(), &t));
+
+            let bytes = t.to_bytes(Endianness::Big);
+            let back = Tensor::from_bytes(&bytes, t.shape.clone(), crate::DType::F32, Endianness::Big).unwrap();
+            prop_assert!(same_bits(&back, &t));
+        }
+
+        #[test]
+        fn reversing_axes_twice_is_identity(t in tensor_with(nonempty_shape(4, 4), any_f32())) {
+            let order: Vec<usize> = (0..t.shape.len()).rev().collect();
+            let twice = t.permute(&order).unwrap().permute(&order).unwrap();
+            prop_assert!(same_bits(&twice, &t));
+        }
+
+        #[test]
+        fn addition_commutes((a, b) in tensor_pair(3, 4)) {
+            let ab = a.add(&b).unwrap();
+            let ba = b.add(&a).unwrap();
+            for (x, y) in ab.data.iter().zip(ba.data.iter()) {
+                prop_assert!(x == y || (x.is_nan() && y.is_nan()));
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// serving 只用到编码与整数字段，其余读取接口仅 onnx 使用
+#![cfg_attr(not(feature = "onnx"), allow(dead_code))]
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Value<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+pub(crate) struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        return Reader { buf: buf, pos: 0 };
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let Some(&byte) = self.buf.get(self.pos) else {
+                return Err(format!("protobuf varint 在偏移 {} 处被截断", self.pos));
+            };
+            self.pos += 1;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        return Err(format!(
+            "protobuf varint 在偏移 {} 处超过 10 字节",
+            self.pos
+        ));
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.buf.len() - self.pos < len {
+            return Err(format!(
+                "protobuf 字段在偏移 {} 处需要 {} 字节，剩余 {} 字节",
+                self.pos,
+                len,
+                self.buf.len() - self.pos
+            ));
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+
+        return Ok(bytes);
+    }
+
+    pub(crate) fn next_field(&mut self) -> Result<Option<(u32, Value<'a>)>, String> {
+        if self.pos >= self.buf.len() {
+            return Ok(None);
+        }
+
+        let key = self.varint()?;
+        let field = (key >> 3) as u32;
+        let value = match key & 7 {
+            0 => Value::Varint(self.varint()?),
+            1 => Value::Fixed64(u64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            2 => {
+                let len = self.varint()? as usize;
+                Value::Bytes(self.take(len)?)
+            }
+            5 => Value::Fixed32(u32::from_le_bytes(self.take(4)?.try_into().unwrap())),
+            wire => {
+                return Err(format!(
+                    "不支持的 protobuf 线类型 {}（字段 {}）",
+                    wire, field
+                ));
+            }
+        };
+
+        return Ok(Some((field, value)));
+    }
+}
+
+impl<'a> Value<'a> {
+    pub(crate) fn as_i64(self) -> Result<i64, String> {
+        return match self {
+            Value::Varint(v) | Value::Fixed64(v) => Ok(v as i64),
+            Value::Fixed32(v) => Ok(v as i32 as i64),
+            Value::Bytes(_) => Err("protobuf 字段应为整数，实际为字节串".to_string()),
+        };
+    }
+
+    pub(crate) fn as_f32(self) -> Result<f32, String> {
+        return match self {
+            Value::Fixed32(v) => Ok(f32::from_bits(v)),
+            _ => Err("protobuf 字段应为 float".to_string()),
+        };
+    }
+
+    pub(crate) fn as_f64(self) -> Result<f64, String> {
+        return match self {
+            Value::Fixed64(v) => Ok(f64::from_bits(v)),
+            _ => Err("protobuf 字段应为 double".to_string()),
+        };
+    }
+
+    pub(crate) fn as_bytes(self) -> Result<&'a [u8], String> {
+        return match self {
+            Value::Bytes(b) => Ok(b),
+            _ => Err("protobuf 字段应为字节串".to_string()),
+        };
+    }
+
+    pub(crate) fn as_str(self) -> Result<String, String> {
+        return String::from_utf8(self.as_bytes()?.to_vec())
+            .map_err(|e| format!("protobuf 字符串不是有效的 UTF-8：{}", e));
+    }
+
+    // repeated 数值字段既可能逐个出现，也可能以 packed 字节串出现
+    pub(crate) fn push_ints(self, out: &mut Vec<i64>) -> Result<(), String> {
+        let Value::Bytes(bytes) = self else {
+            out.push(self.as_i64()?);
+            return Ok(());
+        };
+        let mut reader = Reader::new(bytes);
+        while reader.pos < bytes.len() {
+            out.push(reader.varint()? as i64);
+        }
+
+        return Ok(());
+    }
+
+    pub(crate) fn push_f32s(self, out: &mut Vec<f32>) -> Result<(), String> {
+        let Value::Bytes(bytes) = self else {
+            out.push(self.as_f32()?);
+            return Ok(());
+        };
+        if !bytes.len().is_multiple_of(4) {
+            return Err(format!(
+                "packed float 字段长度 {} 不是 4 的倍数",
+                bytes.len()
+            ));
+        }
+        out.extend(
+            bytes
+                .chunks_exact(4)
+                .map(|c| f32::from_le_bytes(c.try_into().unwrap())),
+        );
+
+        return Ok(());
+    }
+
+    pub(crate) fn push_f64s(self, out: &mut Vec<f64>) -> Result<(), String> {
+        let Value::Bytes(bytes) = self else {
+            out.push(self.as_f64()?);
+            return Ok(());
+        };
+        if !bytes.len().is_multiple_of(8) {
+            return Err(format!(
+                "packed double 字段长度 {} 不是 8 的倍数",
+                bytes.len()
+            ));
+        }
+        out.extend(
+            bytes
+                .chunks_exact(8)
+                .map(|c| f64::from_le_bytes(c.try_into().unwrap())),
+        );
+
+        return Ok(());
+    }
+}
+
+pub(crate) struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn new() -> Self {
+        return Writer { buf: Vec::new() };
+    }
+
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    pub(crate) fn varint(&mut self, field: u32, value: u64) {
+        self.raw_varint((field as u64) << 3);
+        self.raw_varint(value);
+    }
+
+    pub(crate) fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.raw_varint(((field as u64) << 3) | 2);
+        self.raw_varint(bytes.len() as u64);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn packed_varints(&mut self, field: u32, values: &[i64]) {
+        let mut packed = Writer::new();
+        for &v in values {
+            packed.raw_varint(v as u64);
+        }
+        self.bytes(field, &packed.buf);
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        return self.buf;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader_decodes_each_wire_type_and_packed_fields() {
+        // 字段 1 varint 300，字段 2 packed [1, 150]，字段 3 fixed32 1.5，字段 4 截断的字节串
+        let mut bytes = vec![0x08, 0xac, 0x02, 0x12, 0x03, 0x01, 0x96, 0x01, 0x1d];
+        bytes.extend(1.5f32.to_le_bytes());
+        let mut reader = Reader::new(&bytes);
+
+        let (field, value) = reader.next_field().unwrap().unwrap();
+        assert_eq!((field, value.as_i64().unwrap()), (1, 300));
+        let (field, value) = reader.next_field().unwrap().unwrap();
+        let mut ints = Vec::new();
+        value.push_ints(&mut ints).unwrap();
+        assert_eq!((field, ints), (2, vec![1, 150]));
+        let (field, value) = reader.next_field().unwrap().unwrap();
+        assert_eq!((field, value.as_f32().unwrap()), (3, 1.5));
+        assert!(reader.next_field().unwrap().is_none());
+
+        assert!(Reader::new(&[0x22, 0x05, 0x01]).next_field().is_err());
+        assert!(Reader::new(&[0x0b]).next_field().is_err());
+    }
+
+    #[test]
+    fn writer_output_round_trips_through_reader() {
+        let mut w = Writer::new();
+        w.varint(1, -2i64 as u64);
+        w.packed_varints(2, &[0, 300, -1]);
+        w.bytes(3, b"name");
+        let bytes = w.finish();
+
+        let mut reader = Reader::new(&bytes);
+        let mut fields = Vec::new();
+        while let Some(field) = reader.next_field().unwrap() {
+            fields.push(field);
+        }
+        assert_eq!(fields[0].1.as_i64().unwrap(), -2);
+        let mut ints = Vec::new();
+        fields[1].1.push_ints(&mut ints).unwrap();
+        assert_eq!(ints, vec![0, 300, -1]);
+        assert_eq!(fields[2].1.as_str().unwrap(), "name");
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{Tensor, parallel, profile};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedTensor {
+    data: Vec<i8>,
+    shape: Vec<usize>,
+    scale: Vec<f32>,
+    zero_point: Vec<i8>,
+    axis: Option<usize>,
+}
+
+fn affine_params(lane: impl Iterator<Item = f32>) -> Result<(f32, i8), String> {
+    let (mut min, mut max) = (0.0f32, 0.0f32);
+    for x in lane {
+        if !x.is_finite() {
+            return Err(format!("无法量化非有限值 {}", x));
+        }
+        min = min.min(x);
+        max = max.max(x);
+    }
+    if max == min {
+        return Ok((1.0, 0));
+    }
+
+    // 区间总是包含 0，保证 0 能被精确表示
+    let scale = (max - min) / 255.0;
+    let zero_point = (-128.0 - min / scale).round().clamp(-128.0, 127.0);
+
+    return Ok((scale, zero_point as i8));
+}
+
+impl Tensor {
+    pub fn quantize_int8(&self, axis: Option<usize>) -> Result<QuantizedTensor, String> {
+        let _scope = profile::scope("quantize_int8", self.data.len());
+        let (outer, len, inner) = match axis {
+            None => (1, 1, self.data.len()),
+            Some(axis) => {
+                self.check_axis(axis)?;
+                (
+                    self.shape[..axis].iter().product(),
+                    self.shape[axis],
+                    self.shape[axis + 1..].iter().product(),
+                )
+            }
+        };
+
+        let mut scale = Vec::with_capacity(len);
+        let mut zero_point = Vec::with_capacity(len);
+        for c in 0..len {
+            let lane = (0..outer).flat_map(|o| {
+                let base = (o * len + c) * inner;
+                self.data[base..base + inner].iter().cloned()
+            });
+            let (s, z) = affine_params(lane)?;
+            scale.push(s);
+            zero_point.push(z);
+        }
+
+        let data = self
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| {
+                let c = (i / inner) % len;
+                let q = (x / scale[c]).round() + zero_point[c] as f32;
+                q.clamp(-128.0, 127.0) as i8
+            })
+            .collect();
+
+        return Ok(QuantizedTensor {
+            data: data,
+            shape: self.shape.to_vec(),
+            scale: scale,
+            zero_point: zero_point,
+            axis: axis,
+        });
+    }
+}
+
+impl QuantizedTensor {
+    pub fn data(&self) -> &[i8] {
+        return &self.data;
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        return &self.shape;
+    }
+
+    pub fn scale(&self) -> &[f32] {
+        return &self.scale;
+    }
+
+    pub fn zero_point(&self) -> &[i8] {
+        return &self.zero_point;
+    }
+
+    pub fn axis(&self) -> Option<usize> {
+        return self.axis;
+    }
+
+    pub fn nbytes(&self) -> usize {
+        return self.data.len() + self.scale.len() * 5;
+    }
+
+    fn channel_of(&self, index: usize) -> usize {
+        let Some(axis) = self.axis else {
+            return 0;
+        };
+        let inner: usize = self.shape[axis + 1..].iter().product();
+
+        return (index / inner) % self.shape[axis];
+    }
+
+    pub fn dequantize(&self) -> Result<Tensor, String> {
+        let _scope = profile::scope("dequantize", self.data.len());
+        let mut data = crate::alloc::allocate(self.data.len());
+        for (i, &q) in self.data.iter().enumerate() {
+            let c = self.channel_of(i);
+            data.push((q as i32 - self.zero_point[c] as i32) as f32 * self.scale[c]);
+        }
+
+        return Tensor::new(data, self.shape.clone());
+    }
+
+    // 左侧按行（axis 0）、右侧按列（axis 1）量化时仍可在整数域累加
+    pub fn matmul(&self, other: &QuantizedTensor) -> Result<Tensor, String> {
+        if self.shape.len() != 2 || other.shape.len() != 2 || self.shape[1] != other.shape[0] {
+            return Err(format!(
+                "量化矩阵乘法形状不匹配：{:?} 与 {:?}",
+                self.shape, other.shape
+            ));
+        }
+        if matches!(self.axis, Some(a) if a != 0) || matches!(other.axis, Some(a) if a != 1) {
+            return Err(format!(
+                "量化矩阵乘法要求左侧按行、右侧按列量化，实际轴为 {:?} 与 {:?}",
+                self.axis, other.axis
+            ));
+        }
+        let (m, k, n) = (self.shape[0], self.shape[1], other.shape[1]);
+        let _scope = profile::scope("quantized_matmul", m * n);
+
+        let rhs: Vec<i32> = other
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, &q)| q as i32 - other.zero_point[other.channel_of(i)] as i32)
+            .collect();
+        let rows = parallel::map_range(m, m * n * k, |i| {
+            let c = if self.axis.is_some() { i } else { 0 };
+            let (za, sa) = (self.zero_point[c] as i32, self.scale[c]);
+            let mut acc = vec![0i32; n];
+            for (p, &q) in self.data[i * k..(i + 1) * k].iter().enumerate() {
+                let a = q as i32 - za;
+                if a == 0 {
+                    continue;
+                }
+                for (o, &b) in acc.iter_mut().zip(&rhs[p * n..(p + 1) * n]) {
+                    *o += a * b;
+                }
+            }
+            acc.iter()
+                .enumerate()
+                .map(|(j, &v)| {
+                    let sb = if other.axis.is_some() {
+                        other.scale[j]
+                    } else {
+                        other.scale[0]
+                    };
+                    v as f32 * sa * sb
+                })
+                .collect::<Vec<f32>>()
+        });
+
+        let mut data = crate::alloc::allocate(m * n);
+        for row in rows {
+            data.extend(row);
+        }
+
+        return Tensor::new(data, vec![m, n]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn max_error(a: &Tensor, b: &Tensor) -> f32 {
+        return a
+            .data
+            .iter()
+            .zip(&b.data)
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f32::max);
+    }
+
+    #[test]
+    fn round_trip_error_is_bounded_by_half_a_step() {
+        let x = Tensor::new(vec![-1.0, 0.0, 0.25, 3.0, 100.0, -50.0], vec![2, 3]).unwrap();
+        let q = x.quantize_int8(None).unwrap();
+        assert_eq!(q.scale().len(), 1);
+        let back = q.dequantize().unwrap();
+        assert!(max_error(&x, &back) <= q.scale()[0] / 2.0 + 1e-5);
+        assert_eq!(back.data[1], 0.0);
+
+        // 按行量化后小幅值的行不再被大幅值的行拖累
+        let rows = x.quantize_int8(Some(0)).unwrap();
+        assert_eq!(rows.scale().len(), 2);
+        let back = rows.dequantize().unwrap();
+        assert!((back.data[2] - 0.25).abs() <= rows.scale()[0] / 2.0 + 1e-6);
+        assert!(rows.scale()[0] < q.scale()[0]);
+
+        assert!(x.quantize_int8(Some(2)).is_err());
+        let nan = Tensor::new(vec![f32::NAN], vec![1]).unwrap();
+        assert!(nan.quantize_int8(None).is_err());
+        let constant = Tensor::full(vec![3], 0.0)
+            .unwrap()
+            .quantize_int8(None)
+            .unwrap();
+        assert_eq!(constant.dequantize().unwrap().data.to_vec(), vec![0.0; 3]);
+    }
+
+    #[test]
+    fn quantized_matmul_tracks_float_matmul() {
+        let a = Tensor::rand_uniform(vec![8, 16], -1.0, 1.0).unwrap();
+        let b = Tensor::rand_uniform(vec![16, 4], -2.0, 0.5).unwrap();
+        let expected = a.matmul(&b).unwrap();
+
+        let qa = a.quantize_int8(Some(0)).unwrap();
+        let qb = b.quantize_int8(Some(1)).unwrap();
+        let approx = qa.matmul(&qb).unwrap();
+        let exact = qa
+            .dequantize()
+            .unwrap()
+            .matmul(&qb.dequantize().unwrap())
+            .unwrap();
+        assert!(max_error(&approx, &exact) < 1e-4);
+        assert!(max_error(&approx, &expected) < 0.1);
+
+        let per_tensor = a
+            .quantize_int8(None)
+            .unwrap()
+            .matmul(&b.quantize_int8(None).unwrap());
+        assert!(max_error(&per_tensor.unwrap(), &expected) < 0.1);
+        assert!(qb.matmul(&qa).is_err());
+        assert!(qa.matmul(&b.quantize_int8(Some(0)).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use std::cell::RefCell;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config;
+
+static GLOBAL_RNG: Mutex<Option<Rng>> = Mutex::new(None);
+
+// config::set_deterministic 使用的固定种子
+pub const DETERMINISTIC_SEED: u64 = 0x5EED;
+
+thread_local! {
+    static SCOPED_RNG: RefCell<Option<Rng>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        let mut sm = seed;
+        let mut state = [0; 4];
+        for s in state.iter_mut() {
+            sm = sm.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = sm;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            *s = z ^ (z >> 31);
+        }
+
+        return Rng { state: state };
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        return result;
+    }
+
+    pub fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    }
+
+    pub fn next_f32(&mut self) -> f32 {
+        return (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        return ((self.next_u64() as u128 * n as u128) >> 64) as usize;
+    }
+
+    pub fn uniform(&mut self, low: f32, high: f32) -> f32 {
+        return low + (high - low) * self.next_f32();
+    }
+
+    pub fn normal(&mut self, mean: f32, std: f32) -> f32 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+
+        return mean + std * z as f32;
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+pub fn manual_seed(seed: u64) {
+    let mut rng = GLOBAL_RNG.lock().unwrap();
+    *rng = Some(Rng::new(seed));
+}
+
+// 未调用 manual_seed 时全局发生器的种子：确定性模式下固定，否则取当前时间
+fn initial_seed(deterministic: bool) -> u64 {
+    if deterministic {
+        return DETERMINISTIC_SEED;
+    }
+
+    return SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+}
+
+// 当前线程发起的随机运算改用以 seed 初始化的独立发生器，f 返回（或 panic）后恢复；
+// 全局发生器不受影响，其他线程的抽样也不会打乱这里的序列
+pub fn with_seed<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Rng>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_RNG.with(|s| *s.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(SCOPED_RNG.with(|s| s.replace(Some(Rng::new(seed)))));
+    return f();
+}
+
+pub fn with_rng<T, F>(f: F) -> T
+where
+    F: FnOnce(&mut Rng) -> T,
+{
+    if let Some(mut rng) = SCOPED_RNG.with(|s| s.borrow_mut().take()) {
+        let out = f(&mut rng);
+        SCOPED_RNG.with(|s| *s.borrow_mut() = Some(rng));
+        return out;
+    }
+    let mut guard = GLOBAL_RNG.lock().unwrap();
+    let rng = guard.get_or_insert_with(|| Rng::new(initial_seed(config::is_deterministic())));
+
+    return f(rng);
+}
+
+// 自带发生器的组件（Batcher、Pipeline）在创建时从全局发生器取种子，
+// 因此确定性模式与 manual_seed 同样决定它们的随机序列；kmeans 等函数直接接收种子
+pub(crate) fn derive_seed() -> u64 {
+    return with_rng(|rng| rng.next_u64());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_mode_fixes_the_default_seed() {
+        assert_eq!(initial_seed(true), DETERMINISTIC_SEED);
+        assert_ne!(initial_seed(false), DETERMINISTIC_SEED);
+    }
+
+    #[test]
+    fn same_seed_gives_same_stream() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn samples_stay_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..10000 {
+            assert!((0.0..1.0).contains(&rng.next_f32()));
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+            assert!(rng.below(3) < 3);
+            assert!((-2.0..5.0).contains(&rng.uniform(-2.0, 5.0)));
+            assert!(rng.normal(0.0, 1.0).is_finite());
+        }
+        assert_eq!(rng.below(1), 0);
+    }
+
+    #[test]
+    fn below_is_roughly_uniform() {
+        let mut rng = Rng::new(3);
+        let mut counts = [0usize; 5];
+        for _ in 0..50000 {
+            counts[rng.below(5)] += 1;
+        }
+        assert!(
+            counts.iter().all(|&c| (9000..11000).contains(&c)),
+            "{:?}",
+            counts
+        );
+    }
+
+    #[test]
+    fn normal_has_requested_moments() {
+        let mut rng = Rng::new(11);
+        let samples: Vec<f32> = (0..20000).map(|_| rng.normal(3.0, 2.0)).collect();
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let var =
+            samples.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / samples.len() as f32;
+        assert!((mean - 3.0).abs() < 0.05, "均值 {}", mean);
+        assert!((var.sqrt() - 2.0).abs() < 0.05, "标准差 {}", var.sqrt());
+    }
+
+    #[test]
+    fn with_seed_scopes_a_reproducible_stream() {
+        let draw = || with_rng(|rng| (rng.next_u64(), rng.next_u64()));
+        let first = with_seed(9, draw);
+        assert_eq!(with_seed(9, draw), first);
+        let mut rng = Rng::new(9);
+        assert_eq!(first, (rng.next_u64(), rng.next_u64()));
+        // 嵌套时内层结束后继续外层的序列
+        let (outer, inner) = with_seed(9, || {
+            let a = with_rng(|rng| rng.next_u64());
+            let inner = with_seed(1, || with_rng(|rng| rng.next_u64()));
+            (vec![a, with_rng(|rng| rng.next_u64())], inner)
+        });
+        assert_eq!(outer, vec![first.0, first.1]);
+        assert_eq!(inner, Rng::new(1).next_u64());
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        let mut rng = Rng::new(5);
+        let mut items: Vec<usize> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+        rng.shuffle(&mut Vec::<usize>::new());
+    }
+}
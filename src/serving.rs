@@ -0,0 +1,115 @@
+use crate::proto::{Reader, Writer};
+use crate::{DType, Endianness, Tensor};
+
+// 推理服务交换张量用的 protobuf 消息：
+//
+// message Tensor {
+//   repeated uint64 shape = 1;
+//   int32 dtype = 2;  // 取值与 ONNX TensorProto.DataType 一致
+//   bytes data = 3;   // 小端序、行主序的原始元素
+// }
+//
+// 零维张量的 shape 为空，data 含一个元素。
+
+fn dtype_code(dtype: DType) -> u64 {
+    return match dtype {
+        DType::F32 => 1,
+        DType::U8 => 2,
+        DType::I8 => 3,
+        DType::U16 => 4,
+        DType::I16 => 5,
+        DType::I32 => 6,
+        DType::I64 => 7,
+        DType::Bool => 9,
+        DType::F16 => 10,
+        DType::F64 => 11,
+        DType::U32 => 12,
+        DType::U64 => 13,
+        DType::BF16 => 16,
+    };
+}
+
+fn dtype_from_code(code: i64) -> Result<DType, String> {
+    return Ok(match code {
+        1 => DType::F32,
+        2 => DType::U8,
+        3 => DType::I8,
+        4 => DType::U16,
+        5 => DType::I16,
+        6 => DType::I32,
+        7 => DType::I64,
+        9 => DType::Bool,
+        10 => DType::F16,
+        11 => DType::F64,
+        12 => DType::U32,
+        13 => DType::U64,
+        16 => DType::BF16,
+        _ => return Err(format!("张量消息的数据类型 {} 暂不支持", code)),
+    });
+}
+
+pub fn encode(tensor: &Tensor, dtype: DType) -> Result<Vec<u8>, String> {
+    let data = tensor.to_bytes_as(dtype, Endianness::Little)?;
+
+    let mut w = Writer::new();
+    for &d in &tensor.shape {
+        w.varint(1, d as u64);
+    }
+    w.varint(2, dtype_code(dtype));
+    w.bytes(3, &data);
+
+    return Ok(w.finish());
+}
+
+// 返回消息声明的类型，便于按原类型回传结果
+pub fn decode(bytes: &[u8]) -> Result<(DType, Tensor), String> {
+    let mut dims = Vec::new();
+    let mut code = 1;
+    let mut data: &[u8] = &[];
+
+    let mut reader = Reader::new(bytes);
+    while let Some((field, value)) = reader.next_field()? {
+        match field {
+            1 => value.push_ints(&mut dims)?,
+            2 => code = value.as_i64()?,
+            3 => data = value.as_bytes()?,
+            _ => {}
+        }
+    }
+
+    let dtype = dtype_from_code(code)?;
+    let shape: Vec<usize> = dims.iter().map(|&d| d as usize).collect();
+    let tensor = Tensor::from_bytes(data, shape, dtype, Endianness::Little)
+        .map_err(|e| format!("张量消息数据无效：{}", e))?;
+
+    return Ok((dtype, tensor));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_round_trip_shape_dtype_and_data() {
+        let t = Tensor::new(vec![1.0, -2.0, 3.0, 0.0, 5.0, 6.0], vec![2, 3]).unwrap();
+        for dtype in [DType::F32, DType::F16, DType::I32, DType::I8] {
+            let (back_dtype, back) = decode(&encode(&t, dtype).unwrap()).unwrap();
+            assert_eq!(back_dtype, dtype);
+            assert_eq!(back, t);
+        }
+        assert!(encode(&t, DType::U8).is_err());
+
+        let scalar = Tensor::new(vec![4.5], vec![]).unwrap();
+        let (_, back) = decode(&encode(&scalar, DType::F64).unwrap()).unwrap();
+        assert_eq!(back, scalar);
+
+        // 字段 2 为未知类型码，字段 3 长度与形状不符
+        let mut w = Writer::new();
+        w.varint(2, 99);
+        assert!(decode(&w.finish()).is_err());
+        let mut w = Writer::new();
+        w.varint(1, 3);
+        w.bytes(3, &[0; 8]);
+        assert!(decode(&w.finish()).is_err());
+    }
+}
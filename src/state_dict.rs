@@ -0,0 +1,277 @@
+use crate::Tensor;
+
+// 按插入顺序保存的 名称 → 张量 映射，模型权重的管理与序列化都以它为单位
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StateDict {
+    entries: Vec<(String, Tensor)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StateDiff {
+    // 只在左侧 / 只在右侧出现的名称
+    pub missing: Vec<String>,
+    pub unexpected: Vec<String>,
+    // (名称, 左侧形状, 右侧形状)
+    pub shape_mismatches: Vec<(String, Vec<usize>, Vec<usize>)>,
+    // 形状相同但数值不同
+    pub changed: Vec<String>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        return self.missing.is_empty()
+            && self.unexpected.is_empty()
+            && self.shape_mismatches.is_empty()
+            && self.changed.is_empty();
+    }
+}
+
+impl StateDict {
+    pub fn new() -> Self {
+        return StateDict {
+            entries: Vec::new(),
+        };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        return self.entries.iter().position(|(n, _)| n == name);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        return self.position(name).is_some();
+    }
+
+    // 同名时原地替换并返回旧张量，保持原有顺序
+    pub fn insert(&mut self, name: impl Into<String>, tensor: Tensor) -> Option<Tensor> {
+        let name = name.into();
+        if let Some(i) = self.position(&name) {
+            return Some(std::mem::replace(&mut self.entries[i].1, tensor));
+        }
+        self.entries.push((name, tensor));
+
+        return None;
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tensor> {
+        return self.position(name).map(|i| &self.entries[i].1);
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Tensor> {
+        return self.position(name).map(|i| &mut self.entries[i].1);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Tensor> {
+        return self.position(name).map(|i| self.entries.remove(i).1);
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        return self.entries.iter().map(|(n, _)| n.as_str());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Tensor)> {
+        return self.entries.iter().map(|(n, t)| (n.as_str(), t));
+    }
+
+    // 与 checkpoint::save 等接口直接对接
+    pub fn as_slice(&self) -> &[(String, Tensor)] {
+        return &self.entries;
+    }
+
+    pub fn numel(&self) -> usize {
+        return self.entries.iter().map(|(_, t)| t.data.len()).sum();
+    }
+
+    // 保留完整名称
+    pub fn filter_prefix(&self, prefix: &str) -> StateDict {
+        return self
+            .entries
+            .iter()
+            .filter(|(n, _)| n.starts_with(prefix))
+            .cloned()
+            .collect();
+    }
+
+    // 取出子模块的权重并去掉前缀，如 "encoder." 下的 "encoder.weight" → "weight"
+    pub fn strip_prefix(&self, prefix: &str) -> StateDict {
+        return self
+            .entries
+            .iter()
+            .filter_map(|(n, t)| Some((n.strip_prefix(prefix)?.to_string(), t.clone())))
+            .collect();
+    }
+
+    pub fn rename(&mut self, from: &str, to: &str) -> Result<(), String> {
+        let Some(i) = self.position(from) else {
+            return Err(format!("StateDict 中没有名为 {} 的张量", from));
+        };
+        if from != to && self.contains(to) {
+            return Err(format!("无法把 {} 重命名为 {}：目标名称已存在", from, to));
+        }
+        self.entries[i].0 = to.to_string();
+
+        return Ok(());
+    }
+
+    // 批量替换前缀；任一新名称与现有名称冲突时不做任何修改
+    pub fn rename_prefix(&mut self, from: &str, to: &str) -> Result<(), String> {
+        let renamed: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(n, _)| match n.strip_prefix(from) {
+                Some(rest) => format!("{}{}", to, rest),
+                None => n.clone(),
+            })
+            .collect();
+        for (i, name) in renamed.iter().enumerate() {
+            if renamed[..i].contains(name) {
+                return Err(format!(
+                    "前缀 {} → {} 的重命名会产生重复名称 {}",
+                    from, to, name
+                ));
+            }
+        }
+        for ((name, _), new_name) in self.entries.iter_mut().zip(renamed) {
+            *name = new_name;
+        }
+
+        return Ok(());
+    }
+
+    // 同名张量被 other 覆盖（位置不变），新名称追加在末尾
+    pub fn merge(&mut self, other: StateDict) {
+        for (name, tensor) in other.entries {
+            self.insert(name, tensor);
+        }
+    }
+
+    pub fn diff(&self, other: &StateDict) -> StateDiff {
+        let mut diff = StateDiff::default();
+        for (name, tensor) in &self.entries {
+            match other.get(name) {
+                None => diff.missing.push(name.clone()),
+                Some(t) if t.shape != tensor.shape => {
+                    diff.shape_mismatches.push((
+                        name.clone(),
+                        tensor.shape.to_vec(),
+                        t.shape.to_vec(),
+                    ));
+                }
+                Some(t) if !t.equal_data(tensor) => diff.changed.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.unexpected = other
+            .names()
+            .filter(|n| !self.contains(n))
+            .map(|n| n.to_string())
+            .collect();
+
+        return diff;
+    }
+}
+
+// 名称重复时后出现的覆盖先出现的
+impl FromIterator<(String, Tensor)> for StateDict {
+    fn from_iter<I: IntoIterator<Item = (String, Tensor)>>(iter: I) -> Self {
+        let mut dict = StateDict::new();
+        for (name, tensor) in iter {
+            dict.insert(name, tensor);
+        }
+
+        return dict;
+    }
+}
+
+impl From<Vec<(String, Tensor)>> for StateDict {
+    fn from(entries: Vec<(String, Tensor)>) -> Self {
+        return entries.into_iter().collect();
+    }
+}
+
+impl IntoIterator for StateDict {
+    type Item = (String, Tensor);
+    type IntoIter = std::vec::IntoIter<(String, Tensor)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.entries.into_iter();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> StateDict {
+        let mut dict = StateDict::new();
+        dict.insert("encoder.weight", Tensor::ones(vec![2, 3]).unwrap());
+        dict.insert("encoder.bias", Tensor::zeros(vec![3]).unwrap());
+        dict.insert("head.weight", Tensor::ones(vec![3, 1]).unwrap());
+
+        return dict;
+    }
+
+    #[test]
+    fn prefix_filtering_renaming_and_merging_keep_order() {
+        let mut dict = model();
+        assert_eq!(dict.numel(), 12);
+        assert!(
+            dict.insert("encoder.bias", Tensor::ones(vec![3]).unwrap())
+                .is_some()
+        );
+        assert_eq!(
+            dict.names().collect::<Vec<_>>(),
+            ["encoder.weight", "encoder.bias", "head.weight"]
+        );
+
+        assert_eq!(dict.filter_prefix("encoder.").len(), 2);
+        let encoder = dict.strip_prefix("encoder.");
+        assert_eq!(encoder.names().collect::<Vec<_>>(), ["weight", "bias"]);
+
+        dict.rename_prefix("encoder.", "backbone.").unwrap();
+        assert!(dict.contains("backbone.weight"));
+        assert!(dict.rename("head.weight", "backbone.bias").is_err());
+        assert!(dict.rename("missing", "x").is_err());
+        assert!(dict.rename_prefix("backbone.", "").is_ok());
+        assert!(dict.rename_prefix("head.", "").is_err());
+        assert!(dict.contains("head.weight"));
+
+        let mut extra = StateDict::new();
+        extra.insert("weight", Tensor::zeros(vec![2, 3]).unwrap());
+        extra.insert("tail.bias", Tensor::zeros(vec![1]).unwrap());
+        dict.merge(extra);
+        assert_eq!(
+            dict.names().collect::<Vec<_>>(),
+            ["weight", "bias", "head.weight", "tail.bias"]
+        );
+        assert_eq!(dict.get("weight").unwrap().data[0], 0.0);
+    }
+
+    #[test]
+    fn diff_reports_missing_unexpected_and_shape_mismatches() {
+        let reference = model();
+        assert!(reference.diff(&reference.clone()).is_empty());
+
+        let mut loaded = model();
+        loaded.remove("head.weight");
+        loaded.insert("encoder.weight", Tensor::ones(vec![3, 2]).unwrap());
+        loaded.get_mut("encoder.bias").unwrap().data[0] = 1.0;
+        loaded.insert("decoder.weight", Tensor::ones(vec![1]).unwrap());
+
+        let diff = reference.diff(&loaded);
+        assert_eq!(diff.missing, ["head.weight"]);
+        assert_eq!(diff.unexpected, ["decoder.weight"]);
+        assert_eq!(
+            diff.shape_mismatches,
+            [("encoder.weight".to_string(), vec![2, 3], vec![3, 2])]
+        );
+        assert_eq!(diff.changed, ["encoder.bias"]);
+    }
+}
@@ -1,19 +1,85 @@
-use std::fmt;
-use std::{
-    ops::{Index, IndexMut},
-    usize, vec,
-};
+use std::ops::{Index, IndexMut};
+
+mod activation;
+mod attention;
+mod broadcast;
+mod bytes;
+mod calculus;
+mod cast;
+mod chunk;
+mod conv;
+mod device;
+mod display;
+mod distance;
+mod dtype;
+mod elementwise;
+mod encoding;
+mod hash;
+mod indexing;
+mod indices;
+mod init;
+mod kernel;
+mod layout;
+mod linalg;
+mod logic;
+mod manipulation;
+mod matrix;
+mod norm;
+mod normalize;
+mod polynomial;
+mod pool;
+mod rank;
+mod reduce;
+mod render;
+mod rolling;
+mod sampling;
+mod segment;
+mod shape;
+mod signal;
+mod special;
+mod split;
+mod storage;
+mod summation;
+mod table;
+mod tree;
+mod upsample;
+mod view;
+
+pub use bytes::Endianness;
+pub use calculus::Spacing;
+pub(crate) use cast::saturate;
+pub use chunk::{Chunks, IntoChunks};
+pub use device::Device;
+#[cfg(any(feature = "cuda", all(feature = "metal", target_os = "macos")))]
+pub(crate) use device::{Backend, DeviceBuffer, ReduceOp};
+pub use distance::DistanceMetric;
+pub use dtype::DType;
+pub use indices::{Indices, indices, indices_column_major};
+pub use layout::Layout;
+pub use normalize::{MinMaxStats, ZScoreStats};
+pub use rank::RankMethod;
+pub use rolling::RollingEdge;
+pub use shape::Shape;
+pub use signal::WindowFn;
+pub use storage::Storage;
+pub use summation::Summation;
+pub use upsample::UpsampleMode;
+pub use view::TensorView;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Tensor {
-    pub data: Vec<f32>,
-    pub shape: Vec<usize>,
+    // 不公开：外部的写入都要经过 data_mut 等接口的冻结检查
+    pub(crate) data: Storage,
+    pub shape: Shape,
     strides: Vec<usize>,
+    dtype: DType,
+    device: Device,
 }
 
 impl Tensor {
-    pub fn new(data: Vec<f32>, shape: Vec<usize>) -> Result<Self, String> {
-        let total_size = shape.iter().product();
+    pub fn new(data: Vec<f32>, shape: impl Into<Shape>) -> Result<Self, String> {
+        let shape = shape.into();
+        let total_size = shape.numel();
         if data.len() != total_size {
             return Err(format!(
                 "数据长度 {} 与形状 {:?} 不匹配（总大小：{}）",
@@ -23,48 +89,62 @@ impl Tensor {
             ));
         }
 
-        let strides = Self::calculate_strides(&shape);
+        let strides = shape.strides();
 
         return Ok(Tensor {
-            data: data,
+            data: data.into(),
             shape: shape,
             strides: strides,
+            dtype: DType::F32,
+            device: Device::Cpu,
         });
     }
 
-    pub fn zeros(shape: Vec<usize>) -> Result<Self, String> {
-        let total_size = shape.iter().product();
-        let data = vec![0.0; total_size];
-        let strides = Self::calculate_strides(&shape);
+    pub fn zeros(shape: impl Into<Shape>) -> Result<Self, String> {
+        let shape = shape.into();
+        let total_size = shape.numel();
+        let mut data = crate::alloc::allocate(total_size);
+        data.resize(total_size, 0.0);
+        let strides = shape.strides();
 
         return Ok(Tensor {
-            data: data,
+            data: data.into(),
             shape: shape,
             strides: strides,
+            dtype: DType::F32,
+            device: Device::Cpu,
         });
     }
 
-    pub fn ones(shape: Vec<usize>) -> Result<Self, String> {
-        let total_size = shape.iter().product();
-        let data = vec![1.0; total_size];
-        let strides = Self::calculate_strides(&shape);
+    pub fn ones(shape: impl Into<Shape>) -> Result<Self, String> {
+        let shape = shape.into();
+        let total_size = shape.numel();
+        let mut data = crate::alloc::allocate(total_size);
+        data.resize(total_size, 1.0);
+        let strides = shape.strides();
 
         return Ok(Tensor {
-            data: data,
+            data: data.into(),
             shape: shape,
             strides: strides,
+            dtype: DType::F32,
+            device: Device::Cpu,
         });
     }
 
-    pub fn full(shape: Vec<usize>, value: f32) -> Result<Self, String> {
-        let total_size = shape.iter().product();
-        let data = vec![value; total_size];
-        let strides = Self::calculate_strides(&shape);
+    pub fn full(shape: impl Into<Shape>, value: f32) -> Result<Self, String> {
+        let shape = shape.into();
+        let total_size = shape.numel();
+        let mut data = crate::alloc::allocate(total_size);
+        data.resize(total_size, value);
+        let strides = shape.strides();
 
         return Ok(Tensor {
-            data: data,
+            data: data.into(),
             shape: shape,
             strides: strides,
+            dtype: DType::F32,
+            device: Device::Cpu,
         });
     }
 
@@ -84,7 +164,14 @@ impl Tensor {
         return Ok(&self.data);
     }
 
+    // 只读访问底层存储，用于查询是否冻结、是否与其他张量共享、是否常驻设备等
+    pub fn storage(&self) -> &Storage {
+        return &self.data;
+    }
+
     pub fn data_mut(&mut self) -> Result<&mut [f32], String> {
+        self.check_writable("data_mut")?;
+
         return Ok(&mut self.data);
     }
 
@@ -105,11 +192,12 @@ impl Tensor {
             }
         }
 
-        let index = self.calculate_index(&indices);
+        let index = self.calculate_index(indices);
         return Ok(self.data.get(index).unwrap());
     }
 
     pub fn get_mut(&mut self, indices: &[usize]) -> Result<&mut f32, String> {
+        self.check_writable("get_mut")?;
         if indices.len() != self.rank().unwrap() {
             return Err(format!(
                 "索引维度 {} 与张量秩 {} 不匹配",
@@ -126,11 +214,12 @@ impl Tensor {
             }
         }
 
-        let index = self.calculate_index(&indices);
+        let index = self.calculate_index(indices);
         return Ok(self.data.get_mut(index).unwrap());
     }
 
     pub fn set(&mut self, indices: &[usize], value: f32) -> Result<(), String> {
+        self.check_writable("set")?;
         if indices.len() != self.rank().unwrap() {
             return Err(format!(
                 "索引维度 {} 与张量秩 {} 不匹配",
@@ -147,14 +236,16 @@ impl Tensor {
             }
         }
 
-        let index = self.calculate_index(&indices);
+        let index = self.calculate_index(indices);
         self.data[index] = value;
 
         return Ok(());
     }
 
-    pub fn reshape(&mut self, new_shape: Vec<usize>) -> Result<(), String> {
-        let total_size: usize = new_shape.iter().product();
+    pub fn reshape(&mut self, new_shape: impl Into<Shape>) -> Result<(), String> {
+        self.check_writable("reshape")?;
+        let new_shape = new_shape.into();
+        let total_size = new_shape.numel();
         if total_size != self.numel().unwrap() {
             return Err(format!(
                 "新形状 {:?} 的元素总数 {} 与原形状的元素总数 {} 不匹配",
@@ -165,13 +256,14 @@ impl Tensor {
         }
 
         self.shape = new_shape;
-        self.strides = Self::calculate_strides(&self.shape);
+        self.strides = self.shape.strides();
 
         return Ok(());
     }
 
-    pub fn reshaped(&self, new_shape: Vec<usize>) -> Result<Self, String> {
-        let total_size: usize = new_shape.iter().product();
+    pub fn reshaped(&self, new_shape: impl Into<Shape>) -> Result<Self, String> {
+        let new_shape = new_shape.into();
+        let total_size = new_shape.numel();
         if total_size != self.numel().unwrap() {
             return Err(format!(
                 "新形状 {:?} 的元素总数 {} 与原形状的元素总数 {} 不匹配",
@@ -182,22 +274,19 @@ impl Tensor {
         }
 
         let new_data = self.data.clone();
-        let new_strides = Self::calculate_strides(&new_shape);
+        let new_strides = new_shape.strides();
 
         return Ok(Tensor {
             data: new_data,
             shape: new_shape,
             strides: new_strides,
+            dtype: self.dtype,
+            device: self.device,
         });
     }
 
-    fn calculate_strides(shape: &[usize]) -> Vec<usize> {
-        let mut strides = vec![1; shape.len()];
-        for i in (0..shape.len() - 1).rev() {
-            strides[i] = strides[i + 1] * shape[i + 1];
-        }
-
-        return strides;
+    pub(crate) fn check_axis(&self, axis: usize) -> Result<(), String> {
+        return self.shape.check_axis(axis);
     }
 
     fn calculate_index(&self, indices: &[usize]) -> usize {
@@ -210,6 +299,14 @@ impl Tensor {
     }
 }
 
+impl Drop for Tensor {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.data.take_unique() {
+            crate::alloc::release(buffer);
+        }
+    }
+}
+
 impl Index<&[usize]> for Tensor {
     type Output = f32;
 
@@ -223,43 +320,3 @@ impl IndexMut<&[usize]> for Tensor {
         return self.get_mut(indices).unwrap();
     }
 }
-
-impl fmt::Display for Tensor {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Tensor(shape: {:?}, data: [", self.shape)?;
-
-        fn format_data(
-            f: &mut fmt::Formatter,
-            data: &[f32],
-            shape: &[usize],
-            strides: &[usize],
-            offset: usize,
-        ) -> fmt::Result {
-            if shape.is_empty() {
-                return write!(f, "{}", data[offset]);
-            }
-
-            let dim_size = shape[0];
-            let inner_shape = &shape[1..];
-            let inner_strides = &strides[1..];
-
-            write!(f, "[")?;
-            for i in 0..dim_size {
-                let new_offset = offset + i * strides[0];
-                if !inner_shape.is_empty() {
-                    format_data(f, data, inner_shape, inner_strides, new_offset)?;
-                } else {
-                    write!(f, "{}", data[new_offset])?;
-                }
-
-                if i < dim_size - 1 {
-                    write!(f, ", ")?;
-                }
-            }
-            write!(f, "]")
-        }
-
-        format_data(f, &self.data, &self.shape, &self.strides, 0)?;
-        write!(f, "])")
-    }
-}
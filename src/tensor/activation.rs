@@ -0,0 +1,189 @@
+use super::Tensor;
+use crate::{check, profile};
+
+impl Tensor {
+    pub fn softmax(&self, axis: usize) -> Result<Tensor, String> {
+        return self.softmax_lanes("softmax", axis, 1.0);
+    }
+
+    pub fn softmax_with_temperature(
+        &self,
+        axis: usize,
+        temperature: f32,
+    ) -> Result<Tensor, String> {
+        if temperature.is_nan() || temperature <= 0.0 {
+            return Err(format!("softmax 温度 {} 必须为正", temperature));
+        }
+
+        return self.softmax_lanes("softmax_with_temperature", axis, 1.0 / temperature);
+    }
+
+    // mask 中非零位置视为屏蔽，可广播到 self 的形状；整条 lane 都被屏蔽时输出全 0
+    pub fn masked_softmax(&self, axis: usize, mask: &Tensor) -> Result<Tensor, String> {
+        let filled = self.zip_with("masked_fill", mask, |x, m| {
+            if m != 0.0 { f32::NEG_INFINITY } else { x }
+        })?;
+        if filled.shape != self.shape {
+            return Err(format!(
+                "softmax 掩码形状 {:?} 无法广播到 {:?}",
+                mask.shape, self.shape
+            ));
+        }
+
+        return filled.softmax_lanes("masked_softmax", axis, 1.0);
+    }
+
+    fn softmax_lanes(&self, op: &'static str, axis: usize, scale: f32) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+        let _scope = profile::scope(op, self.data.len());
+
+        let mut data = crate::alloc::allocate(self.data.len());
+        data.extend_from_slice(&self.data);
+        softmax_in_place(&mut data, &self.shape, axis, scale);
+
+        let out = self.placed(Tensor::new(data, self.shape.clone())?);
+        check::inspect(op, &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    pub fn softmax_(&mut self, axis: usize) -> Result<(), String> {
+        return self.softmax_lanes_("softmax_", axis, 1.0);
+    }
+
+    pub fn softmax_with_temperature_(
+        &mut self,
+        axis: usize,
+        temperature: f32,
+    ) -> Result<(), String> {
+        if temperature.is_nan() || temperature <= 0.0 {
+            return Err(format!("softmax 温度 {} 必须为正", temperature));
+        }
+
+        return self.softmax_lanes_("softmax_with_temperature_", axis, 1.0 / temperature);
+    }
+
+    pub fn masked_softmax_(&mut self, axis: usize, mask: &Tensor) -> Result<(), String> {
+        self.check_axis(axis)?;
+        self.zip_with_("masked_fill_", mask, |x, m| {
+            if m != 0.0 { f32::NEG_INFINITY } else { x }
+        })?;
+
+        return self.softmax_lanes_("masked_softmax_", axis, 1.0);
+    }
+
+    fn softmax_lanes_(&mut self, op: &'static str, axis: usize, scale: f32) -> Result<(), String> {
+        self.check_writable(op)?;
+        self.check_axis(axis)?;
+        let _scope = profile::scope(op, self.data.len());
+        let mut data = crate::alloc::allocate(self.data.len());
+        data.extend_from_slice(&self.data);
+        softmax_in_place(&mut data, &self.shape, axis, scale);
+        // 检查通过后才写回，Error 模式下失败时 self 保持原样
+        let out = Tensor::new(data, self.shape.clone())?;
+        check::inspect(op, &[&self.shape], &out)?;
+        self.data.copy_from_slice(&out.data);
+
+        return Ok(());
+    }
+}
+
+// 沿 axis 对每条 lane 做 softmax，结果覆盖 data；整条 lane 都是 -inf 时写 0
+fn softmax_in_place(data: &mut [f32], shape: &[usize], axis: usize, scale: f32) {
+    let outer: usize = shape[..axis].iter().product();
+    let len = shape[axis];
+    let inner: usize = shape[axis + 1..].iter().product();
+
+    for o in 0..outer {
+        for i in 0..inner {
+            let base = o * len * inner + i;
+            let max = (0..len)
+                .map(|k| data[base + k * inner] * scale)
+                .fold(f32::NEG_INFINITY, f32::max);
+            if max == f32::NEG_INFINITY {
+                for k in 0..len {
+                    data[base + k * inner] = 0.0;
+                }
+                continue;
+            }
+            let mut total = 0.0;
+            for k in 0..len {
+                let e = (data[base + k * inner] * scale - max).exp();
+                data[base + k * inner] = e;
+                total += e;
+            }
+            for k in 0..len {
+                data[base + k * inner] /= total;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn softmax_normalizes_each_lane_without_overflow() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 1000.0, 1000.0, 1000.0], vec![2, 3]).unwrap();
+        let y = x.softmax(1).unwrap();
+        for row in y.data.chunks(3) {
+            assert!((row.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        }
+        assert!(y.data[2] > y.data[1] && y.data[1] > y.data[0]);
+        assert!((y.data[4] - 1.0 / 3.0).abs() < 1e-6);
+
+        let cols = x.softmax(0).unwrap();
+        assert!((cols.data[0] + cols.data[3] - 1.0).abs() < 1e-6);
+        assert!(x.softmax(2).is_err());
+    }
+
+    #[test]
+    fn temperature_and_mask_reshape_the_distribution() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0], vec![1, 3]).unwrap();
+        let sharp = x.softmax_with_temperature(1, 0.5).unwrap();
+        let doubled = x.mul_scalar(2.0).unwrap().softmax(1).unwrap();
+        for (a, b) in sharp.data.iter().zip(&doubled.data) {
+            assert!((a - b).abs() < 1e-6);
+        }
+        assert!(x.softmax_with_temperature(1, 0.0).is_err());
+
+        // 掩码按行广播，被屏蔽位置概率为 0
+        let scores = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]).unwrap();
+        let mask = Tensor::new(vec![0.0, 0.0, 1.0], vec![3]).unwrap();
+        let y = scores.masked_softmax(1, &mask).unwrap();
+        assert_eq!(y.data[2], 0.0);
+        assert!((y.data[1] - 1.0 / (1.0 + (-1f32).exp())).abs() < 1e-6);
+
+        let all = Tensor::ones(vec![2, 3]).unwrap();
+        assert_eq!(
+            scores.masked_softmax(1, &all).unwrap().data.to_vec(),
+            vec![0.0; 6]
+        );
+        assert!(
+            scores
+                .masked_softmax(1, &Tensor::ones(vec![2, 2, 3]).unwrap())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn in_place_softmax_matches_out_of_place() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]).unwrap();
+        for axis in 0..2 {
+            let mut y = x.clone();
+            y.softmax_(axis).unwrap();
+            assert_eq!(y, x.softmax(axis).unwrap());
+        }
+
+        let mask = Tensor::new(vec![0.0, 1.0, 1.0], vec![3]).unwrap();
+        let mut y = x.clone();
+        y.masked_softmax_(1, &mask).unwrap();
+        assert_eq!(y, x.masked_softmax(1, &mask).unwrap());
+        let mut all = x.clone();
+        all.masked_softmax_(1, &Tensor::ones(vec![3]).unwrap())
+            .unwrap();
+        assert_eq!(all.data.to_vec(), vec![0.0; 6]);
+        assert!(y.softmax_with_temperature_(1, -1.0).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use super::Tensor;
+use crate::{check, parallel, profile};
+
+impl Tensor {
+    // 逐行融合 QK^T、缩放、掩码 softmax 与加权求和，不物化完整的注意力矩阵；
+    // mask 中非零位置被屏蔽，可广播到 [..., L, S]
+    pub fn scaled_dot_product_attention(
+        q: &Tensor,
+        k: &Tensor,
+        v: &Tensor,
+        mask: Option<&Tensor>,
+    ) -> Result<Tensor, String> {
+        let rank = q.shape.len();
+        if rank < 2
+            || k.shape.len() != rank
+            || v.shape.len() != rank
+            || q.shape[..rank - 2] != k.shape[..rank - 2]
+            || q.shape[..rank - 2] != v.shape[..rank - 2]
+            || q.shape[rank - 1] != k.shape[rank - 1]
+            || k.shape[rank - 2] != v.shape[rank - 2]
+        {
+            return Err(format!(
+                "注意力需要 q [..., L, D]、k [..., S, D]、v [..., S, Dv]，实际形状为 {:?}、{:?}、{:?}",
+                q.shape, k.shape, v.shape
+            ));
+        }
+        let batch_shape = &q.shape[..rank - 2];
+        let batch: usize = batch_shape.iter().product();
+        let (l, d) = (q.shape[rank - 2], q.shape[rank - 1]);
+        let (s, dv) = (k.shape[rank - 2], v.shape[rank - 1]);
+
+        let mut score_shape = batch_shape.to_vec();
+        score_shape.extend([l, s]);
+        let mask_strides = match mask {
+            Some(m) => {
+                if m.shape.len() > rank
+                    || Self::broadcast_shapes(&m.shape, &score_shape)? != score_shape
+                {
+                    return Err(format!(
+                        "注意力掩码形状 {:?} 无法广播到 {:?}",
+                        m.shape, score_shape
+                    ));
+                }
+                Some(m.broadcast_strides(&score_shape))
+            }
+            None => None,
+        };
+        let _scope = profile::scope("scaled_dot_product_attention", batch * l * dv);
+
+        let scale = 1.0 / (d as f32).sqrt();
+        let rows = parallel::map_range(batch * l, batch * l * s * (d + dv), |row| {
+            let (b, i) = (row / l, row % l);
+            let query = &q.data[row * d..(row + 1) * d];
+            let keys = &k.data[b * s * d..(b + 1) * s * d];
+            let values = &v.data[b * s * dv..(b + 1) * s * dv];
+
+            let mask_offset = mask_strides.as_ref().map(|strides| {
+                let mut rest = b;
+                let mut offset = i * strides[rank - 2];
+                for dim in (0..rank - 2).rev() {
+                    offset += (rest % batch_shape[dim]) * strides[dim];
+                    rest /= batch_shape[dim];
+                }
+                offset
+            });
+            let mut scores: Vec<f32> = keys
+                .chunks(d)
+                .enumerate()
+                .map(|(j, key)| {
+                    if let (Some(m), Some(base), Some(strides)) =
+                        (mask, mask_offset, mask_strides.as_ref())
+                        && m.data[base + j * strides[rank - 1]] != 0.0
+                    {
+                        return f32::NEG_INFINITY;
+                    }
+                    query.iter().zip(key).map(|(a, b)| a * b).sum::<f32>() * scale
+                })
+                .collect();
+
+            let mut out = vec![0.0f32; dv];
+            let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            if max == f32::NEG_INFINITY {
+                return out;
+            }
+            let mut total = 0.0;
+            for score in scores.iter_mut() {
+                *score = (*score - max).exp();
+                total += *score;
+            }
+            for (&weight, value) in scores.iter().zip(values.chunks(dv)) {
+                if weight == 0.0 {
+                    continue;
+                }
+                let weight = weight / total;
+                for (o, &x) in out.iter_mut().zip(value) {
+                    *o += weight * x;
+                }
+            }
+            out
+        });
+
+        let mut data = crate::alloc::allocate(batch * l * dv);
+        for row in rows {
+            data.extend(row);
+        }
+        let mut shape = batch_shape.to_vec();
+        shape.extend([l, dv]);
+
+        let out = q.placed(Tensor::new(data, shape)?);
+        check::inspect(
+            "scaled_dot_product_attention",
+            &[&q.shape, &k.shape, &v.shape],
+            &out,
+        )?;
+
+        return Ok(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(q: &Tensor, k: &Tensor, v: &Tensor, mask: Option<&Tensor>) -> Tensor {
+        let rank = q.shape.len();
+        let d = q.shape[rank - 1] as f32;
+        let scores = q
+            .matmul(&k.transpose(rank - 2, rank - 1).unwrap())
+            .unwrap()
+            .mul_scalar(1.0 / d.sqrt())
+            .unwrap();
+        let weights = match mask {
+            Some(m) => scores.masked_softmax(rank - 1, m).unwrap(),
+            None => scores.softmax(rank - 1).unwrap(),
+        };
+
+        return weights.matmul(v).unwrap();
+    }
+
+    #[test]
+    fn fused_attention_matches_composed_ops() {
+        let q = Tensor::rand_normal(vec![2, 3, 4, 8], 0.0, 1.0).unwrap();
+        let k = Tensor::rand_normal(vec![2, 3, 5, 8], 0.0, 1.0).unwrap();
+        let v = Tensor::rand_normal(vec![2, 3, 5, 6], 0.0, 1.0).unwrap();
+
+        let out = Tensor::scaled_dot_product_attention(&q, &k, &v, None).unwrap();
+        assert_eq!(out.shape, [2, 3, 4, 6]);
+        let expected = reference(&q, &k, &v, None);
+        assert!(
+            out.data
+                .iter()
+                .zip(&expected.data)
+                .all(|(a, b)| (a - b).abs() < 1e-5)
+        );
+
+        // 因果掩码：第 i 个查询只能看到前 i + 1 个键
+        let causal: Vec<f32> = (0..4)
+            .flat_map(|i| (0..5).map(move |j| if j > i { 1.0 } else { 0.0 }))
+            .collect();
+        let mask = Tensor::new(causal, vec![4, 5]).unwrap();
+        let masked = Tensor::scaled_dot_product_attention(&q, &k, &v, Some(&mask)).unwrap();
+        let expected = reference(&q, &k, &v, Some(&mask));
+        assert!(
+            masked
+                .data
+                .iter()
+                .zip(&expected.data)
+                .all(|(a, b)| (a - b).abs() < 1e-5)
+        );
+        // 第一个查询只看到第一个键，输出即为对应的值
+        assert_eq!(masked.data[..6], v.data[..6]);
+
+        assert!(Tensor::scaled_dot_product_attention(&q, &v, &v, None).is_err());
+        let bad_mask = Tensor::zeros(vec![4, 4]).unwrap();
+        assert!(Tensor::scaled_dot_product_attention(&q, &k, &v, Some(&bad_mask)).is_err());
+    }
+}
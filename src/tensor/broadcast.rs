@@ -0,0 +1,377 @@
+use super::cast::saturate;
+use super::{DType, Shape, Tensor};
+use crate::{check, parallel, profile};
+
+impl Tensor {
+    pub(crate) fn broadcast_shapes(a: &[usize], b: &[usize]) -> Result<Vec<usize>, String> {
+        return Ok(Shape::from(a).broadcast_with(b)?.into_vec());
+    }
+
+    pub(crate) fn broadcast_strides(&self, shape: &[usize]) -> Vec<usize> {
+        let pad = shape.len() - self.shape.len();
+        let mut strides = vec![0; shape.len()];
+        for i in 0..self.shape.len() {
+            if self.shape[i] != 1 {
+                strides[pad + i] = self.strides[i];
+            }
+        }
+
+        return strides;
+    }
+
+    // 两个操作数的 dtype 不同时按提升表决定结果类型；严格模式下直接报错
+    pub(crate) fn result_dtype(&self, op: &str, other: &Tensor) -> Result<DType, String> {
+        let strict = crate::config::is_strict_dtypes();
+        return Self::promote_dtypes(op, self.dtype, other.dtype, strict);
+    }
+
+    pub(crate) fn promote_dtypes(
+        op: &str,
+        a: DType,
+        b: DType,
+        strict: bool,
+    ) -> Result<DType, String> {
+        if a != b && strict {
+            return Err(format!(
+                "{}: 严格模式下不允许混合 dtype {} 与 {}",
+                op,
+                a.name(),
+                b.name()
+            ));
+        }
+
+        return Ok(a.promote(b));
+    }
+
+    // 结果值按 dtype 落到该类型能表示的值上：整数与布尔溢出时饱和、小数部分截断，
+    // f16/bf16 舍入到对应精度；带整数标签的张量因此不会存着越界或带小数的值
+    pub(crate) fn zip_with<F>(
+        &self,
+        op: &'static str,
+        other: &Tensor,
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(f32, f32) -> f32 + Sync,
+    {
+        let dtype = self.result_dtype(op, other)?;
+        return self.zip_into(op, other, dtype, f);
+    }
+
+    // 浮点值的二元运算（pow、logaddexp 等）：整数或布尔操作数的结果为 f32
+    pub(crate) fn zip_float<F>(
+        &self,
+        op: &'static str,
+        other: &Tensor,
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(f32, f32) -> f32 + Sync,
+    {
+        let dtype = self.result_dtype(op, other)?.to_float();
+        return self.zip_into(op, other, dtype, f);
+    }
+
+    pub(crate) fn zip_into<F>(
+        &self,
+        op: &'static str,
+        other: &Tensor,
+        dtype: DType,
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(f32, f32) -> f32 + Sync,
+    {
+        self.check_same_device(op, other)?;
+        let native = dtype.is_native();
+        let f = |x, y| {
+            let v = f(x, y);
+            if native { v } else { saturate(v, dtype) }
+        };
+        if self.shape == other.shape {
+            let _scope = profile::scope(op, self.data.len());
+            let mut data = crate::alloc::allocate(self.data.len());
+            data.resize(self.data.len(), 0.0);
+            parallel::fill_chunks(&mut data, |start, out| {
+                let a = &self.data[start..start + out.len()];
+                let b = &other.data[start..start + out.len()];
+                for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                    *o = f(x, y);
+                }
+            });
+            let mut out = Tensor::new(data, self.shape.clone())?;
+            out.dtype = dtype;
+            out.device = self.device;
+            check::inspect(op, &[&self.shape, &other.shape], &out)?;
+            return Ok(out);
+        }
+
+        let shape = Self::broadcast_shapes(&self.shape, &other.shape)?;
+        let sa = self.broadcast_strides(&shape);
+        let sb = other.broadcast_strides(&shape);
+        let total: usize = shape.iter().product();
+        let _scope = profile::scope(op, total);
+
+        let mut data = crate::alloc::allocate(total);
+        let mut index = vec![0; shape.len()];
+        let (mut ia, mut ib) = (0, 0);
+        for _ in 0..total {
+            data.push(f(self.data[ia], other.data[ib]));
+            for d in (0..shape.len()).rev() {
+                index[d] += 1;
+                ia += sa[d];
+                ib += sb[d];
+                if index[d] < shape[d] {
+                    break;
+                }
+                ia -= sa[d] * shape[d];
+                ib -= sb[d] * shape[d];
+                index[d] = 0;
+            }
+        }
+
+        let mut out = Tensor::new(data, shape)?;
+        out.dtype = dtype;
+        out.device = self.device;
+        check::inspect(op, &[&self.shape, &other.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 一元运算与原地版本遵循同一条规则：保留 self 的 dtype 标签，结果值落到该类型上
+    pub(crate) fn map<F>(&self, op: &'static str, f: F) -> Result<Tensor, String>
+    where
+        F: Fn(f32) -> f32 + Sync,
+    {
+        return self.map_into(op, self.dtype, f);
+    }
+
+    // 浮点值的一元运算（开方、指数、带 f32 标量的运算等）：整数或布尔输入的结果为 f32
+    pub(crate) fn map_float<F>(&self, op: &'static str, f: F) -> Result<Tensor, String>
+    where
+        F: Fn(f32) -> f32 + Sync,
+    {
+        return self.map_into(op, self.dtype.to_float(), f);
+    }
+
+    pub(crate) fn map_into<F>(&self, op: &'static str, dtype: DType, f: F) -> Result<Tensor, String>
+    where
+        F: Fn(f32) -> f32 + Sync,
+    {
+        let _scope = profile::scope(op, self.data.len());
+        let native = dtype.is_native();
+        let mut data = crate::alloc::allocate(self.data.len());
+        data.resize(self.data.len(), 0.0);
+        parallel::fill_chunks(&mut data, |start, out| {
+            let src = &self.data[start..start + out.len()];
+            for (o, &x) in out.iter_mut().zip(src) {
+                let v = f(x);
+                *o = if native { v } else { saturate(v, dtype) };
+            }
+        });
+
+        let mut out = Tensor::new(data, self.shape.clone())?;
+        out.dtype = dtype;
+        out.device = self.device;
+        check::inspect(op, &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 原地版本统一以 `_` 结尾，逐元素结果与同名的非原地版本相同，成功时返回 ()。
+    // 原地运算不改变 self 的形状与 dtype：另一操作数只能单向广播到 self 的形状，
+    // 结果 dtype 必须等于 self 的 dtype；冻结的张量返回 Err。
+    // 设备张量在主机数据上计算，不经过后端分派
+    pub(crate) fn zip_with_<F>(
+        &mut self,
+        op: &'static str,
+        other: &Tensor,
+        f: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(f32, f32) -> f32 + Sync,
+    {
+        self.check_writable(op)?;
+        self.check_same_device(op, other)?;
+        let dtype = self.result_dtype(op, other)?;
+        if dtype != self.dtype {
+            return Err(format!(
+                "{}: 结果 dtype {} 无法原地写回 {} 张量",
+                op,
+                dtype.name(),
+                self.dtype.name()
+            ));
+        }
+        if Self::broadcast_shapes(&self.shape, &other.shape)? != *self.shape {
+            return Err(format!(
+                "{}: 形状 {:?} 无法广播到 {:?}",
+                op, other.shape, self.shape
+            ));
+        }
+        let _scope = profile::scope(op, self.data.len());
+        let native = dtype.is_native();
+        let f = |x, y| {
+            let v = f(x, y);
+            if native { v } else { saturate(v, dtype) }
+        };
+
+        // 结果先写到缓冲区里检查，通过后才写回 self：Error 模式下失败时 self 保持原样
+        let mut data = crate::alloc::allocate(self.data.len());
+        if self.shape == other.shape {
+            data.resize(self.data.len(), 0.0);
+            parallel::fill_chunks(&mut data, |start, out| {
+                let a = &self.data[start..start + out.len()];
+                let b = &other.data[start..start + out.len()];
+                for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                    *o = f(x, y);
+                }
+            });
+        } else if !self.data.is_empty() {
+            let shape = &self.shape;
+            let sb = other.broadcast_strides(shape);
+            let mut index = vec![0; shape.len()];
+            let mut ib = 0;
+            for &x in self.data.iter() {
+                data.push(f(x, other.data[ib]));
+                for d in (0..shape.len()).rev() {
+                    index[d] += 1;
+                    ib += sb[d];
+                    if index[d] < shape[d] {
+                        break;
+                    }
+                    ib -= sb[d] * shape[d];
+                    index[d] = 0;
+                }
+            }
+        }
+        let out = Tensor::new(data, self.shape.clone())?;
+        check::inspect(op, &[&self.shape, &other.shape], &out)?;
+        self.data.copy_from_slice(&out.data);
+
+        return Ok(());
+    }
+
+    pub(crate) fn zip_float_<F>(
+        &mut self,
+        op: &'static str,
+        other: &Tensor,
+        f: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(f32, f32) -> f32 + Sync,
+    {
+        self.check_float_(op)?;
+        return self.zip_with_(op, other, f);
+    }
+
+    pub(crate) fn map_<F>(&mut self, op: &'static str, f: F) -> Result<(), String>
+    where
+        F: Fn(f32) -> f32 + Sync,
+    {
+        self.check_writable(op)?;
+        let _scope = profile::scope(op, self.data.len());
+        let dtype = self.dtype;
+        let native = dtype.is_native();
+        let mut data = crate::alloc::allocate(self.data.len());
+        data.resize(self.data.len(), 0.0);
+        parallel::fill_chunks(&mut data, |start, out| {
+            let src = &self.data[start..start + out.len()];
+            for (o, &x) in out.iter_mut().zip(src) {
+                let v = f(x);
+                *o = if native { v } else { saturate(v, dtype) };
+            }
+        });
+        // 与 zip_with_ 相同，检查通过后才写回
+        let out = Tensor::new(data, self.shape.clone())?;
+        check::inspect(op, &[&self.shape], &out)?;
+        self.data.copy_from_slice(&out.data);
+
+        return Ok(());
+    }
+
+    pub(crate) fn map_float_<F>(&mut self, op: &'static str, f: F) -> Result<(), String>
+    where
+        F: Fn(f32) -> f32 + Sync,
+    {
+        self.check_float_(op)?;
+        return self.map_(op, f);
+    }
+
+    // 浮点值运算的结果无法原地写回整数或布尔张量
+    fn check_float_(&self, op: &str) -> Result<(), String> {
+        if !self.dtype.is_float() {
+            return Err(format!(
+                "{}: 结果为浮点数，不能原地写回 {} 张量",
+                op,
+                self.dtype.name()
+            ));
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(data: Vec<f32>, shape: Vec<usize>) -> Tensor {
+        return Tensor::new(data, shape).unwrap();
+    }
+
+    #[test]
+    fn binary_ops_broadcast_both_sides() {
+        let col = t(vec![1.0, 2.0], vec![2, 1]);
+        let row = t(vec![10.0, 20.0, 30.0], vec![3]);
+        let sum = col.add(&row).unwrap();
+        assert_eq!(sum.shape, [2, 3]);
+        assert_eq!(sum.data.to_vec(), vec![11.0, 21.0, 31.0, 12.0, 22.0, 32.0]);
+        assert_eq!(row.add(&col).unwrap(), sum);
+
+        let scalar = t(vec![2.0], vec![]);
+        assert_eq!(
+            row.mul(&scalar).unwrap().data.to_vec(),
+            vec![20.0, 40.0, 60.0]
+        );
+        assert!(row.add(&t(vec![1.0, 2.0], vec![2])).is_err());
+    }
+
+    #[test]
+    fn special_values_follow_ieee() {
+        let a = t(vec![f32::INFINITY, 0.0, f32::NAN, -0.0], vec![4]);
+        let b = t(vec![f32::NEG_INFINITY], vec![1]);
+        let sum = a.add(&b).unwrap();
+        assert!(sum.data[0].is_nan());
+        assert_eq!(sum.data[1], f32::NEG_INFINITY);
+        assert!(sum.data[2].is_nan());
+        let product = a.mul(&t(vec![0.0], vec![])).unwrap();
+        assert!(product.data[0].is_nan());
+        assert_eq!(product.data[3].to_bits(), (-0.0f32 * 0.0).to_bits());
+    }
+
+    #[test]
+    fn failed_in_place_checks_leave_self_untouched() {
+        use crate::check::{CheckMode, with_mode};
+
+        let x = t(vec![1.0, 0.0, -1.0, 2.0], vec![2, 2]);
+        let mut y = x.deep_copy().unwrap();
+        with_mode(CheckMode::Error, || {
+            assert!(y.div_(&t(vec![0.0], vec![])).is_err());
+            assert!(y.add_(&t(vec![f32::NAN, 1.0], vec![2])).is_err());
+            assert!(y.apply_(|a| a / 0.0).is_err());
+            assert!(y.mul_scalar_(f32::INFINITY).is_err());
+            assert!(y.layer_norm_(&[2], None, None, f32::NAN).is_err());
+        });
+        assert_eq!(y, x);
+
+        let mut lanes = t(vec![f32::INFINITY, 0.0], vec![2]);
+        with_mode(CheckMode::Error, || assert!(lanes.softmax_(0).is_err()));
+        assert_eq!(lanes.data.to_vec(), vec![f32::INFINITY, 0.0]);
+
+        // 检查通过时照常写回
+        with_mode(CheckMode::Error, || {
+            y.add_(&t(vec![1.0, 1.0], vec![2])).unwrap()
+        });
+        assert_eq!(y.data.to_vec(), vec![2.0, 1.0, 0.0, 3.0]);
+    }
+}
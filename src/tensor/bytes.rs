@@ -0,0 +1,215 @@
+use super::{DType, Shape, Tensor};
+use crate::half::{bf16_to_f32, f16_to_f32, f32_to_bf16, f32_to_f16};
+use crate::profile;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Endianness {
+    pub fn native() -> Endianness {
+        if cfg!(target_endian = "big") {
+            return Endianness::Big;
+        }
+
+        return Endianness::Little;
+    }
+}
+
+// 先按小端序整理到定长缓冲区，各类型统一用 from_le_bytes 解码
+fn decode(dtype: DType, chunk: &[u8], endianness: Endianness) -> f32 {
+    let mut b = [0u8; 8];
+    b[..chunk.len()].copy_from_slice(chunk);
+    if endianness == Endianness::Big {
+        b[..chunk.len()].reverse();
+    }
+    let b2 = [b[0], b[1]];
+    let b4 = [b[0], b[1], b[2], b[3]];
+
+    return match dtype {
+        DType::F16 => f16_to_f32(u16::from_le_bytes(b2)),
+        DType::BF16 => bf16_to_f32(u16::from_le_bytes(b2)),
+        DType::F32 => f32::from_le_bytes(b4),
+        DType::F64 => f64::from_le_bytes(b) as f32,
+        DType::I8 => b[0] as i8 as f32,
+        DType::I16 => i16::from_le_bytes(b2) as f32,
+        DType::I32 => i32::from_le_bytes(b4) as f32,
+        DType::I64 => i64::from_le_bytes(b) as f32,
+        DType::U8 => b[0] as f32,
+        DType::U16 => u16::from_le_bytes(b2) as f32,
+        DType::U32 => u32::from_le_bytes(b4) as f32,
+        DType::U64 => u64::from_le_bytes(b) as f32,
+        DType::Bool => (b[0] != 0) as u8 as f32,
+    };
+}
+
+// 与 decode 对称：先按小端序写出，大端序时再原地翻转该元素的字节
+fn encode(dtype: DType, v: f32, endianness: Endianness, out: &mut Vec<u8>) {
+    let start = out.len();
+    match dtype {
+        DType::F16 => out.extend(f32_to_f16(v).to_le_bytes()),
+        DType::BF16 => out.extend(f32_to_bf16(v).to_le_bytes()),
+        DType::F32 => out.extend(v.to_le_bytes()),
+        DType::F64 => out.extend((v as f64).to_le_bytes()),
+        DType::I8 => out.push(v as i8 as u8),
+        DType::I16 => out.extend((v as i16).to_le_bytes()),
+        DType::I32 => out.extend((v as i32).to_le_bytes()),
+        DType::I64 => out.extend((v as i64).to_le_bytes()),
+        DType::U8 => out.push(v as u8),
+        DType::U16 => out.extend((v as u16).to_le_bytes()),
+        DType::U32 => out.extend((v as u32).to_le_bytes()),
+        DType::U64 => out.extend((v as u64).to_le_bytes()),
+        DType::Bool => out.push((v != 0.0) as u8),
+    }
+    if endianness == Endianness::Big {
+        out[start..].reverse();
+    }
+}
+
+impl Tensor {
+    // 只写元素本身（f32），形状与类型由调用方另行传递
+    pub fn to_bytes(&self, endianness: Endianness) -> Vec<u8> {
+        let _scope = profile::scope("to_bytes", self.data.len());
+        let mut bytes = Vec::with_capacity(self.data.len() * 4);
+        for &v in self.data.iter() {
+            match endianness {
+                Endianness::Little => bytes.extend(v.to_le_bytes()),
+                Endianness::Big => bytes.extend(v.to_be_bytes()),
+            }
+        }
+
+        return bytes;
+    }
+
+    // 按指定类型编码；整数类型要求值是范围内的整数，避免静默截断
+    pub fn to_bytes_as(&self, dtype: DType, endianness: Endianness) -> Result<Vec<u8>, String> {
+        if let Some((min, max)) = dtype.integer_range()
+            && let Some(&bad) = self
+                .data
+                .iter()
+                .find(|&&v| v.fract() != 0.0 || (v as f64) < min || (v as f64) > max)
+        {
+            return Err(format!("值 {} 无法无损转换为 {}", bad, dtype.name()));
+        }
+        let _scope = profile::scope("to_bytes_as", self.data.len());
+
+        let mut bytes = Vec::with_capacity(self.data.len() * dtype.size());
+        for &v in self.data.iter() {
+            encode(dtype, v, endianness, &mut bytes);
+        }
+
+        return Ok(bytes);
+    }
+
+    pub fn from_bytes(
+        bytes: &[u8],
+        shape: impl Into<Shape>,
+        dtype: DType,
+        endianness: Endianness,
+    ) -> Result<Tensor, String> {
+        let shape: Shape = shape.into();
+        let numel: usize = shape.iter().product();
+        if bytes.len() != numel * dtype.size() {
+            return Err(format!(
+                "字节长度 {} 与形状 {:?}（{} 个 {} 元素，共 {} 字节）不匹配",
+                bytes.len(),
+                shape,
+                numel,
+                dtype.name(),
+                numel * dtype.size()
+            ));
+        }
+        let _scope = profile::scope("from_bytes", numel);
+
+        let mut data = crate::alloc::allocate(numel);
+        data.extend(
+            bytes
+                .chunks_exact(dtype.size())
+                .map(|c| decode(dtype, c, endianness)),
+        );
+
+        return Tensor::new(data, shape);
+    }
+
+    // f32 字节按 4 对齐且字节序与本机一致时直接借用，否则退回 from_bytes 复制；
+    // 可用 data.is_borrowed() 确认是否零拷贝
+    pub fn from_bytes_ref(
+        bytes: &'static [u8],
+        shape: impl Into<Shape>,
+        endianness: Endianness,
+    ) -> Result<Tensor, String> {
+        let shape: Shape = shape.into();
+        let aligned = bytes.as_ptr().align_offset(std::mem::align_of::<f32>()) == 0;
+        if !aligned || endianness != Endianness::native() || !bytes.len().is_multiple_of(4) {
+            return Tensor::from_bytes(bytes, shape, DType::F32, endianness);
+        }
+
+        // SAFETY: 内存是 'static 且不可变，已检查对齐，任意位模式都是合法的 f32
+        return unsafe { Tensor::from_raw_parts(bytes.as_ptr().cast(), bytes.len() / 4, shape) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_round_trip_in_both_byte_orders() {
+        let t = Tensor::new(vec![1.5, -2.0, 0.0, 1e-3], vec![2, 2]).unwrap();
+        for endianness in [Endianness::Little, Endianness::Big] {
+            let bytes = t.to_bytes(endianness);
+            assert_eq!(bytes.len(), 16);
+            let back = Tensor::from_bytes(&bytes, vec![2, 2], DType::F32, endianness).unwrap();
+            assert_eq!(back, t);
+        }
+        assert_eq!(t.to_bytes(Endianness::Big)[..4], [0x3f, 0xc0, 0, 0]);
+
+        let ints = [0xff, 0xfe, 0x01, 0x00];
+        let le = Tensor::from_bytes(&ints, vec![2], DType::I16, Endianness::Little).unwrap();
+        assert_eq!(le.data.to_vec(), vec![-257.0, 1.0]);
+        let be = Tensor::from_bytes(&ints, vec![2], DType::U16, Endianness::Big).unwrap();
+        assert_eq!(be.data.to_vec(), vec![65534.0, 256.0]);
+        let half = Tensor::from_bytes(&[0x00, 0x3c], vec![1], DType::F16, Endianness::Little);
+        assert_eq!(half.unwrap().data[0], 1.0);
+        assert!(Tensor::from_bytes(&ints, vec![3], DType::I16, Endianness::Little).is_err());
+
+        let mixed = Tensor::new(vec![-3.0, 0.0, 7.0], vec![3]).unwrap();
+        for dtype in [
+            DType::F16,
+            DType::BF16,
+            DType::F64,
+            DType::I8,
+            DType::I64,
+            DType::U32,
+        ] {
+            for endianness in [Endianness::Little, Endianness::Big] {
+                if dtype == DType::U32 {
+                    assert!(mixed.to_bytes_as(dtype, endianness).is_err());
+                    continue;
+                }
+                let bytes = mixed.to_bytes_as(dtype, endianness).unwrap();
+                assert_eq!(bytes.len(), 3 * dtype.size());
+                let back = Tensor::from_bytes(&bytes, vec![3], dtype, endianness).unwrap();
+                assert_eq!(back, mixed);
+            }
+        }
+        assert!(t.to_bytes_as(DType::I32, Endianness::Little).is_err());
+    }
+
+    #[test]
+    fn from_bytes_ref_borrows_only_when_layout_allows() {
+        static WORDS: [f32; 4] = [1.0, 2.0, 3.0, 4.0];
+        let bytes: &'static [u8] = unsafe { std::slice::from_raw_parts(WORDS.as_ptr().cast(), 16) };
+
+        let t = Tensor::from_bytes_ref(bytes, vec![2, 2], Endianness::native()).unwrap();
+        assert!(t.data.is_borrowed());
+        assert_eq!(t.storage_ptr(), WORDS.as_ptr());
+
+        // 错位一个字节后无法借用，退回复制
+        let copied = Tensor::from_bytes_ref(&bytes[1..13], vec![3], Endianness::native()).unwrap();
+        assert!(!copied.data.is_borrowed());
+        assert!(Tensor::from_bytes_ref(bytes, vec![3], Endianness::native()).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use super::Tensor;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Spacing<'a> {
+    Uniform(f32),
+    // 与轴等长的一维采样坐标
+    Points(&'a Tensor),
+}
+
+impl Tensor {
+    fn sample_points(&self, spacing: Spacing, axis: usize) -> Result<Vec<f32>, String> {
+        self.check_axis(axis)?;
+        let len = self.shape[axis];
+
+        return match spacing {
+            Spacing::Uniform(dx) => Ok((0..len).map(|i| i as f32 * dx).collect()),
+            Spacing::Points(x) if x.shape == [len] => Ok(x.data.to_vec()),
+            Spacing::Points(x) => Err(format!("采样坐标形状 {:?} 应为 [{}]", x.shape, len)),
+        };
+    }
+
+    // 与 np.diff 一致：n 阶差分后轴长减少 n，不足时为 0
+    pub fn diff(&self, n: usize, axis: usize) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+        let out_len = self.shape[axis].saturating_sub(n);
+
+        return self.map_lanes("diff", axis, out_len, |lane, out| {
+            let mut work = lane.to_vec();
+            for _ in 0..n.min(lane.len()) {
+                for i in 0..work.len() - 1 {
+                    work[i] = work[i + 1] - work[i];
+                }
+                work.pop();
+            }
+            out.copy_from_slice(&work);
+        });
+    }
+
+    // 内部点用二阶中心差分，两端用一阶单侧差分，与 np.gradient 默认行为一致
+    pub fn gradient(&self, spacing: Spacing, axis: usize) -> Result<Tensor, String> {
+        let x = self.sample_points(spacing, axis)?;
+        let len = x.len();
+        if len < 2 {
+            return Err(format!("gradient 需要轴长至少为 2，实际为 {}", len));
+        }
+
+        return self.map_lanes("gradient", axis, len, |f, out| {
+            out[0] = (f[1] - f[0]) / (x[1] - x[0]);
+            out[len - 1] = (f[len - 1] - f[len - 2]) / (x[len - 1] - x[len - 2]);
+            for i in 1..len - 1 {
+                let (hs, hd) = (x[i] - x[i - 1], x[i + 1] - x[i]);
+                out[i] = (hs * hs * f[i + 1] + (hd * hd - hs * hs) * f[i] - hd * hd * f[i - 1])
+                    / (hs * hd * (hd + hs));
+            }
+        });
+    }
+
+    pub fn trapz(&self, spacing: Spacing, axis: usize) -> Result<Tensor, String> {
+        let x = self.sample_points(spacing, axis)?;
+
+        return self.reduce_lanes("trapz", axis, |f| {
+            (1..f.len())
+                .map(|i| (x[i] - x[i - 1]) * (f[i] + f[i - 1]) / 2.0)
+                .sum()
+        });
+    }
+
+    // 与 torch.cumulative_trapezoid 一致，输出沿轴比输入少一个元素
+    pub fn cumtrapz(&self, spacing: Spacing, axis: usize) -> Result<Tensor, String> {
+        let x = self.sample_points(spacing, axis)?;
+        let out_len = x.len().saturating_sub(1);
+
+        return self.map_lanes("cumtrapz", axis, out_len, |f, out| {
+            let mut total = 0.0;
+            for (i, o) in out.iter_mut().enumerate() {
+                total += (x[i + 1] - x[i]) * (f[i + 1] + f[i]) / 2.0;
+                *o = total;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_and_gradient_match_numpy() {
+        let x = Tensor::new(vec![1.0, 2.0, 4.0, 7.0, 0.0, 1.0, 3.0, 6.0], vec![2, 4]).unwrap();
+        assert_eq!(
+            x.diff(1, 1).unwrap().data.to_vec(),
+            vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]
+        );
+        assert_eq!(
+            x.diff(2, 1).unwrap().data.to_vec(),
+            vec![1.0, 1.0, 1.0, 1.0]
+        );
+        assert_eq!(
+            x.diff(1, 0).unwrap().data.to_vec(),
+            vec![-1.0, -1.0, -1.0, -1.0]
+        );
+        assert_eq!(x.diff(5, 1).unwrap().shape, [2, 0]);
+
+        // np.gradient([1, 2, 4, 7], 2.0) == [0.5, 0.75, 1.25, 1.5]
+        let g = x.gradient(Spacing::Uniform(2.0), 1).unwrap();
+        assert_eq!(g.data[..4], [0.5, 0.75, 1.25, 1.5]);
+
+        // 非均匀坐标下二次函数的内部导数是精确的
+        let points = Tensor::new(vec![0.0, 1.0, 3.0, 4.0], vec![4]).unwrap();
+        let squares = Tensor::new(vec![0.0, 1.0, 9.0, 16.0], vec![4]).unwrap();
+        let g = squares.gradient(Spacing::Points(&points), 0).unwrap();
+        assert_eq!(g.data[1..3], [2.0, 6.0]);
+
+        assert!(
+            x.gradient(Spacing::Points(&points.narrow(0, 0, 3).unwrap()), 1)
+                .is_err()
+        );
+        assert!(
+            x.narrow(1, 0, 1)
+                .unwrap()
+                .gradient(Spacing::Uniform(1.0), 1)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn trapezoidal_integration_computes_roc_auc() {
+        // ROC 曲线 (fpr, tpr)，AUC = 0.875
+        let fpr = Tensor::new(vec![0.0, 0.0, 0.5, 1.0], vec![4]).unwrap();
+        let tpr = Tensor::new(vec![0.0, 0.5, 1.0, 1.0], vec![4]).unwrap();
+        assert_eq!(
+            tpr.trapz(Spacing::Points(&fpr), 0).unwrap().data.to_vec(),
+            vec![0.875]
+        );
+        assert_eq!(
+            tpr.cumtrapz(Spacing::Points(&fpr), 0)
+                .unwrap()
+                .data
+                .to_vec(),
+            vec![0.0, 0.375, 0.875]
+        );
+
+        let rows = Tensor::new(vec![1.0, 2.0, 3.0, 0.0, 0.0, 4.0], vec![2, 3]).unwrap();
+        assert_eq!(
+            rows.trapz(Spacing::Uniform(0.5), 1).unwrap().data.to_vec(),
+            vec![2.0, 1.0]
+        );
+        let columns = rows.cumtrapz(Spacing::Uniform(1.0), 0).unwrap();
+        assert_eq!(columns.shape, [1, 3]);
+        assert_eq!(columns.data.to_vec(), vec![0.5, 1.0, 3.5]);
+        assert!(rows.trapz(Spacing::Points(&fpr), 1).is_err());
+    }
+}
@@ -0,0 +1,239 @@
+use super::{DType, Shape, Tensor};
+use crate::half::{bf16_to_f32, f16_to_f32, f32_to_bf16, f32_to_f16};
+use crate::profile;
+
+// 逐元素把值转换为 dtype 能表示的值，结果仍以 f32 存储，并带上 dtype 标签。
+// 整数类型先向零截断小数部分，三种模式只在越界与 NaN 时有区别：
+//   checked    —— NaN、无穷或越界时报错
+//   saturating —— NaN 变为 0，越界值夹到类型的最小/最大值（与 Rust 的 `as` 相同）
+//   wrapping   —— 按 2^位宽 取模回绕，NaN 与无穷变为 0
+// f16/bf16 按就近舍入到目标精度：checked 在有限值上溢为无穷时报错，
+// saturating 夹到最大有限值，wrapping 保留 IEEE 的上溢结果（±inf）。
+// bool：checked 只接受 0 与 1，另两种模式把非零值视为 1、NaN 视为 0。
+// 注意 i32/i64/u32/u64 的大数值超出 f32 的精确整数范围，存储时仍会舍入
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CastMode {
+    Checked,
+    Saturating,
+    Wrapping,
+}
+
+fn cast_integer(
+    v: f32,
+    dtype: DType,
+    (min, max): (f64, f64),
+    mode: CastMode,
+) -> Result<f32, String> {
+    let t = (v as f64).trunc();
+    if v.is_finite() && t >= min && t <= max {
+        return Ok(t as f32);
+    }
+
+    return match mode {
+        CastMode::Checked => Err(format!("值 {} 超出 {} 的表示范围", v, dtype.name())),
+        CastMode::Saturating if v.is_nan() => Ok(0.0),
+        CastMode::Saturating => Ok(t.clamp(min, max) as f32),
+        CastMode::Wrapping if !v.is_finite() => Ok(0.0),
+        CastMode::Wrapping => {
+            let modulus = max - min + 1.0;
+            Ok(((t - min).rem_euclid(modulus) + min) as f32)
+        }
+    };
+}
+
+fn cast_half(v: f32, dtype: DType, mode: CastMode) -> Result<f32, String> {
+    let (rounded, max) = match dtype {
+        DType::F16 => (f16_to_f32(f32_to_f16(v)), 65504.0),
+        _ => (bf16_to_f32(f32_to_bf16(v)), bf16_to_f32(0x7f7f)),
+    };
+    if rounded.is_finite() || !v.is_finite() {
+        return Ok(rounded);
+    }
+
+    return match mode {
+        CastMode::Checked => Err(format!("值 {} 超出 {} 的表示范围", v, dtype.name())),
+        CastMode::Saturating => Ok(max.copysign(v)),
+        CastMode::Wrapping => Ok(rounded),
+    };
+}
+
+fn cast_bool(v: f32, mode: CastMode) -> Result<f32, String> {
+    if mode == CastMode::Checked && v != 0.0 && v != 1.0 {
+        return Err(format!("值 {} 不是布尔值 0 或 1", v));
+    }
+
+    return Ok((v != 0.0 && !v.is_nan()) as u8 as f32);
+}
+
+fn cast_value(v: f32, dtype: DType, mode: CastMode) -> Result<f32, String> {
+    return match dtype {
+        DType::F32 | DType::F64 => Ok(v),
+        DType::F16 | DType::BF16 => cast_half(v, dtype, mode),
+        DType::Bool => cast_bool(v, mode),
+        _ => cast_integer(v, dtype, dtype.integer_range().unwrap(), mode),
+    };
+}
+
+// 运算结果带上 dtype 标签前按饱和转换落到该类型能表示的值上，饱和模式不会失败
+pub(crate) fn saturate(v: f32, dtype: DType) -> f32 {
+    return cast_value(v, dtype, CastMode::Saturating).unwrap();
+}
+
+impl Tensor {
+    fn cast_with(&self, op: &'static str, dtype: DType, mode: CastMode) -> Result<Tensor, String> {
+        let _scope = profile::scope(op, self.data.len());
+        let mut data = crate::alloc::allocate(self.data.len());
+        for &v in self.data.iter() {
+            data.push(cast_value(v, dtype, mode)?);
+        }
+
+        return Ok(self
+            .placed(Tensor::new(data, self.shape.clone())?)
+            .with_dtype(dtype));
+    }
+
+    // 复制、收集或重排出的结果沿用来源的 dtype 标签；值取自来源本身，已经落在该类型上
+    pub(crate) fn with_dtype(mut self, dtype: DType) -> Tensor {
+        self.dtype = dtype;
+        return self;
+    }
+
+    // 由多个输入拼成的结果按提升规则取 dtype，来自其他类型的值饱和到结果类型上
+    pub(crate) fn joined(
+        op: &str,
+        inputs: &[&Tensor],
+        mut data: Vec<f32>,
+        shape: impl Into<Shape>,
+    ) -> Result<Tensor, String> {
+        let strict = crate::config::is_strict_dtypes();
+        let mut dtype = inputs.first().map_or(DType::F32, |t| t.dtype);
+        for t in inputs {
+            dtype = Self::promote_dtypes(op, dtype, t.dtype, strict)?;
+        }
+        if !dtype.is_native() && inputs.iter().any(|t| t.dtype != dtype) {
+            data.iter_mut().for_each(|v| *v = saturate(*v, dtype));
+        }
+        let out = Tensor::new(data, shape)?.with_dtype(dtype);
+
+        return Ok(match inputs.first() {
+            Some(first) => first.placed(out),
+            None => out,
+        });
+    }
+
+    // 逻辑类型标签，决定二元运算结果的 dtype；新建的张量都是 f32
+    pub fn dtype(&self) -> DType {
+        return self.dtype;
+    }
+
+    pub fn cast_checked(&self, dtype: DType) -> Result<Tensor, String> {
+        return self.cast_with("cast_checked", dtype, CastMode::Checked);
+    }
+
+    pub fn cast_saturating(&self, dtype: DType) -> Result<Tensor, String> {
+        return self.cast_with("cast_saturating", dtype, CastMode::Saturating);
+    }
+
+    pub fn cast_wrapping(&self, dtype: DType) -> Result<Tensor, String> {
+        return self.cast_with("cast_wrapping", dtype, CastMode::Wrapping);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_casts_differ_only_out_of_range() {
+        let t = Tensor::new(vec![-1.7, 2.9, 300.0, -129.0, f32::NAN], vec![5]).unwrap();
+        assert!(t.cast_checked(DType::I8).is_err());
+        assert_eq!(
+            t.narrow(0, 0, 2)
+                .unwrap()
+                .cast_checked(DType::I8)
+                .unwrap()
+                .data
+                .to_vec(),
+            vec![-1.0, 2.0]
+        );
+        assert_eq!(
+            t.cast_saturating(DType::I8).unwrap().data.to_vec(),
+            vec![-1.0, 2.0, 127.0, -128.0, 0.0]
+        );
+        assert_eq!(
+            t.cast_wrapping(DType::I8).unwrap().data.to_vec(),
+            vec![-1.0, 2.0, 44.0, 127.0, 0.0]
+        );
+        assert_eq!(
+            t.cast_saturating(DType::U8).unwrap().data.to_vec(),
+            vec![0.0, 2.0, 255.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            t.cast_wrapping(DType::U8).unwrap().data.to_vec(),
+            vec![255.0, 2.0, 44.0, 127.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn float_and_bool_casts() {
+        let t = Tensor::new(vec![1.0 / 3.0, 70000.0, -1e6, 2.0], vec![4]).unwrap();
+        assert!(t.cast_checked(DType::F16).is_err());
+        let sat = t.cast_saturating(DType::F16).unwrap();
+        assert_eq!(&sat.data[1..], &[65504.0, -65504.0, 2.0]);
+        assert!((sat.data[0] - 1.0 / 3.0).abs() < 1e-3 && sat.data[0] != 1.0 / 3.0);
+        let wrapped = t.cast_wrapping(DType::F16).unwrap();
+        assert_eq!(wrapped.data[1], f32::INFINITY);
+        assert_eq!(t.cast_checked(DType::BF16).unwrap().data[3], 2.0);
+        assert_eq!(t.cast_checked(DType::F64).unwrap().data, t.data);
+        assert_eq!(t.cast_checked(DType::F64).unwrap().dtype(), DType::F64);
+
+        let b = Tensor::new(vec![0.0, 1.0, 0.5, f32::NAN], vec![4]).unwrap();
+        assert!(b.cast_checked(DType::Bool).is_err());
+        assert_eq!(
+            b.cast_saturating(DType::Bool).unwrap().data.to_vec(),
+            vec![0.0, 1.0, 1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn copies_and_gathers_keep_the_dtype() {
+        let x = Tensor::new((0..6).map(|i| i as f32).collect(), vec![2, 3])
+            .unwrap()
+            .cast_checked(DType::I8)
+            .unwrap();
+        let picks = Tensor::new(vec![1.0], vec![1]).unwrap();
+        let copies = [
+            x.deep_copy().unwrap(),
+            x.column(1).unwrap(),
+            x.select_columns(&[2, 0]).unwrap(),
+            x.diagonal(0).unwrap(),
+            x.transpose(0, 1).unwrap(),
+            x.index_select(1, &picks).unwrap(),
+            x.narrow(0, 1, 1).unwrap(),
+            x.narrow(1, 1, 2).unwrap(),
+            x.frame(2, 1, 1).unwrap(),
+            x.max_axis(1).unwrap(),
+            x.min_max(0).unwrap().0,
+            Tensor::from_blocks(&[&[&x, &x]]).unwrap(),
+            Tensor::concat(&[&x, &x], 0).unwrap(),
+        ];
+        for copy in &copies {
+            assert_eq!(copy.dtype(), DType::I8);
+        }
+        assert_eq!(copies[0], x);
+
+        // 混合 dtype 拼接按提升规则取结果类型，值饱和到该类型上
+        let wide = Tensor::new(vec![300.0; 3], vec![1, 3]).unwrap();
+        let joined = Tensor::concat(&[&x, &wide.cast_checked(DType::I16).unwrap()], 0).unwrap();
+        assert_eq!(joined.dtype(), DType::I16);
+        assert_eq!(joined.data[6..], [300.0; 3]);
+        let half = wide.cast_checked(DType::F16).unwrap();
+        let big = Tensor::full(vec![1, 3], 70000.0)
+            .unwrap()
+            .cast_checked(DType::I32);
+        let joined = Tensor::concat(&[&half, &big.unwrap()], 0).unwrap();
+        assert_eq!(joined.dtype(), DType::F16);
+        assert_eq!(joined.data[3..], [65504.0; 3]);
+        assert_eq!(Tensor::from_blocks(&[]).unwrap().dtype(), DType::F32);
+    }
+}
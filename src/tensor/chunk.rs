@@ -0,0 +1,151 @@
+use super::Tensor;
+
+// 惰性迭代器：每次 next 才用 narrow 取出下一块。沿轴 0（或前面各轴长度都为 1）
+// 分块时每块是与原张量共享缓冲区的视图，不复制；沿其他轴分块时每块复制一份
+pub struct Chunks<'a> {
+    source: &'a Tensor,
+    axis: usize,
+    size: usize,
+    start: usize,
+}
+
+pub struct IntoChunks {
+    source: Tensor,
+    axis: usize,
+    size: usize,
+    start: usize,
+}
+
+fn check_chunking(tensor: &Tensor, axis: usize, size: usize) -> Result<(), String> {
+    tensor.check_axis(axis)?;
+    if size == 0 {
+        return Err("分块大小必须为正".to_string());
+    }
+
+    return Ok(());
+}
+
+fn next_chunk(source: &Tensor, axis: usize, size: usize, start: &mut usize) -> Option<Tensor> {
+    let total = source.shape[axis];
+    if *start >= total {
+        return None;
+    }
+    let len = size.min(total - *start);
+    let chunk = source.narrow(axis, *start, len).ok()?;
+    *start += len;
+
+    return Some(chunk);
+}
+
+fn remaining(source: &Tensor, axis: usize, size: usize, start: usize) -> usize {
+    return (source.shape[axis] - start).div_ceil(size);
+}
+
+impl Tensor {
+    pub fn chunks(&self, axis: usize, chunk_size: usize) -> Result<Chunks<'_>, String> {
+        check_chunking(self, axis, chunk_size)?;
+
+        return Ok(Chunks {
+            source: self,
+            axis: axis,
+            size: chunk_size,
+            start: 0,
+        });
+    }
+
+    pub fn into_chunks(self, axis: usize, chunk_size: usize) -> Result<IntoChunks, String> {
+        check_chunking(&self, axis, chunk_size)?;
+
+        return Ok(IntoChunks {
+            source: self,
+            axis: axis,
+            size: chunk_size,
+            start: 0,
+        });
+    }
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = Tensor;
+
+    fn next(&mut self) -> Option<Tensor> {
+        return next_chunk(self.source, self.axis, self.size, &mut self.start);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = remaining(self.source, self.axis, self.size, self.start);
+
+        return (n, Some(n));
+    }
+}
+
+impl ExactSizeIterator for Chunks<'_> {}
+
+impl Iterator for IntoChunks {
+    type Item = Tensor;
+
+    fn next(&mut self) -> Option<Tensor> {
+        return next_chunk(&self.source, self.axis, self.size, &mut self.start);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = remaining(&self.source, self.axis, self.size, self.start);
+
+        return (n, Some(n));
+    }
+}
+
+impl ExactSizeIterator for IntoChunks {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arange(shape: Vec<usize>) -> Tensor {
+        let n: usize = shape.iter().product();
+
+        return Tensor::new((0..n).map(|i| i as f32).collect(), shape).unwrap();
+    }
+
+    #[test]
+    fn chunks_cover_axis_with_short_tail() {
+        let x = arange(vec![5, 2]);
+        let rows: Vec<Tensor> = x.chunks(0, 2).unwrap().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].data.to_vec(), vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(rows[2].shape, [1, 2]);
+
+        let cols = x.chunks(1, 1).unwrap();
+        assert_eq!(cols.len(), 2);
+        let last = cols.last().unwrap();
+        assert_eq!(last.data.to_vec(), vec![1.0, 3.0, 5.0, 7.0, 9.0]);
+
+        let owned: Vec<Tensor> = x.clone().into_chunks(0, 5).unwrap().collect();
+        assert_eq!(owned, vec![x.clone()]);
+        assert_eq!(arange(vec![0, 3]).chunks(0, 4).unwrap().count(), 0);
+        assert!(x.chunks(0, 0).is_err());
+        assert!(x.chunks(2, 1).is_err());
+    }
+
+    #[test]
+    fn leading_axis_chunks_are_views() {
+        let x = arange(vec![4, 3]);
+        let mut rows = x.chunks(0, 3).unwrap();
+        let mut head = rows.next().unwrap();
+        let tail = rows.next().unwrap();
+        assert!(head.shares_storage(&x) && tail.shares_storage(&x));
+        assert_eq!(tail.storage_ptr(), x.data[9..].as_ptr());
+        assert_eq!(tail.data.to_vec(), vec![9.0, 10.0, 11.0]);
+
+        // 写入视图只改自己的副本
+        head.data_mut().unwrap()[0] = -1.0;
+        assert!(!head.shares_storage(&x));
+        assert_eq!(head.shape, [3, 3]);
+        assert_eq!(x.data[0], 0.0);
+
+        let columns: Vec<Tensor> = x.chunks(1, 2).unwrap().collect();
+        assert!(!columns[0].shares_storage(&x));
+        let owned: Vec<Tensor> = x.clone().into_chunks(0, 2).unwrap().collect();
+        assert!(owned.iter().all(|c| c.shares_storage(&x)));
+    }
+}
@@ -0,0 +1,521 @@
+use super::Tensor;
+use crate::{check, parallel, profile};
+
+#[allow(clippy::too_many_arguments)]
+fn im2col(
+    image: &[f32],
+    channels: usize,
+    (h, w): (usize, usize),
+    (kh, kw): (usize, usize),
+    (sh, sw): (usize, usize),
+    (ph, pw): (usize, usize),
+    (dh, dw): (usize, usize),
+    (oh, ow): (usize, usize),
+) -> Vec<f32> {
+    let mut cols = vec![0.0; channels * kh * kw * oh * ow];
+    for c in 0..channels {
+        for ki in 0..kh {
+            for kj in 0..kw {
+                let row = (c * kh + ki) * kw + kj;
+                for y in 0..oh {
+                    let iy = (y * sh + ki * dh) as isize - ph as isize;
+                    if iy < 0 || iy >= h as isize {
+                        continue;
+                    }
+                    for x in 0..ow {
+                        let ix = (x * sw + kj * dw) as isize - pw as isize;
+                        if ix < 0 || ix >= w as isize {
+                            continue;
+                        }
+                        cols[(row * oh + y) * ow + x] =
+                            image[(c * h + iy as usize) * w + ix as usize];
+                    }
+                }
+            }
+        }
+    }
+
+    return cols;
+}
+
+// im2col 的伴随：把每列累加回图像，重叠窗口处求和
+#[allow(clippy::too_many_arguments)]
+fn col2im(
+    cols: &[f32],
+    channels: usize,
+    (h, w): (usize, usize),
+    (kh, kw): (usize, usize),
+    (sh, sw): (usize, usize),
+    (ph, pw): (usize, usize),
+    (dh, dw): (usize, usize),
+    (oh, ow): (usize, usize),
+) -> Vec<f32> {
+    let mut image = vec![0.0; channels * h * w];
+    for c in 0..channels {
+        for ki in 0..kh {
+            for kj in 0..kw {
+                let row = (c * kh + ki) * kw + kj;
+                for y in 0..oh {
+                    let iy = (y * sh + ki * dh) as isize - ph as isize;
+                    if iy < 0 || iy >= h as isize {
+                        continue;
+                    }
+                    for x in 0..ow {
+                        let ix = (x * sw + kj * dw) as isize - pw as isize;
+                        if ix < 0 || ix >= w as isize {
+                            continue;
+                        }
+                        image[(c * h + iy as usize) * w + ix as usize] +=
+                            cols[(row * oh + y) * ow + x];
+                    }
+                }
+            }
+        }
+    }
+
+    return image;
+}
+
+fn conv_output_size(
+    op: &str,
+    (h, w): (usize, usize),
+    (kh, kw): (usize, usize),
+    stride: (usize, usize),
+    (ph, pw): (usize, usize),
+    dilation: (usize, usize),
+) -> Result<(usize, usize), String> {
+    if stride.0 == 0 || stride.1 == 0 || dilation.0 == 0 || dilation.1 == 0 {
+        return Err(format!(
+            "{} 步长 {:?} 与膨胀 {:?} 必须为正",
+            op, stride, dilation
+        ));
+    }
+    let (hp, wp) = (h + 2 * ph, w + 2 * pw);
+    let (span_h, span_w) = (
+        dilation.0 * (kh.max(1) - 1) + 1,
+        dilation.1 * (kw.max(1) - 1) + 1,
+    );
+    if kh == 0 || kw == 0 || span_h > hp || span_w > wp {
+        return Err(format!(
+            "{} 卷积核 {}x{}（膨胀后 {}x{}）超出填充后的输入尺寸 {}x{}",
+            op, kh, kw, span_h, span_w, hp, wp
+        ));
+    }
+
+    return Ok(((hp - span_h) / stride.0 + 1, (wp - span_w) / stride.1 + 1));
+}
+
+impl Tensor {
+    // groups > 1 时输入通道与卷积核按组切分，groups == C 即为逐通道（depthwise）卷积
+    pub fn conv2d(
+        &self,
+        weight: &Tensor,
+        bias: Option<&Tensor>,
+        stride: (usize, usize),
+        padding: (usize, usize),
+        groups: usize,
+    ) -> Result<Tensor, String> {
+        if self.shape.len() != 4 || weight.shape.len() != 4 {
+            return Err(format!(
+                "conv2d 需要 [N, C, H, W] 输入与 [M, C/groups, kH, kW] 卷积核，实际形状为 {:?} 与 {:?}",
+                self.shape, weight.shape
+            ));
+        }
+        let (n, c, h, w) = (self.shape[0], self.shape[1], self.shape[2], self.shape[3]);
+        let (m, kc, kh, kw) = (
+            weight.shape[0],
+            weight.shape[1],
+            weight.shape[2],
+            weight.shape[3],
+        );
+        if groups == 0 || !c.is_multiple_of(groups) || !m.is_multiple_of(groups) {
+            return Err(format!(
+                "conv2d 输入通道数 {} 与输出通道数 {} 无法均分为 {} 组",
+                c, m, groups
+            ));
+        }
+        if kc * groups != c {
+            return Err(format!(
+                "conv2d 输入通道数 {} 与卷积核通道数 {}（{} 组）不匹配",
+                c, kc, groups
+            ));
+        }
+        if let Some(b) = bias
+            && b.shape != [m]
+        {
+            return Err(format!("conv2d 偏置形状 {:?} 应为 [{}]", b.shape, m));
+        }
+        let (oh, ow) = conv_output_size("conv2d", (h, w), (kh, kw), stride, padding, (1, 1))?;
+        let _scope = profile::scope("conv2d", n * m * oh * ow);
+
+        let k = kc * kh * kw;
+        let plane = oh * ow;
+        let filters = m / groups;
+        let group_len = kc * h * w;
+        // 按 (图像, 组) 并行，每组只展开自己的输入通道
+        let blocks = parallel::map_range(n * groups, n * m * plane * k, |job| {
+            let (i, g) = (job / groups, job % groups);
+            let start = i * c * h * w + g * group_len;
+            let cols = im2col(
+                &self.data[start..start + group_len],
+                kc,
+                (h, w),
+                (kh, kw),
+                stride,
+                padding,
+                (1, 1),
+                (oh, ow),
+            );
+            let mut out = vec![0.0f32; filters * plane];
+            for (local, row) in out.chunks_mut(plane).enumerate() {
+                let f = g * filters + local;
+                if let Some(b) = bias {
+                    row.fill(b.data[f]);
+                }
+                for (p, &wv) in weight.data[f * k..(f + 1) * k].iter().enumerate() {
+                    if wv == 0.0 {
+                        continue;
+                    }
+                    for (o, &v) in row.iter_mut().zip(&cols[p * plane..(p + 1) * plane]) {
+                        *o += wv * v;
+                    }
+                }
+            }
+            out
+        });
+
+        let mut data = crate::alloc::allocate(n * m * plane);
+        for block in blocks {
+            data.extend(block);
+        }
+
+        let out = self.placed(Tensor::new(data, vec![n, m, oh, ow])?);
+        check::inspect("conv2d", &[&self.shape, &weight.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // conv2d 对输入的梯度：每个输入像素把 W^T x 散射回输出，卷积核为 [C_in, C_out, kH, kW]
+    pub fn conv_transpose2d(
+        &self,
+        weight: &Tensor,
+        bias: Option<&Tensor>,
+        stride: (usize, usize),
+        padding: (usize, usize),
+        output_padding: (usize, usize),
+    ) -> Result<Tensor, String> {
+        if self.shape.len() != 4 || weight.shape.len() != 4 || weight.shape[0] != self.shape[1] {
+            return Err(format!(
+                "conv_transpose2d 需要 [N, C, H, W] 输入与 [C, M, kH, kW] 卷积核，实际形状为 {:?} 与 {:?}",
+                self.shape, weight.shape
+            ));
+        }
+        let (n, c, h, w) = (self.shape[0], self.shape[1], self.shape[2], self.shape[3]);
+        let (m, kh, kw) = (weight.shape[1], weight.shape[2], weight.shape[3]);
+        if stride.0 == 0
+            || stride.1 == 0
+            || output_padding.0 >= stride.0
+            || output_padding.1 >= stride.1
+        {
+            return Err(format!(
+                "conv_transpose2d 步长 {:?} 必须为正且大于输出填充 {:?}",
+                stride, output_padding
+            ));
+        }
+        if let Some(b) = bias
+            && b.shape != [m]
+        {
+            return Err(format!(
+                "conv_transpose2d 偏置形状 {:?} 应为 [{}]",
+                b.shape, m
+            ));
+        }
+        let full = (
+            (h.max(1) - 1) * stride.0 + kh + output_padding.0,
+            (w.max(1) - 1) * stride.1 + kw + output_padding.1,
+        );
+        if h == 0
+            || w == 0
+            || kh == 0
+            || kw == 0
+            || full.0 <= 2 * padding.0
+            || full.1 <= 2 * padding.1
+        {
+            return Err(format!(
+                "conv_transpose2d 填充 {:?} 使输出尺寸非正（输入 {}x{}，卷积核 {}x{}）",
+                padding, h, w, kh, kw
+            ));
+        }
+        let (oh, ow) = (full.0 - 2 * padding.0, full.1 - 2 * padding.1);
+        let _scope = profile::scope("conv_transpose2d", n * m * oh * ow);
+
+        let rows = m * kh * kw;
+        let plane = h * w;
+        let images = parallel::map_range(n, n * c * rows * plane, |i| {
+            let image = &self.data[i * c * plane..(i + 1) * c * plane];
+            let mut cols = vec![0.0f32; rows * plane];
+            for ci in 0..c {
+                let src = &image[ci * plane..(ci + 1) * plane];
+                for (r, &wv) in weight.data[ci * rows..(ci + 1) * rows].iter().enumerate() {
+                    if wv == 0.0 {
+                        continue;
+                    }
+                    for (o, &v) in cols[r * plane..(r + 1) * plane].iter_mut().zip(src) {
+                        *o += wv * v;
+                    }
+                }
+            }
+            let mut out = col2im(
+                &cols,
+                m,
+                (oh, ow),
+                (kh, kw),
+                stride,
+                padding,
+                (1, 1),
+                (h, w),
+            );
+            if let Some(b) = bias {
+                for (row, &bv) in out.chunks_mut(oh * ow).zip(b.data.iter()) {
+                    row.iter_mut().for_each(|v| *v += bv);
+                }
+            }
+            out
+        });
+
+        let mut data = crate::alloc::allocate(n * m * oh * ow);
+        for image in images {
+            data.extend(image);
+        }
+
+        let out = self.placed(Tensor::new(data, vec![n, m, oh, ow])?);
+        check::inspect("conv_transpose2d", &[&self.shape, &weight.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // [N, C, H, W] -> [N, C*kH*kW, L]，L 为滑动窗口个数，与 torch.nn.functional.unfold 一致
+    pub fn im2col(
+        &self,
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+        dilation: (usize, usize),
+    ) -> Result<Tensor, String> {
+        if self.shape.len() != 4 {
+            return Err(format!(
+                "im2col 需要 [N, C, H, W] 输入，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+        let (n, c, h, w) = (self.shape[0], self.shape[1], self.shape[2], self.shape[3]);
+        let (oh, ow) = conv_output_size("im2col", (h, w), kernel, stride, padding, dilation)?;
+        let rows = c * kernel.0 * kernel.1;
+        let _scope = profile::scope("im2col", n * rows * oh * ow);
+
+        let images = parallel::map_range(n, n * rows * oh * ow, |i| {
+            im2col(
+                &self.data[i * c * h * w..(i + 1) * c * h * w],
+                c,
+                (h, w),
+                kernel,
+                stride,
+                padding,
+                dilation,
+                (oh, ow),
+            )
+        });
+        let mut data = crate::alloc::allocate(n * rows * oh * ow);
+        for image in images {
+            data.extend(image);
+        }
+
+        let out = Tensor::new(data, vec![n, rows, oh * ow])?;
+
+        return Ok(self.placed(out).with_dtype(self.dtype));
+    }
+
+    // im2col 的逆向累加：[N, C*kH*kW, L] -> [N, C, H, W]，重叠位置求和，与 fold 一致
+    pub fn col2im(
+        &self,
+        output_size: (usize, usize),
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+        dilation: (usize, usize),
+    ) -> Result<Tensor, String> {
+        let (oh, ow) = conv_output_size("col2im", output_size, kernel, stride, padding, dilation)?;
+        let window = kernel.0 * kernel.1;
+        if self.shape.len() != 3
+            || !self.shape[1].is_multiple_of(window)
+            || self.shape[2] != oh * ow
+        {
+            return Err(format!(
+                "col2im 输入形状 {:?} 应为 [N, C*{}, {}]",
+                self.shape,
+                window,
+                oh * ow
+            ));
+        }
+        let (n, c) = (self.shape[0], self.shape[1] / window);
+        let (h, w) = output_size;
+        let _scope = profile::scope("col2im", n * c * h * w);
+
+        let cols = self.shape[1] * self.shape[2];
+        let images = parallel::map_range(n, n * cols, |i| {
+            col2im(
+                &self.data[i * cols..(i + 1) * cols],
+                c,
+                (h, w),
+                kernel,
+                stride,
+                padding,
+                dilation,
+                (oh, ow),
+            )
+        });
+        let mut data = crate::alloc::allocate(n * c * h * w);
+        for image in images {
+            data.extend(image);
+        }
+
+        return Ok(self.placed(Tensor::new(data, vec![n, c, h, w])?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arange(shape: Vec<usize>) -> Tensor {
+        let n: usize = shape.iter().product();
+
+        return Tensor::new((0..n).map(|i| i as f32).collect(), shape).unwrap();
+    }
+
+    #[test]
+    fn conv2d_matches_direct_sum_with_padding_and_stride() {
+        let x = arange(vec![1, 2, 3, 3]);
+        let weight = Tensor::ones(vec![1, 2, 2, 2]).unwrap();
+        let bias = Tensor::new(vec![0.5], vec![1]).unwrap();
+
+        let y = x.conv2d(&weight, Some(&bias), (1, 1), (0, 0), 1).unwrap();
+        assert_eq!(y.shape, [1, 1, 2, 2]);
+        // 通道 0 左上角窗口 0+1+3+4，通道 1 为 9+10+12+13
+        assert_eq!(y.data.to_vec(), vec![52.5, 60.5, 76.5, 84.5]);
+
+        let padded = x.conv2d(&weight, None, (2, 2), (1, 1), 1).unwrap();
+        assert_eq!(padded.shape, [1, 1, 2, 2]);
+        assert_eq!(padded.data[0], 0.0 + 9.0);
+        assert_eq!(
+            padded.data[3],
+            4.0 + 5.0 + 7.0 + 8.0 + 13.0 + 14.0 + 16.0 + 17.0
+        );
+
+        assert!(
+            x.conv2d(
+                &Tensor::ones(vec![1, 3, 2, 2]).unwrap(),
+                None,
+                (1, 1),
+                (0, 0),
+                1
+            )
+            .is_err()
+        );
+        assert!(x.conv2d(&weight, None, (0, 1), (0, 0), 1).is_err());
+    }
+
+    #[test]
+    fn im2col_and_col2im_are_adjoint() {
+        let x = arange(vec![2, 2, 4, 5]);
+        let cols = x.im2col((2, 3), (1, 2), (1, 0), (2, 1)).unwrap();
+        // 膨胀后核高 3：(4 + 2 - 3) / 1 + 1 = 4，宽 (5 - 3) / 2 + 1 = 2
+        assert_eq!(cols.shape, [2, 12, 8]);
+
+        // <im2col(x), y> == <x, col2im(y)>
+        let y = Tensor::rand_normal(cols.shape.clone(), 0.0, 1.0).unwrap();
+        let back = y.col2im((4, 5), (2, 3), (1, 2), (1, 0), (2, 1)).unwrap();
+        assert_eq!(back.shape, x.shape);
+        let lhs: f32 = cols.data.iter().zip(&y.data).map(|(a, b)| a * b).sum();
+        let rhs: f32 = x.data.iter().zip(&back.data).map(|(a, b)| a * b).sum();
+        assert!((lhs - rhs).abs() < 1e-2 * lhs.abs().max(1.0));
+
+        // 步长等于核大小时 fold(unfold(x)) == x
+        let tiles = x.im2col((2, 5), (2, 1), (0, 0), (1, 1)).unwrap();
+        assert_eq!(
+            tiles
+                .col2im((4, 5), (2, 5), (2, 1), (0, 0), (1, 1))
+                .unwrap(),
+            x
+        );
+
+        assert!(x.im2col((2, 2), (1, 1), (0, 0), (0, 1)).is_err());
+        assert!(cols.col2im((4, 5), (2, 3), (1, 1), (1, 0), (2, 1)).is_err());
+    }
+
+    #[test]
+    fn grouped_conv_equals_per_group_convolutions() {
+        let x = Tensor::rand_normal(vec![2, 4, 5, 5], 0.0, 1.0).unwrap();
+        let weight = Tensor::rand_normal(vec![6, 2, 3, 3], 0.0, 1.0).unwrap();
+        let bias = Tensor::rand_normal(vec![6], 0.0, 1.0).unwrap();
+        let y = x.conv2d(&weight, Some(&bias), (1, 1), (1, 1), 2).unwrap();
+        assert_eq!(y.shape, [2, 6, 5, 5]);
+
+        for g in 0..2 {
+            let part = x.narrow(1, g * 2, 2).unwrap();
+            let w = weight.narrow(0, g * 3, 3).unwrap();
+            let b = bias.narrow(0, g * 3, 3).unwrap();
+            let expected = part.conv2d(&w, Some(&b), (1, 1), (1, 1), 1).unwrap();
+            let actual = y.narrow(1, g * 3, 3).unwrap();
+            assert!(
+                actual
+                    .data
+                    .iter()
+                    .zip(&expected.data)
+                    .all(|(a, b)| (a - b).abs() < 1e-5)
+            );
+        }
+
+        // depthwise：每个通道用自己的 1 个卷积核
+        let depthwise = Tensor::ones(vec![4, 1, 1, 1]).unwrap();
+        assert_eq!(x.conv2d(&depthwise, None, (1, 1), (0, 0), 4).unwrap(), x);
+        assert!(x.conv2d(&weight, None, (1, 1), (0, 0), 3).is_err());
+        assert!(x.conv2d(&weight, None, (1, 1), (0, 0), 0).is_err());
+    }
+
+    #[test]
+    fn conv_transpose2d_is_the_adjoint_of_conv2d() {
+        let x = Tensor::rand_normal(vec![1, 2, 7, 7], 0.0, 1.0).unwrap();
+        let weight = Tensor::rand_normal(vec![3, 2, 3, 2], 0.0, 1.0).unwrap();
+        let y = x.conv2d(&weight, None, (2, 2), (1, 0), 1).unwrap();
+        assert_eq!(y.shape, [1, 3, 4, 3]);
+
+        // <conv2d(x), g> == <x, conv_transpose2d(g)>，output_padding 补回被步长截掉的行
+        let g = Tensor::rand_normal(y.shape.clone(), 0.0, 1.0).unwrap();
+        let back = g
+            .conv_transpose2d(&weight, None, (2, 2), (1, 0), (0, 1))
+            .unwrap();
+        assert_eq!(back.shape, x.shape);
+        let lhs: f32 = y.data.iter().zip(&g.data).map(|(a, b)| a * b).sum();
+        let rhs: f32 = x.data.iter().zip(&back.data).map(|(a, b)| a * b).sum();
+        assert!((lhs - rhs).abs() < 1e-3 * lhs.abs().max(1.0));
+
+        // 1x1 输入、步长 2 的上采样直接铺出卷积核并加上偏置
+        let pixel = Tensor::full(vec![1, 1, 1, 1], 2.0).unwrap();
+        let kernel = arange(vec![1, 1, 2, 2]);
+        let bias = Tensor::new(vec![1.0], vec![1]).unwrap();
+        let up = pixel
+            .conv_transpose2d(&kernel, Some(&bias), (2, 2), (0, 0), (0, 0))
+            .unwrap();
+        assert_eq!(up.data.to_vec(), vec![1.0, 3.0, 5.0, 7.0]);
+
+        assert!(
+            g.conv_transpose2d(&weight, None, (2, 2), (0, 0), (2, 0))
+                .is_err()
+        );
+        assert!(
+            x.conv_transpose2d(&weight, None, (1, 1), (0, 0), (0, 0))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::Arc;
+
+use super::{Shape, Storage, Tensor};
+use crate::lazy::{BinaryOp, UnaryOp};
+
+// 张量所在的设备。设备决定可分派的算子（BinaryOp、UnaryOp、ReduceOp 与 matmul）
+// 由哪个后端执行，其结果常驻在设备上；其余算子读取主机副本在主机上计算，
+// 结果仍标记为同一设备，下次分派时再上传
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Device {
+    #[default]
+    Cpu,
+    Cuda(usize),
+    Metal(usize),
+}
+
+impl Device {
+    pub fn is_available(self) -> bool {
+        return backend(self).is_ok();
+    }
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Device::Cpu => write!(f, "cpu"),
+            Device::Cuda(ordinal) => write!(f, "cuda:{}", ordinal),
+            Device::Metal(ordinal) => write!(f, "metal:{}", ordinal),
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReduceOp {
+    Sum,
+    Max,
+    Min,
+}
+
+impl ReduceOp {
+    pub(crate) fn identity(self) -> f32 {
+        return match self {
+            ReduceOp::Sum => 0.0,
+            ReduceOp::Max => f32::NEG_INFINITY,
+            ReduceOp::Min => f32::INFINITY,
+        };
+    }
+
+    // 与 argmax/argmin 一致，max/min 遇到 NaN 时结果为 NaN
+    pub(crate) fn combine(self, a: f32, b: f32) -> f32 {
+        return match self {
+            ReduceOp::Sum => a + b,
+            ReduceOp::Max if a.is_nan() || a > b => a,
+            ReduceOp::Min if a.is_nan() || a < b => a,
+            ReduceOp::Max | ReduceOp::Min => b,
+        };
+    }
+}
+
+// 后端持有的设备内存。read 等待写入它的工作完成后把内容拷回主机。
+// 要求 UnwindSafe + RefUnwindSafe，Tensor 才能照常跨过 catch_unwind
+pub(crate) trait DeviceBuffer: Send + Sync + UnwindSafe + RefUnwindSafe {
+    #[cfg_attr(
+        not(any(feature = "cuda", all(feature = "metal", target_os = "macos"))),
+        allow(dead_code)
+    )]
+    fn as_any(&self) -> &dyn Any;
+    fn len(&self) -> usize;
+    fn read(&self) -> Result<Vec<f32>, String>;
+}
+
+// 设备后端。返回 Ok(None) 表示后端不处理这种情况（例如需要广播），
+// 由调用方退回主机实现；结果的 device 与 dtype 标签由分派层统一设置
+pub(crate) trait Backend: Send + Sync {
+    fn upload(&self, data: &[f32]) -> Result<Arc<dyn DeviceBuffer>, String>;
+    fn binary(&self, op: BinaryOp, a: &Tensor, b: &Tensor) -> Result<Option<Tensor>, String>;
+    fn unary(&self, op: UnaryOp, x: &Tensor) -> Result<Option<Tensor>, String>;
+    fn reduce(&self, op: ReduceOp, x: &Tensor) -> Result<Option<f32>, String>;
+    fn matmul(&self, a: &Tensor, b: &Tensor) -> Result<Option<Tensor>, String>;
+}
+
+// CPU 没有独立的后端，返回 None 走主机实现
+fn backend(device: Device) -> Result<Option<&'static dyn Backend>, String> {
+    return match device {
+        Device::Cpu => Ok(None),
+        #[cfg(feature = "cuda")]
+        Device::Cuda(ordinal) => Ok(Some(crate::cuda::backend(ordinal)?)),
+        #[cfg(all(feature = "metal", target_os = "macos"))]
+        Device::Metal(ordinal) => Ok(Some(crate::metal::backend(ordinal)?)),
+        _ => Err(format!("设备 {} 不可用：未编译对应的后端", device)),
+    };
+}
+
+impl Tensor {
+    pub fn device(&self) -> Device {
+        return self.device;
+    }
+
+    // 在设备之间搬运数据：上传到目标设备的常驻缓冲区，或取回主机。同一设备上只是 clone
+    pub fn to(&self, device: Device) -> Result<Tensor, String> {
+        let target = backend(device)?;
+        if device == self.device {
+            return Ok(self.clone());
+        }
+        let mut out = self.clone();
+        out.data = self.data.host_copy()?;
+        out.device = device;
+        if let Some(backend) = target {
+            out.data.cache_resident(backend.upload(&out.data)?);
+        }
+
+        return Ok(out);
+    }
+
+    // 后端结果：数据只在设备上，读取时才取回主机
+    #[cfg_attr(
+        not(any(feature = "cuda", all(feature = "metal", target_os = "macos"))),
+        allow(dead_code)
+    )]
+    pub(crate) fn from_resident(
+        buffer: Arc<dyn DeviceBuffer>,
+        shape: impl Into<Shape>,
+    ) -> Result<Tensor, String> {
+        return Tensor::from_storage(Storage::from_resident(buffer), shape.into());
+    }
+
+    // 取得张量在 backend 上的缓冲区；只有主机副本时上传一次并缓存在存储里
+    #[cfg_attr(
+        not(any(feature = "cuda", all(feature = "metal", target_os = "macos"))),
+        allow(dead_code)
+    )]
+    pub(crate) fn device_buffer(
+        &self,
+        backend: &dyn Backend,
+    ) -> Result<Arc<dyn DeviceBuffer>, String> {
+        if let Some(buffer) = self.data.resident() {
+            return Ok(buffer.clone());
+        }
+
+        return Ok(self.data.cache_resident(backend.upload(&self.data)?));
+    }
+
+    pub(crate) fn check_same_device(&self, op: &str, other: &Tensor) -> Result<(), String> {
+        if self.device != other.device {
+            return Err(format!(
+                "{}: 张量位于不同设备 {} 与 {}",
+                op, self.device, other.device
+            ));
+        }
+
+        return Ok(());
+    }
+
+    pub(crate) fn placed(&self, mut out: Tensor) -> Tensor {
+        out.device = self.device;
+        return out;
+    }
+
+    // 逐元素算子与 lazy 模块共用同一组 BinaryOp/UnaryOp，主机实现直接用 op.apply。
+    // 后端只计算 f32，结果 dtype 需要取整或舍入时（整数、布尔、f16/bf16）走主机实现
+    pub(crate) fn binary_op(&self, op: BinaryOp, other: &Tensor) -> Result<Tensor, String> {
+        self.check_same_device(op.name(), other)?;
+        let mut dtype = self.result_dtype(op.name(), other)?;
+        if op.float_valued() {
+            dtype = dtype.to_float();
+        }
+        if let Some(backend) = backend(self.device)?
+            && dtype.is_native()
+            && let Some(mut out) = backend.binary(op, self, other)?
+        {
+            out.dtype = dtype;
+            return Ok(self.placed(out));
+        }
+
+        return self.zip_into(op.name(), other, dtype, |a, b| op.apply(a, b));
+    }
+
+    pub(crate) fn unary_op(&self, op: UnaryOp) -> Result<Tensor, String> {
+        let mut dtype = self.dtype;
+        if op.float_valued() {
+            dtype = dtype.to_float();
+        }
+        if let Some(backend) = backend(self.device)?
+            && dtype.is_native()
+            && let Some(mut out) = backend.unary(op, self)?
+        {
+            out.dtype = dtype;
+            return Ok(self.placed(out));
+        }
+
+        return self.map_into(op.name(), dtype, |a| op.apply(a));
+    }
+
+    pub(crate) fn dispatch_reduce(&self, op: ReduceOp) -> Result<Option<f32>, String> {
+        return match backend(self.device)? {
+            Some(backend) => backend.reduce(op, self),
+            None => Ok(None),
+        };
+    }
+
+    pub(crate) fn dispatch_matmul(&self, other: &Tensor) -> Result<Option<Tensor>, String> {
+        self.check_same_device("matmul", other)?;
+        return match backend(self.device)? {
+            Some(backend) => Ok(backend.matmul(self, other)?.map(|out| self.placed(out))),
+            None => Ok(None),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // 用主机内存模拟设备缓冲区，记录读回次数
+    struct FakeBuffer {
+        values: Vec<f32>,
+        reads: AtomicUsize,
+        broken: bool,
+    }
+
+    impl DeviceBuffer for FakeBuffer {
+        fn as_any(&self) -> &dyn Any {
+            return self;
+        }
+
+        fn len(&self) -> usize {
+            return self.values.len();
+        }
+
+        fn read(&self) -> Result<Vec<f32>, String> {
+            if self.broken {
+                return Err("设备已丢失".to_string());
+            }
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            return Ok(self.values.clone());
+        }
+    }
+
+    fn fake(values: Vec<f32>, broken: bool) -> Arc<FakeBuffer> {
+        return Arc::new(FakeBuffer {
+            values: values,
+            reads: AtomicUsize::new(0),
+            broken: broken,
+        });
+    }
+
+    #[test]
+    fn tensors_start_on_cpu_and_reject_missing_backends() {
+        let t = Tensor::ones(vec![2, 2]).unwrap();
+        assert_eq!(t.device(), Device::Cpu);
+        assert_eq!(t.to(Device::Cpu).unwrap(), t);
+        assert!(Device::Cpu.is_available());
+        assert!(t.to(Device::Metal(7)).is_err());
+        assert_eq!(Device::Cuda(1).to_string(), "cuda:1");
+        assert_eq!(Device::Metal(0).to_string(), "metal:0");
+
+        let mut other = t.clone();
+        other.device = Device::Cuda(0);
+        assert!(t.add(&other).is_err());
+        assert!(t.matmul(&other).is_err());
+    }
+
+    #[test]
+    fn host_fallback_results_inherit_the_device() {
+        let mut x = Tensor::new((0..6).map(|i| i as f32).collect(), vec![2, 3]).unwrap();
+        x.device = Device::Cuda(0);
+        let derived = [
+            x.sum_axis(0).unwrap(),
+            x.narrow(1, 1, 2).unwrap(),
+            x.permute(&[1, 0]).unwrap(),
+            x.softmax(1).unwrap(),
+            x.pow_scalar(2.0).unwrap(),
+            x.transpose(0, 1).unwrap(),
+            x.min_max(1).unwrap().0,
+            x.lazy().sigmoid().eval().unwrap(),
+        ];
+        for y in &derived {
+            assert_eq!(y.device(), Device::Cuda(0));
+        }
+    }
+
+    #[test]
+    fn resident_storage_reads_back_lazily_and_drops_on_write() {
+        let buffer = fake(vec![1.0, 2.0, 3.0, 4.0], false);
+        let mut y = Tensor::from_resident(buffer.clone(), vec![2, 2]).unwrap();
+        let view = y.reshaped(vec![4]).unwrap();
+        assert_eq!(y.data.len(), 4);
+        assert!(!y.data.is_on_host());
+        assert!(view.shares_storage(&y));
+        assert_eq!(buffer.reads.load(Ordering::SeqCst), 0);
+
+        // 第一次按切片读取时取回，之后复用主机副本
+        assert_eq!(y.sum().unwrap(), 10.0);
+        assert_eq!(y.data.to_vec(), vec![1.0, 2.0, 3.0, 4.0]);
+        assert!(y.data.is_on_host());
+        assert_eq!(buffer.reads.load(Ordering::SeqCst), 1);
+
+        // 主机端写入使设备副本失效，共享同一设备缓冲区的句柄不受影响
+        assert!(y.data.resident().is_some());
+        y.data_mut().unwrap()[0] = 5.0;
+        assert!(y.data.resident().is_none());
+        assert_eq!(view.data[0], 1.0);
+
+        let mut lost = Tensor::from_resident(fake(vec![0.0; 2], true), vec![2]).unwrap();
+        lost.device = Device::Cuda(0);
+        assert!(lost.to(Device::Cpu).is_err());
+        let host = Tensor::from_resident(buffer, vec![4]).unwrap();
+        let mut labelled = host.clone();
+        labelled.device = Device::Cuda(0);
+        let back = labelled.to(Device::Cpu).unwrap();
+        assert_eq!(back.device(), Device::Cpu);
+        assert!(back.data.resident().is_none());
+        assert_eq!(back, host);
+    }
+}
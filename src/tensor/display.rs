@@ -0,0 +1,184 @@
+use std::fmt;
+
+use super::Tensor;
+
+// 元素数超过该阈值时，每个轴只显示首尾 EDGE_ITEMS 项，中间以 "..." 省略
+const SUMMARY_THRESHOLD: usize = 1000;
+const EDGE_ITEMS: usize = 3;
+const PREFIX: &str = "Tensor(";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FloatStyle {
+    Integer,
+    Fixed(usize),
+    Scientific(usize),
+}
+
+impl FloatStyle {
+    // 与 NumPy 相同的规则：全为整数时省略小数，量级跨度过大时改用科学计数法
+    fn choose(values: &[f32], precision: Option<usize>) -> FloatStyle {
+        let finite: Vec<f32> = values
+            .iter()
+            .filter(|v| v.is_finite())
+            .map(|v| v.abs())
+            .collect();
+        let max = finite.iter().cloned().fold(0.0f32, f32::max);
+        let min_nonzero = finite
+            .iter()
+            .cloned()
+            .filter(|&v| v > 0.0)
+            .fold(f32::INFINITY, f32::min);
+
+        if precision.is_none() && max < 1e8 && finite.iter().all(|v| v.fract() == 0.0) {
+            return FloatStyle::Integer;
+        }
+        if max >= 1e8 || min_nonzero < 1e-4 || max / min_nonzero > 1e3 {
+            return FloatStyle::Scientific(precision.unwrap_or(4));
+        }
+
+        return FloatStyle::Fixed(precision.unwrap_or(4));
+    }
+
+    fn format(self, v: f32) -> String {
+        if v.is_nan() {
+            return "nan".to_string();
+        }
+        if v.is_infinite() {
+            return if v > 0.0 { "inf" } else { "-inf" }.to_string();
+        }
+
+        return match self {
+            FloatStyle::Integer => format!("{:.0}.", v),
+            FloatStyle::Fixed(p) => format!("{:.*}", p, v),
+            FloatStyle::Scientific(p) => format!("{:.*e}", p, v),
+        };
+    }
+}
+
+// 轴上要显示的下标，None 表示省略号
+fn shown(len: usize, summarize: bool) -> Vec<Option<usize>> {
+    if !summarize || len <= 2 * EDGE_ITEMS {
+        return (0..len).map(Some).collect();
+    }
+
+    return (0..EDGE_ITEMS)
+        .map(Some)
+        .chain(std::iter::once(None))
+        .chain((len - EDGE_ITEMS..len).map(Some))
+        .collect();
+}
+
+struct Layout<'a> {
+    tensor: &'a Tensor,
+    summarize: bool,
+    style: FloatStyle,
+    width: usize,
+}
+
+impl Layout<'_> {
+    fn collect(&self, axis: usize, offset: usize, out: &mut Vec<f32>) {
+        let t = self.tensor;
+        if axis == t.shape.len() {
+            out.push(t.data[offset]);
+            return;
+        }
+        for i in shown(t.shape[axis], self.summarize).into_iter().flatten() {
+            self.collect(axis + 1, offset + i * t.strides[axis], out);
+        }
+    }
+
+    fn write(&self, axis: usize, offset: usize, out: &mut String) {
+        let t = self.tensor;
+        let ndim = t.shape.len();
+        if axis == ndim {
+            let cell = self.style.format(t.data[offset]);
+            out.push_str(&format!("{:>1$}", cell, self.width));
+            return;
+        }
+
+        out.push('[');
+        for (k, index) in shown(t.shape[axis], self.summarize).into_iter().enumerate() {
+            if k > 0 {
+                out.push(',');
+                if axis + 1 < ndim {
+                    // 外层每深一维，块之间多空一行
+                    out.push_str(&"\n".repeat(ndim - axis - 1));
+                    out.push_str(&" ".repeat(PREFIX.len() + axis + 1));
+                } else {
+                    out.push(' ');
+                }
+            }
+            match index {
+                Some(i) => self.write(axis + 1, offset + i * t.strides[axis], out),
+                None => out.push_str("..."),
+            }
+        }
+        out.push(']');
+    }
+}
+
+impl fmt::Display for Tensor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut layout = Layout {
+            tensor: self,
+            summarize: self.data.len() > SUMMARY_THRESHOLD,
+            style: FloatStyle::Integer,
+            width: 0,
+        };
+        let mut values = Vec::new();
+        layout.collect(0, 0, &mut values);
+        layout.style = FloatStyle::choose(&values, f.precision());
+        layout.width = values
+            .iter()
+            .map(|&v| layout.style.format(v).len())
+            .max()
+            .unwrap_or(0);
+
+        let mut body = String::new();
+        layout.write(0, 0, &mut body);
+
+        return write!(f, "{}{}, shape={:?})", PREFIX, body, self.shape);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_rows_are_aligned_on_separate_lines() {
+        let t = Tensor::new(vec![1.0, 2.5, -30.0, f32::NAN], vec![2, 2]).unwrap();
+        assert_eq!(
+            t.to_string(),
+            "Tensor([[  1.0000,   2.5000],\n        [-30.0000,      nan]], shape=[2, 2])"
+        );
+        assert_eq!(
+            format!("{:.1}", t),
+            "Tensor([[  1.0,   2.5],\n        [-30.0,   nan]], shape=[2, 2])"
+        );
+
+        let cube = Tensor::new((0..8).map(|i| i as f32).collect(), vec![2, 2, 2]).unwrap();
+        assert_eq!(
+            cube.to_string(),
+            "Tensor([[[0., 1.],\n         [2., 3.]],\n\n        [[4., 5.],\n         [6., 7.]]], shape=[2, 2, 2])"
+        );
+        assert_eq!(
+            Tensor::full(vec![], 3.0).unwrap().to_string(),
+            "Tensor(3., shape=[])"
+        );
+        let wide = Tensor::new(vec![1e-5, 1.0], vec![2]).unwrap();
+        assert_eq!(
+            wide.to_string(),
+            "Tensor([1.0000e-5,  1.0000e0], shape=[2])"
+        );
+    }
+
+    #[test]
+    fn large_tensors_are_summarized() {
+        let t = Tensor::new((0..2000).map(|i| i as f32).collect(), vec![2, 1000]).unwrap();
+        assert_eq!(
+            t.to_string(),
+            "Tensor([[   0.,    1.,    2., ...,  997.,  998.,  999.],\n        [1000., 1001., 1002., ..., 1997., 1998., 1999.]], shape=[2, 1000])"
+        );
+    }
+}
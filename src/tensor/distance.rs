@@ -0,0 +1,149 @@
+use super::Tensor;
+use crate::{parallel, profile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    Euclidean,
+    Manhattan,
+    Cosine,
+}
+
+const BLOCK_ROWS: usize = 64;
+
+impl Tensor {
+    pub fn cdist(&self, other: &Tensor, metric: DistanceMetric) -> Result<Tensor, String> {
+        if self.shape.len() != 2 || other.shape.len() != 2 || self.shape[1] != other.shape[1] {
+            return Err(format!(
+                "cdist 需要列数相同的二维张量，实际形状为 {:?} 与 {:?}",
+                self.shape, other.shape
+            ));
+        }
+        let (m, n, d) = (self.shape[0], other.shape[0], self.shape[1]);
+        let _scope = profile::scope("cdist", m * n);
+
+        let norms = |t: &Tensor, rows: usize| -> Vec<f32> {
+            (0..rows)
+                .map(|i| {
+                    t.data[i * d..(i + 1) * d]
+                        .iter()
+                        .map(|x| x * x)
+                        .sum::<f32>()
+                        .sqrt()
+                })
+                .collect()
+        };
+        let (norm_a, norm_b) = if metric == DistanceMetric::Cosine {
+            (norms(self, m), norms(other, n))
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        let blocks = m.div_ceil(BLOCK_ROWS);
+        let results = parallel::map_range(blocks, m * n * d, |block| {
+            let rows = block * BLOCK_ROWS..((block + 1) * BLOCK_ROWS).min(m);
+            let mut out = vec![0.0f32; rows.len() * n];
+            for col_start in (0..n).step_by(BLOCK_ROWS) {
+                let cols = col_start..(col_start + BLOCK_ROWS).min(n);
+                for (ri, i) in rows.clone().enumerate() {
+                    let a = &self.data[i * d..(i + 1) * d];
+                    for j in cols.clone() {
+                        let b = &other.data[j * d..(j + 1) * d];
+                        out[ri * n + j] = match metric {
+                            DistanceMetric::Euclidean => a
+                                .iter()
+                                .zip(b)
+                                .map(|(x, y)| (x - y) * (x - y))
+                                .sum::<f32>()
+                                .sqrt(),
+                            DistanceMetric::Manhattan => {
+                                a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+                            }
+                            DistanceMetric::Cosine => {
+                                let denom = norm_a[i] * norm_b[j];
+                                if denom > 0.0 {
+                                    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                                    1.0 - dot / denom
+                                } else {
+                                    1.0
+                                }
+                            }
+                        };
+                    }
+                }
+            }
+            out
+        });
+
+        let mut data = crate::alloc::allocate(m * n);
+        for block in results {
+            data.extend(block);
+        }
+
+        return Ok(self.placed(Tensor::new(data, vec![m, n])?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(data: Vec<f32>, shape: Vec<usize>) -> Tensor {
+        return Tensor::new(data, shape).unwrap();
+    }
+
+    #[test]
+    fn metrics_match_hand_computation() {
+        let a = t(vec![0.0, 0.0, 1.0, 1.0], vec![2, 2]);
+        let b = t(vec![3.0, 4.0, 0.0, 2.0], vec![2, 2]);
+        assert_eq!(
+            a.cdist(&b, DistanceMetric::Euclidean)
+                .unwrap()
+                .data
+                .to_vec(),
+            vec![5.0, 2.0, 13.0f32.sqrt(), 2.0f32.sqrt()]
+        );
+        assert_eq!(
+            a.cdist(&b, DistanceMetric::Manhattan)
+                .unwrap()
+                .data
+                .to_vec(),
+            vec![7.0, 2.0, 5.0, 2.0]
+        );
+        // 零向量与任何向量的余弦距离按 1 处理
+        let cosine = a.cdist(&b, DistanceMetric::Cosine).unwrap();
+        assert_eq!(cosine.data[..2].to_vec(), vec![1.0, 1.0]);
+        assert!((cosine.data[3] - (1.0 - 2.0 / (2.0f32.sqrt() * 2.0))).abs() < 1e-6);
+    }
+
+    #[test]
+    fn blocks_cover_large_inputs() {
+        let a = t((0..130 * 3).map(|i| (i % 7) as f32).collect(), vec![130, 3]);
+        let d = a.cdist(&a, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(d.shape, [130, 130]);
+        for i in 0..130 {
+            assert_eq!(d.data[i * 130 + i], 0.0);
+            assert_eq!(d.data[i * 130 + 129 - i], d.data[(129 - i) * 130 + i]);
+        }
+    }
+
+    #[test]
+    fn shapes_are_checked() {
+        let a = t(vec![1.0, 2.0], vec![1, 2]);
+        assert!(
+            a.cdist(
+                &t(vec![1.0, 2.0, 3.0], vec![1, 3]),
+                DistanceMetric::Euclidean
+            )
+            .is_err()
+        );
+        assert!(
+            a.cdist(&t(vec![1.0, 2.0], vec![2]), DistanceMetric::Euclidean)
+                .is_err()
+        );
+        let empty = t(vec![], vec![0, 2]);
+        assert_eq!(
+            empty.cdist(&a, DistanceMetric::Cosine).unwrap().shape,
+            [0, 1]
+        );
+    }
+}
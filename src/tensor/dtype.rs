@@ -0,0 +1,174 @@
+// 张量在内存中始终是 f32；DType 描述外部数据（字节流、文件）的元素类型，
+// 也作为张量的逻辑类型标签参与二元运算的类型提升
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DType {
+    F16,
+    BF16,
+    F32,
+    F64,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Bool,
+}
+
+impl DType {
+    pub fn size(self) -> usize {
+        return match self {
+            DType::I8 | DType::U8 | DType::Bool => 1,
+            DType::F16 | DType::BF16 | DType::I16 | DType::U16 => 2,
+            DType::F32 | DType::I32 | DType::U32 => 4,
+            DType::F64 | DType::I64 | DType::U64 => 8,
+        };
+    }
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            DType::F16 => "f16",
+            DType::BF16 => "bf16",
+            DType::F32 => "f32",
+            DType::F64 => "f64",
+            DType::I8 => "i8",
+            DType::I16 => "i16",
+            DType::I32 => "i32",
+            DType::I64 => "i64",
+            DType::U8 => "u8",
+            DType::U16 => "u16",
+            DType::U32 => "u32",
+            DType::U64 => "u64",
+            DType::Bool => "bool",
+        };
+    }
+
+    // 整数类型可表示的闭区间；浮点与布尔类型返回 None
+    pub fn integer_range(self) -> Option<(f64, f64)> {
+        return match self {
+            DType::I8 => Some((i8::MIN as f64, i8::MAX as f64)),
+            DType::I16 => Some((i16::MIN as f64, i16::MAX as f64)),
+            DType::I32 => Some((i32::MIN as f64, i32::MAX as f64)),
+            DType::I64 => Some((i64::MIN as f64, i64::MAX as f64)),
+            DType::U8 => Some((0.0, u8::MAX as f64)),
+            DType::U16 => Some((0.0, u16::MAX as f64)),
+            DType::U32 => Some((0.0, u32::MAX as f64)),
+            DType::U64 => Some((0.0, u64::MAX as f64)),
+            _ => None,
+        };
+    }
+
+    pub fn is_integer(self) -> bool {
+        return self.integer_range().is_some();
+    }
+
+    pub fn is_float(self) -> bool {
+        return matches!(self, DType::F16 | DType::BF16 | DType::F32 | DType::F64);
+    }
+
+    // f32 存储可以直接作为该类型的运算结果，不需要再取整或舍入
+    pub(crate) fn is_native(self) -> bool {
+        return matches!(self, DType::F32 | DType::F64);
+    }
+
+    // 开方、指数、带 f32 标量等浮点值运算的结果类型：整数与布尔取 f32
+    pub(crate) fn to_float(self) -> DType {
+        if self.is_float() {
+            return self;
+        }
+
+        return DType::F32;
+    }
+
+    // 二元运算的类型提升表：
+    //   bool 与任意类型 → 另一方
+    //   整数与浮点      → 浮点一方（i32 + f32 → f32）
+    //   浮点与浮点      → 较宽者，f16 与 bf16 互不包含，取 f32
+    //   同符号整数      → 较宽者
+    //   有符号与无符号  → 能同时容纳两者的有符号整数，u64 与任何有符号整数取 f64
+    pub fn promote(self, other: DType) -> DType {
+        if self == other {
+            return self;
+        }
+
+        return match (self, other) {
+            (DType::Bool, x) | (x, DType::Bool) => x,
+            (DType::F16, DType::BF16) | (DType::BF16, DType::F16) => DType::F32,
+            (a, b) if a.is_float() && b.is_float() => {
+                if a.size() >= b.size() {
+                    a
+                } else {
+                    b
+                }
+            }
+            (a, _) if a.is_float() => a,
+            (_, b) if b.is_float() => b,
+            (a, b) if a.is_signed() == b.is_signed() => {
+                if a.size() >= b.size() {
+                    a
+                } else {
+                    b
+                }
+            }
+            (a, b) => {
+                let (signed, unsigned) = if a.is_signed() { (a, b) } else { (b, a) };
+                if signed.size() > unsigned.size() {
+                    signed
+                } else {
+                    match unsigned {
+                        DType::U8 => DType::I16,
+                        DType::U16 => DType::I32,
+                        DType::U32 => DType::I64,
+                        _ => DType::F64,
+                    }
+                }
+            }
+        };
+    }
+
+    fn is_signed(self) -> bool {
+        return matches!(self, DType::I8 | DType::I16 | DType::I32 | DType::I64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn promotion_table() {
+        assert_eq!(DType::I32.promote(DType::F32), DType::F32);
+        assert_eq!(DType::F32.promote(DType::F64), DType::F64);
+        assert_eq!(DType::I64.promote(DType::F16), DType::F16);
+        assert_eq!(DType::F16.promote(DType::BF16), DType::F32);
+        assert_eq!(DType::Bool.promote(DType::U8), DType::U8);
+        assert_eq!(DType::I8.promote(DType::I32), DType::I32);
+        assert_eq!(DType::U8.promote(DType::I8), DType::I16);
+        assert_eq!(DType::U8.promote(DType::I16), DType::I16);
+        assert_eq!(DType::I32.promote(DType::U32), DType::I64);
+        assert_eq!(DType::U64.promote(DType::I64), DType::F64);
+        // 提升表是对称的
+        let all = [
+            DType::F16,
+            DType::BF16,
+            DType::F32,
+            DType::F64,
+            DType::I8,
+            DType::I16,
+            DType::I32,
+            DType::I64,
+            DType::U8,
+            DType::U16,
+            DType::U32,
+            DType::U64,
+            DType::Bool,
+        ];
+        for &a in &all {
+            for &b in &all {
+                assert_eq!(a.promote(b), b.promote(a));
+            }
+        }
+    }
+}
@@ -0,0 +1,575 @@
+use super::Tensor;
+use crate::lazy::{BinaryOp, UnaryOp};
+use crate::{check, parallel, profile};
+
+// np.remainder 语义：结果与除数同号
+fn remainder(a: f32, b: f32) -> f32 {
+    let r = a % b;
+    if r != 0.0 && (r < 0.0) != (b < 0.0) {
+        return r + b;
+    }
+
+    return r;
+}
+
+fn log_add_exp(a: f32, b: f32) -> f32 {
+    let max = a.max(b);
+    if max.is_infinite() {
+        return max;
+    }
+
+    return max + ((a - max).exp() + (b - max).exp()).ln();
+}
+
+impl Tensor {
+    pub fn add(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.binary_op(BinaryOp::Add, other);
+    }
+
+    pub fn sub(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.binary_op(BinaryOp::Sub, other);
+    }
+
+    pub fn mul(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.binary_op(BinaryOp::Mul, other);
+    }
+
+    // 真除法：整数或布尔操作数的商为 f32
+    pub fn div(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.binary_op(BinaryOp::Div, other);
+    }
+
+    // 幂可能带小数（负指数、分数指数），整数或布尔操作数的结果为 f32
+    pub fn pow(&self, exponent: &Tensor) -> Result<Tensor, String> {
+        return self.zip_float("pow", exponent, f32::powf);
+    }
+
+    // 整数操作数的余数仍是整数，除数为 0 时按饱和规则得到 0
+    pub fn rem(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.zip_with("rem", other, remainder);
+    }
+
+    // C 的 fmod 语义：结果与被除数同号
+    pub fn fmod(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.zip_with("fmod", other, |a, b| a % b);
+    }
+
+    pub fn maximum(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.binary_op(BinaryOp::Max, other);
+    }
+
+    pub fn minimum(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.binary_op(BinaryOp::Min, other);
+    }
+
+    // 标量按 f32 参与类型提升，整数或布尔张量的结果为 f32
+    pub fn add_scalar(&self, value: f32) -> Result<Tensor, String> {
+        return self.map_float("add_scalar", |a| a + value);
+    }
+
+    pub fn mul_scalar(&self, value: f32) -> Result<Tensor, String> {
+        return self.map_float("mul_scalar", |a| a * value);
+    }
+
+    pub fn pow_scalar(&self, exponent: f32) -> Result<Tensor, String> {
+        return self.map_float("pow_scalar", |a| a.powf(exponent));
+    }
+
+    pub fn powi(&self, exponent: i32) -> Result<Tensor, String> {
+        return self.map("powi", |a| a.powi(exponent));
+    }
+
+    pub fn neg(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Neg);
+    }
+
+    pub fn abs(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Abs);
+    }
+
+    pub fn sqrt(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Sqrt);
+    }
+
+    pub fn exp(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Exp);
+    }
+
+    pub fn log(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Log);
+    }
+
+    pub fn relu(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Relu);
+    }
+
+    pub fn sigmoid(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Sigmoid);
+    }
+
+    pub fn tanh(&self) -> Result<Tensor, String> {
+        return self.unary_op(UnaryOp::Tanh);
+    }
+
+    pub fn trunc(&self) -> Result<Tensor, String> {
+        return self.map("trunc", f32::trunc);
+    }
+
+    // 与 NumPy 一致：恰在中点时取偶数
+    pub fn round_half_even(&self) -> Result<Tensor, String> {
+        return self.map("round_half_even", f32::round_ties_even);
+    }
+
+    // 与 np.round(x, decimals) 相同：先缩放再按偶数规则取整，decimals 可为负
+    pub fn round_decimals(&self, decimals: i32) -> Result<Tensor, String> {
+        let scale = 10f64.powi(decimals);
+
+        return self.map("round_decimals", |a| {
+            ((a as f64 * scale).round_ties_even() / scale) as f32
+        });
+    }
+
+    pub fn clamp(&self, min: f32, max: f32) -> Result<Tensor, String> {
+        if min.is_nan() || max.is_nan() {
+            return Err(format!(
+                "clamp 的边界不能是 NaN（下界 {}，上界 {}）",
+                min, max
+            ));
+        }
+        if min > max {
+            return Err(format!("clamp 下界 {} 大于上界 {}", min, max));
+        }
+
+        return self.map_float("clamp", |a| a.clamp(min, max));
+    }
+
+    pub fn logaddexp(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.zip_float("logaddexp", other, log_add_exp);
+    }
+
+    pub fn fma(a: &Tensor, b: &Tensor, c: &Tensor) -> Result<Tensor, String> {
+        if a.shape != b.shape || a.shape != c.shape {
+            return Err(format!(
+                "fma 需要形状一致的三个张量，实际为 {:?}、{:?} 与 {:?}",
+                a.shape, b.shape, c.shape
+            ));
+        }
+        let _scope = profile::scope("fma", a.data.len());
+        let mut data = crate::alloc::allocate(a.data.len());
+        data.resize(a.data.len(), 0.0);
+        parallel::fill_chunks(&mut data, |start, out| {
+            let end = start + out.len();
+            let lanes = a.data[start..end]
+                .iter()
+                .zip(&b.data[start..end])
+                .zip(&c.data[start..end]);
+            for (o, ((&x, &y), &z)) in out.iter_mut().zip(lanes) {
+                *o = x.mul_add(y, z);
+            }
+        });
+
+        let out = a.placed(Tensor::new(data, a.shape.clone())?);
+        check::inspect("fma", &[&a.shape, &b.shape, &c.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 与其他原地运算相同地检查 dtype、饱和并检查结果，只是 x 不做广播
+    pub fn axpy_(&mut self, alpha: f32, x: &Tensor) -> Result<(), String> {
+        if self.shape != x.shape {
+            return Err(format!(
+                "axpy_ 形状不匹配：{:?} 与 {:?}",
+                self.shape, x.shape
+            ));
+        }
+
+        return self.zip_with_("axpy_", x, |o, v| alpha.mul_add(v, o));
+    }
+
+    // 没有对应具名运算时的通用原地入口
+    pub fn apply_<F>(&mut self, f: F) -> Result<(), String>
+    where
+        F: Fn(f32) -> f32 + Sync,
+    {
+        return self.map_("apply_", f);
+    }
+
+    pub fn add_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("add_", other, |a, b| a + b);
+    }
+
+    pub fn sub_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("sub_", other, |a, b| a - b);
+    }
+
+    pub fn mul_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("mul_", other, |a, b| a * b);
+    }
+
+    // 浮点值运算（div_、pow_、开方、指数、带标量的运算等）不能原地写回整数或布尔张量
+    pub fn div_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_float_("div_", other, |a, b| a / b);
+    }
+
+    pub fn pow_(&mut self, exponent: &Tensor) -> Result<(), String> {
+        return self.zip_float_("pow_", exponent, f32::powf);
+    }
+
+    pub fn rem_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("rem_", other, remainder);
+    }
+
+    pub fn fmod_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("fmod_", other, |a, b| a % b);
+    }
+
+    pub fn maximum_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("maximum_", other, f32::max);
+    }
+
+    pub fn minimum_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("minimum_", other, f32::min);
+    }
+
+    pub fn logaddexp_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_float_("logaddexp_", other, log_add_exp);
+    }
+
+    pub fn add_scalar_(&mut self, value: f32) -> Result<(), String> {
+        return self.map_float_("add_scalar_", |a| a + value);
+    }
+
+    pub fn mul_scalar_(&mut self, value: f32) -> Result<(), String> {
+        return self.map_float_("mul_scalar_", |a| a * value);
+    }
+
+    pub fn pow_scalar_(&mut self, exponent: f32) -> Result<(), String> {
+        return self.map_float_("pow_scalar_", |a| a.powf(exponent));
+    }
+
+    pub fn powi_(&mut self, exponent: i32) -> Result<(), String> {
+        return self.map_("powi_", |a| a.powi(exponent));
+    }
+
+    pub fn neg_(&mut self) -> Result<(), String> {
+        return self.map_("neg_", |a| -a);
+    }
+
+    pub fn abs_(&mut self) -> Result<(), String> {
+        return self.map_("abs_", f32::abs);
+    }
+
+    pub fn sqrt_(&mut self) -> Result<(), String> {
+        return self.map_float_("sqrt_", f32::sqrt);
+    }
+
+    pub fn exp_(&mut self) -> Result<(), String> {
+        return self.map_float_("exp_", f32::exp);
+    }
+
+    pub fn log_(&mut self) -> Result<(), String> {
+        return self.map_float_("log_", f32::ln);
+    }
+
+    pub fn relu_(&mut self) -> Result<(), String> {
+        return self.map_("relu_", |a| a.max(0.0));
+    }
+
+    pub fn sigmoid_(&mut self) -> Result<(), String> {
+        return self.map_float_("sigmoid_", |a| 1.0 / (1.0 + (-a).exp()));
+    }
+
+    pub fn tanh_(&mut self) -> Result<(), String> {
+        return self.map_float_("tanh_", f32::tanh);
+    }
+
+    pub fn trunc_(&mut self) -> Result<(), String> {
+        return self.map_("trunc_", f32::trunc);
+    }
+
+    pub fn round_half_even_(&mut self) -> Result<(), String> {
+        return self.map_("round_half_even_", f32::round_ties_even);
+    }
+
+    pub fn round_decimals_(&mut self, decimals: i32) -> Result<(), String> {
+        let scale = 10f64.powi(decimals);
+
+        return self.map_("round_decimals_", |a| {
+            ((a as f64 * scale).round_ties_even() / scale) as f32
+        });
+    }
+
+    pub fn clamp_(&mut self, min: f32, max: f32) -> Result<(), String> {
+        if min.is_nan() || max.is_nan() {
+            return Err(format!(
+                "clamp_ 的边界不能是 NaN（下界 {}，上界 {}）",
+                min, max
+            ));
+        }
+        if min > max {
+            return Err(format!("clamp_ 下界 {} 大于上界 {}", min, max));
+        }
+
+        return self.map_float_("clamp_", |a| a.clamp(min, max));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DType;
+
+    fn t(values: &[f32]) -> Tensor {
+        return Tensor::new(values.to_vec(), vec![values.len()]).unwrap();
+    }
+
+    #[test]
+    fn clamp_rejects_nan_bounds() {
+        let mut x = t(&[-2.0, 0.5, 3.0]);
+        assert!(x.clamp(f32::NAN, 1.0).is_err());
+        assert!(x.clamp(0.0, f32::NAN).is_err());
+        assert!(x.clamp_(f32::NAN, f32::NAN).is_err());
+        assert_eq!(x.data.to_vec(), vec![-2.0, 0.5, 3.0]);
+        assert_eq!(
+            x.clamp(-1.0, 1.0).unwrap().data.to_vec(),
+            vec![-1.0, 0.5, 1.0]
+        );
+    }
+
+    #[test]
+    fn logaddexp_handles_infinities() {
+        let a = t(&[f32::NEG_INFINITY, f32::INFINITY, 0.0]);
+        let b = t(&[f32::NEG_INFINITY, 1.0, 0.0]);
+        let y = a.logaddexp(&b).unwrap();
+        assert_eq!(y.data[..2], [f32::NEG_INFINITY, f32::INFINITY]);
+        assert!((y.data[2] - 2f32.ln()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn binary_ops_promote_mixed_dtypes() {
+        let i = t(&[1.0, 2.0, 3.0]).cast_checked(DType::I32).unwrap();
+        let f = t(&[0.5, 0.5, 0.5]);
+        let sum = i.add(&f).unwrap();
+        assert_eq!(sum.dtype(), DType::F32);
+        assert_eq!(sum.data.to_vec(), vec![1.5, 2.5, 3.5]);
+        assert_eq!(i.mul(&i).unwrap().dtype(), DType::I32);
+        assert_eq!(i.div(&i).unwrap().dtype(), DType::F32);
+        let wide = f.cast_checked(DType::F64).unwrap();
+        assert_eq!(f.sub(&wide).unwrap().dtype(), DType::F64);
+        let u = t(&[1.0]).cast_checked(DType::U8).unwrap();
+        assert_eq!(
+            u.add(&t(&[1.0]).cast_checked(DType::I8).unwrap())
+                .unwrap()
+                .dtype(),
+            DType::I16
+        );
+
+        // 严格模式只拒绝混合 dtype，同类型运算不受影响；模式直接传入，不改动全局配置
+        assert!(Tensor::promote_dtypes("add", DType::I32, DType::F32, true).is_err());
+        assert_eq!(
+            Tensor::promote_dtypes("add", DType::I32, DType::I32, true),
+            Ok(DType::I32)
+        );
+        assert_eq!(
+            Tensor::promote_dtypes("add", DType::I32, DType::F32, false),
+            Ok(DType::F32)
+        );
+    }
+
+    // 整数标签的结果值总在该类型的取值范围内，带小数的运算提升为 f32
+    #[test]
+    fn integer_results_fit_their_dtype() {
+        let a = t(&[100.0, -100.0, 7.0, 3.0])
+            .cast_checked(DType::I8)
+            .unwrap();
+        let b = t(&[100.0, -100.0, 2.0, 0.0])
+            .cast_checked(DType::I8)
+            .unwrap();
+        let sum = a.add(&b).unwrap();
+        assert_eq!(sum.dtype(), DType::I8);
+        assert_eq!(sum.data.to_vec(), vec![127.0, -128.0, 9.0, 3.0]);
+        assert_eq!(a.rem(&b).unwrap().data.to_vec(), vec![0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(a.fmod(&b).unwrap().dtype(), DType::I8);
+        let u = t(&[1.0, 5.0]).cast_checked(DType::U8).unwrap();
+        assert_eq!(u.neg().unwrap().data.to_vec(), vec![0.0, 0.0]);
+        assert_eq!(
+            u.sub(&u.mul(&u).unwrap()).unwrap().data.to_vec(),
+            vec![0.0, 0.0]
+        );
+
+        for y in [
+            a.pow(&t(&[-1.0])).unwrap(),
+            a.logaddexp(&b).unwrap(),
+            a.div(&b).unwrap(),
+            a.sqrt().unwrap(),
+            a.add_scalar(0.5).unwrap(),
+        ] {
+            assert_eq!(y.dtype(), DType::F32);
+        }
+        assert_eq!(a.pow(&t(&[-1.0])).unwrap().data[3], 1.0 / 3.0);
+        assert_eq!(a.add_scalar(0.5).unwrap().data[2], 7.5);
+        let mut x = a.clone();
+        assert!(x.pow_(&b).is_err());
+        assert!(x.sqrt_().is_err());
+        assert!(x.add_scalar_(1.0).is_err());
+        assert_eq!(x, a);
+
+        let f = t(&[1.0, 2.0]).cast_checked(DType::F16).unwrap();
+        assert_eq!(f.pow(&f).unwrap().dtype(), DType::F16);
+        assert_eq!(
+            f.mul_scalar(1e5).unwrap().data.to_vec(),
+            vec![65504.0, 65504.0]
+        );
+    }
+
+    // 非原地与原地版本对同一输入得到相同的 dtype 与数值
+    #[test]
+    fn unary_ops_keep_the_dtype_in_both_forms() {
+        let x = t(&[-3.0, 0.0, 2.0]).cast_checked(DType::I16).unwrap();
+        let unary: [(Unary, UnaryInPlace); 4] = [
+            (Tensor::neg, Tensor::neg_),
+            (Tensor::abs, Tensor::abs_),
+            (Tensor::relu, Tensor::relu_),
+            (Tensor::logical_not, Tensor::logical_not_),
+        ];
+        for (op, op_) in unary {
+            let mut y = x.clone();
+            op_(&mut y).unwrap();
+            let out = op(&x).unwrap();
+            assert_eq!(out.dtype(), DType::I16);
+            assert_eq!(y.dtype(), DType::I16);
+            assert_eq!(y, out);
+        }
+        let mut y = x.clone();
+        y.apply_(|a| a * 0.5).unwrap();
+        assert_eq!(y.data.to_vec(), vec![-1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn fused_kernels_match_two_pass_results() {
+        let a = t(&[1.0, 2.0, -3.0]);
+        let b = t(&[4.0, 0.5, 2.0]);
+        let c = t(&[0.5, 1.0, 1.0]);
+        let fused = Tensor::fma(&a, &b, &c).unwrap();
+        assert_eq!(fused.data.to_vec(), vec![4.5, 2.0, -5.0]);
+        assert!(Tensor::fma(&a, &b, &t(&[1.0])).is_err());
+
+        let mut y = c.clone();
+        y.axpy_(-2.0, &a).unwrap();
+        assert_eq!(y.data.to_vec(), vec![-1.5, -3.0, 7.0]);
+        assert!(y.axpy_(1.0, &t(&[1.0, 2.0])).is_err());
+
+        // 整数张量上的结果与其他原地运算一样饱和，非有限结果在 Error 模式下报错
+        let mut small = t(&[100.0, -100.0]).cast_checked(crate::DType::I8).unwrap();
+        small.axpy_(2.0, &small.clone()).unwrap();
+        assert_eq!(small.data.to_vec(), vec![127.0, -128.0]);
+        let mut z = t(&[1.0, 2.0, 3.0]);
+        let overflow = crate::check::with_mode(crate::check::CheckMode::Error, || {
+            return z.axpy_(f32::MAX, &t(&[f32::MAX, 1.0, 1.0]));
+        });
+        assert!(overflow.is_err());
+    }
+
+    #[test]
+    fn rounding_matches_numpy_conventions() {
+        let x = t(&[0.5, 1.5, 2.5, -0.5, -1.7, 2.675]);
+        assert_eq!(
+            x.round_half_even().unwrap().data.to_vec(),
+            vec![0.0, 2.0, 2.0, -0.0, -2.0, 3.0]
+        );
+        assert_eq!(
+            x.trunc().unwrap().data.to_vec(),
+            vec![0.0, 1.0, 2.0, -0.0, -1.0, 2.0]
+        );
+        let y = t(&[1.25, 1.35, -0.125, 1234.5]);
+        assert_eq!(
+            y.round_decimals(1).unwrap().data.to_vec(),
+            vec![1.2, 1.4, -0.1, 1234.5]
+        );
+        assert_eq!(
+            y.round_decimals(-2).unwrap().data.to_vec(),
+            vec![0.0, 0.0, -0.0, 1200.0]
+        );
+    }
+
+    #[test]
+    fn power_and_modulo_follow_numpy_sign_rules() {
+        let base = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        let exponent = t(&[2.0, 0.5]);
+        let y = base.pow(&exponent).unwrap();
+        assert_eq!(y.data[0], 1.0);
+        assert!((y.data[1] - 2f32.sqrt()).abs() < 1e-6);
+        assert_eq!(y.data[2], 9.0);
+        assert_eq!(
+            base.powi(3).unwrap().data.to_vec(),
+            vec![1.0, 8.0, 27.0, 64.0]
+        );
+        assert_eq!(base.pow_scalar(2.0).unwrap(), base.powi(2).unwrap());
+
+        let a = t(&[7.0, -7.0, 7.0, -7.0, 6.0]);
+        let b = t(&[3.0, 3.0, -3.0, -3.0, -3.0]);
+        assert_eq!(
+            a.rem(&b).unwrap().data.to_vec(),
+            vec![1.0, 2.0, -2.0, -1.0, 0.0]
+        );
+        assert_eq!(
+            a.fmod(&b).unwrap().data.to_vec(),
+            vec![1.0, -1.0, 1.0, -1.0, 0.0]
+        );
+        assert!(a.rem(&t(&[0.0])).unwrap().data[0].is_nan());
+    }
+
+    type Binary = fn(&Tensor, &Tensor) -> Result<Tensor, String>;
+    type BinaryInPlace = fn(&mut Tensor, &Tensor) -> Result<(), String>;
+    type Unary = fn(&Tensor) -> Result<Tensor, String>;
+    type UnaryInPlace = fn(&mut Tensor) -> Result<(), String>;
+
+    #[test]
+    fn in_place_ops_match_out_of_place() {
+        let x = Tensor::new(vec![-2.0, -0.5, 0.0, 1.5, 3.0, 4.0], vec![2, 3]).unwrap();
+        let row = t(&[1.0, -2.0, 0.5]);
+        let binary: [(Binary, BinaryInPlace); 5] = [
+            (Tensor::add, Tensor::add_),
+            (Tensor::sub, Tensor::sub_),
+            (Tensor::div, Tensor::div_),
+            (Tensor::rem, Tensor::rem_),
+            (Tensor::maximum, Tensor::maximum_),
+        ];
+        for (op, op_) in binary {
+            let mut y = x.clone();
+            op_(&mut y, &row).unwrap();
+            assert_eq!(y, op(&x, &row).unwrap());
+        }
+        let unary: [(Unary, UnaryInPlace); 4] = [
+            (Tensor::neg, Tensor::neg_),
+            (Tensor::relu, Tensor::relu_),
+            (Tensor::sigmoid, Tensor::sigmoid_),
+            (Tensor::round_half_even, Tensor::round_half_even_),
+        ];
+        for (op, op_) in unary {
+            let mut y = x.clone();
+            op_(&mut y).unwrap();
+            assert_eq!(y, op(&x).unwrap());
+        }
+
+        let mut y = x.clone();
+        y.clamp_(-1.0, 1.0).unwrap();
+        assert_eq!(y, x.clamp(-1.0, 1.0).unwrap());
+        y.apply_(|a| a * 10.0).unwrap();
+        assert_eq!(y, x.clamp(-1.0, 1.0).unwrap().mul_scalar(10.0).unwrap());
+        // 原地运算要求写回的形状与 dtype 不变，失败时 self 保持原样
+        assert!(row.clone().add_(&x).is_err());
+        assert!(y.clamp_(1.0, -1.0).is_err());
+        let mut i = x.cast_checked(DType::I32).unwrap();
+        let before = i.clone();
+        assert!(i.add_(&x).is_err());
+        assert!(i.div_(&i.clone()).is_err());
+        assert_eq!(i, before);
+        i.mul_(&before).unwrap();
+        assert_eq!(i.dtype(), DType::I32);
+
+        y.freeze();
+        assert!(y.relu_().is_err());
+        assert!(y.add_(&row).is_err());
+    }
+}
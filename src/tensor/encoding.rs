@@ -0,0 +1,150 @@
+use std::cmp::Ordering;
+
+use super::Tensor;
+
+impl Tensor {
+    fn unique_sorted(values: impl Iterator<Item = f32>) -> Vec<f32> {
+        let mut classes: Vec<f32> = values.collect();
+        classes.sort_by(|a, b| a.total_cmp(b));
+        classes.dedup_by(|a, b| a.total_cmp(b) == Ordering::Equal);
+
+        return classes;
+    }
+
+    fn code_of(classes: &[f32], value: f32) -> Result<f32, String> {
+        return match classes.binary_search_by(|c| c.total_cmp(&value)) {
+            Ok(code) => Ok(code as f32),
+            Err(_) => Err(format!("值 {} 不在已知类别中", value)),
+        };
+    }
+
+    fn class_of(classes: &[f32], code: f32) -> Result<f32, String> {
+        if code < 0.0 || code.fract() != 0.0 || code as usize >= classes.len() {
+            return Err(format!(
+                "编码 {} 不是 [0, {}) 内的整数",
+                code,
+                classes.len()
+            ));
+        }
+
+        return Ok(classes[code as usize]);
+    }
+
+    pub fn encode_labels(values: &[f32]) -> Result<(Tensor, Vec<f32>), String> {
+        let classes = Self::unique_sorted(values.iter().cloned());
+        let codes = values
+            .iter()
+            .map(|&v| Self::code_of(&classes, v))
+            .collect::<Result<Vec<f32>, String>>()?;
+
+        return Ok((Tensor::new(codes, vec![values.len()])?, classes));
+    }
+
+    pub fn decode_labels(codes: &Tensor, classes: &[f32]) -> Result<Tensor, String> {
+        let data = codes
+            .data
+            .iter()
+            .map(|&c| Self::class_of(classes, c))
+            .collect::<Result<Vec<f32>, String>>()?;
+
+        return Ok(codes.placed(Tensor::new(data, codes.shape.clone())?));
+    }
+
+    pub fn encode_columns(&self) -> Result<(Tensor, Vec<Vec<f32>>), String> {
+        if self.shape.len() != 2 {
+            return Err(format!(
+                "encode_columns 需要二维特征矩阵，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+
+        let mut data = self.data.to_vec();
+        let mut all_classes = Vec::with_capacity(cols);
+        for j in 0..cols {
+            let classes = Self::unique_sorted((0..rows).map(|i| self.data[i * cols + j]));
+            for i in 0..rows {
+                data[i * cols + j] = Self::code_of(&classes, self.data[i * cols + j])?;
+            }
+            all_classes.push(classes);
+        }
+
+        return Ok((
+            self.placed(Tensor::new(data, self.shape.clone())?),
+            all_classes,
+        ));
+    }
+
+    pub fn decode_columns(&self, classes: &[Vec<f32>]) -> Result<Tensor, String> {
+        if self.shape.len() != 2 || self.shape[1] != classes.len() {
+            return Err(format!(
+                "编码矩阵形状 {:?} 与类别表列数 {} 不匹配",
+                self.shape,
+                classes.len()
+            ));
+        }
+        let cols = self.shape[1];
+
+        let mut data = self.data.to_vec();
+        for (k, v) in data.iter_mut().enumerate() {
+            *v = Self::class_of(&classes[k % cols], *v)?;
+        }
+
+        return Ok(self.placed(Tensor::new(data, self.shape.clone())?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_round_trip() {
+        let values = [3.0, -1.0, 3.0, 7.5, -1.0];
+        let (codes, classes) = Tensor::encode_labels(&values).unwrap();
+        assert_eq!(classes, vec![-1.0, 3.0, 7.5]);
+        assert_eq!(codes.data.to_vec(), vec![1.0, 0.0, 1.0, 2.0, 0.0]);
+        assert_eq!(
+            Tensor::decode_labels(&codes, &classes)
+                .unwrap()
+                .data
+                .to_vec(),
+            values
+        );
+
+        let (empty, none) = Tensor::encode_labels(&[]).unwrap();
+        assert!(empty.data.is_empty() && none.is_empty());
+    }
+
+    #[test]
+    fn nan_is_its_own_class() {
+        let (codes, classes) = Tensor::encode_labels(&[f32::NAN, 1.0, f32::NAN]).unwrap();
+        assert_eq!(codes.data.to_vec(), vec![1.0, 0.0, 1.0]);
+        assert!(classes[1].is_nan());
+        let back = Tensor::decode_labels(&codes, &classes).unwrap();
+        assert!(back.data[0].is_nan() && back.data[1] == 1.0);
+    }
+
+    #[test]
+    fn columns_round_trip() {
+        let x = Tensor::new(vec![5.0, 1.0, 2.0, 1.0, 5.0, 0.0], vec![3, 2]).unwrap();
+        let (codes, classes) = x.encode_columns().unwrap();
+        assert_eq!(codes.data.to_vec(), vec![1.0, 1.0, 0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(codes.decode_columns(&classes).unwrap(), x);
+        assert!(codes.decode_columns(&classes[..1]).is_err());
+        assert!(Tensor::zeros(vec![2]).unwrap().encode_columns().is_err());
+    }
+
+    #[test]
+    fn invalid_codes_are_rejected() {
+        let classes = [1.0, 2.0];
+        for bad in [2.0, -1.0, 0.5, f32::NAN] {
+            let codes = Tensor::new(vec![bad], vec![1]).unwrap();
+            assert!(
+                Tensor::decode_labels(&codes, &classes).is_err(),
+                "{} 被接受",
+                bad
+            );
+        }
+    }
+}
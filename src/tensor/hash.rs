@@ -0,0 +1,65 @@
+use super::Tensor;
+use crate::profile;
+
+const FNV64_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV64_PRIME: u64 = 0x0000_0100_0000_01b3;
+const FNV128_OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+const FNV128_PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+impl Tensor {
+    // 摘要覆盖元素类型标记、形状与按位的小端数据，因此 -0.0 与 0.0、不同的 NaN 载荷互不相同
+    fn hashed_bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        let header = b"f32"
+            .iter()
+            .copied()
+            .chain((self.shape.len() as u64).to_le_bytes());
+        let dims = self.shape.iter().flat_map(|&d| (d as u64).to_le_bytes());
+        let data = self.data.iter().flat_map(|x| x.to_bits().to_le_bytes());
+
+        return header.chain(dims).chain(data);
+    }
+
+    // FNV-1a，跨平台与跨版本稳定，适合缓存键与数据集版本号；不具备密码学强度
+    pub fn content_hash(&self) -> u64 {
+        let _scope = profile::scope("content_hash", self.data.len());
+
+        return self.hashed_bytes().fold(FNV64_OFFSET, |h, b| {
+            (h ^ b as u64).wrapping_mul(FNV64_PRIME)
+        });
+    }
+
+    pub fn content_hash128(&self) -> u128 {
+        let _scope = profile::scope("content_hash128", self.data.len());
+
+        return self.hashed_bytes().fold(FNV128_OFFSET, |h, b| {
+            (h ^ b as u128).wrapping_mul(FNV128_PRIME)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_stable_and_sensitive_to_shape_and_bits() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        assert_eq!(x.content_hash(), x.clone().content_hash());
+        assert_eq!(x.content_hash128(), x.clone().content_hash128());
+        // 固定的参考值保证摘要不会在版本间悄悄变化
+        assert_eq!(x.content_hash(), 0x27ca_131e_d593_a0b7);
+
+        assert_ne!(
+            x.content_hash(),
+            x.reshaped(vec![4]).unwrap().content_hash()
+        );
+        assert_ne!(
+            x.content_hash128(),
+            x.reshaped(vec![4, 1]).unwrap().content_hash128()
+        );
+        let zero = Tensor::new(vec![0.0], vec![1]).unwrap();
+        let negative_zero = Tensor::new(vec![-0.0], vec![1]).unwrap();
+        assert_eq!(zero, negative_zero);
+        assert_ne!(zero.content_hash(), negative_zero.content_hash());
+    }
+}
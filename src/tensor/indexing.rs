@@ -0,0 +1,204 @@
+use super::Tensor;
+use crate::profile;
+
+impl Tensor {
+    pub(crate) fn to_indices(indices: &Tensor, bound: usize) -> Result<Vec<usize>, String> {
+        let mut out = Vec::with_capacity(indices.data.len());
+        for &v in indices.data.iter() {
+            if v < 0.0 || v.fract() != 0.0 || v as usize >= bound {
+                return Err(format!("索引 {} 不是 [0, {}) 内的整数", v, bound));
+            }
+            out.push(v as usize);
+        }
+
+        return Ok(out);
+    }
+
+    pub fn index_select(&self, axis: usize, indices: &Tensor) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+        if indices.shape.len() != 1 {
+            return Err(format!(
+                "index_select 需要一维索引张量，实际形状为 {:?}",
+                indices.shape
+            ));
+        }
+        let picks = Self::to_indices(indices, self.shape[axis])?;
+
+        let outer: usize = self.shape[..axis].iter().product();
+        let len = self.shape[axis];
+        let inner: usize = self.shape[axis + 1..].iter().product();
+        let _scope = profile::scope("index_select", outer * picks.len() * inner);
+
+        let mut data = crate::alloc::allocate(outer * picks.len() * inner);
+        for o in 0..outer {
+            for &p in &picks {
+                let start = (o * len + p) * inner;
+                data.extend_from_slice(&self.data[start..start + inner]);
+            }
+        }
+
+        let mut shape = self.shape.clone();
+        shape[axis] = picks.len();
+
+        return Ok(self
+            .placed(Tensor::new(data, shape)?)
+            .with_dtype(self.dtype));
+    }
+
+    pub fn embedding(
+        weight: &Tensor,
+        indices: &Tensor,
+        padding_idx: Option<usize>,
+    ) -> Result<Tensor, String> {
+        if weight.shape.len() != 2 {
+            return Err(format!(
+                "embedding 权重应为 [V, D]，实际形状为 {:?}",
+                weight.shape
+            ));
+        }
+        let (vocab, dim) = (weight.shape[0], weight.shape[1]);
+        if let Some(p) = padding_idx
+            && p >= vocab
+        {
+            return Err(format!("padding_idx {} 超出词表大小 {}", p, vocab));
+        }
+        let picks = Self::to_indices(indices, vocab)?;
+        let _scope = profile::scope("embedding", picks.len() * dim);
+
+        let mut data = crate::alloc::allocate(picks.len() * dim);
+        for &p in &picks {
+            data.extend_from_slice(&weight.data[p * dim..(p + 1) * dim]);
+        }
+        let mut shape = indices.shape.to_vec();
+        shape.push(dim);
+
+        return Ok(weight
+            .placed(Tensor::new(data, shape)?)
+            .with_dtype(weight.dtype));
+    }
+
+    // 把输出梯度按索引散射累加回 [V, D]；padding_idx 对应的行不接收梯度
+    pub fn embedding_backward(
+        grad: &Tensor,
+        indices: &Tensor,
+        num_embeddings: usize,
+        padding_idx: Option<usize>,
+    ) -> Result<Tensor, String> {
+        let dim = grad.shape.last().copied().unwrap_or(0);
+        if grad.shape.len() != indices.shape.len() + 1
+            || grad.shape[..indices.shape.len()] != indices.shape
+        {
+            return Err(format!(
+                "embedding 梯度形状 {:?} 与索引形状 {:?} 不匹配",
+                grad.shape, indices.shape
+            ));
+        }
+        let picks = Self::to_indices(indices, num_embeddings)?;
+        let _scope = profile::scope("embedding_backward", picks.len() * dim);
+
+        let mut out = grad.placed(Tensor::zeros(vec![num_embeddings, dim])?);
+        for (row, &p) in grad.data.chunks(dim.max(1)).zip(&picks) {
+            if Some(p) == padding_idx {
+                continue;
+            }
+            for (o, &g) in out.data[p * dim..(p + 1) * dim].iter_mut().zip(row) {
+                *o += g;
+            }
+        }
+
+        return Ok(out);
+    }
+
+    // 前面各轴长度都为 1 时（例如沿轴 0 截取）结果在内存中是连续的一段，返回与 self
+    // 共享缓冲区的视图，不复制；写入视图时按写时复制拷出自己的一份，不会改写 self。
+    // 其他情况下每个外层切片各复制一段
+    pub fn narrow(&self, axis: usize, start: usize, len: usize) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+        if start + len > self.shape[axis] {
+            return Err(format!(
+                "区间 [{}, {}) 超出轴 {} 的长度 {}",
+                start,
+                start + len,
+                axis,
+                self.shape[axis]
+            ));
+        }
+
+        let outer: usize = self.shape[..axis].iter().product();
+        let inner: usize = self.shape[axis + 1..].iter().product();
+        let mut shape = self.shape.clone();
+        shape[axis] = len;
+        if outer == 1 {
+            let view = self.data.view(start * inner, len * inner);
+            let out = Tensor::from_storage(view, shape)?;
+            return Ok(self.placed(out).with_dtype(self.dtype));
+        }
+        let _scope = profile::scope("narrow", outer * len * inner);
+
+        let mut data = crate::alloc::allocate(outer * len * inner);
+        for o in 0..outer {
+            let base = (o * self.shape[axis] + start) * inner;
+            data.extend_from_slice(&self.data[base..base + len * inner]);
+        }
+
+        return Ok(self
+            .placed(Tensor::new(data, shape)?)
+            .with_dtype(self.dtype));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(data: Vec<f32>, shape: Vec<usize>) -> Tensor {
+        return Tensor::new(data, shape).unwrap();
+    }
+
+    #[test]
+    fn to_indices_rejects_non_integers() {
+        assert_eq!(
+            Tensor::to_indices(&t(vec![0.0, 2.0, -0.0], vec![3]), 3),
+            Ok(vec![0, 2, 0])
+        );
+        for bad in [-1.0, 0.5, 3.0, f32::NAN, f32::INFINITY, 1.0e30] {
+            assert!(
+                Tensor::to_indices(&t(vec![bad], vec![1]), 3).is_err(),
+                "{} 被接受",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn index_select_gathers_along_axis() {
+        let x = t((0..6).map(|i| i as f32).collect(), vec![2, 3]);
+        let cols = x.index_select(1, &t(vec![2.0, 0.0, 2.0], vec![3])).unwrap();
+        assert_eq!(cols.shape, [2, 3]);
+        assert_eq!(cols.data.to_vec(), vec![2.0, 0.0, 2.0, 5.0, 3.0, 5.0]);
+        let none = x.index_select(0, &t(vec![], vec![0])).unwrap();
+        assert_eq!(none.shape, [0, 3]);
+        assert!(x.index_select(0, &t(vec![0.0], vec![1, 1])).is_err());
+        assert!(x.index_select(2, &t(vec![0.0], vec![1])).is_err());
+    }
+
+    #[test]
+    fn embedding_gathers_rows_and_scatters_gradients() {
+        let weight = t((0..8).map(|i| i as f32).collect(), vec![4, 2]);
+        let ids = t(vec![3.0, 0.0, 3.0, 1.0], vec![2, 2]);
+        let out = Tensor::embedding(&weight, &ids, None).unwrap();
+        assert_eq!(out.shape, [2, 2, 2]);
+        assert_eq!(out.data[..4], [6.0, 7.0, 0.0, 1.0]);
+
+        let grad = Tensor::ones(vec![2, 2, 2]).unwrap();
+        let dw = Tensor::embedding_backward(&grad, &ids, 4, Some(0)).unwrap();
+        assert_eq!(
+            dw.data.to_vec(),
+            vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 2.0, 2.0]
+        );
+
+        assert!(Tensor::embedding(&weight, &t(vec![4.0], vec![1]), None).is_err());
+        assert!(Tensor::embedding(&weight, &ids, Some(4)).is_err());
+        assert!(Tensor::embedding_backward(&grad, &t(vec![0.0], vec![1]), 4, None).is_err());
+    }
+}
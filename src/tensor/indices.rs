@@ -0,0 +1,81 @@
+// 按行主序（末维最快）或列主序（首维最快）枚举形状内的每个多维下标。
+// 零维形状产生唯一的空下标，含 0 的形状不产生任何下标
+#[derive(Debug, Clone)]
+pub struct Indices {
+    shape: Vec<usize>,
+    next: Vec<usize>,
+    remaining: usize,
+    column_major: bool,
+}
+
+pub fn indices(shape: &[usize]) -> Indices {
+    return Indices {
+        shape: shape.to_vec(),
+        next: vec![0; shape.len()],
+        remaining: shape.iter().product(),
+        column_major: false,
+    };
+}
+
+pub fn indices_column_major(shape: &[usize]) -> Indices {
+    return Indices {
+        column_major: true,
+        ..indices(shape)
+    };
+}
+
+impl Iterator for Indices {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let current = self.next.clone();
+
+        let rank = self.shape.len();
+        for k in 0..rank {
+            let axis = if self.column_major { k } else { rank - 1 - k };
+            self.next[axis] += 1;
+            if self.next[axis] < self.shape[axis] {
+                break;
+            }
+            self.next[axis] = 0;
+        }
+
+        return Some(current);
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.remaining, Some(self.remaining));
+    }
+}
+
+impl ExactSizeIterator for Indices {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumerates_in_both_orders() {
+        let rows: Vec<Vec<usize>> = indices(&[2, 3]).collect();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[1], [0, 1]);
+        assert_eq!(rows[3], [1, 0]);
+
+        let cols: Vec<Vec<usize>> = indices_column_major(&[2, 3]).collect();
+        assert_eq!(cols[1], [1, 0]);
+        assert_eq!(cols[5], [1, 2]);
+
+        assert_eq!(indices(&[]).collect::<Vec<_>>(), vec![Vec::<usize>::new()]);
+        assert_eq!(indices(&[3, 0, 2]).len(), 0);
+
+        // 与按下标访问的结果一致
+        let t = crate::Tensor::new((0..24).map(|i| i as f32).collect(), vec![2, 3, 4]).unwrap();
+        for (flat, index) in indices(&t.shape).enumerate() {
+            assert_eq!(t[index.as_slice()], flat as f32);
+        }
+    }
+}
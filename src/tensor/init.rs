@@ -0,0 +1,203 @@
+use super::{Shape, Tensor};
+use crate::random;
+
+impl Tensor {
+    pub(crate) fn fans(shape: &[usize]) -> Result<(usize, usize), String> {
+        if shape.len() < 2 {
+            return Err(format!(
+                "计算 fan_in/fan_out 至少需要二维形状，实际为 {:?}",
+                shape
+            ));
+        }
+
+        let receptive: usize = shape[2..].iter().product();
+        let fan_in = shape[1] * receptive;
+        let fan_out = shape[0] * receptive;
+
+        return Ok((fan_in, fan_out));
+    }
+
+    pub fn rand_uniform(shape: impl Into<Shape>, low: f32, high: f32) -> Result<Self, String> {
+        let shape: Shape = shape.into();
+        let total_size: usize = shape.iter().product();
+        let data =
+            random::with_rng(|rng| (0..total_size).map(|_| rng.uniform(low, high)).collect());
+
+        return Tensor::new(data, shape);
+    }
+
+    pub fn rand_normal(shape: impl Into<Shape>, mean: f32, std: f32) -> Result<Self, String> {
+        let shape: Shape = shape.into();
+        let total_size: usize = shape.iter().product();
+        let data = random::with_rng(|rng| (0..total_size).map(|_| rng.normal(mean, std)).collect());
+
+        return Tensor::new(data, shape);
+    }
+
+    pub fn xavier_uniform(shape: impl Into<Shape>, gain: f32) -> Result<Self, String> {
+        let shape: Shape = shape.into();
+        let (fan_in, fan_out) = Self::fans(&shape)?;
+        let bound = gain * (6.0 / (fan_in + fan_out) as f32).sqrt();
+
+        return Self::rand_uniform(shape, -bound, bound);
+    }
+
+    pub fn xavier_normal(shape: impl Into<Shape>, gain: f32) -> Result<Self, String> {
+        let shape: Shape = shape.into();
+        let (fan_in, fan_out) = Self::fans(&shape)?;
+        let std = gain * (2.0 / (fan_in + fan_out) as f32).sqrt();
+
+        return Self::rand_normal(shape, 0.0, std);
+    }
+
+    pub fn kaiming_uniform(shape: impl Into<Shape>, negative_slope: f32) -> Result<Self, String> {
+        let shape: Shape = shape.into();
+        let (fan_in, _) = Self::fans(&shape)?;
+        let gain = (2.0 / (1.0 + negative_slope * negative_slope)).sqrt();
+        let bound = gain * (3.0 / fan_in as f32).sqrt();
+
+        return Self::rand_uniform(shape, -bound, bound);
+    }
+
+    pub fn kaiming_normal(shape: impl Into<Shape>, negative_slope: f32) -> Result<Self, String> {
+        let shape: Shape = shape.into();
+        let (fan_in, _) = Self::fans(&shape)?;
+        let gain = (2.0 / (1.0 + negative_slope * negative_slope)).sqrt();
+        let std = gain / (fan_in as f32).sqrt();
+
+        return Self::rand_normal(shape, 0.0, std);
+    }
+
+    pub fn orthogonal(shape: impl Into<Shape>, gain: f32) -> Result<Self, String> {
+        let shape: Shape = shape.into();
+        if shape.len() < 2 {
+            return Err(format!("正交初始化至少需要二维形状，实际为 {:?}", shape));
+        }
+
+        let rows = shape[0];
+        let cols: usize = shape[1..].iter().product();
+        let (n, m) = if rows < cols {
+            (cols, rows)
+        } else {
+            (rows, cols)
+        };
+
+        // 对 n×m 高斯矩阵做 Gram-Schmidt 正交化，得到 m 个正交的 n 维列向量
+        let gaussian: Vec<f64> =
+            random::with_rng(|rng| (0..n * m).map(|_| rng.normal(0.0, 1.0) as f64).collect());
+        let mut q = vec![0.0f64; n * m];
+        for j in 0..m {
+            let mut v: Vec<f64> = (0..n).map(|i| gaussian[i * m + j]).collect();
+            for _ in 0..2 {
+                for k in 0..j {
+                    let dot: f64 = (0..n).map(|i| q[i * m + k] * v[i]).sum();
+                    for i in 0..n {
+                        v[i] -= dot * q[i * m + k];
+                    }
+                }
+            }
+            let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norm < 1e-12 {
+                return Err("正交初始化过程中出现退化向量".to_string());
+            }
+            for i in 0..n {
+                q[i * m + j] = v[i] / norm;
+            }
+        }
+
+        let mut data = Vec::with_capacity(rows * cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                let value = if rows < cols {
+                    q[c * m + r]
+                } else {
+                    q[r * m + c]
+                };
+                data.push(gain * value as f32);
+            }
+        }
+
+        return Tensor::new(data, shape);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fans_follow_pytorch() {
+        assert_eq!(Tensor::fans(&[4, 3]), Ok((3, 4)));
+        assert_eq!(Tensor::fans(&[8, 2, 3, 3]), Ok((18, 72)));
+        assert!(Tensor::fans(&[5]).is_err());
+    }
+
+    #[test]
+    fn uniform_initializers_stay_in_bounds() {
+        let x = Tensor::xavier_uniform(vec![20, 30], 1.0).unwrap();
+        let bound = (6.0f32 / 50.0).sqrt();
+        assert!(x.data.iter().all(|v| v.abs() <= bound));
+        let k = Tensor::kaiming_uniform(vec![16, 4, 3, 3], 0.0).unwrap();
+        let bound = 2.0f32.sqrt() * (3.0f32 / 36.0).sqrt();
+        assert!(k.data.iter().all(|v| v.abs() <= bound));
+        let u = Tensor::rand_uniform(vec![1000], 2.0, 3.0).unwrap();
+        assert!(u.data.iter().all(|v| (2.0..3.0).contains(v)));
+    }
+
+    #[test]
+    fn normal_initializers_have_expected_spread() {
+        let x = Tensor::kaiming_normal(vec![200, 50], 0.0).unwrap();
+        let std = (2.0f32 / 50.0).sqrt();
+        let mean = x.data.iter().sum::<f32>() / 10000.0;
+        let var = x.data.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / 10000.0;
+        assert!(mean.abs() < 0.01, "均值 {}", mean);
+        assert!(
+            (var.sqrt() - std).abs() < 0.1 * std,
+            "标准差 {}",
+            var.sqrt()
+        );
+        assert!(Tensor::xavier_normal(vec![3], 1.0).is_err());
+    }
+
+    #[test]
+    fn orthogonal_rows_or_columns_are_orthonormal() {
+        for (rows, cols) in [(6, 3), (3, 6), (4, 4)] {
+            let q = Tensor::orthogonal(vec![rows, cols], 2.0).unwrap();
+            // 较短的一边彼此正交，且范数等于 gain
+            let (small, long) = (rows.min(cols), rows.max(cols));
+            let at = |i: usize, k: usize| {
+                if rows < cols {
+                    q.data[i * cols + k]
+                } else {
+                    q.data[k * cols + i]
+                }
+            };
+            for i in 0..small {
+                for j in 0..small {
+                    let dot: f32 = (0..long).map(|k| at(i, k) * at(j, k)).sum();
+                    let expected = if i == j { 4.0 } else { 0.0 };
+                    assert!((dot - expected).abs() < 1e-4);
+                }
+            }
+        }
+        assert!(Tensor::orthogonal(vec![4], 1.0).is_err());
+        let empty = Tensor::orthogonal(vec![0, 3], 1.0).unwrap();
+        assert!(empty.data.is_empty());
+    }
+
+    #[test]
+    fn empty_shapes_initialize_to_empty_tensors() {
+        assert!(
+            Tensor::kaiming_uniform(vec![0, 3], 0.0)
+                .unwrap()
+                .data
+                .is_empty()
+        );
+        assert!(
+            Tensor::xavier_normal(vec![4, 0], 1.0)
+                .unwrap()
+                .data
+                .is_empty()
+        );
+    }
+}
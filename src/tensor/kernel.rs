@@ -0,0 +1,106 @@
+// 跨步读取的微内核。转置或换轴后最内维的步长往往很大，相邻两次读取落在不同的
+// 缓存行上；最内层循环展开 4 次，并对 PREFETCH_DISTANCE 个元素之后的位置发出
+// 预取提示，让访存延迟与拷贝重叠。步长为 1 时退化为整段拷贝
+const UNROLL: usize = 4;
+const PREFETCH_DISTANCE: usize = 16;
+
+#[inline(always)]
+fn prefetch(src: &[f32], index: usize) {
+    #[cfg(target_arch = "x86_64")]
+    if index < src.len() {
+        use std::arch::x86_64::{_MM_HINT_T0, _mm_prefetch};
+        // 预取只是提示，不会读取内存，也不会因地址无效而出错
+        unsafe { _mm_prefetch(src.as_ptr().add(index) as *const i8, _MM_HINT_T0) };
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = (src, index);
+}
+
+// 把 src[base], src[base + stride], … 共 len 个元素追加到 out
+pub(crate) fn gather_row(src: &[f32], base: usize, stride: usize, len: usize, out: &mut Vec<f32>) {
+    if len == 0 {
+        return;
+    }
+    if stride == 1 {
+        out.extend_from_slice(&src[base..base + len]);
+        return;
+    }
+
+    let mut i = 0;
+    while i + UNROLL <= len {
+        let p = base + i * stride;
+        for j in 0..UNROLL {
+            prefetch(src, p + (PREFETCH_DISTANCE + j) * stride);
+        }
+        out.extend_from_slice(&[
+            src[p],
+            src[p + stride],
+            src[p + 2 * stride],
+            src[p + 3 * stride],
+        ]);
+        i += UNROLL;
+    }
+    while i < len {
+        out.push(src[base + i * stride]);
+        i += 1;
+    }
+}
+
+// 按行主序遍历 shape，从 src 中按 strides 读取每个元素并追加到 out
+pub(crate) fn gather_strided(src: &[f32], shape: &[usize], strides: &[usize], out: &mut Vec<f32>) {
+    let total: usize = shape.iter().product();
+    if total == 0 {
+        return;
+    }
+    let rank = shape.len();
+    if rank == 0 {
+        out.push(src[0]);
+        return;
+    }
+
+    let (len, stride) = (shape[rank - 1], strides[rank - 1]);
+    let (outer, outer_strides) = (&shape[..rank - 1], &strides[..rank - 1]);
+    let mut index = vec![0; rank - 1];
+    let mut offset = 0;
+    for _ in 0..total / len {
+        gather_row(src, offset, stride, len, out);
+        for d in (0..outer.len()).rev() {
+            index[d] += 1;
+            offset += outer_strides[d];
+            if index[d] < outer[d] {
+                break;
+            }
+            offset -= outer_strides[d] * outer[d];
+            index[d] = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indices;
+
+    #[test]
+    fn strided_gather_matches_index_arithmetic() {
+        let src: Vec<f32> = (0..200).map(|i| i as f32).collect();
+        let cases: [(&[usize], &[usize]); 5] = [
+            (&[3, 7], &[1, 3]),
+            (&[2, 3, 9], &[1, 2, 6]),
+            (&[4, 5], &[0, 1]),
+            (&[2, 0, 3], &[1, 1, 1]),
+            (&[], &[]),
+        ];
+        for (shape, strides) in cases {
+            let mut out = Vec::new();
+            gather_strided(&src, shape, strides, &mut out);
+            let expected: Vec<f32> = indices(shape)
+                .map(|index| {
+                    let offset: usize = index.iter().zip(strides).map(|(i, s)| i * s).sum();
+                    src[offset]
+                })
+                .collect();
+            assert_eq!(out, expected, "shape {:?} strides {:?}", shape, strides);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use super::{Shape, Storage, Tensor};
+
+// 张量的元素总是按行主序连续存放，但可以从共享缓冲区的某个偏移处开始
+// （narrow、chunks 取出的视图，或 from_parts 带偏移构造的张量）。
+// 算子都假定连续步长，因此 from_parts 只接受行主序连续的布局；列主序、转置、
+// 步长为 0 的广播视图等外部缓冲区需要调用方先整理成连续布局
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub shape: Shape,
+    pub strides: Vec<usize>,
+    pub offset: usize,
+}
+
+impl Layout {
+    // 长度为 1 的维度不会被跨越，其步长不影响布局
+    pub fn is_contiguous(&self) -> bool {
+        let expected = self.shape.strides();
+        return self.strides.len() == expected.len()
+            && (0..expected.len()).all(|d| self.shape[d] <= 1 || self.strides[d] == expected[d]);
+    }
+
+    // 第一维变化最快
+    pub fn is_column_major(&self) -> bool {
+        let mut expected = 1;
+        for (&dim, &stride) in self.shape.iter().zip(&self.strides) {
+            if dim > 1 && stride != expected {
+                return false;
+            }
+            expected *= dim;
+        }
+
+        return true;
+    }
+}
+
+impl Tensor {
+    pub fn strides(&self) -> &[usize] {
+        return &self.strides;
+    }
+
+    pub fn offset(&self) -> usize {
+        return self.data.offset();
+    }
+
+    pub fn layout(&self) -> Layout {
+        return Layout {
+            shape: self.shape.clone(),
+            strides: self.strides.clone(),
+            offset: self.offset(),
+        };
+    }
+
+    // 以 data 为底层缓冲区、从 offset 开始的张量，不复制数据；offset() 返回该偏移。
+    // 非连续的步长返回 Err，而不是悄悄复制成另一种布局
+    pub fn from_parts(
+        data: Vec<f32>,
+        shape: impl Into<Shape>,
+        strides: Vec<usize>,
+        offset: usize,
+    ) -> Result<Tensor, String> {
+        let layout = Layout {
+            shape: shape.into(),
+            strides: strides,
+            offset: offset,
+        };
+        if layout.strides.len() != layout.shape.len() {
+            return Err(format!(
+                "步长 {:?} 的维数与形状 {:?} 不一致",
+                layout.strides, layout.shape
+            ));
+        }
+        if !layout.is_contiguous() {
+            return Err(format!(
+                "步长 {:?} 不是形状 {:?} 的行主序连续步长，张量只支持连续布局，请先整理数据",
+                layout.strides, layout.shape
+            ));
+        }
+        let len = layout.shape.numel();
+        if offset + len > data.len() {
+            return Err(format!(
+                "偏移 {} 处的形状 {:?} 需要至少 {} 个元素，实际只有 {}",
+                offset,
+                layout.shape,
+                offset + len,
+                data.len()
+            ));
+        }
+
+        let storage = Storage::from(data).view(offset, len);
+
+        return Tensor::from_storage(storage, layout.shape);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_parts_keeps_the_offset_and_rejects_strided_layouts() {
+        let t = Tensor::from_parts(
+            (0..8).map(|i| i as f32).collect(),
+            vec![2, 3],
+            vec![3, 1],
+            2,
+        )
+        .unwrap();
+        assert_eq!(t.data.to_vec(), vec![2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(t.strides(), [3, 1]);
+        assert_eq!(t.offset(), 2);
+        assert!(t.layout().is_contiguous());
+        assert!(!t.layout().is_column_major());
+
+        // 视图沿用原缓冲区的偏移，复制出的结果从 0 开始
+        let row = t.narrow(0, 1, 1).unwrap();
+        assert_eq!(row.offset(), 5);
+        assert!(row.shares_storage(&t));
+        assert_eq!(row.deep_copy().unwrap().offset(), 0);
+        assert_eq!(Tensor::zeros(vec![2]).unwrap().offset(), 0);
+
+        // 长度为 1 的维度步长任意
+        let single = Tensor::from_parts(vec![1.0, 2.0, 3.0], vec![1, 3], vec![7, 1], 0).unwrap();
+        assert_eq!(single.shape, [1, 3]);
+
+        let layout = Layout {
+            shape: Shape::from([2, 3]),
+            strides: vec![1, 2],
+            offset: 0,
+        };
+        assert!(layout.is_column_major() && !layout.is_contiguous());
+
+        // 列主序与步长 0 的广播视图不会被悄悄整理成别的布局
+        assert!(Tensor::from_parts(vec![1.0; 6], vec![2, 3], vec![1, 2], 0).is_err());
+        assert!(Tensor::from_parts(vec![7.0, 8.0], vec![3, 2], vec![0, 1], 0).is_err());
+        assert!(Tensor::from_parts(vec![1.0; 6], vec![2, 3], vec![3, 1], 1).is_err());
+        assert!(Tensor::from_parts(vec![1.0; 6], vec![2, 3], vec![1], 0).is_err());
+        assert_eq!(
+            Tensor::from_parts(vec![], vec![0, 3], vec![3, 1], 0)
+                .unwrap()
+                .shape,
+            [0, 3]
+        );
+    }
+}
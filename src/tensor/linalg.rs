@@ -0,0 +1,618 @@
+use super::Tensor;
+use crate::{parallel, profile};
+
+fn solve_system(a: &[f32], b: &[f32], n: usize, k: usize) -> Result<Vec<f32>, String> {
+    let mut m: Vec<f64> = a.iter().map(|&v| v as f64).collect();
+    let mut x: Vec<f64> = b.iter().map(|&v| v as f64).collect();
+    // 主元阈值与矩阵的量级相关：输入是 f32，低于 eps * n * max|a| 的主元视为舍入误差。
+    // 只按有限值取最大值，NaN/Inf 输入继续参与消元并传播到结果
+    let scale = m
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold(0.0f64, |acc, v| acc.max(v.abs()));
+    let tolerance = f32::EPSILON as f64 * n as f64 * scale;
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| m[i * n + col].abs().total_cmp(&m[j * n + col].abs()))
+            .unwrap();
+        if m[pivot * n + col].abs() <= tolerance {
+            return Err("矩阵奇异，无法求解".to_string());
+        }
+        if pivot != col {
+            for j in 0..n {
+                m.swap(col * n + j, pivot * n + j);
+            }
+            for j in 0..k {
+                x.swap(col * k + j, pivot * k + j);
+            }
+        }
+
+        let diag = m[col * n + col];
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let factor = m[row * n + col] / diag;
+            if factor == 0.0 {
+                continue;
+            }
+            for j in col..n {
+                m[row * n + j] -= factor * m[col * n + j];
+            }
+            for j in 0..k {
+                x[row * k + j] -= factor * x[col * k + j];
+            }
+        }
+    }
+
+    for row in 0..n {
+        let diag = m[row * n + row];
+        for j in 0..k {
+            x[row * k + j] /= diag;
+        }
+    }
+
+    return Ok(x.into_iter().map(|v| v as f32).collect());
+}
+
+fn jacobi_eigh(a: &[f32], n: usize) -> Result<(Vec<f32>, Vec<f32>), String> {
+    let mut m: Vec<f64> = a.iter().map(|&v| v as f64).collect();
+    let mut v = vec![0.0f64; n * n];
+    for i in 0..n {
+        v[i * n + i] = 1.0;
+    }
+
+    let scale: f64 = m
+        .iter()
+        .map(|x| x * x)
+        .sum::<f64>()
+        .sqrt()
+        .max(f64::MIN_POSITIVE);
+    let mut converged = n < 2;
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| m[i * n + j] * m[i * n + j])
+            .sum::<f64>()
+            .sqrt();
+        if off <= 1e-12 * scale {
+            converged = true;
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = m[p * n + q];
+                if apq.abs() <= f64::MIN_POSITIVE {
+                    continue;
+                }
+                let theta = (m[q * n + q] - m[p * n + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..n {
+                    let mkp = m[k * n + p];
+                    let mkq = m[k * n + q];
+                    m[k * n + p] = c * mkp - s * mkq;
+                    m[k * n + q] = s * mkp + c * mkq;
+                }
+                for k in 0..n {
+                    let mpk = m[p * n + k];
+                    let mqk = m[q * n + k];
+                    m[p * n + k] = c * mpk - s * mqk;
+                    m[q * n + k] = s * mpk + c * mqk;
+                }
+                for k in 0..n {
+                    let vkp = v[k * n + p];
+                    let vkq = v[k * n + q];
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    if !converged {
+        return Err("Jacobi 迭代未收敛".to_string());
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| m[i * n + i].total_cmp(&m[j * n + j]));
+
+    let values = order.iter().map(|&i| m[i * n + i] as f32).collect();
+    let mut vectors = vec![0.0f32; n * n];
+    for (col, &src) in order.iter().enumerate() {
+        for row in 0..n {
+            vectors[row * n + col] = v[row * n + src] as f32;
+        }
+    }
+
+    return Ok((values, vectors));
+}
+
+fn gemm_accumulate(
+    a: &[f32],
+    b: &[f32],
+    (m, k, n): (usize, usize, usize),
+    alpha: f32,
+    out: &mut [f32],
+) {
+    for i in 0..m {
+        let row = &mut out[i * n..(i + 1) * n];
+        for (p, &av) in a[i * k..(i + 1) * k].iter().enumerate() {
+            if av == 0.0 {
+                continue;
+            }
+            let av = av * alpha;
+            for (o, &bv) in row.iter_mut().zip(&b[p * n..(p + 1) * n]) {
+                *o += av * bv;
+            }
+        }
+    }
+}
+
+impl Tensor {
+    fn bmm_dims(&self, other: &Tensor) -> Result<(usize, usize, usize, usize), String> {
+        if self.shape.len() != 3
+            || other.shape.len() != 3
+            || self.shape[0] != other.shape[0]
+            || self.shape[2] != other.shape[1]
+        {
+            return Err(format!(
+                "bmm 需要 [B, M, K] 与 [B, K, N]，实际形状为 {:?} 与 {:?}",
+                self.shape, other.shape
+            ));
+        }
+
+        return Ok((self.shape[0], self.shape[1], self.shape[2], other.shape[2]));
+    }
+
+    pub fn bmm(&self, other: &Tensor) -> Result<Tensor, String> {
+        let (batch, m, _, n) = self.bmm_dims(other)?;
+
+        let out = Tensor::zeros(vec![batch, m, n])?.baddbmm(self, other, 0.0, 1.0)?;
+
+        return Ok(self.placed(out));
+    }
+
+    pub fn baddbmm(
+        &self,
+        batch1: &Tensor,
+        batch2: &Tensor,
+        beta: f32,
+        alpha: f32,
+    ) -> Result<Tensor, String> {
+        let (batch, m, k, n) = batch1.bmm_dims(batch2)?;
+        if self.shape != [batch, m, n] {
+            return Err(format!(
+                "baddbmm 累加项形状 {:?} 应为 {:?}",
+                self.shape,
+                [batch, m, n]
+            ));
+        }
+        let _scope = profile::scope("baddbmm", batch * m * n);
+
+        let blocks = parallel::map_range(batch, batch * m * n * k, |b| {
+            // beta 为 0 时忽略累加项，与 BLAS 约定一致，避免 NaN 传播
+            let mut out: Vec<f32> = if beta == 0.0 {
+                vec![0.0; m * n]
+            } else {
+                self.data[b * m * n..(b + 1) * m * n]
+                    .iter()
+                    .map(|&v| v * beta)
+                    .collect()
+            };
+            gemm_accumulate(
+                &batch1.data[b * m * k..(b + 1) * m * k],
+                &batch2.data[b * k * n..(b + 1) * k * n],
+                (m, k, n),
+                alpha,
+                &mut out,
+            );
+            out
+        });
+
+        let mut data = crate::alloc::allocate(batch * m * n);
+        for block in blocks {
+            data.extend(block);
+        }
+
+        return Ok(self.placed(Tensor::new(data, vec![batch, m, n])?));
+    }
+
+    pub fn addmm(
+        bias: &Tensor,
+        a: &Tensor,
+        b: &Tensor,
+        alpha: f32,
+        beta: f32,
+    ) -> Result<Tensor, String> {
+        if a.shape.len() != 2 || b.shape.len() != 2 || a.shape[1] != b.shape[0] {
+            return Err(format!(
+                "addmm 需要 [M, K] 与 [K, N]，实际形状为 {:?} 与 {:?}",
+                a.shape, b.shape
+            ));
+        }
+        let (m, k, n) = (a.shape[0], a.shape[1], b.shape[1]);
+        if bias.shape.len() > 2 || Self::broadcast_shapes(&bias.shape, &[m, n])? != [m, n] {
+            return Err(format!(
+                "addmm 偏置形状 {:?} 无法广播到 {:?}",
+                bias.shape,
+                [m, n]
+            ));
+        }
+        let _scope = profile::scope("addmm", m * n);
+
+        let strides = bias.broadcast_strides(&[m, n]);
+        let rows = parallel::map_range(m, m * n * k, |i| {
+            let mut out: Vec<f32> = if beta == 0.0 {
+                vec![0.0; n]
+            } else {
+                (0..n)
+                    .map(|j| beta * bias.data[i * strides[0] + j * strides[1]])
+                    .collect()
+            };
+            gemm_accumulate(
+                &a.data[i * k..(i + 1) * k],
+                &b.data,
+                (1, k, n),
+                alpha,
+                &mut out,
+            );
+            out
+        });
+
+        let mut data = crate::alloc::allocate(m * n);
+        for row in rows {
+            data.extend(row);
+        }
+
+        return Ok(a.placed(Tensor::new(data, vec![m, n])?));
+    }
+
+    fn square_batch(&self, op: &str) -> Result<(usize, usize), String> {
+        let rank = self.shape.len();
+        if rank < 2 || self.shape[rank - 1] != self.shape[rank - 2] {
+            return Err(format!(
+                "{} 需要形状为 [N, N] 或 [B, N, N] 的方阵，实际形状为 {:?}",
+                op, self.shape
+            ));
+        }
+        let n = self.shape[rank - 1];
+        let batch: usize = self.shape[..rank - 2].iter().product();
+
+        return Ok((batch, n));
+    }
+
+    pub fn inv(&self) -> Result<Tensor, String> {
+        let (batch, n) = self.square_batch("inv")?;
+        let _scope = profile::scope("inv", self.data.len());
+
+        let mut identity = vec![0.0; n * n];
+        for i in 0..n {
+            identity[i * n + i] = 1.0;
+        }
+        let results = parallel::map_range(batch, batch * n * n * n, |b| {
+            solve_system(&self.data[b * n * n..(b + 1) * n * n], &identity, n, n)
+        });
+
+        let mut data = crate::alloc::allocate(self.data.len());
+        for r in results {
+            data.extend(r?);
+        }
+
+        return Ok(self.placed(Tensor::new(data, self.shape.clone())?));
+    }
+
+    pub fn solve(&self, b: &Tensor) -> Result<Tensor, String> {
+        let (batch, n) = self.square_batch("solve")?;
+        let batch_dims = &self.shape[..self.shape.len() - 2];
+
+        let k = if b.shape.len() == batch_dims.len() + 1
+            && b.shape[..batch_dims.len()] == *batch_dims
+        {
+            1
+        } else if b.shape.len() == batch_dims.len() + 2
+            && b.shape[..batch_dims.len()] == *batch_dims
+        {
+            b.shape[b.shape.len() - 1]
+        } else {
+            return Err(format!(
+                "右端项形状 {:?} 与系数矩阵形状 {:?} 不匹配",
+                b.shape, self.shape
+            ));
+        };
+        if b.shape[batch_dims.len()] != n {
+            return Err(format!(
+                "右端项形状 {:?} 与系数矩阵形状 {:?} 不匹配",
+                b.shape, self.shape
+            ));
+        }
+        let _scope = profile::scope("solve", self.data.len() + b.data.len());
+
+        let results = parallel::map_range(batch, batch * n * n * (n + k), |i| {
+            solve_system(
+                &self.data[i * n * n..(i + 1) * n * n],
+                &b.data[i * n * k..(i + 1) * n * k],
+                n,
+                k,
+            )
+        });
+
+        let mut data = crate::alloc::allocate(b.data.len());
+        for r in results {
+            data.extend(r?);
+        }
+
+        return Ok(self.placed(Tensor::new(data, b.shape.clone())?));
+    }
+
+    pub fn eigh(&self) -> Result<(Tensor, Tensor), String> {
+        let (batch, n) = self.square_batch("eigh")?;
+        for b in 0..batch {
+            let m = &self.data[b * n * n..(b + 1) * n * n];
+            for i in 0..n {
+                for j in i + 1..n {
+                    let (x, y) = (m[i * n + j], m[j * n + i]);
+                    if (x - y).abs() > 1e-5 * (1.0 + x.abs().max(y.abs())) {
+                        return Err(format!("eigh 需要对称矩阵，第 {} 个矩阵不对称", b));
+                    }
+                }
+            }
+        }
+        let _scope = profile::scope("eigh", self.data.len());
+
+        let results = parallel::map_range(batch, batch * n * n * n, |b| {
+            jacobi_eigh(&self.data[b * n * n..(b + 1) * n * n], n)
+        });
+
+        let mut values = Vec::with_capacity(batch * n);
+        let mut vectors = crate::alloc::allocate(self.data.len());
+        for r in results {
+            let (val, vec) = r?;
+            values.extend(val);
+            vectors.extend(vec);
+        }
+
+        let value_shape = self.shape[..self.shape.len() - 1].to_vec();
+
+        return Ok((
+            self.placed(Tensor::new(values, value_shape)?),
+            self.placed(Tensor::new(vectors, self.shape.clone())?),
+        ));
+    }
+
+    pub fn matmul(&self, other: &Tensor) -> Result<Tensor, String> {
+        let (ra, rb) = (self.shape.len(), other.shape.len());
+        if ra < 2 || rb < 2 || self.shape[ra - 1] != other.shape[rb - 2] {
+            return Err(format!(
+                "矩阵乘法形状不匹配：{:?} 与 {:?}",
+                self.shape, other.shape
+            ));
+        }
+        if let Some(out) = self.dispatch_matmul(other)? {
+            return Ok(out);
+        }
+        let (m, k, n) = (self.shape[ra - 2], self.shape[ra - 1], other.shape[rb - 1]);
+
+        let batch_a = &self.shape[..ra - 2];
+        let batch_b = &other.shape[..rb - 2];
+        let batch_shape = Self::broadcast_shapes(batch_a, batch_b)?;
+        let batch: usize = batch_shape.iter().product();
+        let _scope = profile::scope("matmul", batch * m * n);
+
+        let batch_offset = |dims: &[usize], mut b: usize| -> usize {
+            let pad = batch_shape.len() - dims.len();
+            let mut offset = 0;
+            let mut stride = 1;
+            for d in (0..batch_shape.len()).rev() {
+                let idx = b % batch_shape[d];
+                b /= batch_shape[d];
+                if d >= pad {
+                    let size = dims[d - pad];
+                    if size != 1 {
+                        offset += idx * stride;
+                    }
+                    stride *= size;
+                }
+            }
+            offset
+        };
+
+        let rows = parallel::map_range(batch * m, batch * m * n * k, |row| {
+            let (b, i) = (row / m, row % m);
+            let a = &self.data[(batch_offset(batch_a, b) * m + i) * k..][..k];
+            let rhs = &other.data[batch_offset(batch_b, b) * k * n..][..k * n];
+            let mut out = vec![0.0f32; n];
+            for (p, &av) in a.iter().enumerate() {
+                if av == 0.0 {
+                    continue;
+                }
+                for (o, &bv) in out.iter_mut().zip(&rhs[p * n..(p + 1) * n]) {
+                    *o += av * bv;
+                }
+            }
+            out
+        });
+
+        let mut data = crate::alloc::allocate(batch * m * n);
+        for row in rows {
+            data.extend(row);
+        }
+
+        let mut shape = batch_shape;
+        shape.push(m);
+        shape.push(n);
+
+        return Ok(self.placed(Tensor::new(data, shape)?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matmul_broadcasts_batches_and_handles_empty_inner_dim() {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![1, 2, 2]).unwrap();
+        let b = Tensor::new(vec![1.0, 0.0, 0.0, 1.0, 2.0, 0.0, 0.0, 2.0], vec![2, 2, 2]).unwrap();
+        let out = a.matmul(&b).unwrap();
+        assert_eq!(out.shape, vec![2, 2, 2]);
+        assert_eq!(
+            out.data.to_vec(),
+            vec![1.0, 2.0, 3.0, 4.0, 2.0, 4.0, 6.0, 8.0]
+        );
+
+        let empty = Tensor::zeros(vec![2, 0])
+            .unwrap()
+            .matmul(&Tensor::zeros(vec![0, 3]).unwrap());
+        assert_eq!(empty.unwrap().data.to_vec(), vec![0.0; 6]);
+    }
+
+    #[test]
+    fn bmm_and_baddbmm_match_broadcasting_matmul() {
+        let a = Tensor::rand_normal(vec![3, 2, 4], 0.0, 1.0).unwrap();
+        let b = Tensor::rand_normal(vec![3, 4, 5], 0.0, 1.0).unwrap();
+        let c = Tensor::rand_normal(vec![3, 2, 5], 0.0, 1.0).unwrap();
+        let expected = a.matmul(&b).unwrap();
+        assert!(close(&a.bmm(&b).unwrap().data, &expected.data, 1e-5));
+
+        let fused = c.baddbmm(&a, &b, 0.5, 2.0).unwrap();
+        let reference = c
+            .mul_scalar(0.5)
+            .unwrap()
+            .add(&expected.mul_scalar(2.0).unwrap())
+            .unwrap();
+        assert!(close(&fused.data, &reference.data, 1e-5));
+
+        let nan = Tensor::full(vec![3, 2, 5], f32::NAN).unwrap();
+        assert!(close(
+            &nan.baddbmm(&a, &b, 0.0, 1.0).unwrap().data,
+            &expected.data,
+            1e-5
+        ));
+        assert!(a.bmm(&a).is_err());
+        assert!(b.baddbmm(&a, &b, 1.0, 1.0).is_err());
+        assert!(a.bmm(&Tensor::zeros(vec![2, 4, 5]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn addmm_broadcasts_bias_like_a_linear_layer() {
+        let x = Tensor::rand_normal(vec![4, 3], 0.0, 1.0).unwrap();
+        let w = Tensor::rand_normal(vec![3, 2], 0.0, 1.0).unwrap();
+        let bias = Tensor::new(vec![1.0, -1.0], vec![2]).unwrap();
+        let out = Tensor::addmm(&bias, &x, &w, 1.0, 1.0).unwrap();
+        let reference = x.matmul(&w).unwrap().add(&bias).unwrap();
+        assert_eq!(out.shape, [4, 2]);
+        assert!(close(&out.data, &reference.data, 1e-5));
+
+        let full = Tensor::rand_normal(vec![4, 2], 0.0, 1.0).unwrap();
+        let scaled = Tensor::addmm(&full, &x, &w, 0.5, 2.0).unwrap();
+        let reference = full
+            .mul_scalar(2.0)
+            .unwrap()
+            .add(&x.matmul(&w).unwrap().mul_scalar(0.5).unwrap())
+            .unwrap();
+        assert!(close(&scaled.data, &reference.data, 1e-5));
+
+        assert!(Tensor::addmm(&Tensor::zeros(vec![3]).unwrap(), &x, &w, 1.0, 1.0).is_err());
+        assert!(Tensor::addmm(&bias, &w, &x, 1.0, 1.0).is_err());
+    }
+
+    fn close(a: &[f32], b: &[f32], tol: f32) -> bool {
+        return a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() <= tol);
+    }
+
+    #[test]
+    fn inv_round_trips_through_matmul() {
+        let a = Tensor::new(vec![4.0, 7.0, 2.0, 6.0, 0.0, 1.0, 1.0, 0.0], vec![2, 2, 2]).unwrap();
+        let inv = a.inv().unwrap();
+        let eye = a.matmul(&inv).unwrap();
+        assert!(close(
+            &eye.data,
+            &[1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0],
+            1e-5
+        ));
+        assert!(close(&inv.inv().unwrap().data, &a.data, 1e-4));
+    }
+
+    #[test]
+    fn solve_matches_inverse_and_broadcasts_vectors() {
+        let a = Tensor::new(vec![3.0, 1.0, 1.0, 2.0], vec![2, 2]).unwrap();
+        let b = Tensor::new(vec![9.0, 8.0], vec![2]).unwrap();
+        let x = a.solve(&b).unwrap();
+        assert!(close(&x.data, &[2.0, 3.0], 1e-5));
+
+        let bm = Tensor::new(vec![9.0, 1.0, 8.0, 0.0], vec![2, 2]).unwrap();
+        let xm = a.solve(&bm).unwrap();
+        assert!(close(
+            &xm.data,
+            &a.inv().unwrap().matmul(&bm).unwrap().data,
+            1e-5
+        ));
+    }
+
+    #[test]
+    fn singularity_is_judged_relative_to_the_matrix_scale() {
+        // 整体缩小的良态矩阵仍可求解
+        let tiny = Tensor::new(vec![3e-13, 1e-13, 1e-13, 2e-13], vec![2, 2]).unwrap();
+        let b = Tensor::new(vec![9e-13, 8e-13], vec![2]).unwrap();
+        assert!(close(&tiny.solve(&b).unwrap().data, &[2.0, 3.0], 1e-4));
+        assert!(close(
+            &tiny.inv().unwrap().data,
+            &[0.4e13, -0.2e13, -0.2e13, 0.6e13],
+            1e8
+        ));
+
+        // 第二行是第一行的 3 倍，消元只剩 f32 舍入残差，应判为奇异
+        let rank_one = Tensor::new(vec![0.1, 0.3, 0.3, 0.9], vec![2, 2]).unwrap();
+        assert!(rank_one.solve(&b).is_err());
+        assert!(rank_one.inv().is_err());
+        assert!(Tensor::zeros(vec![2, 2]).unwrap().inv().is_err());
+    }
+
+    #[test]
+    fn solvers_do_not_hide_nan_inputs() {
+        let a = Tensor::new(vec![f32::NAN, 1.0, 1.0, 2.0], vec![2, 2]).unwrap();
+        assert!(a.inv().unwrap().data.iter().all(|v| v.is_nan()));
+        assert!(a.eigh().is_err());
+    }
+
+    #[test]
+    fn eigh_reconstructs_symmetric_matrix() {
+        let a = Tensor::new(
+            vec![2.0, 1.0, 0.0, 1.0, 2.0, 1.0, 0.0, 1.0, 2.0],
+            vec![3, 3],
+        )
+        .unwrap();
+        let (values, vectors) = a.eigh().unwrap();
+        assert!(values.data.windows(2).all(|w| w[0] <= w[1]));
+        let s = 2.0f32.sqrt();
+        assert!(close(&values.data, &[2.0 - s, 2.0, 2.0 + s], 1e-5));
+
+        let mut scaled = vectors.clone();
+        for row in 0..3 {
+            for col in 0..3 {
+                scaled.data[row * 3 + col] *= values.data[col];
+            }
+        }
+        let back = scaled.matmul(&vectors.transpose(0, 1).unwrap()).unwrap();
+        assert!(close(&back.data, &a.data, 1e-4));
+        assert!(
+            Tensor::new(vec![1.0, 2.0, 0.0, 1.0], vec![2, 2])
+                .unwrap()
+                .eigh()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn solvers_handle_empty_matrices() {
+        let empty = Tensor::zeros(vec![0, 0]).unwrap();
+        assert_eq!(empty.inv().unwrap().shape, [0, 0]);
+        let (values, vectors) = empty.eigh().unwrap();
+        assert_eq!((values.numel(), vectors.numel()), (Ok(0), Ok(0)));
+    }
+}
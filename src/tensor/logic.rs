@@ -0,0 +1,106 @@
+use super::Tensor;
+
+fn truth(value: bool) -> f32 {
+    if value { 1.0 } else { 0.0 }
+}
+
+impl Tensor {
+    pub fn logical_and(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.zip_with("logical_and", other, |a, b| truth(a != 0.0 && b != 0.0));
+    }
+
+    pub fn logical_or(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.zip_with("logical_or", other, |a, b| truth(a != 0.0 || b != 0.0));
+    }
+
+    pub fn logical_xor(&self, other: &Tensor) -> Result<Tensor, String> {
+        return self.zip_with("logical_xor", other, |a, b| truth((a != 0.0) != (b != 0.0)));
+    }
+
+    pub fn logical_not(&self) -> Result<Tensor, String> {
+        return self.map("logical_not", |a| truth(a == 0.0));
+    }
+
+    pub fn logical_and_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("logical_and_", other, |a, b| truth(a != 0.0 && b != 0.0));
+    }
+
+    pub fn logical_or_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("logical_or_", other, |a, b| truth(a != 0.0 || b != 0.0));
+    }
+
+    pub fn logical_xor_(&mut self, other: &Tensor) -> Result<(), String> {
+        return self.zip_with_("logical_xor_", other, |a, b| {
+            truth((a != 0.0) != (b != 0.0))
+        });
+    }
+
+    pub fn logical_not_(&mut self) -> Result<(), String> {
+        return self.map_("logical_not_", |a| truth(a == 0.0));
+    }
+
+    pub fn equal_shape(&self, other: &Tensor) -> bool {
+        return self.shape == other.shape;
+    }
+
+    // 只比较按行优先展开后的元素，忽略形状；NaN 与任何值都不相等
+    pub fn equal_data(&self, other: &Tensor) -> bool {
+        return self.data.len() == other.data.len()
+            && self.data.iter().zip(other.data.iter()).all(|(a, b)| a == b);
+    }
+
+    // 与 np.array_equal 一致：形状与元素都相同；equal_nan 为真时两侧 NaN 视为相等
+    pub fn array_equal(&self, other: &Tensor, equal_nan: bool) -> bool {
+        return self.equal_shape(other)
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .all(|(a, b)| a == b || (equal_nan && a.is_nan() && b.is_nan()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logical_ops_broadcast_masks() {
+        let a = Tensor::new(vec![0.0, 1.0, 2.0, 0.0, -0.0, 3.0], vec![2, 3]).unwrap();
+        let b = Tensor::new(vec![1.0, 0.0, 1.0], vec![3]).unwrap();
+        assert_eq!(
+            a.logical_and(&b).unwrap().data.to_vec(),
+            vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            a.logical_or(&b).unwrap().data.to_vec(),
+            vec![1.0, 1.0, 1.0, 1.0, 0.0, 1.0]
+        );
+        assert_eq!(
+            a.logical_xor(&b).unwrap().data.to_vec(),
+            vec![1.0, 1.0, 0.0, 1.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            a.logical_not().unwrap().data.to_vec(),
+            vec![1.0, 0.0, 0.0, 1.0, 1.0, 0.0]
+        );
+        assert_eq!(a.logical_and(&b).unwrap().shape, [2, 3]);
+        assert!(a.logical_or(&Tensor::ones(vec![2]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn equality_modes_separate_shape_data_and_nan() {
+        let a = Tensor::new(vec![1.0, f32::NAN, 3.0, 4.0], vec![2, 2]).unwrap();
+        let flat = a.reshaped(vec![4]).unwrap();
+        assert!(!a.equal_shape(&flat));
+        assert!(a.equal_shape(&Tensor::zeros(vec![2, 2]).unwrap()));
+
+        let finite = Tensor::new(vec![1.0, 2.0], vec![2]).unwrap();
+        assert!(finite.equal_data(&finite.reshaped(vec![1, 2]).unwrap()));
+        assert!(!a.equal_data(&flat));
+
+        assert!(!a.array_equal(&a.clone(), false));
+        assert!(a.array_equal(&a.clone(), true));
+        assert!(!a.array_equal(&flat, true));
+    }
+}
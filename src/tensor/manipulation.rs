@@ -0,0 +1,212 @@
+use super::{Tensor, kernel};
+use crate::profile;
+
+impl Tensor {
+    pub fn transpose(&self, dim0: usize, dim1: usize) -> Result<Tensor, String> {
+        self.check_axis(dim0)?;
+        self.check_axis(dim1)?;
+
+        let mut order: Vec<usize> = (0..self.shape.len()).collect();
+        order.swap(dim0, dim1);
+
+        return self.permute(&order);
+    }
+
+    pub fn permute(&self, order: &[usize]) -> Result<Tensor, String> {
+        let mut seen = vec![false; self.shape.len()];
+        if order.len() != self.shape.len() {
+            return Err(format!(
+                "维度排列 {:?} 与张量秩 {} 不匹配",
+                order,
+                self.shape.len()
+            ));
+        }
+        for &d in order {
+            if d >= seen.len() || seen[d] {
+                return Err(format!("维度排列 {:?} 不是有效排列", order));
+            }
+            seen[d] = true;
+        }
+        let _scope = profile::scope("permute", self.data.len());
+
+        let shape: Vec<usize> = order.iter().map(|&d| self.shape[d]).collect();
+        let strides: Vec<usize> = order.iter().map(|&d| self.strides[d]).collect();
+
+        let mut data = crate::alloc::allocate(self.data.len());
+        kernel::gather_strided(&self.data, &shape, &strides, &mut data);
+
+        return Ok(self
+            .placed(Tensor::new(data, shape)?)
+            .with_dtype(self.dtype));
+    }
+
+    pub fn append(&self, other: &Tensor, axis: usize) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+
+        return self.insert(self.shape[axis], other, axis);
+    }
+
+    pub fn insert(&self, index: usize, other: &Tensor, axis: usize) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+        let compatible = other.shape.len() == self.shape.len()
+            && (0..self.shape.len()).all(|d| d == axis || other.shape[d] == self.shape[d]);
+        if !compatible {
+            return Err(format!(
+                "形状 {:?} 无法沿轴 {} 插入到形状 {:?} 中",
+                other.shape, axis, self.shape
+            ));
+        }
+        if index > self.shape[axis] {
+            return Err(format!(
+                "插入位置 {} 超出轴 {} 的长度 {}",
+                index, axis, self.shape[axis]
+            ));
+        }
+
+        let outer: usize = self.shape[..axis].iter().product();
+        let inner: usize = self.shape[axis + 1..].iter().product();
+        let (len, extra) = (self.shape[axis], other.shape[axis]);
+        let _scope = profile::scope("insert", outer * (len + extra) * inner);
+
+        let mut data = crate::alloc::allocate(outer * (len + extra) * inner);
+        for o in 0..outer {
+            let base = o * len * inner;
+            data.extend_from_slice(&self.data[base..base + index * inner]);
+            data.extend_from_slice(&other.data[o * extra * inner..(o + 1) * extra * inner]);
+            data.extend_from_slice(&self.data[base + index * inner..base + len * inner]);
+        }
+
+        let mut shape = self.shape.clone();
+        shape[axis] = len + extra;
+
+        return Tensor::joined("insert", &[self, other], data, shape);
+    }
+
+    // 沿 axis 拼接，允许其中任意张量在该轴上长度为 0
+    pub fn concat(tensors: &[&Tensor], axis: usize) -> Result<Tensor, String> {
+        let Some(first) = tensors.first() else {
+            return Err("concat 至少需要一个张量".to_string());
+        };
+        first.check_axis(axis)?;
+        for t in tensors {
+            let compatible = t.shape.len() == first.shape.len()
+                && (0..first.shape.len()).all(|d| d == axis || t.shape[d] == first.shape[d]);
+            if !compatible {
+                return Err(format!(
+                    "形状 {:?} 无法沿轴 {} 与形状 {:?} 拼接",
+                    t.shape, axis, first.shape
+                ));
+            }
+        }
+
+        let outer: usize = first.shape[..axis].iter().product();
+        let inner: usize = first.shape[axis + 1..].iter().product();
+        let total: usize = tensors.iter().map(|t| t.shape[axis]).sum();
+        let _scope = profile::scope("concat", outer * total * inner);
+
+        let mut data = crate::alloc::allocate(outer * total * inner);
+        for o in 0..outer {
+            for t in tensors {
+                let block = t.shape[axis] * inner;
+                data.extend_from_slice(&t.data[o * block..(o + 1) * block]);
+            }
+        }
+
+        let mut shape = first.shape.clone();
+        shape[axis] = total;
+
+        return Tensor::joined("concat", tensors, data, shape);
+    }
+
+    pub fn delete(&self, indices: &Tensor, axis: usize) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+        let mut removed = vec![false; self.shape[axis]];
+        for i in Self::to_indices(indices, self.shape[axis])? {
+            removed[i] = true;
+        }
+        let keep: Vec<f32> = (0..removed.len())
+            .filter(|&i| !removed[i])
+            .map(|i| i as f32)
+            .collect();
+        let n = keep.len();
+
+        return self.index_select(axis, &Tensor::new(keep, vec![n])?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arange(shape: Vec<usize>) -> Tensor {
+        let n: usize = shape.iter().product();
+
+        return Tensor::new((0..n).map(|i| i as f32).collect(), shape).unwrap();
+    }
+
+    #[test]
+    fn transpose_and_permute_move_data() {
+        let x = arange(vec![2, 3]);
+        let t = x.transpose(0, 1).unwrap();
+        assert_eq!(t.shape, [3, 2]);
+        assert_eq!(t.data.to_vec(), vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+
+        let y = arange(vec![2, 3, 4]);
+        let p = y.permute(&[2, 0, 1]).unwrap();
+        assert_eq!(p.shape, [4, 2, 3]);
+        assert_eq!(p[&[1, 1, 2][..]], y[&[1, 2, 1][..]]);
+        assert!(y.permute(&[0, 1]).is_err());
+        assert!(y.permute(&[0, 1, 1]).is_err());
+        assert!(y.permute(&[0, 1, 3]).is_err());
+        assert!(y.transpose(0, 3).is_err());
+
+        let empty = arange(vec![0, 3]);
+        assert_eq!(empty.transpose(0, 1).unwrap().shape, [3, 0]);
+    }
+
+    #[test]
+    fn append_insert_and_delete_edit_along_axis() {
+        let x = arange(vec![2, 3]);
+        let col = Tensor::new(vec![10.0, 20.0], vec![2, 1]).unwrap();
+        let appended = x.append(&col, 1).unwrap();
+        assert_eq!(appended.shape, [2, 4]);
+        assert_eq!(
+            appended.data.to_vec(),
+            vec![0.0, 1.0, 2.0, 10.0, 3.0, 4.0, 5.0, 20.0]
+        );
+        let front = x.insert(0, &col, 1).unwrap();
+        assert_eq!(front.data[..4], [10.0, 0.0, 1.0, 2.0]);
+        let rows = x.insert(1, &arange(vec![1, 3]), 0).unwrap();
+        assert_eq!(rows.data[3..6], [0.0, 1.0, 2.0]);
+        assert!(x.insert(4, &col, 1).is_err());
+        assert!(x.append(&col, 0).is_err());
+
+        let idx = Tensor::new(vec![2.0, 0.0, 2.0], vec![3]).unwrap();
+        let deleted = x.delete(&idx, 1).unwrap();
+        assert_eq!(deleted.shape, [2, 1]);
+        assert_eq!(deleted.data.to_vec(), vec![1.0, 4.0]);
+        assert!(
+            x.delete(&Tensor::new(vec![3.0], vec![1]).unwrap(), 1)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn concat_accepts_empty_pieces() {
+        let x = arange(vec![2, 3]);
+        let empty = Tensor::zeros(vec![2, 0]).unwrap();
+        let joined = Tensor::concat(&[&empty, &x, &empty, &arange(vec![2, 1])], 1).unwrap();
+        assert_eq!(joined.shape, [2, 4]);
+        assert_eq!(
+            joined.data.to_vec(),
+            vec![0.0, 1.0, 2.0, 0.0, 3.0, 4.0, 5.0, 1.0]
+        );
+
+        let none = Tensor::concat(&[&Tensor::zeros(vec![0, 3]).unwrap(); 2], 0).unwrap();
+        assert_eq!(none.shape, [0, 3]);
+        assert!(Tensor::concat(&[], 0).is_err());
+        assert!(Tensor::concat(&[&x, &empty], 0).is_err());
+        assert_eq!(x.narrow(1, 3, 0).unwrap().shape, [2, 0]);
+        assert_eq!(empty.to_string(), "Tensor([[],\n        []], shape=[2, 0])");
+    }
+}
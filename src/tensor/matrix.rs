@@ -0,0 +1,304 @@
+use super::Tensor;
+use crate::profile;
+
+impl Tensor {
+    pub(crate) fn matrix_dims(&self, op: &str) -> Result<(usize, usize), String> {
+        if self.shape.len() != 2 {
+            return Err(format!("{} 需要二维张量，实际形状为 {:?}", op, self.shape));
+        }
+
+        return Ok((self.shape[0], self.shape[1]));
+    }
+
+    fn check_row(&self, op: &str, i: usize) -> Result<usize, String> {
+        let (rows, cols) = self.matrix_dims(op)?;
+        if i >= rows {
+            return Err(format!("行索引 {} 超出行数 {}", i, rows));
+        }
+
+        return Ok(cols);
+    }
+
+    fn check_column(&self, op: &str, j: usize) -> Result<(usize, usize), String> {
+        let (rows, cols) = self.matrix_dims(op)?;
+        if j >= cols {
+            return Err(format!("列索引 {} 超出列数 {}", j, cols));
+        }
+
+        return Ok((rows, cols));
+    }
+
+    pub fn row(&self, i: usize) -> Result<&[f32], String> {
+        let cols = self.check_row("row", i)?;
+
+        return Ok(&self.data[i * cols..(i + 1) * cols]);
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> Result<&mut [f32], String> {
+        self.check_writable("row_mut")?;
+        let cols = self.check_row("row_mut", i)?;
+
+        return Ok(&mut self.data[i * cols..(i + 1) * cols]);
+    }
+
+    pub fn set_row(&mut self, i: usize, values: &[f32]) -> Result<(), String> {
+        let row = self.row_mut(i)?;
+        if values.len() != row.len() {
+            return Err(format!(
+                "行长度 {} 与列数 {} 不匹配",
+                values.len(),
+                row.len()
+            ));
+        }
+        row.copy_from_slice(values);
+
+        return Ok(());
+    }
+
+    // 行主序下列不连续，只能返回拷贝
+    pub fn column(&self, j: usize) -> Result<Tensor, String> {
+        let (rows, cols) = self.check_column("column", j)?;
+        let data = (0..rows).map(|i| self.data[i * cols + j]).collect();
+
+        return Ok(self
+            .placed(Tensor::new(data, vec![rows])?)
+            .with_dtype(self.dtype));
+    }
+
+    pub fn set_column(&mut self, j: usize, values: &[f32]) -> Result<(), String> {
+        self.check_writable("set_column")?;
+        let (rows, cols) = self.check_column("set_column", j)?;
+        if values.len() != rows {
+            return Err(format!("列长度 {} 与行数 {} 不匹配", values.len(), rows));
+        }
+        for (i, &v) in values.iter().enumerate() {
+            self.data[i * cols + j] = v;
+        }
+
+        return Ok(());
+    }
+
+    pub fn select_columns(&self, columns: &[usize]) -> Result<Tensor, String> {
+        let (rows, cols) = self.matrix_dims("select_columns")?;
+        if let Some(&bad) = columns.iter().find(|&&j| j >= cols) {
+            return Err(format!("列索引 {} 超出列数 {}", bad, cols));
+        }
+        let _scope = profile::scope("select_columns", rows * columns.len());
+
+        let mut data = crate::alloc::allocate(rows * columns.len());
+        for row in self.data.chunks_exact(cols.max(1)).take(rows) {
+            data.extend(columns.iter().map(|&j| row[j]));
+        }
+
+        let out = Tensor::new(data, vec![rows, columns.len()])?;
+
+        return Ok(self.placed(out).with_dtype(self.dtype));
+    }
+
+    pub fn from_blocks(blocks: &[&[&Tensor]]) -> Result<Tensor, String> {
+        let mut width = None;
+        let mut heights = Vec::with_capacity(blocks.len());
+        for (r, row) in blocks.iter().enumerate() {
+            if row.is_empty() {
+                return Err(format!("块矩阵第 {} 行为空", r));
+            }
+            let mut row_width = 0;
+            for (c, block) in row.iter().enumerate() {
+                let (h, w) = block.matrix_dims("from_blocks")?;
+                if h != row[0].shape[0] {
+                    return Err(format!(
+                        "块 ({}, {}) 的行数 {} 与同一行首块的行数 {} 不一致",
+                        r, c, h, row[0].shape[0]
+                    ));
+                }
+                row_width += w;
+            }
+            if *width.get_or_insert(row_width) != row_width {
+                return Err(format!(
+                    "块矩阵第 {} 行总列数 {} 与第 0 行的 {} 不一致",
+                    r,
+                    row_width,
+                    width.unwrap()
+                ));
+            }
+            heights.push(row[0].shape[0]);
+        }
+
+        let cols = width.unwrap_or(0);
+        let rows: usize = heights.iter().sum();
+        let _scope = profile::scope("from_blocks", rows * cols);
+
+        let mut data = crate::alloc::allocate(rows * cols);
+        for (row, &h) in blocks.iter().zip(&heights) {
+            for i in 0..h {
+                for block in row.iter() {
+                    data.extend_from_slice(block.row(i)?);
+                }
+            }
+        }
+
+        let inputs: Vec<&Tensor> = blocks.iter().flat_map(|row| row.iter().copied()).collect();
+
+        return Tensor::joined("from_blocks", &inputs, data, vec![rows, cols]);
+    }
+
+    fn diagonal_positions(&self, op: &str, offset: isize) -> Result<(usize, Vec<usize>), String> {
+        let rank = self.shape.len();
+        if rank < 2 {
+            return Err(format!(
+                "{} 需要形状为 [..., M, N] 的张量，实际形状为 {:?}",
+                op, self.shape
+            ));
+        }
+        let (m, n) = (self.shape[rank - 2], self.shape[rank - 1]);
+        let (r0, c0) = if offset >= 0 {
+            (0, offset.unsigned_abs())
+        } else {
+            (offset.unsigned_abs(), 0)
+        };
+        let len = m.saturating_sub(r0).min(n.saturating_sub(c0));
+        let positions = (0..len).map(|k| (r0 + k) * n + c0 + k).collect();
+
+        return Ok((m * n, positions));
+    }
+
+    // 返回复制出的新张量而不是视图：张量总是按行优先连续存放，对角线元素的步长为 n + 1，
+    // 无法用共享缓冲区表示；改写结果不会影响 self，原地修改对角线用 fill_diagonal_
+    pub fn diagonal(&self, offset: isize) -> Result<Tensor, String> {
+        let (plane, positions) = self.diagonal_positions("diagonal", offset)?;
+        let batch = self.data.len().checked_div(plane).unwrap_or(0);
+
+        let mut data = crate::alloc::allocate(batch * positions.len());
+        for b in 0..batch {
+            data.extend(positions.iter().map(|&p| self.data[b * plane + p]));
+        }
+
+        let mut shape = self.shape[..self.shape.len() - 2].to_vec();
+        shape.push(positions.len());
+
+        return Ok(self
+            .placed(Tensor::new(data, shape)?)
+            .with_dtype(self.dtype));
+    }
+
+    pub fn fill_diagonal_(&mut self, value: f32) -> Result<(), String> {
+        self.check_writable("fill_diagonal_")?;
+        let (plane, positions) = self.diagonal_positions("fill_diagonal_", 0)?;
+        if plane == 0 {
+            return Ok(());
+        }
+        for matrix in self.data.chunks_exact_mut(plane) {
+            for &p in &positions {
+                matrix[p] = value;
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn diag_embed(&self, offset: isize) -> Result<Tensor, String> {
+        let Some(&len) = self.shape.last() else {
+            return Err("diag_embed 需要至少一维的张量".to_string());
+        };
+        let size = len + offset.unsigned_abs();
+        let batch = self.data.len().checked_div(len).unwrap_or(0);
+
+        let mut shape = self.shape[..self.shape.len() - 1].to_vec();
+        shape.extend([size, size]);
+        let mut out = self.placed(Tensor::zeros(shape)?);
+        let (plane, positions) = out.diagonal_positions("diag_embed", offset)?;
+        for b in 0..batch {
+            for (k, &p) in positions.iter().enumerate() {
+                out.data[b * plane + p] = self.data[b * len + k];
+            }
+        }
+
+        return Ok(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_are_slices_and_columns_are_copies() {
+        let mut x = Tensor::new((0..6).map(|i| i as f32).collect(), vec![2, 3]).unwrap();
+        assert_eq!(x.row(1).unwrap(), [3.0, 4.0, 5.0]);
+        x.row_mut(0).unwrap()[2] = -1.0;
+        assert_eq!(x.column(2).unwrap().data.to_vec(), vec![-1.0, 5.0]);
+
+        x.set_row(1, &[7.0, 8.0, 9.0]).unwrap();
+        x.set_column(0, &[10.0, 20.0]).unwrap();
+        assert_eq!(x.data.to_vec(), vec![10.0, 1.0, -1.0, 20.0, 8.0, 9.0]);
+
+        let picked = x.select_columns(&[2, 0, 2]).unwrap();
+        assert_eq!(picked.shape, [2, 3]);
+        assert_eq!(picked.data.to_vec(), vec![-1.0, 10.0, -1.0, 9.0, 20.0, 9.0]);
+
+        assert!(x.row(2).is_err());
+        assert!(x.set_row(0, &[1.0]).is_err());
+        assert!(x.set_column(3, &[1.0, 2.0]).is_err());
+        assert!(x.select_columns(&[3]).is_err());
+        assert!(Tensor::zeros(vec![3]).unwrap().column(0).is_err());
+        assert_eq!(
+            Tensor::zeros(vec![2, 0])
+                .unwrap()
+                .select_columns(&[])
+                .unwrap()
+                .shape,
+            [2, 0]
+        );
+    }
+
+    #[test]
+    fn from_blocks_assembles_grid_like_np_block() {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        let b = Tensor::new(vec![5.0, 6.0], vec![2, 1]).unwrap();
+        let c = Tensor::new(vec![7.0, 8.0, 9.0], vec![1, 3]).unwrap();
+        let m = Tensor::from_blocks(&[&[&a, &b], &[&c]]).unwrap();
+        assert_eq!(m.shape, [3, 3]);
+        assert_eq!(
+            m.data.to_vec(),
+            vec![1.0, 2.0, 5.0, 3.0, 4.0, 6.0, 7.0, 8.0, 9.0]
+        );
+
+        assert!(Tensor::from_blocks(&[&[&a, &c]]).is_err());
+        assert!(Tensor::from_blocks(&[&[&a], &[&c]]).is_err());
+        assert!(Tensor::from_blocks(&[&[]]).is_err());
+        assert_eq!(Tensor::from_blocks(&[]).unwrap().shape, [0, 0]);
+    }
+
+    #[test]
+    fn diagonal_fill_and_embed_are_batched() {
+        let x = Tensor::new((0..12).map(|i| i as f32).collect(), vec![2, 2, 3]).unwrap();
+        let d = x.diagonal(0).unwrap();
+        assert_eq!(d.shape, [2, 2]);
+        assert_eq!(d.data.to_vec(), vec![0.0, 4.0, 6.0, 10.0]);
+        assert_eq!(
+            x.diagonal(1).unwrap().data.to_vec(),
+            vec![1.0, 5.0, 7.0, 11.0]
+        );
+        assert_eq!(x.diagonal(-1).unwrap().data.to_vec(), vec![3.0, 9.0]);
+        assert_eq!(x.diagonal(5).unwrap().shape, [2, 0]);
+
+        let mut eye = Tensor::zeros(vec![3, 2]).unwrap();
+        eye.fill_diagonal_(1.0).unwrap();
+        assert_eq!(eye.data.to_vec(), vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+        let v = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        let m = v.diag_embed(0).unwrap();
+        assert_eq!(m.shape, [2, 2, 2]);
+        assert_eq!(m.diagonal(0).unwrap(), v);
+        let upper = v.diag_embed(1).unwrap();
+        assert_eq!(upper.shape, [2, 3, 3]);
+        assert_eq!(upper.diagonal(1).unwrap(), v);
+        assert!(Tensor::zeros(vec![3]).unwrap().diagonal(0).is_err());
+
+        // 对角线是副本，改写它不影响原矩阵
+        let mut copy = m.diagonal(0).unwrap();
+        assert!(!copy.shares_storage(&m));
+        copy.data_mut().unwrap()[0] = 100.0;
+        assert_eq!(m.data[0], 1.0);
+    }
+}
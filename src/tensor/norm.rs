@@ -0,0 +1,402 @@
+use super::Tensor;
+use crate::{check, parallel, profile};
+
+// Welford 单遍同时得到均值与方差，返回 (平移, 缩放)
+fn layer_stats(row: &[f32], eps: f32) -> (f32, f32) {
+    let (mut mean, mut m2) = (0.0f64, 0.0f64);
+    for (i, &x) in row.iter().enumerate() {
+        let delta = x as f64 - mean;
+        mean += delta / (i + 1) as f64;
+        m2 += delta * (x as f64 - mean);
+    }
+    let var = m2 / row.len() as f64;
+
+    return (mean as f32, 1.0 / (var + eps as f64).sqrt() as f32);
+}
+
+fn rms_stats(row: &[f32], eps: f32) -> (f32, f32) {
+    let mean_sq = row.iter().map(|&x| x as f64 * x as f64).sum::<f64>() / row.len() as f64;
+
+    return (0.0, 1.0 / (mean_sq + eps as f64).sqrt() as f32);
+}
+
+fn affine(weight: Option<&Tensor>, bias: Option<&Tensor>, i: usize, mut y: f32) -> f32 {
+    if let Some(w) = weight {
+        y *= w.data[i];
+    }
+    if let Some(b) = bias {
+        y += b.data[i];
+    }
+
+    return y;
+}
+
+impl Tensor {
+    // 检查 normalized_shape 是输入的末尾维度且参数形状一致，返回每行的长度
+    fn check_rows(
+        &self,
+        op: &str,
+        normalized_shape: &[usize],
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+    ) -> Result<usize, String> {
+        let rank = self.shape.len();
+        if normalized_shape.len() > rank
+            || self.shape[rank - normalized_shape.len()..] != *normalized_shape
+        {
+            return Err(format!(
+                "{} 的归一化形状 {:?} 不是输入形状 {:?} 的末尾维度",
+                op, normalized_shape, self.shape
+            ));
+        }
+        for (name, param) in [("权重", weight), ("偏置", bias)] {
+            if let Some(p) = param
+                && p.shape != normalized_shape
+            {
+                return Err(format!(
+                    "{} 的{}形状 {:?} 应为 {:?}",
+                    op, name, p.shape, normalized_shape
+                ));
+            }
+        }
+
+        return Ok(normalized_shape.iter().product());
+    }
+
+    // 返回 (N, C)
+    fn check_groups(
+        &self,
+        op: &str,
+        groups: usize,
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+    ) -> Result<(usize, usize), String> {
+        if self.shape.len() != 4 {
+            return Err(format!(
+                "{} 需要 [N, C, H, W] 输入，实际形状为 {:?}",
+                op, self.shape
+            ));
+        }
+        let (n, c) = (self.shape[0], self.shape[1]);
+        if groups == 0 || !c.is_multiple_of(groups) {
+            return Err(format!("通道数 {} 无法均分为 {} 组", c, groups));
+        }
+        for (name, param) in [("权重", weight), ("偏置", bias)] {
+            if let Some(p) = param
+                && p.shape != [c]
+            {
+                return Err(format!("{} 的{}形状 {:?} 应为 [{}]", op, name, p.shape, c));
+            }
+        }
+
+        return Ok((n, c));
+    }
+
+    // 对末尾 normalized_shape 维组成的每一行求 (平移, 缩放)，再叠加逐元素仿射
+    fn normalize_rows<F>(
+        &self,
+        op: &'static str,
+        normalized_shape: &[usize],
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+        stats: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(&[f32]) -> (f32, f32) + Sync,
+    {
+        let len = self.check_rows(op, normalized_shape, weight, bias)?;
+        let count = self.data.len().checked_div(len).unwrap_or(0);
+        let _scope = profile::scope(op, self.data.len());
+
+        let rows = parallel::map_range(count, self.data.len(), |r| {
+            let row = &self.data[r * len..(r + 1) * len];
+            let (shift, scale) = stats(row);
+            row.iter()
+                .enumerate()
+                .map(|(i, &x)| affine(weight, bias, i, (x - shift) * scale))
+                .collect::<Vec<f32>>()
+        });
+
+        let mut data = crate::alloc::allocate(self.data.len());
+        for row in rows {
+            data.extend(row);
+        }
+
+        let out = self.placed(Tensor::new(data, self.shape.clone())?);
+        check::inspect(op, &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 原地版本先求出每行的 (平移, 缩放)，再按扁平下标逐元素算出结果，检查通过后整体写回
+    fn normalize_rows_<S, A>(
+        &mut self,
+        op: &'static str,
+        len: usize,
+        stats: S,
+        affine: A,
+    ) -> Result<(), String>
+    where
+        S: Fn(&[f32]) -> (f32, f32) + Sync,
+        A: Fn(usize, f32) -> f32 + Sync,
+    {
+        self.check_writable(op)?;
+        let count = self.data.len().checked_div(len).unwrap_or(0);
+        let _scope = profile::scope(op, self.data.len());
+
+        let params = parallel::map_range(count, self.data.len(), |r| {
+            stats(&self.data[r * len..(r + 1) * len])
+        });
+        let mut data = crate::alloc::allocate(self.data.len());
+        data.resize(self.data.len(), 0.0);
+        parallel::fill_chunks(&mut data, |start, out| {
+            for (k, o) in out.iter_mut().enumerate() {
+                let i = start + k;
+                let (shift, scale) = params[i / len];
+                *o = affine(i, (self.data[i] - shift) * scale);
+            }
+        });
+        // Error 模式下检查失败时 self 保持原样
+        let out = Tensor::new(data, self.shape.clone())?;
+        check::inspect(op, &[&self.shape], &out)?;
+        self.data.copy_from_slice(&out.data);
+
+        return Ok(());
+    }
+
+    pub fn layer_norm(
+        &self,
+        normalized_shape: &[usize],
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<Tensor, String> {
+        return self.normalize_rows("layer_norm", normalized_shape, weight, bias, |row| {
+            layer_stats(row, eps)
+        });
+    }
+
+    // 与 layer_norm 共用行归一化，只是每行由一组通道的全部空间位置组成
+    pub fn group_norm(
+        &self,
+        groups: usize,
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<Tensor, String> {
+        let (n, c) = self.check_groups("group_norm", groups, weight, bias)?;
+
+        let row = self.data.len().checked_div(n * groups).unwrap_or(0);
+        let mut out = self
+            .reshaped(vec![n, groups, row])?
+            .layer_norm(&[row], None, None, eps)?
+            .reshaped(self.shape.clone())?;
+        if let Some(w) = weight {
+            out = out.mul(&w.reshaped(vec![c, 1, 1])?)?;
+        }
+        if let Some(b) = bias {
+            out = out.add(&b.reshaped(vec![c, 1, 1])?)?;
+        }
+
+        return Ok(out);
+    }
+
+    pub fn instance_norm(
+        &self,
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<Tensor, String> {
+        let channels = self.shape.get(1).copied().unwrap_or(0);
+
+        return self.group_norm(channels, weight, bias, eps);
+    }
+
+    pub fn rms_norm(
+        &self,
+        normalized_shape: &[usize],
+        weight: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<Tensor, String> {
+        return self.normalize_rows("rms_norm", normalized_shape, weight, None, |row| {
+            rms_stats(row, eps)
+        });
+    }
+
+    pub fn layer_norm_(
+        &mut self,
+        normalized_shape: &[usize],
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<(), String> {
+        let len = self.check_rows("layer_norm_", normalized_shape, weight, bias)?;
+
+        return self.normalize_rows_(
+            "layer_norm_",
+            len,
+            |row| layer_stats(row, eps),
+            |i, y| affine(weight, bias, i % len, y),
+        );
+    }
+
+    pub fn group_norm_(
+        &mut self,
+        groups: usize,
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<(), String> {
+        let (n, c) = self.check_groups("group_norm_", groups, weight, bias)?;
+        let row = self.data.len().checked_div(n * groups).unwrap_or(0);
+        let plane = self.data.len().checked_div(n * c).unwrap_or(0);
+
+        return self.normalize_rows_(
+            "group_norm_",
+            row,
+            |row| layer_stats(row, eps),
+            |i, y| affine(weight, bias, (i / plane) % c, y),
+        );
+    }
+
+    pub fn instance_norm_(
+        &mut self,
+        weight: Option<&Tensor>,
+        bias: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<(), String> {
+        let channels = self.shape.get(1).copied().unwrap_or(0);
+
+        return self.group_norm_(channels, weight, bias, eps);
+    }
+
+    pub fn rms_norm_(
+        &mut self,
+        normalized_shape: &[usize],
+        weight: Option<&Tensor>,
+        eps: f32,
+    ) -> Result<(), String> {
+        let len = self.check_rows("rms_norm_", normalized_shape, weight, None)?;
+
+        return self.normalize_rows_(
+            "rms_norm_",
+            len,
+            |row| rms_stats(row, eps),
+            |i, y| affine(weight, None, i % len, y),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-5, "{:?} 与 {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn layer_norm_normalizes_trailing_dims_and_applies_affine() {
+        let x = Tensor::new((0..12).map(|i| i as f32).collect(), vec![2, 2, 3]).unwrap();
+        let y = x.layer_norm(&[2, 3], None, None, 0.0).unwrap();
+        let (z, _) = x.reshaped(vec![2, 6]).unwrap().standardize(1).unwrap();
+        assert_close(&y.data, &z.data);
+
+        let weight = Tensor::full(vec![3], 2.0).unwrap();
+        let bias = Tensor::new(vec![1.0, 0.0, -1.0], vec![3]).unwrap();
+        let y = x.layer_norm(&[3], Some(&weight), Some(&bias), 0.0).unwrap();
+        let s = 1.5f32.sqrt();
+        assert_close(&y.data[..3], &[1.0 - 2.0 * s, 0.0, -1.0 + 2.0 * s]);
+
+        assert!(x.layer_norm(&[2], None, None, 1e-5).is_err());
+        assert!(
+            x.layer_norm(&[3], Some(&bias.reshaped(vec![1, 3]).unwrap()), None, 1e-5)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rms_norm_scales_by_root_mean_square() {
+        let x = Tensor::new(vec![3.0, 4.0, 0.0, 0.0], vec![2, 2]).unwrap();
+        let weight = Tensor::new(vec![1.0, 2.0], vec![2]).unwrap();
+        let y = x.rms_norm(&[2], Some(&weight), 0.0).unwrap();
+        let rms = 12.5f32.sqrt();
+        assert_close(&y.data[..2], &[3.0 / rms, 8.0 / rms]);
+        // eps 保证全零行不会除以 0
+        let y = x.rms_norm(&[2], None, 1e-6).unwrap();
+        assert_eq!(y.data[2..], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn group_and_instance_norm_reduce_over_channel_groups() {
+        let x = Tensor::rand_normal(vec![2, 4, 3, 3], 1.0, 2.0).unwrap();
+        let y = x.group_norm(2, None, None, 0.0).unwrap();
+        // 每组 2 个通道 × 9 个位置标准化为零均值单位方差
+        let groups = y.reshaped(vec![4, 18]).unwrap();
+        assert_close(&groups.mean_axis(1).unwrap().data, &[0.0; 4]);
+        assert_close(&groups.var_axis(1).unwrap().data, &[1.0; 4]);
+
+        let weight = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![4]).unwrap();
+        let bias = Tensor::full(vec![4], 0.5).unwrap();
+        let inst = x.instance_norm(Some(&weight), Some(&bias), 0.0).unwrap();
+        let plain = x.group_norm(4, None, None, 0.0).unwrap();
+        assert_close(
+            &inst.data[9..18],
+            &plain.mul_scalar(2.0).unwrap().add_scalar(0.5).unwrap().data[9..18],
+        );
+
+        assert!(x.group_norm(3, None, None, 1e-5).is_err());
+        assert!(
+            x.group_norm(2, Some(&bias.reshaped(vec![2, 2]).unwrap()), None, 1e-5)
+                .is_err()
+        );
+        assert!(
+            Tensor::zeros(vec![2, 4])
+                .unwrap()
+                .instance_norm(None, None, 1e-5)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn in_place_norms_match_out_of_place() {
+        let x = Tensor::rand_normal(vec![2, 4, 2, 3], 1.0, 2.0).unwrap();
+        let weight = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![4]).unwrap();
+        let bias = Tensor::full(vec![4], 0.5).unwrap();
+
+        let mut y = x.clone();
+        y.group_norm_(2, Some(&weight), Some(&bias), 1e-5).unwrap();
+        assert_close(
+            &y.data,
+            &x.group_norm(2, Some(&weight), Some(&bias), 1e-5)
+                .unwrap()
+                .data,
+        );
+
+        let mut y = x.clone();
+        y.instance_norm_(None, Some(&bias), 1e-5).unwrap();
+        assert_close(
+            &y.data,
+            &x.instance_norm(None, Some(&bias), 1e-5).unwrap().data,
+        );
+
+        let row = Tensor::new(vec![1.0, -1.0, 2.0], vec![3]).unwrap();
+        let mut y = x.clone();
+        y.layer_norm_(&[3], Some(&row), Some(&row), 1e-5).unwrap();
+        assert_close(
+            &y.data,
+            &x.layer_norm(&[3], Some(&row), Some(&row), 1e-5)
+                .unwrap()
+                .data,
+        );
+
+        let mut y = x.clone();
+        y.rms_norm_(&[2, 3], None, 1e-6).unwrap();
+        assert_close(&y.data, &x.rms_norm(&[2, 3], None, 1e-6).unwrap().data);
+
+        assert!(y.layer_norm_(&[4], None, None, 1e-5).is_err());
+        assert!(y.group_norm_(3, None, None, 1e-5).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use super::Tensor;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinMaxStats {
+    pub min: Tensor,
+    pub max: Tensor,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZScoreStats {
+    pub mean: Tensor,
+    pub std: Tensor,
+}
+
+impl MinMaxStats {
+    pub fn transform(&self, x: &Tensor) -> Result<Tensor, String> {
+        let range = self.max.zip_with("minmax_normalize", &self.min, |hi, lo| {
+            if hi > lo { hi - lo } else { 1.0 }
+        })?;
+        let shifted = x.zip_with("minmax_normalize", &self.min, |v, lo| v - lo)?;
+
+        return shifted.zip_with("minmax_normalize", &range, |v, r| v / r);
+    }
+
+    pub fn inverse_transform(&self, x: &Tensor) -> Result<Tensor, String> {
+        let range = self.max.zip_with("minmax_inverse", &self.min, |hi, lo| {
+            if hi > lo { hi - lo } else { 1.0 }
+        })?;
+        let scaled = x.zip_with("minmax_inverse", &range, |v, r| v * r)?;
+
+        return scaled.zip_with("minmax_inverse", &self.min, |v, lo| v + lo);
+    }
+
+    // 原地版本分两步写入：先在 x 的副本上完成，两步都成功后才替换 x，
+    // 第二步出错（如检查模式发现 Inf）时 x 保持原样
+    pub fn transform_(&self, x: &mut Tensor) -> Result<(), String> {
+        let range = self
+            .max
+            .zip_with("minmax_normalize_", &self.min, |hi, lo| {
+                if hi > lo { hi - lo } else { 1.0 }
+            })?;
+        let mut out = x.clone();
+        out.zip_with_("minmax_normalize_", &self.min, |v, lo| v - lo)?;
+        out.zip_with_("minmax_normalize_", &range, |v, r| v / r)?;
+        *x = out;
+
+        return Ok(());
+    }
+
+    pub fn inverse_transform_(&self, x: &mut Tensor) -> Result<(), String> {
+        let range = self.max.zip_with("minmax_inverse_", &self.min, |hi, lo| {
+            if hi > lo { hi - lo } else { 1.0 }
+        })?;
+        let mut out = x.clone();
+        out.zip_with_("minmax_inverse_", &range, |v, r| v * r)?;
+        out.zip_with_("minmax_inverse_", &self.min, |v, lo| v + lo)?;
+        *x = out;
+
+        return Ok(());
+    }
+}
+
+impl ZScoreStats {
+    pub fn transform(&self, x: &Tensor) -> Result<Tensor, String> {
+        let centered = x.zip_with("standardize", &self.mean, |v, m| v - m)?;
+
+        return centered.zip_with(
+            "standardize",
+            &self.std,
+            |v, s| if s > 0.0 { v / s } else { v },
+        );
+    }
+
+    pub fn inverse_transform(&self, x: &Tensor) -> Result<Tensor, String> {
+        let scaled = x.zip_with("standardize_inverse", &self.std, |v, s| {
+            if s > 0.0 { v * s } else { v }
+        })?;
+
+        return scaled.zip_with("standardize_inverse", &self.mean, |v, m| v + m);
+    }
+
+    // 与 MinMaxStats 相同，两步都在副本上完成后才替换 x
+    pub fn transform_(&self, x: &mut Tensor) -> Result<(), String> {
+        let mut out = x.clone();
+        out.zip_with_("standardize_", &self.mean, |v, m| v - m)?;
+        out.zip_with_(
+            "standardize_",
+            &self.std,
+            |v, s| if s > 0.0 { v / s } else { v },
+        )?;
+        *x = out;
+
+        return Ok(());
+    }
+
+    pub fn inverse_transform_(&self, x: &mut Tensor) -> Result<(), String> {
+        let mut out = x.clone();
+        out.zip_with_("standardize_inverse_", &self.std, |v, s| {
+            if s > 0.0 { v * s } else { v }
+        })?;
+        out.zip_with_("standardize_inverse_", &self.mean, |v, m| v + m)?;
+        *x = out;
+
+        return Ok(());
+    }
+}
+
+impl Tensor {
+    fn keep_axis(&self, reduced: Tensor, axis: usize) -> Result<Tensor, String> {
+        let mut shape = self.shape.clone();
+        shape[axis] = 1;
+
+        return reduced.reshaped(shape);
+    }
+
+    pub fn minmax_normalize(&self, axis: usize) -> Result<(Tensor, MinMaxStats), String> {
+        let stats = MinMaxStats {
+            min: self.keep_axis(self.min_axis(axis)?, axis)?,
+            max: self.keep_axis(self.max_axis(axis)?, axis)?,
+        };
+        let normalized = stats.transform(self)?;
+
+        return Ok((normalized, stats));
+    }
+
+    pub fn standardize(&self, axis: usize) -> Result<(Tensor, ZScoreStats), String> {
+        let stats = ZScoreStats {
+            mean: self.keep_axis(self.mean_axis(axis)?, axis)?,
+            std: self.keep_axis(self.std_axis(axis)?, axis)?,
+        };
+        let normalized = stats.transform(self)?;
+
+        return Ok((normalized, stats));
+    }
+
+    pub fn minmax_normalize_(&mut self, axis: usize) -> Result<MinMaxStats, String> {
+        self.check_writable("minmax_normalize_")?;
+        let stats = MinMaxStats {
+            min: self.keep_axis(self.min_axis(axis)?, axis)?,
+            max: self.keep_axis(self.max_axis(axis)?, axis)?,
+        };
+        stats.transform_(self)?;
+
+        return Ok(stats);
+    }
+
+    pub fn standardize_(&mut self, axis: usize) -> Result<ZScoreStats, String> {
+        self.check_writable("standardize_")?;
+        let stats = ZScoreStats {
+            mean: self.keep_axis(self.mean_axis(axis)?, axis)?,
+            std: self.keep_axis(self.std_axis(axis)?, axis)?,
+        };
+        stats.transform_(self)?;
+
+        return Ok(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(data: Vec<f32>, shape: Vec<usize>) -> Tensor {
+        return Tensor::new(data, shape).unwrap();
+    }
+
+    fn assert_close(a: &Tensor, b: &Tensor) {
+        assert_eq!(a.shape, b.shape);
+        for (x, y) in a.data.iter().zip(b.data.iter()) {
+            assert!((x - y).abs() < 1e-5, "{:?} 与 {:?}", a.data, b.data);
+        }
+    }
+
+    #[test]
+    fn minmax_round_trip_and_constant_columns() {
+        let x = t(vec![1.0, 5.0, 3.0, 5.0, 5.0, 5.0], vec![3, 2]);
+        let (normalized, stats) = x.minmax_normalize(0).unwrap();
+        assert_eq!(normalized.data.to_vec(), vec![0.0, 0.0, 0.5, 0.0, 1.0, 0.0]);
+        assert_close(&stats.inverse_transform(&normalized).unwrap(), &x);
+    }
+
+    #[test]
+    fn standardize_round_trip() {
+        let x = t(vec![1.0, 2.0, 3.0, 7.0, 7.0, 7.0], vec![2, 3]);
+        let (z, stats) = x.standardize(1).unwrap();
+        assert_close(&z.mean_axis(1).unwrap(), &t(vec![0.0, 0.0], vec![2]));
+        // 标准差为 0 的行只做中心化
+        assert_eq!(z.data[3..].to_vec(), vec![0.0, 0.0, 0.0]);
+        assert_close(&stats.inverse_transform(&z).unwrap(), &x);
+
+        // 统计量可以应用到新数据上
+        let other = stats
+            .transform(&t(vec![2.0, 2.0, 2.0, 8.0, 8.0, 8.0], vec![2, 3]))
+            .unwrap();
+        assert_eq!(other.data[3], 1.0);
+    }
+
+    #[test]
+    fn invalid_axis_is_rejected() {
+        let x = t(vec![1.0, 2.0], vec![2]);
+        assert!(x.minmax_normalize(1).is_err());
+        assert!(x.standardize(1).is_err());
+    }
+
+    #[test]
+    fn in_place_normalization_matches_out_of_place() {
+        let x = t(vec![1.0, 5.0, 3.0, 5.0, 4.0, 6.0], vec![3, 2]);
+        let (z, stats) = x.standardize(0).unwrap();
+        let mut y = x.clone();
+        assert_eq!(y.standardize_(0).unwrap(), stats);
+        assert_close(&y, &z);
+        stats.inverse_transform_(&mut y).unwrap();
+        assert_close(&y, &x);
+
+        let (m, stats) = x.minmax_normalize(1).unwrap();
+        let mut y = x.clone();
+        assert_eq!(y.minmax_normalize_(1).unwrap(), stats);
+        assert_close(&y, &m);
+        stats.inverse_transform_(&mut y).unwrap();
+        assert_close(&y, &x);
+        assert!(y.standardize_(2).is_err());
+    }
+
+    #[test]
+    fn failed_in_place_transforms_leave_x_untouched() {
+        // 第一步结果有限，第二步溢出为 Inf，检查模式在第二步报错
+        let minmax = MinMaxStats {
+            min: t(vec![0.0], vec![1]),
+            max: t(vec![1e-45], vec![1]),
+        };
+        let shifted = MinMaxStats {
+            min: t(vec![3e38], vec![1]),
+            max: t(vec![3.4e38], vec![1]),
+        };
+        let zscore = ZScoreStats {
+            mean: t(vec![0.0], vec![1]),
+            std: t(vec![1e-45], vec![1]),
+        };
+        let x = t(vec![1e30, 0.5], vec![2]);
+        crate::check::with_mode(crate::check::CheckMode::Error, || {
+            let mut y = x.clone();
+            assert!(minmax.transform_(&mut y).is_err());
+            assert_eq!(y, x);
+            let mut scaled = t(vec![5.0, 0.5], vec![2]);
+            assert!(shifted.inverse_transform_(&mut scaled).is_err());
+            assert_eq!(scaled, t(vec![5.0, 0.5], vec![2]));
+            assert!(zscore.transform_(&mut y).is_err());
+            assert_eq!(y, x);
+        });
+    }
+}
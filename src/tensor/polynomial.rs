@@ -0,0 +1,128 @@
+use super::Tensor;
+
+// Householder QR 最小二乘，在 f64 中求解以避免 Vandermonde 矩阵的病态放大
+fn least_squares(mut a: Vec<f64>, m: usize, n: usize, mut b: Vec<f64>) -> Result<Vec<f64>, String> {
+    for k in 0..n {
+        let norm = (k..m)
+            .map(|i| a[i * n + k] * a[i * n + k])
+            .sum::<f64>()
+            .sqrt();
+        if norm < 1e-12 {
+            return Err(format!("最小二乘矩阵第 {} 列秩亏", k));
+        }
+        let alpha = if a[k * n + k] > 0.0 { -norm } else { norm };
+        let mut v: Vec<f64> = (k..m).map(|i| a[i * n + k]).collect();
+        v[0] -= alpha;
+        let vnorm = v.iter().map(|x| x * x).sum::<f64>();
+        for j in k..n {
+            let dot: f64 = (k..m).map(|i| v[i - k] * a[i * n + j]).sum();
+            for i in k..m {
+                a[i * n + j] -= 2.0 * v[i - k] * dot / vnorm;
+            }
+        }
+        let dot: f64 = (k..m).map(|i| v[i - k] * b[i]).sum();
+        for i in k..m {
+            b[i] -= 2.0 * v[i - k] * dot / vnorm;
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        let rest: f64 = (k + 1..n).map(|j| a[k * n + j] * x[j]).sum();
+        x[k] = (b[k] - rest) / a[k * n + k];
+    }
+
+    return Ok(x);
+}
+
+impl Tensor {
+    // 系数按降幂排列，与 np.polyval 一致
+    pub fn polyval(&self, coeffs: &Tensor) -> Result<Tensor, String> {
+        if coeffs.shape.len() != 1 {
+            return Err(format!(
+                "polyval 需要一维系数张量，实际形状为 {:?}",
+                coeffs.shape
+            ));
+        }
+
+        return self.map_float("polyval", |x| {
+            coeffs.data.iter().fold(0.0, |acc, &c| acc * x + c)
+        });
+    }
+
+    pub fn polyval_(&mut self, coeffs: &Tensor) -> Result<(), String> {
+        if coeffs.shape.len() != 1 {
+            return Err(format!(
+                "polyval_ 需要一维系数张量，实际形状为 {:?}",
+                coeffs.shape
+            ));
+        }
+
+        return self.map_float_("polyval_", |x| {
+            coeffs.data.iter().fold(0.0, |acc, &c| acc * x + c)
+        });
+    }
+
+    pub fn polyfit(x: &Tensor, y: &Tensor, degree: usize) -> Result<Tensor, String> {
+        if x.shape.len() != 1 || x.shape != y.shape {
+            return Err(format!(
+                "polyfit 需要等长的一维 x 与 y，实际形状为 {:?} 与 {:?}",
+                x.shape, y.shape
+            ));
+        }
+        let (m, n) = (x.shape[0], degree + 1);
+        if m < n {
+            return Err(format!("{} 个样本不足以拟合 {} 次多项式", m, degree));
+        }
+
+        let mut vandermonde = Vec::with_capacity(m * n);
+        for &xi in x.data.iter() {
+            let xi = xi as f64;
+            vandermonde.extend((0..n).map(|j| xi.powi((n - 1 - j) as i32)));
+        }
+        let b = y.data.iter().map(|&v| v as f64).collect();
+        let coeffs = least_squares(vandermonde, m, n, b)?;
+
+        return Ok(x.placed(Tensor::new(
+            coeffs.into_iter().map(|c| c as f32).collect(),
+            vec![n],
+        )?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polyfit_recovers_coefficients_and_polyval_evaluates_them() {
+        let x = Tensor::new((0..20).map(|i| i as f32 * 0.5 - 3.0).collect(), vec![20]).unwrap();
+        let truth = Tensor::new(vec![0.5, -2.0, 1.0, 3.0], vec![4]).unwrap();
+        let y = x.polyval(&truth).unwrap();
+        // x = 2：0.5*8 - 2*4 + 2 + 3
+        assert_eq!(y.data[10], 1.0);
+
+        let fitted = Tensor::polyfit(&x, &y, 3).unwrap();
+        assert!(
+            fitted
+                .data
+                .iter()
+                .zip(&truth.data)
+                .all(|(a, b)| (a - b).abs() < 1e-3)
+        );
+
+        // 带噪声时得到最小二乘直线：对称噪声不改变斜率与截距
+        let noisy: Vec<f32> = (0..20)
+            .map(|i| 2.0 * x.data[i] + 1.0 + if i % 2 == 0 { 0.1 } else { -0.1 })
+            .collect();
+        let line = Tensor::polyfit(&x, &Tensor::new(noisy, vec![20]).unwrap(), 1).unwrap();
+        assert!((line.data[0] - 2.0).abs() < 0.01 && (line.data[1] - 1.0).abs() < 0.05);
+
+        assert!(
+            Tensor::polyfit(&x.narrow(0, 0, 3).unwrap(), &y.narrow(0, 0, 3).unwrap(), 3).is_err()
+        );
+        let constant = Tensor::ones(vec![5]).unwrap();
+        assert!(Tensor::polyfit(&constant, &constant, 1).is_err());
+        assert!(x.polyval(&truth.reshaped(vec![2, 2]).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use super::Tensor;
+use crate::{check, parallel, profile};
+
+impl Tensor {
+    // 第 i 个输出覆盖 [floor(i*H/oh), ceil((i+1)*H/oh))，与 PyTorch 的自适应池化一致
+    fn adaptive_pool2d<F>(
+        &self,
+        op: &'static str,
+        (oh, ow): (usize, usize),
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(&mut dyn Iterator<Item = f32>, usize) -> f32 + Sync,
+    {
+        if self.shape.len() != 4 {
+            return Err(format!(
+                "{} 需要 [N, C, H, W] 输入，实际形状为 {:?}",
+                op, self.shape
+            ));
+        }
+        let (n, c, h, w) = (self.shape[0], self.shape[1], self.shape[2], self.shape[3]);
+        if oh == 0 || ow == 0 || h == 0 || w == 0 {
+            return Err(format!(
+                "{} 的输出尺寸 {}x{} 与输入尺寸 {}x{} 必须为正",
+                op, oh, ow, h, w
+            ));
+        }
+        let _scope = profile::scope(op, self.data.len());
+
+        let planes = parallel::map_range(n * c, self.data.len(), |p| {
+            let plane = &self.data[p * h * w..(p + 1) * h * w];
+            let mut out = Vec::with_capacity(oh * ow);
+            for i in 0..oh {
+                let (y0, y1) = (i * h / oh, ((i + 1) * h).div_ceil(oh));
+                for j in 0..ow {
+                    let (x0, x1) = (j * w / ow, ((j + 1) * w).div_ceil(ow));
+                    let mut window =
+                        (y0..y1).flat_map(|y| plane[y * w + x0..y * w + x1].iter().cloned());
+                    out.push(f(&mut window, (y1 - y0) * (x1 - x0)));
+                }
+            }
+            out
+        });
+
+        let mut data = crate::alloc::allocate(n * c * oh * ow);
+        for plane in planes {
+            data.extend(plane);
+        }
+
+        let out = self.placed(Tensor::new(data, vec![n, c, oh, ow])?);
+        check::inspect(op, &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    pub fn adaptive_avg_pool2d(&self, output_size: (usize, usize)) -> Result<Tensor, String> {
+        return self.adaptive_pool2d("adaptive_avg_pool2d", output_size, |window, count| {
+            window.sum::<f32>() / count as f32
+        });
+    }
+
+    pub fn adaptive_max_pool2d(&self, output_size: (usize, usize)) -> Result<Tensor, String> {
+        return self.adaptive_pool2d("adaptive_max_pool2d", output_size, |window, _| {
+            window.fold(f32::NEG_INFINITY, f32::max)
+        });
+    }
+
+    pub fn global_avg_pool(&self) -> Result<Tensor, String> {
+        return self.adaptive_avg_pool2d((1, 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adaptive_pooling_covers_uneven_bins() {
+        let x = Tensor::new((0..20).map(|i| i as f32).collect(), vec![1, 1, 4, 5]).unwrap();
+        // 宽 5 分成 3 段：[0, 2)、[1, 4)、[3, 5)
+        let avg = x.adaptive_avg_pool2d((2, 3)).unwrap();
+        assert_eq!(avg.shape, [1, 1, 2, 3]);
+        assert_eq!(avg.data.to_vec(), vec![3.0, 4.5, 6.0, 13.0, 14.5, 16.0]);
+        let max = x.adaptive_max_pool2d((2, 3)).unwrap();
+        assert_eq!(max.data.to_vec(), vec![6.0, 8.0, 9.0, 16.0, 18.0, 19.0]);
+
+        // 输出比输入大时窗口重复取样
+        let up = x.adaptive_max_pool2d((8, 5)).unwrap();
+        assert_eq!(up.data[..5], up.data[5..10]);
+
+        let g = x.global_avg_pool().unwrap();
+        assert_eq!((g.shape.to_vec(), g.data[0]), (vec![1, 1, 1, 1], 9.5));
+        assert!(x.adaptive_avg_pool2d((0, 1)).is_err());
+        assert!(
+            Tensor::zeros(vec![4, 5])
+                .unwrap()
+                .global_avg_pool()
+                .is_err()
+        );
+    }
+}
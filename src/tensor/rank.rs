@@ -0,0 +1,168 @@
+use super::Tensor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankMethod {
+    Average,
+    Min,
+    Max,
+    Dense,
+}
+
+// 线性插值分位数（NumPy 默认方法），用选择算法避免整条 lane 排序
+fn lane_quantile(work: &mut [f32], q: f32) -> f32 {
+    let pos = q * (work.len() - 1) as f32;
+    let lo = pos.floor() as usize;
+    let (_, &mut low, rest) = work.select_nth_unstable_by(lo, f32::total_cmp);
+    if pos == lo as f32 {
+        return low;
+    }
+    let high = rest.iter().cloned().fold(f32::INFINITY, f32::min);
+
+    return low + (high - low) * (pos - lo as f32);
+}
+
+impl Tensor {
+    // 秩从 1 开始，与 scipy.stats.rankdata 一致；NaN 排在最后
+    pub fn rankdata(&self, axis: usize, method: RankMethod) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+
+        return self.map_lanes("rankdata", axis, self.shape[axis], |lane, out| {
+            let mut order: Vec<usize> = (0..lane.len()).collect();
+            order.sort_by(|&a, &b| lane[a].total_cmp(&lane[b]));
+            let (mut start, mut dense) = (0, 0);
+            while start < order.len() {
+                let value = lane[order[start]];
+                let mut end = start + 1;
+                while end < order.len() && lane[order[end]] == value {
+                    end += 1;
+                }
+                dense += 1;
+                let rank = match method {
+                    RankMethod::Average => (start + end + 1) as f32 / 2.0,
+                    RankMethod::Min => (start + 1) as f32,
+                    RankMethod::Max => end as f32,
+                    RankMethod::Dense => dense as f32,
+                };
+                for &i in &order[start..end] {
+                    out[i] = rank;
+                }
+                start = end;
+            }
+        });
+    }
+
+    pub fn winsorize(&self, lower_q: f32, upper_q: f32, axis: usize) -> Result<Tensor, String> {
+        if !(0.0..=1.0).contains(&lower_q) || !(0.0..=1.0).contains(&upper_q) || lower_q > upper_q {
+            return Err(format!(
+                "winsorize 分位数 [{}, {}] 必须满足 0 <= lower <= upper <= 1",
+                lower_q, upper_q
+            ));
+        }
+        self.check_axis(axis)?;
+
+        // 分位数只按非 NaN 的值计算，NaN 原样保留；全是 NaN 的 lane 不变
+        return self.map_lanes("winsorize", axis, self.shape[axis], |lane, out| {
+            let mut work: Vec<f32> = lane.iter().cloned().filter(|x| !x.is_nan()).collect();
+            if work.is_empty() {
+                out.copy_from_slice(lane);
+                return;
+            }
+            let low = lane_quantile(&mut work, lower_q);
+            let high = lane_quantile(&mut work, upper_q);
+            for (o, &x) in out.iter_mut().zip(lane) {
+                // 含 ±Inf 的插值可能得到 NaN 边界，max/min 会忽略它而不是 panic
+                *o = if x.is_nan() { x } else { x.max(low).min(high) };
+            }
+        });
+    }
+
+    // scipy.stats.percentileofscore 的 kind="rank"：严格小于与小于等于两种百分位的平均
+    pub fn percentile_of_score(&self, score: f32, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("percentile_of_score", axis, |lane| {
+            let below = lane.iter().filter(|&&x| x < score).count();
+            let at_most = lane.iter().filter(|&&x| x <= score).count();
+            let tie = if at_most > below { 1 } else { 0 };
+            (below + at_most + tie) as f32 * 50.0 / lane.len() as f32
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rankdata_tie_methods_match_scipy() {
+        let x = Tensor::new(vec![0.0, 2.0, 3.0, 2.0], vec![4]).unwrap();
+        let ranks = |method| x.rankdata(0, method).unwrap().data.to_vec();
+        assert_eq!(ranks(RankMethod::Average), vec![1.0, 2.5, 4.0, 2.5]);
+        assert_eq!(ranks(RankMethod::Min), vec![1.0, 2.0, 4.0, 2.0]);
+        assert_eq!(ranks(RankMethod::Max), vec![1.0, 3.0, 4.0, 3.0]);
+        assert_eq!(ranks(RankMethod::Dense), vec![1.0, 2.0, 3.0, 2.0]);
+
+        let columns = Tensor::new(vec![3.0, 1.0, 1.0, 2.0], vec![2, 2]).unwrap();
+        assert_eq!(
+            columns.rankdata(0, RankMethod::Min).unwrap().data.to_vec(),
+            vec![2.0, 1.0, 1.0, 2.0]
+        );
+    }
+
+    #[test]
+    fn percentile_of_score_averages_strict_and_weak() {
+        // scipy: percentileofscore([1, 2, 3, 4], 3) == 75.0
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 4.0, 10.0, 20.0, 30.0, 40.0], vec![2, 4]).unwrap();
+        let p = x.percentile_of_score(3.0, 1).unwrap();
+        assert_eq!(p.data.to_vec(), vec![75.0, 0.0]);
+        assert_eq!(x.percentile_of_score(2.5, 1).unwrap().data[0], 50.0);
+        assert!(x.percentile_of_score(1.0, 2).is_err());
+    }
+
+    #[test]
+    fn winsorize_clips_to_per_lane_quantiles() {
+        let x = Tensor::new(
+            vec![-100.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 100.0],
+            vec![10],
+        )
+        .unwrap();
+        // 10% 与 90% 分位数分别为 0.1 * 9 与 0.9 * 9 处的插值：-9.1 与 17.2
+        let y = x.winsorize(0.1, 0.9, 0).unwrap();
+        assert!((y.data[0] + 9.1).abs() < 1e-4);
+        assert!((y.data[9] - 17.2).abs() < 1e-4);
+        assert_eq!(y.data[1..9], x.data[1..9]);
+
+        let columns = Tensor::new(vec![1.0, 10.0, 2.0, 20.0, 3.0, 30.0], vec![3, 2]).unwrap();
+        let clipped = columns.winsorize(0.5, 1.0, 0).unwrap();
+        assert_eq!(clipped.data.to_vec(), vec![2.0, 20.0, 2.0, 20.0, 3.0, 30.0]);
+        assert!(x.winsorize(0.9, 0.1, 0).is_err());
+        assert!(x.winsorize(-0.1, 0.5, 0).is_err());
+    }
+
+    #[test]
+    fn winsorize_ignores_nan_when_computing_bounds() {
+        let x = Tensor::new(
+            vec![f32::NAN, -50.0, 1.0, 2.0, 3.0, 50.0, f32::NAN, f32::NAN],
+            vec![2, 4],
+        )
+        .unwrap();
+        // 第一行的分位数来自 [-50, 1, 2]，第二行来自 [3, 50]，第二行末尾两个 NaN 保留
+        let y = x.winsorize(0.5, 1.0, 1).unwrap();
+        assert!(y.data[0].is_nan());
+        assert_eq!(y.data[1..6], [1.0, 1.0, 2.0, 26.5, 50.0]);
+        assert!(y.data[6].is_nan() && y.data[7].is_nan());
+
+        let all_nan = Tensor::new(vec![f32::NAN; 3], vec![3]).unwrap();
+        assert!(
+            all_nan
+                .winsorize(0.1, 0.9, 0)
+                .unwrap()
+                .data
+                .iter()
+                .all(|v| v.is_nan())
+        );
+        let infinite = Tensor::new(vec![f32::NEG_INFINITY, 0.0, f32::INFINITY], vec![3]).unwrap();
+        assert_eq!(
+            infinite.winsorize(0.25, 0.75, 0).unwrap().data.to_vec(),
+            vec![f32::NEG_INFINITY, 0.0, f32::INFINITY]
+        );
+    }
+}
@@ -0,0 +1,614 @@
+use super::device::ReduceOp;
+use super::summation::{Summation, sum_all, sum_lane};
+use super::{Tensor, kernel};
+use crate::{check, config, parallel, profile};
+
+// 按 config::summation 选择的方式求和
+fn lane_sum(lane: &[f32]) -> f32 {
+    return sum_lane(lane, config::summation());
+}
+
+// 两遍法方差：均值与离差平方和都按 mode 求和
+fn var_lane(lane: &[f32], mode: Summation) -> f32 {
+    let n = lane.len() as f32;
+    let mean = sum_lane(lane, mode) / n;
+    let deviations: Vec<f32> = lane.iter().map(|&x| (x - mean) * (x - mean)).collect();
+
+    return sum_lane(&deviations, mode) / n;
+}
+
+// 返回首个最优元素的下标；better(v, best) 为真时 v 取代当前最优，NaN 优先于任何数
+fn tree_arg<F>(data: &[f32], better: F) -> Option<usize>
+where
+    F: Fn(f32, f32) -> bool + Sync,
+{
+    let wins = |v: f32, best: f32| better(v, best) || (v.is_nan() && !best.is_nan());
+    let (index, _) = parallel::reduce_tree(
+        data,
+        |start, chunk| {
+            let mut best = 0;
+            for (i, &v) in chunk.iter().enumerate() {
+                if wins(v, chunk[best]) {
+                    best = i;
+                }
+            }
+            (start + best, chunk[best])
+        },
+        |left, right| if wins(right.1, left.1) { right } else { left },
+    )?;
+
+    return Some(index);
+}
+
+// max/min 与 argmax/argmin 一致：车道里有 NaN 时结果为 NaN
+fn lane_max(lane: &[f32]) -> f32 {
+    return lane.iter().fold(ReduceOp::Max.identity(), |a, &b| {
+        ReduceOp::Max.combine(a, b)
+    });
+}
+
+fn lane_min(lane: &[f32]) -> f32 {
+    return lane.iter().fold(ReduceOp::Min.identity(), |a, &b| {
+        ReduceOp::Min.combine(a, b)
+    });
+}
+
+fn lane_min_max(lane: &[f32]) -> (f32, f32) {
+    return (lane_min(lane), lane_max(lane));
+}
+
+impl Tensor {
+    pub(crate) fn reduce_lanes<F>(
+        &self,
+        op: &'static str,
+        axis: usize,
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(&[f32]) -> f32,
+    {
+        self.check_axis(axis)?;
+        let _scope = profile::scope(op, self.data.len());
+
+        let outer: usize = self.shape[..axis].iter().product();
+        let len = self.shape[axis];
+        let inner: usize = self.shape[axis + 1..].iter().product();
+
+        let mut out = crate::alloc::allocate(outer * inner);
+        let mut lane = Vec::with_capacity(len);
+        for o in 0..outer {
+            for i in 0..inner {
+                lane.clear();
+                kernel::gather_row(&self.data, o * len * inner + i, inner, len, &mut lane);
+                out.push(f(&lane));
+            }
+        }
+
+        let new_shape = self.shape.remove_axis(axis)?;
+
+        let out = self.placed(Tensor::new(out, new_shape)?);
+        check::inspect(op, &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 沿 axis 把每条 lane 变换为长度 out_len 的新 lane
+    pub(crate) fn map_lanes<F>(
+        &self,
+        op: &'static str,
+        axis: usize,
+        out_len: usize,
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(&[f32], &mut [f32]),
+    {
+        self.check_axis(axis)?;
+        let _scope = profile::scope(op, self.data.len());
+
+        let outer: usize = self.shape[..axis].iter().product();
+        let len = self.shape[axis];
+        let inner: usize = self.shape[axis + 1..].iter().product();
+
+        let mut data = crate::alloc::allocate(outer * out_len * inner);
+        data.resize(outer * out_len * inner, 0.0);
+        let mut lane = vec![0.0; len];
+        let mut mapped = vec![0.0; out_len];
+        for o in 0..outer {
+            for i in 0..inner {
+                for (k, v) in lane.iter_mut().enumerate() {
+                    *v = self.data[(o * len + k) * inner + i];
+                }
+                f(&lane, &mut mapped);
+                for (k, &v) in mapped.iter().enumerate() {
+                    data[(o * out_len + k) * inner + i] = v;
+                }
+            }
+        }
+
+        let mut new_shape = self.shape.clone();
+        new_shape[axis] = out_len;
+
+        let out = self.placed(Tensor::new(data, new_shape)?);
+        check::inspect(op, &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 先把被归约的轴换到末尾，每个输出元素对应一段连续的 lane
+    pub(crate) fn reduce_axes<F>(
+        &self,
+        op: &'static str,
+        axes: &[usize],
+        keepdims: bool,
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(&[f32]) -> f32,
+    {
+        let mut reduced = vec![false; self.shape.len()];
+        for &axis in axes {
+            self.check_axis(axis)?;
+            if reduced[axis] {
+                return Err(format!("{} 的归约轴 {:?} 含有重复项", op, axes));
+            }
+            reduced[axis] = true;
+        }
+        let _scope = profile::scope(op, self.data.len());
+
+        let mut order: Vec<usize> = (0..self.shape.len()).filter(|&d| !reduced[d]).collect();
+        order.extend((0..self.shape.len()).filter(|&d| reduced[d]));
+        let permuted = self.permute(&order)?;
+        let len: usize = axes.iter().map(|&d| self.shape[d]).product();
+        let count: usize = order[..order.len() - axes.len()]
+            .iter()
+            .map(|&d| self.shape[d])
+            .product();
+
+        let mut out = crate::alloc::allocate(count);
+        for o in 0..count {
+            out.push(f(&permuted.data[o * len..(o + 1) * len]));
+        }
+
+        let new_shape: Vec<usize> = if keepdims {
+            (0..self.shape.len())
+                .map(|d| if reduced[d] { 1 } else { self.shape[d] })
+                .collect()
+        } else {
+            (0..self.shape.len())
+                .filter(|&d| !reduced[d])
+                .map(|d| self.shape[d])
+                .collect()
+        };
+
+        let out = self.placed(Tensor::new(out, new_shape)?);
+        check::inspect(op, &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 没有单位元的归约（arg 系列、极差）不能作用在长度为 0 的轴上
+    fn check_nonempty_axis(&self, op: &str, axis: usize) -> Result<(), String> {
+        self.check_axis(axis)?;
+        if self.shape[axis] == 0 {
+            return Err(format!(
+                "{} 不能沿长度为 0 的轴 {} 归约（形状 {:?}）",
+                op, axis, self.shape
+            ));
+        }
+
+        return Ok(());
+    }
+
+    pub fn any(&self) -> Result<bool, String> {
+        return Ok(self.data.iter().any(|&x| x != 0.0));
+    }
+
+    pub fn all(&self) -> Result<bool, String> {
+        return Ok(self.data.iter().all(|&x| x != 0.0));
+    }
+
+    pub fn any_axis(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("any_axis", axis, |lane| {
+            if lane.iter().any(|&x| x != 0.0) {
+                1.0
+            } else {
+                0.0
+            }
+        });
+    }
+
+    pub fn all_axis(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("all_axis", axis, |lane| {
+            if lane.iter().all(|&x| x != 0.0) {
+                1.0
+            } else {
+                0.0
+            }
+        });
+    }
+
+    pub fn sum(&self) -> Result<f32, String> {
+        if let Some(total) = self.dispatch_reduce(ReduceOp::Sum)? {
+            return Ok(total);
+        }
+        return Ok(sum_all(&self.data, config::summation()));
+    }
+
+    pub fn mean(&self) -> Result<f32, String> {
+        return Ok(self.sum()? / self.data.len() as f32);
+    }
+
+    pub fn max(&self) -> Result<f32, String> {
+        if let Some(max) = self.dispatch_reduce(ReduceOp::Max)? {
+            return Ok(max);
+        }
+        let max = parallel::reduce_tree(
+            &self.data,
+            |_, chunk| lane_max(chunk),
+            |a, b| ReduceOp::Max.combine(a, b),
+        );
+        return Ok(max.unwrap_or(f32::NEG_INFINITY));
+    }
+
+    pub fn min(&self) -> Result<f32, String> {
+        if let Some(min) = self.dispatch_reduce(ReduceOp::Min)? {
+            return Ok(min);
+        }
+        let min = parallel::reduce_tree(
+            &self.data,
+            |_, chunk| lane_min(chunk),
+            |a, b| ReduceOp::Min.combine(a, b),
+        );
+        return Ok(min.unwrap_or(f32::INFINITY));
+    }
+
+    // 用 f64 累加，大量概率连乘时不会过早下溢
+    pub fn sum_of_logs(&self) -> Result<f32, String> {
+        return Ok(self
+            .data
+            .iter()
+            .fold(0.0f64, |acc, &x| acc + (x as f64).ln()) as f32);
+    }
+
+    pub fn sum_axis(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("sum_axis", axis, lane_sum);
+    }
+
+    pub fn mean_axis(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("mean_axis", axis, |lane| lane_sum(lane) / lane.len() as f32);
+    }
+
+    pub fn max_axis(&self, axis: usize) -> Result<Tensor, String> {
+        let out = self.reduce_lanes("max_axis", axis, lane_max)?;
+
+        return Ok(out.with_dtype(self.dtype));
+    }
+
+    pub fn min_axis(&self, axis: usize) -> Result<Tensor, String> {
+        let out = self.reduce_lanes("min_axis", axis, lane_min)?;
+
+        return Ok(out.with_dtype(self.dtype));
+    }
+
+    pub fn prod(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("prod", axis, |lane| lane.iter().product());
+    }
+
+    // 返回 ln|∏x|，符号可由 prod 的符号或负因子个数得到
+    pub fn logprod(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("logprod", axis, |lane| {
+            lane.iter()
+                .fold(0.0f64, |acc, &x| acc + (x.abs() as f64).ln()) as f32
+        });
+    }
+
+    pub fn sum_axes(&self, axes: &[usize], keepdims: bool) -> Result<Tensor, String> {
+        return self.reduce_axes("sum_axes", axes, keepdims, lane_sum);
+    }
+
+    pub fn mean_axes(&self, axes: &[usize], keepdims: bool) -> Result<Tensor, String> {
+        return self.reduce_axes("mean_axes", axes, keepdims, |lane| {
+            lane_sum(lane) / lane.len() as f32
+        });
+    }
+
+    pub fn max_axes(&self, axes: &[usize], keepdims: bool) -> Result<Tensor, String> {
+        return self.reduce_axes("max_axes", axes, keepdims, lane_max);
+    }
+
+    pub fn min_axes(&self, axes: &[usize], keepdims: bool) -> Result<Tensor, String> {
+        return self.reduce_axes("min_axes", axes, keepdims, lane_min);
+    }
+
+    pub fn ptp(&self, axis: usize) -> Result<Tensor, String> {
+        self.check_nonempty_axis("ptp", axis)?;
+        return self.reduce_lanes("ptp", axis, |lane| {
+            let (min, max) = lane_min_max(lane);
+            max - min
+        });
+    }
+
+    pub fn min_max(&self, axis: usize) -> Result<(Tensor, Tensor), String> {
+        self.check_axis(axis)?;
+        let _scope = profile::scope("min_max", self.data.len());
+
+        let outer: usize = self.shape[..axis].iter().product();
+        let len = self.shape[axis];
+        let inner: usize = self.shape[axis + 1..].iter().product();
+
+        let mut mins = crate::alloc::allocate(outer * inner);
+        let mut maxs = crate::alloc::allocate(outer * inner);
+        mins.resize(outer * inner, f32::INFINITY);
+        maxs.resize(outer * inner, f32::NEG_INFINITY);
+        // 按内存顺序扫描一遍，同时更新两个极值
+        for o in 0..outer {
+            for k in 0..len {
+                let row = &self.data[(o * len + k) * inner..][..inner];
+                let lo = &mut mins[o * inner..(o + 1) * inner];
+                let hi = &mut maxs[o * inner..(o + 1) * inner];
+                for ((l, h), &x) in lo.iter_mut().zip(hi.iter_mut()).zip(row) {
+                    *l = ReduceOp::Min.combine(*l, x);
+                    *h = ReduceOp::Max.combine(*h, x);
+                }
+            }
+        }
+
+        let new_shape = self.shape.remove_axis(axis)?;
+
+        let mins = Tensor::new(mins, new_shape.clone())?;
+        let maxs = Tensor::new(maxs, new_shape)?;
+
+        return Ok((
+            self.placed(mins).with_dtype(self.dtype),
+            self.placed(maxs).with_dtype(self.dtype),
+        ));
+    }
+
+    pub fn var_axis(&self, axis: usize) -> Result<Tensor, String> {
+        let mode = config::summation();
+        return self.reduce_lanes("var_axis", axis, |lane| var_lane(lane, mode));
+    }
+
+    pub fn std_axis(&self, axis: usize) -> Result<Tensor, String> {
+        return self.var_axis(axis)?.map_float("std_axis", f32::sqrt);
+    }
+
+    pub fn gini(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("gini", axis, |lane| {
+            let total: f32 = lane.iter().sum();
+            if total <= 0.0 {
+                return 0.0;
+            }
+            1.0 - lane.iter().map(|&c| (c / total) * (c / total)).sum::<f32>()
+        });
+    }
+
+    // 以 2 为底，与决策树中的信息增益约定一致
+    pub fn entropy(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("entropy", axis, |lane| {
+            let total: f32 = lane.iter().sum();
+            if total <= 0.0 {
+                return 0.0;
+            }
+            let sum: f32 = lane
+                .iter()
+                .filter(|&&c| c > 0.0)
+                .map(|&c| (c / total) * (c / total).log2())
+                .sum();
+            0.0 - sum
+        });
+    }
+
+    // 展平后的下标
+    pub fn argmax(&self) -> Result<usize, String> {
+        return tree_arg(&self.data, |v, best| v > best)
+            .ok_or_else(|| "argmax: 空张量没有最大值".to_string());
+    }
+
+    pub fn argmin(&self) -> Result<usize, String> {
+        return tree_arg(&self.data, |v, best| v < best)
+            .ok_or_else(|| "argmin: 空张量没有最小值".to_string());
+    }
+
+    pub fn argmax_axis(&self, axis: usize) -> Result<Tensor, String> {
+        self.check_nonempty_axis("argmax_axis", axis)?;
+        return self.reduce_lanes("argmax_axis", axis, |lane| {
+            let mut best = 0;
+            for (i, &v) in lane.iter().enumerate() {
+                if v > lane[best] || (v.is_nan() && !lane[best].is_nan()) {
+                    best = i;
+                }
+            }
+            best as f32
+        });
+    }
+
+    pub fn argmin_axis(&self, axis: usize) -> Result<Tensor, String> {
+        self.check_nonempty_axis("argmin_axis", axis)?;
+        return self.reduce_lanes("argmin_axis", axis, |lane| {
+            let mut best = 0;
+            for (i, &v) in lane.iter().enumerate() {
+                if v < lane[best] || (v.is_nan() && !lane[best].is_nan()) {
+                    best = i;
+                }
+            }
+            best as f32
+        });
+    }
+
+    pub fn logsumexp(&self, axis: usize) -> Result<Tensor, String> {
+        return self.reduce_lanes("logsumexp", axis, |lane| {
+            let max = lane.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            if max.is_infinite() {
+                return max;
+            }
+            max + lane.iter().map(|&x| (x - max).exp()).sum::<f32>().ln()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_and_all_treat_nonzero_as_true() {
+        let t = Tensor::new(vec![0.0, 2.0, 0.0, -1.0, 3.0, 0.5], vec![2, 3]).unwrap();
+        assert!(t.any().unwrap());
+        assert!(!t.all().unwrap());
+        assert_eq!(t.any_axis(0).unwrap().data.to_vec(), vec![1.0, 1.0, 1.0]);
+        assert_eq!(t.all_axis(0).unwrap().data.to_vec(), vec![0.0, 1.0, 0.0]);
+        assert_eq!(t.all_axis(1).unwrap().data.to_vec(), vec![0.0, 1.0]);
+        // 空张量：any 为假，all 为真
+        let empty = Tensor::zeros(vec![0]).unwrap();
+        assert!(!empty.any().unwrap());
+        assert!(empty.all().unwrap());
+        assert!(t.any_axis(2).is_err());
+    }
+
+    #[test]
+    fn full_reductions_combine_chunks_in_a_fixed_tree() {
+        // 顺序累加 1e6 个 0.1 会漂移到 100958 左右，两两合并的误差小得多
+        let n = 1_000_000;
+        let t = Tensor::full(vec![n], 0.1).unwrap();
+        assert!((t.sum().unwrap() - 100_000.0).abs() < 10.0);
+        assert!((t.mean().unwrap() - 0.1).abs() < 1e-4);
+        assert_eq!(t.sum().unwrap().to_bits(), t.sum().unwrap().to_bits());
+
+        let mut data: Vec<f32> = (0..n).map(|i| (i % 977) as f32).collect();
+        data[123_456] = 5000.0;
+        data[654_321] = -5000.0;
+        data[900_000] = 5000.0;
+        let t = Tensor::new(data, vec![n]).unwrap();
+        assert_eq!(t.max().unwrap(), 5000.0);
+        assert_eq!(t.min().unwrap(), -5000.0);
+        assert_eq!(t.argmax().unwrap(), 123_456);
+        assert_eq!(t.argmin().unwrap(), 654_321);
+
+        let with_nan = Tensor::new(vec![1.0, f32::NAN, 3.0, f32::NAN], vec![4]).unwrap();
+        assert_eq!(with_nan.argmax().unwrap(), 1);
+        assert_eq!(with_nan.argmin().unwrap(), 1);
+        // 极值与其下标对 NaN 的处理一致
+        assert!(with_nan.max().unwrap().is_nan());
+        assert!(with_nan.min().unwrap().is_nan());
+        let lanes = with_nan.reshaped(vec![2, 2]).unwrap();
+        let (lo, hi) = lanes.min_max(0).unwrap();
+        for (t, first) in [
+            (lanes.max_axis(0).unwrap(), 3.0),
+            (lanes.min_axis(0).unwrap(), 1.0),
+            (hi, 3.0),
+            (lo, 1.0),
+        ] {
+            assert_eq!(t.data[0], first);
+            assert!(t.data[1].is_nan());
+        }
+        assert!(lanes.max_axes(&[0, 1], false).unwrap().data[0].is_nan());
+        assert!(Tensor::zeros(vec![0]).unwrap().argmax().is_err());
+        assert_eq!(Tensor::zeros(vec![0]).unwrap().sum().unwrap().to_bits(), 0);
+    }
+
+    #[test]
+    fn variance_follows_the_summation_mode() {
+        // 顺序累加时均值漂移，常数车道也会算出非零方差
+        let lane = vec![0.1; 1_000_000];
+        let naive = var_lane(&lane, Summation::Naive);
+        let kahan = var_lane(&lane, Summation::Kahan);
+        assert!(kahan < 1e-9, "{}", kahan);
+        assert!(naive > 1e-7, "{}", naive);
+
+        let t = Tensor::new(lane, vec![1000, 1000]).unwrap();
+        let configured = var_lane(&t.data[..1000], config::summation());
+        assert_eq!(t.var_axis(1).unwrap().data[0], configured);
+    }
+
+    #[test]
+    fn gini_and_entropy_measure_impurity_per_lane() {
+        let counts = Tensor::new(vec![5.0, 5.0, 10.0, 0.0, 0.0, 0.0], vec![3, 2]).unwrap();
+        let gini = counts.gini(1).unwrap();
+        assert_eq!(gini.data.to_vec(), vec![0.5, 0.0, 0.0]);
+        let entropy = counts.entropy(1).unwrap();
+        assert_eq!(entropy.data.to_vec(), vec![1.0, 0.0, 0.0]);
+        let uniform = Tensor::new(vec![1.0; 4], vec![4]).unwrap();
+        assert!((uniform.entropy(0).unwrap().data[0] - 2.0).abs() < 1e-6);
+        assert!((uniform.gini(0).unwrap().data[0] - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn multi_axis_reductions_keep_dims_for_broadcasting() {
+        let t = Tensor::new((0..24).map(|i| i as f32).collect(), vec![2, 3, 4]).unwrap();
+        let kept = t.sum_axes(&[0, 2], true).unwrap();
+        assert_eq!(kept.shape, [1, 3, 1]);
+        // 第 1 轴第 0 片：0..4 与 12..16
+        assert_eq!(kept.data.to_vec(), vec![60.0, 92.0, 124.0]);
+        assert_eq!(t.sub(&kept).unwrap().shape, [2, 3, 4]);
+
+        let dropped = t.mean_axes(&[2, 0], false).unwrap();
+        assert_eq!(dropped.shape, [3]);
+        assert_eq!(dropped.data.to_vec(), vec![7.5, 11.5, 15.5]);
+        assert_eq!(
+            t.max_axes(&[0, 1, 2], false).unwrap().data.to_vec(),
+            vec![23.0]
+        );
+        assert_eq!(t.min_axes(&[1], true).unwrap().shape, [2, 1, 4]);
+        assert_eq!(t.sum_axes(&[], false).unwrap(), t);
+
+        assert!(t.sum_axes(&[0, 0], false).is_err());
+        assert!(t.sum_axes(&[3], false).is_err());
+    }
+
+    #[test]
+    fn products_in_linear_and_log_domain() {
+        let t = Tensor::new(vec![2.0, -3.0, 0.5, 4.0], vec![2, 2]).unwrap();
+        assert_eq!(t.prod(0).unwrap().data.to_vec(), vec![1.0, -12.0]);
+        assert_eq!(t.prod(1).unwrap().data.to_vec(), vec![-6.0, 2.0]);
+        let log = t.logprod(1).unwrap();
+        assert!((log.data[0] - 6f32.ln()).abs() < 1e-6);
+        assert!((log.data[1] - 2f32.ln()).abs() < 1e-6);
+
+        // 一千个 1e-3 连乘在 f32 中下溢为 0，对数域仍然准确
+        let tiny = Tensor::full(vec![1000], 1e-3).unwrap();
+        assert_eq!(tiny.prod(0).unwrap().data[0], 0.0);
+        let expected = 1000.0 * 1e-3f32.ln();
+        assert!((tiny.logprod(0).unwrap().data[0] - expected).abs() < 1e-1);
+        assert!((tiny.sum_of_logs().unwrap() - expected).abs() < 1e-1);
+        assert!(t.sum_of_logs().unwrap().is_nan());
+        assert_eq!(
+            Tensor::zeros(vec![0]).unwrap().prod(0).unwrap().data[0],
+            1.0
+        );
+    }
+
+    #[test]
+    fn min_max_and_ptp_agree_with_separate_passes() {
+        let t = Tensor::new(vec![3.0, -1.0, 4.0, 1.0, -5.0, 9.0], vec![2, 3]).unwrap();
+        for axis in 0..2 {
+            let (lo, hi) = t.min_max(axis).unwrap();
+            assert_eq!(lo, t.min_axis(axis).unwrap());
+            assert_eq!(hi, t.max_axis(axis).unwrap());
+        }
+        assert_eq!(t.ptp(0).unwrap().data.to_vec(), vec![2.0, 4.0, 5.0]);
+        assert_eq!(t.ptp(1).unwrap().data.to_vec(), vec![5.0, 14.0]);
+        assert!(t.min_max(2).is_err());
+    }
+
+    #[test]
+    fn zero_length_axes_reduce_to_identities() {
+        let t = Tensor::zeros(vec![3, 0]).unwrap();
+        let sum = t.sum_axis(1).unwrap();
+        assert_eq!(sum.shape, [3]);
+        assert!(sum.data.iter().all(|x| x.to_bits() == 0));
+        assert_eq!(t.sum().unwrap().to_bits(), 0);
+        assert_eq!(t.prod(1).unwrap().data.to_vec(), vec![1.0; 3]);
+        assert_eq!(t.max_axis(1).unwrap().data[0], f32::NEG_INFINITY);
+        assert_eq!(t.all_axis(1).unwrap().data.to_vec(), vec![1.0; 3]);
+        assert!(t.mean_axis(1).unwrap().data[0].is_nan());
+        assert_eq!(t.sum_axes(&[0, 1], true).unwrap().shape, [1, 1]);
+        assert!(t.argmax_axis(1).is_err());
+        assert!(t.ptp(1).is_err());
+
+        // 沿非空轴归约时输出本身为空
+        assert_eq!(t.sum_axis(0).unwrap().shape, [0]);
+        assert_eq!(t.argmax_axis(0).unwrap().shape, [0]);
+    }
+}
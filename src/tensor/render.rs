@@ -0,0 +1,91 @@
+use super::Tensor;
+
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// 按有限值的最小/最大值把 v 映射到 0..levels；非有限值返回 None，常数输入落在中间档
+fn level(v: f32, min: f32, max: f32, levels: usize) -> Option<usize> {
+    if !v.is_finite() {
+        return None;
+    }
+    if max == min {
+        return Some(levels / 2);
+    }
+    let t = (v - min) / (max - min);
+
+    return Some(((t * levels as f32) as usize).min(levels - 1));
+}
+
+fn finite_range(data: &[f32]) -> (f32, f32) {
+    return data
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+}
+
+impl Tensor {
+    // 每个元素占两个字符宽度，使终端中的格子接近正方形；NaN 与无穷显示为 "??"
+    pub fn render_heatmap(&self) -> Result<String, String> {
+        let (rows, cols) = self.matrix_dims("render_heatmap")?;
+        let (min, max) = finite_range(&self.data);
+
+        let mut out = String::with_capacity(rows * (cols * 2 + 1) * 3);
+        for i in 0..rows {
+            for &v in &self.data[i * cols..(i + 1) * cols] {
+                let cell = match level(v, min, max, SHADES.len()) {
+                    Some(l) => SHADES[l],
+                    None => '?',
+                };
+                out.push(cell);
+                out.push(cell);
+            }
+            out.push('\n');
+        }
+
+        return Ok(out);
+    }
+
+    // 非有限值显示为空格
+    pub fn sparkline(&self) -> Result<String, String> {
+        if self.shape.len() != 1 {
+            return Err(format!(
+                "sparkline 需要一维张量，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+        let (min, max) = finite_range(&self.data);
+
+        return Ok(self
+            .data
+            .iter()
+            .map(|&v| match level(v, min, max, BARS.len()) {
+                Some(l) => BARS[l],
+                None => ' ',
+            })
+            .collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_and_heatmap_scale_to_the_finite_range() {
+        let t = Tensor::new((0..8).map(|i| i as f32).collect(), vec![8]).unwrap();
+        assert_eq!(t.sparkline().unwrap(), "▁▂▃▄▅▆▇█");
+        let gaps = Tensor::new(vec![0.0, f32::NAN, 1.0], vec![3]).unwrap();
+        assert_eq!(gaps.sparkline().unwrap(), "▁ █");
+        assert_eq!(
+            Tensor::full(vec![3], 2.0).unwrap().sparkline().unwrap(),
+            "▅▅▅"
+        );
+
+        let m = Tensor::new(vec![0.0, 1.0, 2.0, 3.0, 4.0, f32::INFINITY], vec![2, 3]).unwrap();
+        assert_eq!(m.render_heatmap().unwrap(), "  ░░▒▒\n▓▓██??\n");
+        assert!(t.render_heatmap().is_err());
+        assert!(m.sparkline().is_err());
+    }
+}
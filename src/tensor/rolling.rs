@@ -0,0 +1,128 @@
+use super::Tensor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollingEdge {
+    // 只输出完整窗口，轴长变为 len - window + 1
+    Valid,
+    // 前 window - 1 个位置使用已有的部分窗口
+    Partial,
+    // 前 window - 1 个位置填 NaN，与 pandas 默认行为一致
+    Nan,
+}
+
+impl Tensor {
+    // 无偏差修正的递推形式：y[0] = x[0]，y[t] = alpha * x[t] + (1 - alpha) * y[t - 1]
+    pub fn ema(&self, alpha: f32, axis: usize) -> Result<Tensor, String> {
+        if alpha.is_nan() || alpha <= 0.0 || alpha > 1.0 {
+            return Err(format!("ema 的 alpha {} 必须位于 (0, 1]", alpha));
+        }
+        self.check_axis(axis)?;
+
+        return self.map_lanes("ema", axis, self.shape[axis], |lane, out| {
+            let mut state = 0.0;
+            for (t, (o, &x)) in out.iter_mut().zip(lane).enumerate() {
+                state = if t == 0 {
+                    x
+                } else {
+                    alpha * x + (1.0 - alpha) * state
+                };
+                *o = state;
+            }
+        });
+    }
+
+    // 每个窗口的 (均值, 总体方差)，用滑动的和与平方和在 f64 中累加
+    fn rolling<F>(
+        &self,
+        op: &'static str,
+        window: usize,
+        axis: usize,
+        edge: RollingEdge,
+        f: F,
+    ) -> Result<Tensor, String>
+    where
+        F: Fn(f64, f64) -> f32,
+    {
+        self.check_axis(axis)?;
+        let len = self.shape[axis];
+        if window == 0 || window > len {
+            return Err(format!("{} 的窗口 {} 必须位于 [1, {}]", op, window, len));
+        }
+        let out_len = match edge {
+            RollingEdge::Valid => len - window + 1,
+            RollingEdge::Partial | RollingEdge::Nan => len,
+        };
+
+        return self.map_lanes(op, axis, out_len, |lane, out| {
+            let (mut sum, mut sq) = (0.0f64, 0.0f64);
+            let mut k = 0;
+            for (t, &x) in lane.iter().enumerate() {
+                sum += x as f64;
+                sq += x as f64 * x as f64;
+                if t >= window {
+                    let old = lane[t - window] as f64;
+                    sum -= old;
+                    sq -= old * old;
+                }
+                let count = (t + 1).min(window);
+                if count < window && edge == RollingEdge::Valid {
+                    continue;
+                }
+                out[k] = if count < window && edge == RollingEdge::Nan {
+                    f32::NAN
+                } else {
+                    let mean = sum / count as f64;
+                    f(mean, (sq / count as f64 - mean * mean).max(0.0))
+                };
+                k += 1;
+            }
+        });
+    }
+
+    pub fn rolling_mean(
+        &self,
+        window: usize,
+        axis: usize,
+        edge: RollingEdge,
+    ) -> Result<Tensor, String> {
+        return self.rolling("rolling_mean", window, axis, edge, |mean, _| mean as f32);
+    }
+
+    // 总体标准差（除以窗口长度），与 std_axis 一致
+    pub fn rolling_std(
+        &self,
+        window: usize,
+        axis: usize,
+        edge: RollingEdge,
+    ) -> Result<Tensor, String> {
+        return self.rolling("rolling_std", window, axis, edge, |_, var| {
+            var.sqrt() as f32
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ema_and_rolling_windows_handle_edges() {
+        let x = Tensor::new(vec![1.0, 3.0, 5.0, 7.0, 2.0, 4.0, 6.0, 8.0], vec![2, 4]).unwrap();
+        let smoothed = x.ema(0.5, 1).unwrap();
+        assert_eq!(smoothed.data[..4], [1.0, 2.0, 3.5, 5.25]);
+        assert!(x.ema(0.0, 1).is_err());
+
+        let valid = x.rolling_mean(2, 1, RollingEdge::Valid).unwrap();
+        assert_eq!(valid.shape, [2, 3]);
+        assert_eq!(valid.data.to_vec(), vec![2.0, 4.0, 6.0, 3.0, 5.0, 7.0]);
+        let partial = x.rolling_mean(3, 1, RollingEdge::Partial).unwrap();
+        assert_eq!(partial.data[..4], [1.0, 2.0, 3.0, 5.0]);
+        let padded = x.rolling_std(2, 1, RollingEdge::Nan).unwrap();
+        assert!(padded.data[0].is_nan());
+        assert_eq!(padded.data[1..4], [1.0, 1.0, 1.0]);
+
+        let down = x.rolling_mean(2, 0, RollingEdge::Valid).unwrap();
+        assert_eq!(down.data.to_vec(), vec![1.5, 3.5, 5.5, 7.5]);
+        assert!(x.rolling_mean(5, 1, RollingEdge::Partial).is_err());
+    }
+}
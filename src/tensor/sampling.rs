@@ -0,0 +1,264 @@
+use super::{Shape, Tensor};
+use crate::random;
+
+impl Tensor {
+    pub fn randperm(n: usize) -> Result<Tensor, String> {
+        let mut perm: Vec<f32> = (0..n).map(|i| i as f32).collect();
+        random::with_rng(|rng| rng.shuffle(&mut perm));
+
+        return Tensor::new(perm, vec![n]);
+    }
+
+    pub fn multinomial(probs: &Tensor, n: usize, replacement: bool) -> Result<Tensor, String> {
+        let (rows, categories) = match probs.shape.len() {
+            1 => (1, probs.shape[0]),
+            2 => (probs.shape[0], probs.shape[1]),
+            _ => {
+                return Err(format!(
+                    "multinomial 需要一维或二维概率张量，实际形状为 {:?}",
+                    probs.shape
+                ));
+            }
+        };
+
+        let mut out = Vec::with_capacity(rows * n);
+        for r in 0..rows {
+            let mut weights = probs.data[r * categories..(r + 1) * categories].to_vec();
+            if weights.iter().any(|&w| w < 0.0 || !w.is_finite()) {
+                return Err(format!("第 {} 行概率包含负数或非有限值", r));
+            }
+            let positive = weights.iter().filter(|&&w| w > 0.0).count();
+            if positive == 0 {
+                return Err(format!("第 {} 行概率之和为零", r));
+            }
+            if !replacement && n > positive {
+                return Err(format!(
+                    "无放回抽样数量 {} 超过第 {} 行非零概率类别数 {}",
+                    n, r, positive
+                ));
+            }
+
+            random::with_rng(|rng| {
+                for _ in 0..n {
+                    let total: f32 = weights.iter().sum();
+                    let mut target = rng.next_f32() * total;
+                    let mut chosen = categories - 1;
+                    for (k, &w) in weights.iter().enumerate() {
+                        if w > 0.0 && target < w {
+                            chosen = k;
+                            break;
+                        }
+                        target -= w;
+                    }
+                    while weights[chosen] <= 0.0 {
+                        chosen -= 1;
+                    }
+
+                    out.push(chosen as f32);
+                    if !replacement {
+                        weights[chosen] = 0.0;
+                    }
+                }
+            });
+        }
+
+        let shape = if probs.shape.len() == 1 {
+            vec![n]
+        } else {
+            vec![rows, n]
+        };
+
+        return Ok(probs.placed(Tensor::new(out, shape)?));
+    }
+
+    pub fn choice(&self, n: usize, replacement: bool) -> Result<Tensor, String> {
+        if self.shape.is_empty() {
+            return Err("choice 不支持零维张量".to_string());
+        }
+        let rows = self.shape[0];
+        if rows == 0 && n > 0 {
+            return Err("无法从空张量中抽样".to_string());
+        }
+        if !replacement && n > rows {
+            return Err(format!("无放回抽样数量 {} 超过行数 {}", n, rows));
+        }
+
+        let picks: Vec<usize> = random::with_rng(|rng| {
+            if replacement {
+                (0..n).map(|_| rng.below(rows)).collect()
+            } else {
+                let mut perm: Vec<usize> = (0..rows).collect();
+                for i in 0..n {
+                    let j = i + rng.below(rows - i);
+                    perm.swap(i, j);
+                }
+                perm.truncate(n);
+                perm
+            }
+        });
+
+        let row_size: usize = self.shape[1..].iter().product();
+        let mut data = Vec::with_capacity(n * row_size);
+        for &p in &picks {
+            data.extend_from_slice(&self.data[p * row_size..(p + 1) * row_size]);
+        }
+
+        let mut shape = self.shape.clone();
+        shape[0] = n;
+
+        return Ok(self
+            .placed(Tensor::new(data, shape)?)
+            .with_dtype(self.dtype));
+    }
+
+    pub fn bernoulli(p: f32, shape: impl Into<Shape>) -> Result<Tensor, String> {
+        let shape: Shape = shape.into();
+        if !(0.0..=1.0).contains(&p) {
+            return Err(format!("伯努利概率 {} 不在 [0, 1] 范围内", p));
+        }
+
+        let total_size: usize = shape.iter().product();
+        let data = random::with_rng(|rng| {
+            (0..total_size)
+                .map(|_| if rng.next_f32() < p { 1.0 } else { 0.0 })
+                .collect()
+        });
+
+        return Tensor::new(data, shape);
+    }
+
+    pub fn dropout(&self, p: f32, training: bool) -> Result<Tensor, String> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(format!("dropout 概率 {} 不在 [0, 1] 范围内", p));
+        }
+        if !training || p == 0.0 {
+            return Ok(self.clone());
+        }
+        if p == 1.0 {
+            return Ok(self.placed(Tensor::zeros(self.shape.clone())?));
+        }
+
+        let scale = 1.0 / (1.0 - p);
+        let mask = self.placed(Tensor::bernoulli(1.0 - p, self.shape.clone())?);
+
+        return self.zip_with("dropout", &mask, |x, m| x * m * scale);
+    }
+
+    pub fn dropout_(&mut self, p: f32, training: bool) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(format!("dropout 概率 {} 不在 [0, 1] 范围内", p));
+        }
+        if !training || p == 0.0 {
+            return self.check_writable("dropout_");
+        }
+        if p == 1.0 {
+            return self.map_("dropout_", |_| 0.0);
+        }
+
+        let scale = 1.0 / (1.0 - p);
+        let mask = self.placed(Tensor::bernoulli(1.0 - p, self.shape.clone())?);
+
+        return self.zip_with_("dropout_", &mask, |x, m| x * m * scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bernoulli_and_dropout_sample_masks() {
+        let mask = Tensor::bernoulli(0.25, vec![4000]).unwrap();
+        assert!(mask.data.iter().all(|&v| v == 0.0 || v == 1.0));
+        let rate = mask.data.iter().sum::<f32>() / 4000.0;
+        assert!((rate - 0.25).abs() < 0.05);
+        assert_eq!(
+            Tensor::bernoulli(1.0, vec![3]).unwrap().data.to_vec(),
+            vec![1.0; 3]
+        );
+        assert!(Tensor::bernoulli(-0.1, vec![3]).is_err());
+
+        let x = Tensor::ones(vec![64]).unwrap();
+        let y = x.dropout(0.5, true).unwrap();
+        assert!(y.data.iter().all(|&v| v == 0.0 || v == 2.0));
+        assert_eq!(x.dropout(0.5, false).unwrap().data.to_vec(), vec![1.0; 64]);
+        assert_eq!(x.dropout(1.0, true).unwrap().data.to_vec(), vec![0.0; 64]);
+        assert!(x.dropout(f32::NAN, true).is_err());
+    }
+
+    #[test]
+    fn multinomial_respects_weights_and_replacement() {
+        let probs = Tensor::new(vec![0.1, 0.0, 0.6, 0.3], vec![4]).unwrap();
+        let draws = random::with_seed(17, || Tensor::multinomial(&probs, 20000, true)).unwrap();
+        assert_eq!(draws.shape, [20000]);
+        let mut counts = [0usize; 4];
+        for &k in draws.data.iter() {
+            counts[k as usize] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        for (k, expected) in [(0, 2000), (2, 12000), (3, 6000)] {
+            assert!(counts[k].abs_diff(expected) < 400, "{:?}", counts);
+        }
+        let again = random::with_seed(17, || Tensor::multinomial(&probs, 20000, true)).unwrap();
+        assert_eq!(again, draws);
+
+        // 无放回：每行三个非零类别各抽到一次，零概率类别永远不出现
+        let rows = Tensor::new(vec![0.1, 0.0, 0.6, 0.3, 0.0, 5.0, 1.0, 1.0], vec![2, 4]).unwrap();
+        let picks = random::with_seed(3, || Tensor::multinomial(&rows, 3, false)).unwrap();
+        assert_eq!(picks.shape, [2, 3]);
+        let mut first = picks.data[..3].to_vec();
+        let mut second = picks.data[3..].to_vec();
+        first.sort_by(f32::total_cmp);
+        second.sort_by(f32::total_cmp);
+        assert_eq!(first, vec![0.0, 2.0, 3.0]);
+        assert_eq!(second, vec![1.0, 2.0, 3.0]);
+        assert!(Tensor::multinomial(&rows, 4, false).is_err());
+        assert!(Tensor::multinomial(&rows, 4, true).is_ok());
+        assert!(Tensor::multinomial(&Tensor::zeros(vec![3]).unwrap(), 1, true).is_err());
+        let negative = Tensor::new(vec![0.5, -0.5], vec![2]).unwrap();
+        assert!(Tensor::multinomial(&negative, 1, true).is_err());
+    }
+
+    #[test]
+    fn choice_picks_rows_with_and_without_replacement() {
+        let x = Tensor::new((0..12).map(|v| v as f32).collect(), vec![6, 2]).unwrap();
+        let picked = random::with_seed(5, || x.choice(6, false)).unwrap();
+        assert_eq!(picked.shape, [6, 2]);
+        let mut rows: Vec<f32> = picked.data.chunks(2).map(|r| r[0]).collect();
+        assert!(picked.data.chunks(2).all(|r| r[1] == r[0] + 1.0));
+        rows.sort_by(f32::total_cmp);
+        assert_eq!(rows, vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert!(x.choice(7, false).is_err());
+
+        let draws = random::with_seed(5, || x.choice(6000, true)).unwrap();
+        assert_eq!(draws.shape, [6000, 2]);
+        let mut counts = [0usize; 6];
+        for r in draws.data.chunks(2) {
+            counts[r[0] as usize / 2] += 1;
+        }
+        assert!(
+            counts.iter().all(|&c| c.abs_diff(1000) < 150),
+            "{:?}",
+            counts
+        );
+        assert_eq!(
+            random::with_seed(5, || x.choice(6000, true)).unwrap(),
+            draws
+        );
+        assert!(Tensor::zeros(vec![0, 2]).unwrap().choice(1, true).is_err());
+        assert!(
+            Tensor::new(vec![1.0], vec![])
+                .unwrap()
+                .choice(1, true)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn randperm_is_a_permutation() {
+        let mut p = Tensor::randperm(10).unwrap().data.to_vec();
+        p.sort_by(f32::total_cmp);
+        assert_eq!(p, (0..10).map(|i| i as f32).collect::<Vec<f32>>());
+        assert_eq!(Tensor::randperm(0).unwrap().numel(), Ok(0));
+    }
+}
@@ -0,0 +1,139 @@
+use super::Tensor;
+use crate::profile;
+
+impl Tensor {
+    fn segment_reduce<F>(
+        &self,
+        op: &'static str,
+        segment_ids: &Tensor,
+        num_segments: usize,
+        init: f32,
+        f: F,
+    ) -> Result<(Tensor, Vec<usize>), String>
+    where
+        F: Fn(f32, f32) -> f32,
+    {
+        if self.shape.is_empty() {
+            return Err(format!("{} 不支持零维张量", op));
+        }
+        if segment_ids.shape.len() != 1 || segment_ids.shape[0] != self.shape[0] {
+            return Err(format!(
+                "分段索引形状 {:?} 与张量首维 {} 不匹配",
+                segment_ids.shape, self.shape[0]
+            ));
+        }
+        let _scope = profile::scope(op, self.data.len());
+
+        let row_size: usize = self.shape[1..].iter().product();
+        let mut out = crate::alloc::allocate(num_segments * row_size);
+        out.resize(num_segments * row_size, init);
+        let mut counts = vec![0; num_segments];
+        for (row, &id) in segment_ids.data.iter().enumerate() {
+            if id < 0.0 || id.fract() != 0.0 || id as usize >= num_segments {
+                return Err(format!(
+                    "第 {} 个分段索引 {} 不是 [0, {}) 内的整数",
+                    row, id, num_segments
+                ));
+            }
+            let seg = id as usize;
+            counts[seg] += 1;
+
+            let src = &self.data[row * row_size..(row + 1) * row_size];
+            let dst = &mut out[seg * row_size..(seg + 1) * row_size];
+            for (d, &s) in dst.iter_mut().zip(src) {
+                *d = f(*d, s);
+            }
+        }
+
+        let mut shape = self.shape.clone();
+        shape[0] = num_segments;
+
+        return Ok((self.placed(Tensor::new(out, shape)?), counts));
+    }
+
+    pub fn segment_sum(&self, segment_ids: &Tensor, num_segments: usize) -> Result<Tensor, String> {
+        let (out, _) =
+            self.segment_reduce("segment_sum", segment_ids, num_segments, 0.0, |a, b| a + b)?;
+
+        return Ok(out);
+    }
+
+    pub fn segment_mean(
+        &self,
+        segment_ids: &Tensor,
+        num_segments: usize,
+    ) -> Result<Tensor, String> {
+        let (mut out, counts) =
+            self.segment_reduce("segment_mean", segment_ids, num_segments, 0.0, |a, b| a + b)?;
+
+        let row_size: usize = out.shape[1..].iter().product();
+        for (seg, &count) in counts.iter().enumerate() {
+            if count > 0 {
+                for v in &mut out.data[seg * row_size..(seg + 1) * row_size] {
+                    *v /= count as f32;
+                }
+            }
+        }
+
+        return Ok(out);
+    }
+
+    pub fn segment_max(&self, segment_ids: &Tensor, num_segments: usize) -> Result<Tensor, String> {
+        let (out, _) = self.segment_reduce(
+            "segment_max",
+            segment_ids,
+            num_segments,
+            f32::NEG_INFINITY,
+            f32::max,
+        )?;
+
+        return Ok(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(data: Vec<f32>, shape: Vec<usize>) -> Tensor {
+        return Tensor::new(data, shape).unwrap();
+    }
+
+    #[test]
+    fn segment_reductions() {
+        let x = t(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], vec![3, 2]);
+        let ids = t(vec![2.0, 0.0, 2.0], vec![3]);
+        assert_eq!(
+            x.segment_sum(&ids, 3).unwrap().data.to_vec(),
+            vec![3.0, 4.0, 0.0, 0.0, 6.0, 8.0]
+        );
+        assert_eq!(
+            x.segment_mean(&ids, 3).unwrap().data.to_vec(),
+            vec![3.0, 4.0, 0.0, 0.0, 3.0, 4.0]
+        );
+        // 空分段的最大值为 -Inf
+        let max = x.segment_max(&ids, 3).unwrap();
+        assert_eq!(max.shape, [3, 2]);
+        assert_eq!(
+            max.data.to_vec(),
+            vec![3.0, 4.0, f32::NEG_INFINITY, f32::NEG_INFINITY, 5.0, 6.0]
+        );
+    }
+
+    #[test]
+    fn invalid_segments_are_rejected() {
+        let x = t(vec![1.0, 2.0], vec![2]);
+        assert!(x.segment_sum(&t(vec![0.0, 2.0], vec![2]), 2).is_err());
+        assert!(x.segment_sum(&t(vec![0.0, 0.5], vec![2]), 2).is_err());
+        assert!(x.segment_sum(&t(vec![0.0], vec![1]), 2).is_err());
+        assert!(
+            t(vec![1.0], vec![])
+                .segment_sum(&t(vec![0.0], vec![1]), 1)
+                .is_err()
+        );
+        let empty = t(vec![], vec![0, 3])
+            .segment_sum(&t(vec![], vec![0]), 2)
+            .unwrap();
+        assert_eq!(empty.data.to_vec(), vec![0.0; 6]);
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+// 张量的形状。解引用为 [usize]，因此下标、切片与迭代照常可用；
+// 轴检查、广播等形状规则集中在这里，避免各个算子各写一份
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Shape(Vec<usize>);
+
+impl Shape {
+    pub fn new(dims: Vec<usize>) -> Shape {
+        return Shape(dims);
+    }
+
+    pub fn scalar() -> Shape {
+        return Shape(Vec::new());
+    }
+
+    pub fn numel(&self) -> usize {
+        return self.0.iter().product();
+    }
+
+    pub fn as_slice(&self) -> &[usize] {
+        return &self.0;
+    }
+
+    pub fn into_vec(self) -> Vec<usize> {
+        return self.0;
+    }
+
+    pub fn check_axis(&self, axis: usize) -> Result<(), String> {
+        if axis >= self.0.len() {
+            return Err(format!("轴 {} 超出张量秩 {} 的范围", axis, self.0.len()));
+        }
+
+        return Ok(());
+    }
+
+    // 行主序的连续步长
+    pub fn strides(&self) -> Vec<usize> {
+        let mut strides = vec![1; self.0.len()];
+        for i in (1..self.0.len()).rev() {
+            strides[i - 1] = strides[i] * self.0[i];
+        }
+
+        return strides;
+    }
+
+    // NumPy 广播规则：右对齐，各维相等或其中一方为 1
+    pub fn broadcast_with(&self, other: &[usize]) -> Result<Shape, String> {
+        let (a, b) = (&self.0, other);
+        let rank = a.len().max(b.len());
+        let mut shape = vec![0; rank];
+        for i in 0..rank {
+            let da = if i < rank - a.len() {
+                1
+            } else {
+                a[i - (rank - a.len())]
+            };
+            let db = if i < rank - b.len() {
+                1
+            } else {
+                b[i - (rank - b.len())]
+            };
+            shape[i] = if da == db || db == 1 {
+                da
+            } else if da == 1 {
+                db
+            } else {
+                return Err(format!("形状 {:?} 与 {:?} 无法广播", a, b));
+            };
+        }
+
+        return Ok(Shape(shape));
+    }
+
+    // 在 axis 处插入长度为 1 的新轴，axis 可以等于秩
+    pub fn insert_axis(&self, axis: usize) -> Result<Shape, String> {
+        if axis > self.0.len() {
+            return Err(format!(
+                "插入位置 {} 超出张量秩 {} 的范围",
+                axis,
+                self.0.len()
+            ));
+        }
+        let mut dims = self.0.clone();
+        dims.insert(axis, 1);
+
+        return Ok(Shape(dims));
+    }
+
+    pub fn remove_axis(&self, axis: usize) -> Result<Shape, String> {
+        self.check_axis(axis)?;
+        let mut dims = self.0.clone();
+        dims.remove(axis);
+
+        return Ok(Shape(dims));
+    }
+}
+
+impl Deref for Shape {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        return &self.0;
+    }
+}
+
+impl DerefMut for Shape {
+    fn deref_mut(&mut self) -> &mut [usize] {
+        return &mut self.0;
+    }
+}
+
+impl From<Vec<usize>> for Shape {
+    fn from(dims: Vec<usize>) -> Shape {
+        return Shape(dims);
+    }
+}
+
+impl From<&[usize]> for Shape {
+    fn from(dims: &[usize]) -> Shape {
+        return Shape(dims.to_vec());
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for Shape {
+    fn from(dims: [usize; N]) -> Shape {
+        return Shape(dims.to_vec());
+    }
+}
+
+impl From<&Shape> for Shape {
+    fn from(shape: &Shape) -> Shape {
+        return shape.clone();
+    }
+}
+
+impl From<Shape> for Vec<usize> {
+    fn from(shape: Shape) -> Vec<usize> {
+        return shape.0;
+    }
+}
+
+impl PartialEq<[usize]> for Shape {
+    fn eq(&self, other: &[usize]) -> bool {
+        return self.0 == other;
+    }
+}
+
+impl PartialEq<&[usize]> for Shape {
+    fn eq(&self, other: &&[usize]) -> bool {
+        return self.0 == *other;
+    }
+}
+
+impl PartialEq<Vec<usize>> for Shape {
+    fn eq(&self, other: &Vec<usize>) -> bool {
+        return &self.0 == other;
+    }
+}
+
+impl<const N: usize> PartialEq<[usize; N]> for Shape {
+    fn eq(&self, other: &[usize; N]) -> bool {
+        return self.0 == other;
+    }
+}
+
+impl PartialEq<Shape> for Vec<usize> {
+    fn eq(&self, other: &Shape) -> bool {
+        return *self == other.0;
+    }
+}
+
+impl PartialEq<Shape> for [usize] {
+    fn eq(&self, other: &Shape) -> bool {
+        return self == other.0;
+    }
+}
+
+impl<'a> IntoIterator for &'a Shape {
+    type Item = &'a usize;
+    type IntoIter = std::slice::Iter<'a, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.0.iter();
+    }
+}
+
+// 调试输出与 Vec 一致，错误信息中的形状写法保持不变
+impl fmt::Debug for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return fmt::Debug::fmt(&self.0, f);
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dims: Vec<String> = self.0.iter().map(|d| d.to_string()).collect();
+        return write!(f, "({})", dims.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape_algebra() {
+        let s = Shape::from([2, 1, 3]);
+        assert_eq!(s.numel(), 6);
+        assert_eq!(s.len(), 3);
+        assert_eq!(s.strides(), [3, 3, 1]);
+        assert_eq!(s.broadcast_with(&[4, 1]).unwrap(), [2, 4, 3]);
+        assert!(s.broadcast_with(&[2]).is_err());
+        assert_eq!(s.insert_axis(3).unwrap(), [2, 1, 3, 1]);
+        assert!(s.insert_axis(4).is_err());
+        assert_eq!(s.remove_axis(1).unwrap(), vec![2, 3]);
+        assert!(s.remove_axis(3).is_err());
+        assert_eq!(Shape::scalar().numel(), 1);
+        assert_eq!(s.to_string(), "(2, 1, 3)");
+        assert_eq!(format!("{:?}", s), "[2, 1, 3]");
+    }
+}
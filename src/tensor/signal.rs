@@ -0,0 +1,309 @@
+use super::Tensor;
+use crate::{parallel, profile};
+use std::f64::consts::PI;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowFn {
+    Rectangular,
+    Hann,
+    Hamming,
+}
+
+impl WindowFn {
+    // 周期窗，与 torch.hann_window(periodic=True) 一致
+    fn weights(self, len: usize) -> Vec<f64> {
+        return (0..len)
+            .map(|n| {
+                let phase = 2.0 * PI * n as f64 / len as f64;
+                match self {
+                    WindowFn::Rectangular => 1.0,
+                    WindowFn::Hann => 0.5 - 0.5 * phase.cos(),
+                    WindowFn::Hamming => 0.54 - 0.46 * phase.cos(),
+                }
+            })
+            .collect();
+    }
+}
+
+// 长度为 2 的幂时用迭代基 2 FFT，否则退回 O(n²) 的直接 DFT
+fn fft(buf: &mut [(f64, f64)], inverse: bool) {
+    let n = buf.len();
+    let sign = if inverse { 1.0 } else { -1.0 };
+    if n <= 1 {
+        return;
+    }
+    if !n.is_power_of_two() {
+        let input = buf.to_vec();
+        for (k, out) in buf.iter_mut().enumerate() {
+            *out = input
+                .iter()
+                .enumerate()
+                .fold((0.0, 0.0), |(re, im), (t, &(xr, xi))| {
+                    let angle = sign * 2.0 * PI * ((k * t) % n) as f64 / n as f64;
+                    let (s, c) = angle.sin_cos();
+                    (re + xr * c - xi * s, im + xr * s + xi * c)
+                });
+        }
+        return;
+    }
+
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+    let mut size = 2;
+    while size <= n {
+        let (s, c) = (sign * 2.0 * PI / size as f64).sin_cos();
+        for start in (0..n).step_by(size) {
+            let mut w = (1.0, 0.0);
+            for k in 0..size / 2 {
+                let (ar, ai) = buf[start + k];
+                let (br, bi) = buf[start + k + size / 2];
+                let (tr, ti) = (br * w.0 - bi * w.1, br * w.1 + bi * w.0);
+                buf[start + k] = (ar + tr, ai + ti);
+                buf[start + k + size / 2] = (ar - tr, ai - ti);
+                w = (w.0 * c - w.1 * s, w.0 * s + w.1 * c);
+            }
+        }
+        size *= 2;
+    }
+}
+
+impl Tensor {
+    // 轴 axis 被替换为 [帧数, window] 两维；张量总是连续存储，因此帧被复制出来而不是视图
+    pub fn frame(&self, window: usize, hop: usize, axis: usize) -> Result<Tensor, String> {
+        self.check_axis(axis)?;
+        let len = self.shape[axis];
+        if window == 0 || hop == 0 || window > len {
+            return Err(format!(
+                "frame 的窗口 {} 与步长 {} 必须为正，且窗口不超过轴长 {}",
+                window, hop, len
+            ));
+        }
+        let frames = (len - window) / hop + 1;
+        let outer: usize = self.shape[..axis].iter().product();
+        let inner: usize = self.shape[axis + 1..].iter().product();
+        let _scope = profile::scope("frame", outer * frames * window * inner);
+
+        let mut data = crate::alloc::allocate(outer * frames * window * inner);
+        for o in 0..outer {
+            for f in 0..frames {
+                let start = (o * len + f * hop) * inner;
+                data.extend_from_slice(&self.data[start..start + window * inner]);
+            }
+        }
+
+        let mut shape = self.shape[..axis].to_vec();
+        shape.extend([frames, window]);
+        shape.extend_from_slice(&self.shape[axis + 1..]);
+
+        return Ok(self
+            .placed(Tensor::new(data, shape)?)
+            .with_dtype(self.dtype));
+    }
+
+    // 输入 [T] 或 [B, T]，返回形状为 [..., 帧数, window/2 + 1] 的幅度与相位
+    pub fn stft(
+        &self,
+        window: usize,
+        hop: usize,
+        window_fn: WindowFn,
+    ) -> Result<(Tensor, Tensor), String> {
+        if self.shape.is_empty() || self.shape.len() > 2 {
+            return Err(format!(
+                "stft 需要 [T] 或 [B, T] 输入，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+        let frames = self.frame(window, hop, self.shape.len() - 1)?;
+        let count = frames.data.len() / window;
+        let bins = window / 2 + 1;
+        let weights = window_fn.weights(window);
+        let _scope = profile::scope("stft", count * window);
+
+        let spectra = parallel::map_range(
+            count,
+            count * window * window.ilog2().max(1) as usize,
+            |f| {
+                let mut buf: Vec<(f64, f64)> = frames.data[f * window..(f + 1) * window]
+                    .iter()
+                    .zip(&weights)
+                    .map(|(&x, &w)| (x as f64 * w, 0.0))
+                    .collect();
+                fft(&mut buf, false);
+                buf.truncate(bins);
+                buf
+            },
+        );
+
+        let mut magnitude = crate::alloc::allocate(count * bins);
+        let mut phase = crate::alloc::allocate(count * bins);
+        for (re, im) in spectra.into_iter().flatten() {
+            magnitude.push(re.hypot(im) as f32);
+            phase.push(im.atan2(re) as f32);
+        }
+        let mut shape = frames.shape.clone();
+        *shape.last_mut().unwrap() = bins;
+
+        return Ok((
+            self.placed(Tensor::new(magnitude, shape.clone())?),
+            self.placed(Tensor::new(phase, shape)?),
+        ));
+    }
+
+    // 加窗重叠相加，按 Σw² 归一化；窗函数和为 0 的位置（如 Hann 的首个采样）输出 0
+    pub fn istft(
+        magnitude: &Tensor,
+        phase: &Tensor,
+        window: usize,
+        hop: usize,
+        window_fn: WindowFn,
+    ) -> Result<Tensor, String> {
+        let rank = magnitude.shape.len();
+        if magnitude.shape != phase.shape
+            || !(2..=3).contains(&rank)
+            || magnitude.shape[rank - 1] != window / 2 + 1
+            || hop == 0
+        {
+            return Err(format!(
+                "istft 需要形状一致的 [..., 帧数, {}] 幅度与相位及正的步长，实际为 {:?} 与 {:?}",
+                window / 2 + 1,
+                magnitude.shape,
+                phase.shape
+            ));
+        }
+        let frames = magnitude.shape[rank - 2];
+        let batch: usize = magnitude.shape[..rank - 2].iter().product();
+        let bins = window / 2 + 1;
+        let len = if frames == 0 {
+            0
+        } else {
+            (frames - 1) * hop + window
+        };
+        let weights = window_fn.weights(window);
+        let _scope = profile::scope("istft", batch * len);
+
+        let mut data = crate::alloc::allocate(batch * len);
+        for b in 0..batch {
+            let mut signal = vec![0.0f64; len];
+            let mut norm = vec![0.0f64; len];
+            for f in 0..frames {
+                let offset = (b * frames + f) * bins;
+                let mut buf = vec![(0.0, 0.0); window];
+                for k in 0..bins {
+                    let (m, p) = (
+                        magnitude.data[offset + k] as f64,
+                        phase.data[offset + k] as f64,
+                    );
+                    buf[k] = (m * p.cos(), m * p.sin());
+                    // 实信号频谱共轭对称
+                    if k > 0 && k < window - k {
+                        buf[window - k] = (buf[k].0, -buf[k].1);
+                    }
+                }
+                fft(&mut buf, true);
+                for (n, &(re, _)) in buf.iter().enumerate() {
+                    signal[f * hop + n] += re / window as f64 * weights[n];
+                    norm[f * hop + n] += weights[n] * weights[n];
+                }
+            }
+            data.extend(signal.iter().zip(&norm).map(
+                |(&x, &w)| {
+                    if w > 1e-10 { (x / w) as f32 } else { 0.0 }
+                },
+            ));
+        }
+
+        let mut shape = magnitude.shape[..rank - 2].to_vec();
+        shape.push(len);
+
+        return Ok(magnitude.placed(Tensor::new(data, shape)?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_extracts_overlapping_windows() {
+        let x = Tensor::new((0..7).map(|i| i as f32).collect(), vec![7]).unwrap();
+        let frames = x.frame(3, 2, 0).unwrap();
+        assert_eq!(frames.shape, [3, 3]);
+        assert_eq!(
+            frames.data.to_vec(),
+            vec![0.0, 1.0, 2.0, 2.0, 3.0, 4.0, 4.0, 5.0, 6.0]
+        );
+
+        // 多通道序列沿时间轴分帧
+        let channels = Tensor::new((0..8).map(|i| i as f32).collect(), vec![2, 4]).unwrap();
+        let framed = channels.frame(2, 2, 1).unwrap();
+        assert_eq!(framed.shape, [2, 2, 2]);
+        assert_eq!(framed.data[4..6], [4.0, 5.0]);
+        let columns = channels.frame(2, 1, 0).unwrap();
+        assert_eq!(columns.shape, [1, 2, 4]);
+
+        assert!(x.frame(8, 1, 0).is_err());
+        assert!(x.frame(2, 0, 0).is_err());
+    }
+
+    #[test]
+    fn fft_matches_direct_dft_for_any_length() {
+        for n in [1, 6, 8] {
+            let input: Vec<(f64, f64)> = (0..n).map(|i| ((i * i) as f64 * 0.3, i as f64)).collect();
+            let mut fast = input.clone();
+            fft(&mut fast, false);
+            for (k, &(re, im)) in fast.iter().enumerate() {
+                let (mut er, mut ei) = (0.0, 0.0);
+                for (t, &(xr, xi)) in input.iter().enumerate() {
+                    let (s, c) = (-2.0 * PI * (k * t) as f64 / n as f64).sin_cos();
+                    er += xr * c - xi * s;
+                    ei += xr * s + xi * c;
+                }
+                assert!((re - er).abs() < 1e-9 && (im - ei).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn stft_finds_tone_and_istft_reconstructs() {
+        // 频率为 4 个周期 / 16 采样的余弦落在第 4 个频点
+        let signal: Vec<f32> = (0..64)
+            .map(|t| (2.0 * PI * 4.0 * t as f64 / 16.0).cos() as f32)
+            .collect();
+        let x = Tensor::new(signal, vec![64]).unwrap();
+        let (magnitude, phase) = x.stft(16, 4, WindowFn::Rectangular).unwrap();
+        assert_eq!(magnitude.shape, [13, 9]);
+        assert!((magnitude.data[4] - 8.0).abs() < 1e-4);
+        assert!(
+            magnitude.data[..9]
+                .iter()
+                .enumerate()
+                .all(|(k, &m)| k == 4 || m < 1e-4)
+        );
+
+        for window_fn in [WindowFn::Rectangular, WindowFn::Hamming, WindowFn::Hann] {
+            let (m, p) = x.stft(16, 4, window_fn).unwrap();
+            let back = Tensor::istft(&m, &p, 16, 4, window_fn).unwrap();
+            assert_eq!(back.shape, [64]);
+            // Hann 窗首个采样权重为 0，无法恢复
+            let start = if window_fn == WindowFn::Hann { 1 } else { 0 };
+            for (a, b) in back.data[start..].iter().zip(&x.data[start..]) {
+                assert!((a - b).abs() < 1e-4);
+            }
+        }
+
+        let batch = Tensor::zeros(vec![2, 32]).unwrap();
+        assert_eq!(batch.stft(8, 8, WindowFn::Hann).unwrap().0.shape, [2, 4, 5]);
+        assert!(Tensor::istft(&magnitude, &phase, 15, 4, WindowFn::Hann).is_err());
+        assert!(
+            Tensor::zeros(vec![1, 1, 8])
+                .unwrap()
+                .stft(4, 1, WindowFn::Hann)
+                .is_err()
+        );
+    }
+}
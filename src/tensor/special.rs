@@ -0,0 +1,173 @@
+use super::Tensor;
+use std::f64::consts::PI;
+
+const LANCZOS_G: f64 = 7.0;
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+// Numerical Recipes 的 Chebyshev 拟合，全域相对误差小于 1.2e-7
+fn erfc_f64(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let ans = t * (-z * z + poly).exp();
+    if x >= 0.0 {
+        return ans;
+    }
+
+    return 2.0 - ans;
+}
+
+// 原点附近 1 - erfc 会丢失有效位，改用泰勒级数
+fn erf_f64(x: f64) -> f64 {
+    if x.abs() >= 0.5 {
+        return 1.0 - erfc_f64(x);
+    }
+    let (mut term, mut sum) = (x, x);
+    for n in 1..20 {
+        term *= -x * x / n as f64;
+        sum += term / (2 * n + 1) as f64;
+    }
+
+    return sum * 2.0 / PI.sqrt();
+}
+
+fn lanczos_sum(x: f64) -> f64 {
+    return LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |acc, (i, &c)| acc + c / (x + i as f64 + 1.0));
+}
+
+fn gamma_f64(x: f64) -> f64 {
+    if x <= 0.0 && x == x.floor() {
+        return if x == 0.0 { 1.0 / x } else { f64::NAN };
+    }
+    if x < 0.5 {
+        return PI / ((PI * x).sin() * gamma_f64(1.0 - x));
+    }
+    let x = x - 1.0;
+    let t = x + LANCZOS_G + 0.5;
+
+    return (2.0 * PI).sqrt() * t.powf(x + 0.5) * (-t).exp() * lanczos_sum(x);
+}
+
+fn lgamma_f64(x: f64) -> f64 {
+    if x <= 0.0 && x == x.floor() {
+        return f64::INFINITY;
+    }
+    if x < 0.5 {
+        return (PI / (PI * x).sin().abs()).ln() - lgamma_f64(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + LANCZOS_G + 0.5;
+
+    return 0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + lanczos_sum(x).ln();
+}
+
+impl Tensor {
+    pub fn erf(&self) -> Result<Tensor, String> {
+        return self.map_float("erf", |a| erf_f64(a as f64) as f32);
+    }
+
+    pub fn erfc(&self) -> Result<Tensor, String> {
+        return self.map_float("erfc", |a| erfc_f64(a as f64) as f32);
+    }
+
+    // 非正整数处为极点：0 返回带符号的无穷，负整数返回 NaN
+    pub fn gamma(&self) -> Result<Tensor, String> {
+        return self.map_float("gamma", |a| gamma_f64(a as f64) as f32);
+    }
+
+    // ln|Γ(x)|，极点处为 +inf
+    pub fn lgamma(&self) -> Result<Tensor, String> {
+        return self.map_float("lgamma", |a| lgamma_f64(a as f64) as f32);
+    }
+
+    pub fn erf_(&mut self) -> Result<(), String> {
+        return self.map_float_("erf_", |a| erf_f64(a as f64) as f32);
+    }
+
+    pub fn erfc_(&mut self) -> Result<(), String> {
+        return self.map_float_("erfc_", |a| erfc_f64(a as f64) as f32);
+    }
+
+    pub fn gamma_(&mut self) -> Result<(), String> {
+        return self.map_float_("gamma_", |a| gamma_f64(a as f64) as f32);
+    }
+
+    pub fn lgamma_(&mut self) -> Result<(), String> {
+        return self.map_float_("lgamma_", |a| lgamma_f64(a as f64) as f32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(values: &[f32]) -> Tensor {
+        return Tensor::new(values.to_vec(), vec![values.len()]).unwrap();
+    }
+
+    fn assert_rel(actual: &[f32], expected: &[f32]) {
+        for (&a, &e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() <= 1e-6 * e.abs().max(1.0), "{} != {}", a, e);
+        }
+    }
+
+    #[test]
+    fn erf_and_erfc_match_reference_values() {
+        let x = t(&[0.0, 0.1, 1.0, -2.0, 3.0]);
+        assert_rel(
+            &x.erf().unwrap().data,
+            &[0.0, 0.112_462_92, 0.842_700_8, -0.995_322_3, 0.999_977_9],
+        );
+        let tail = t(&[3.0, 5.0]).erfc().unwrap();
+        assert!((tail.data[0] / 2.209_049_7e-5 - 1.0).abs() < 1e-6);
+        assert!((tail.data[1] / 1.537_459_8e-12 - 1.0).abs() < 1e-6);
+        assert_eq!(t(&[-1.0]).erfc().unwrap().data[0], 1.842_700_8);
+    }
+
+    #[test]
+    fn gamma_and_lgamma_cover_reflection_and_poles() {
+        let sqrt_pi = PI.sqrt() as f32;
+        let x = t(&[5.0, 0.5, -0.5, 1.0]);
+        assert_rel(
+            &x.gamma().unwrap().data,
+            &[24.0, sqrt_pi, -2.0 * sqrt_pi, 1.0],
+        );
+        assert_rel(
+            &t(&[100.0, -0.5, 1.0, 2.0]).lgamma().unwrap().data,
+            &[359.134_2, (2.0 * sqrt_pi).ln(), 0.0, 0.0],
+        );
+
+        let poles = t(&[0.0, -0.0, -3.0]);
+        let g = poles.gamma().unwrap();
+        assert_eq!(g.data[..2], [f32::INFINITY, f32::NEG_INFINITY]);
+        assert!(g.data[2].is_nan());
+        assert!(
+            poles
+                .lgamma()
+                .unwrap()
+                .data
+                .iter()
+                .all(|&v| v == f32::INFINITY)
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use super::Tensor;
+use crate::random::Rng;
+
+impl Tensor {
+    fn check_ratio(ratio: f32) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(format!("训练集比例 {} 不在 [0, 1] 范围内", ratio));
+        }
+
+        return Ok(());
+    }
+
+    fn index_tensor(indices: &[usize]) -> Result<Tensor, String> {
+        let data = indices.iter().map(|&i| i as f32).collect();
+
+        return Tensor::new(data, vec![indices.len()]);
+    }
+
+    pub fn split_rows(&self, train_ratio: f32, seed: u64) -> Result<(Tensor, Tensor), String> {
+        Self::check_ratio(train_ratio)?;
+        if self.shape.is_empty() {
+            return Err("split_rows 不支持零维张量".to_string());
+        }
+
+        let rows = self.shape[0];
+        let mut order: Vec<usize> = (0..rows).collect();
+        Rng::new(seed).shuffle(&mut order);
+
+        let n_train = (rows as f32 * train_ratio).round() as usize;
+        let (train, test) = order.split_at(n_train);
+
+        return Ok((Self::index_tensor(train)?, Self::index_tensor(test)?));
+    }
+
+    pub fn stratified_split(
+        &self,
+        labels: &Tensor,
+        train_ratio: f32,
+        seed: u64,
+    ) -> Result<(Tensor, Tensor), String> {
+        Self::check_ratio(train_ratio)?;
+        if self.shape.is_empty() || labels.data.len() != self.shape[0] {
+            return Err(format!(
+                "标签数量 {} 与样本行数不匹配（形状：{:?}）",
+                labels.data.len(),
+                self.shape
+            ));
+        }
+
+        let mut classes: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        for (row, &label) in labels.data.iter().enumerate() {
+            classes.entry(label.to_bits()).or_default().push(row);
+        }
+
+        let mut rng = Rng::new(seed);
+        let mut train = Vec::new();
+        let mut test = Vec::new();
+        for rows in classes.values_mut() {
+            rng.shuffle(rows);
+            let n_train = (rows.len() as f32 * train_ratio).round() as usize;
+            train.extend_from_slice(&rows[..n_train]);
+            test.extend_from_slice(&rows[n_train..]);
+        }
+        rng.shuffle(&mut train);
+        rng.shuffle(&mut test);
+
+        return Ok((Self::index_tensor(&train)?, Self::index_tensor(&test)?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(t: &Tensor) -> Vec<f32> {
+        let mut v = t.data.to_vec();
+        v.sort_by(f32::total_cmp);
+
+        return v;
+    }
+
+    #[test]
+    fn split_rows_partitions_every_row_once() {
+        let x = Tensor::zeros(vec![10, 2]).unwrap();
+        let (train, test) = x.split_rows(0.7, 3).unwrap();
+        assert_eq!((train.data.len(), test.data.len()), (7, 3));
+        let mut all = sorted(&train);
+        all.extend(sorted(&test));
+        all.sort_by(f32::total_cmp);
+        assert_eq!(all, (0..10).map(|i| i as f32).collect::<Vec<_>>());
+        assert_eq!(x.split_rows(0.7, 3).unwrap(), (train, test));
+
+        let (all_train, none) = x.split_rows(1.0, 0).unwrap();
+        assert_eq!((all_train.data.len(), none.data.len()), (10, 0));
+        let empty = Tensor::zeros(vec![0, 2]).unwrap();
+        assert_eq!(empty.split_rows(0.5, 0).unwrap().0.data.len(), 0);
+    }
+
+    #[test]
+    fn stratified_split_keeps_class_ratios() {
+        let x = Tensor::zeros(vec![8, 1]).unwrap();
+        let labels = Tensor::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0], vec![8]).unwrap();
+        let (train, test) = x.stratified_split(&labels, 0.5, 11).unwrap();
+        assert_eq!((train.data.len(), test.data.len()), (4, 4));
+        let zeros = train.data.iter().filter(|&&r| r < 4.0).count();
+        assert_eq!(zeros, 2);
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let x = Tensor::zeros(vec![4]).unwrap();
+        let labels = Tensor::zeros(vec![4]).unwrap();
+        for ratio in [-0.1, 1.5, f32::NAN] {
+            assert!(x.split_rows(ratio, 0).is_err());
+            assert!(x.stratified_split(&labels, ratio, 0).is_err());
+        }
+        let scalar = Tensor::new(vec![1.0], Vec::<usize>::new()).unwrap();
+        assert!(scalar.split_rows(0.5, 0).is_err());
+        assert!(
+            x.stratified_split(&Tensor::zeros(vec![3]).unwrap(), 0.5, 0)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,502 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut, Range};
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::{Arc, OnceLock};
+
+use super::device::DeviceBuffer;
+use super::{DType, Device, Shape};
+use crate::Tensor;
+
+// 张量的元素缓冲区：clone 只增加引用计数，首次可变访问时若仍被共享才复制（写时复制）。
+// 跨线程共享规则：
+// - Tensor 是 Send + Sync，clone 后把句柄交给其他线程不会复制数据，多线程并发读无需加锁；
+// - 写入总要 &mut 句柄，且只会作用于该句柄自己的副本，其他线程看不到；
+// - 需要保证共享权重绝不被改写（也就不会产生副本）时先 freeze()。
+// 设备张量的数据常驻在设备缓冲区中，算子之间不经过主机；主机副本在第一次按切片
+// 读取时才从设备取回并缓存。两份数据同时存在时内容一致，主机端写入会丢弃设备副本
+#[derive(Clone)]
+pub struct Storage {
+    host: OnceLock<Buffer>,
+    resident: OnceLock<Arc<dyn DeviceBuffer>>,
+    frozen: bool,
+}
+
+#[derive(Clone)]
+enum Buffer {
+    // 自有缓冲区中的一段；narrow、chunks 取出的连续视图与原张量共享同一个 Vec
+    Owned(Arc<Vec<f32>>, Range<usize>),
+    // 借用外部内存（mmap、FFI 调用方等），只读；第一次写入时复制成 Owned
+    Borrowed(&'static [f32]),
+}
+
+impl Buffer {
+    fn owned(data: Vec<f32>) -> Buffer {
+        let len = data.len();
+        return Buffer::Owned(Arc::new(data), 0..len);
+    }
+}
+
+impl Default for Buffer {
+    fn default() -> Self {
+        return Buffer::Borrowed(&[]);
+    }
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        return Storage::from_buffer(Buffer::default());
+    }
+}
+
+impl Storage {
+    fn from_buffer(buf: Buffer) -> Storage {
+        return Storage {
+            host: OnceLock::from(buf),
+            resident: OnceLock::new(),
+            frozen: false,
+        };
+    }
+
+    // 只有设备副本的存储，主机副本在第一次读取时取回
+    #[cfg_attr(
+        not(any(feature = "cuda", all(feature = "metal", target_os = "macos"))),
+        allow(dead_code)
+    )]
+    pub(crate) fn from_resident(buffer: Arc<dyn DeviceBuffer>) -> Storage {
+        return Storage {
+            host: OnceLock::new(),
+            resident: OnceLock::from(buffer),
+            frozen: false,
+        };
+    }
+
+    // 设备缓冲区不可用时无法返回 Result，只能 panic；需要处理错误时先调用 Tensor::to(Device::Cpu)
+    fn host(&self) -> &Buffer {
+        return self.host.get_or_init(|| {
+            let resident = self
+                .resident
+                .get()
+                .expect("存储既没有主机副本也没有设备副本");
+            match resident.read() {
+                Ok(values) => Buffer::owned(values),
+                Err(e) => panic!("从设备读回数据失败：{}", e),
+            }
+        });
+    }
+
+    pub(crate) fn try_host(&self) -> Result<&[f32], String> {
+        if self.host.get().is_none() {
+            let resident = self
+                .resident
+                .get()
+                .expect("存储既没有主机副本也没有设备副本");
+            let values = resident.read()?;
+            let _ = self.host.set(Buffer::owned(values));
+        }
+
+        return Ok(self.as_slice_ref());
+    }
+
+    #[cfg_attr(
+        not(any(feature = "cuda", all(feature = "metal", target_os = "macos"))),
+        allow(dead_code)
+    )]
+    pub(crate) fn resident(&self) -> Option<&Arc<dyn DeviceBuffer>> {
+        return self.resident.get();
+    }
+
+    // 缓存上传得到的设备副本；并发上传时只保留先写入的一份
+    pub(crate) fn cache_resident(&self, buffer: Arc<dyn DeviceBuffer>) -> Arc<dyn DeviceBuffer> {
+        let _ = self.resident.set(buffer);
+        return self.resident.get().unwrap().clone();
+    }
+
+    // 只带主机副本的新句柄，共享同一块主机内存
+    pub(crate) fn host_copy(&self) -> Result<Storage, String> {
+        self.try_host()?;
+        let mut out = Storage::from_buffer(self.host().clone());
+        out.frozen = self.frozen;
+
+        return Ok(out);
+    }
+
+    // [start, start + len) 段的视图，与 self 共享主机缓冲区；写入视图时先复制出自己的一份
+    pub(crate) fn view(&self, start: usize, len: usize) -> Storage {
+        let buf = match self.host() {
+            Buffer::Owned(buf, range) => {
+                let start = range.start + start;
+                Buffer::Owned(buf.clone(), start..start + len)
+            }
+            Buffer::Borrowed(slice) => Buffer::Borrowed(&slice[start..start + len]),
+        };
+
+        return Storage::from_buffer(buf);
+    }
+
+    // 视图在自有缓冲区中的起始位置；借用的外部内存与只有设备副本的存储总是 0
+    pub(crate) fn offset(&self) -> usize {
+        return match self.host.get() {
+            Some(Buffer::Owned(_, range)) => range.start,
+            _ => 0,
+        };
+    }
+
+    // 主机副本是否已经存在；设备算子链中间的结果在读取前始终为 false
+    pub fn is_on_host(&self) -> bool {
+        return self.host.get().is_some();
+    }
+
+    pub fn len(&self) -> usize {
+        return match (self.host.get(), self.resident.get()) {
+            (Some(_), _) => self.as_slice_ref().len(),
+            (None, Some(resident)) => resident.len(),
+            (None, None) => 0,
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    pub fn as_ptr(&self) -> *const f32 {
+        return self.as_slice_ref().as_ptr();
+    }
+
+    fn as_slice_ref(&self) -> &[f32] {
+        return match self.host() {
+            Buffer::Owned(buf, range) => &buf[range.clone()],
+            Buffer::Borrowed(slice) => slice,
+        };
+    }
+
+    pub fn is_shared(&self) -> bool {
+        return match self.host() {
+            Buffer::Owned(buf, _) => Arc::strong_count(buf) > 1,
+            Buffer::Borrowed(_) => true,
+        };
+    }
+
+    pub fn is_borrowed(&self) -> bool {
+        return matches!(self.host.get(), Some(Buffer::Borrowed(_)));
+    }
+
+    pub fn shares_with(&self, other: &Storage) -> bool {
+        if let (Some(a), Some(b)) = (self.resident.get(), other.resident.get())
+            && Arc::ptr_eq(a, b)
+        {
+            return true;
+        }
+
+        return match (self.host.get(), other.host.get()) {
+            (Some(Buffer::Owned(a, _)), Some(Buffer::Owned(b, _))) => Arc::ptr_eq(a, b),
+            (Some(Buffer::Borrowed(a)), Some(Buffer::Borrowed(b))) => {
+                let (a, b) = (a.as_ptr_range(), b.as_ptr_range());
+                a == b || (a.start < b.end && b.start < a.end)
+            }
+            _ => false,
+        };
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        return self.frozen;
+    }
+
+    pub fn into_vec(mut self) -> Vec<f32> {
+        self.host();
+        return match self.host.take() {
+            Some(Buffer::Owned(buf, range)) if range == (0..buf.len()) => Arc::unwrap_or_clone(buf),
+            Some(Buffer::Owned(buf, range)) => buf[range].to_vec(),
+            Some(Buffer::Borrowed(slice)) => slice.to_vec(),
+            None => unreachable!(),
+        };
+    }
+
+    // 独占时取回底层 Vec 供分配池复用；仍被共享、借用外部内存或只在设备上时只放弃自己的引用
+    pub(crate) fn take_unique(&mut self) -> Option<Vec<f32>> {
+        self.resident.take();
+        return match self.host.take() {
+            Some(Buffer::Owned(buf, _)) => Arc::into_inner(buf),
+            _ => None,
+        };
+    }
+}
+
+impl From<Vec<f32>> for Storage {
+    fn from(data: Vec<f32>) -> Self {
+        return Storage::from_buffer(Buffer::owned(data));
+    }
+}
+
+impl Deref for Storage {
+    type Target = [f32];
+
+    fn deref(&self) -> &[f32] {
+        return self.as_slice_ref();
+    }
+}
+
+impl DerefMut for Storage {
+    // data 字段只在 crate 内可见，外部写入都经过 check_writable；
+    // 走到这里说明 crate 内部的实现漏掉了检查
+    fn deref_mut(&mut self) -> &mut [f32] {
+        assert!(!self.frozen, "不能修改已冻结的张量");
+        self.host();
+        // 主机端写入之后设备副本就过期了
+        self.resident.take();
+        let host = self.host.get_mut().unwrap();
+        // 借用的内存与自有缓冲区中的一段视图都先复制成完整的自有缓冲区
+        let part = match &*host {
+            Buffer::Owned(buf, range) if *range == (0..buf.len()) => None,
+            Buffer::Owned(buf, range) => Some(buf[range.clone()].to_vec()),
+            Buffer::Borrowed(slice) => Some(slice.to_vec()),
+        };
+        if let Some(data) = part {
+            *host = Buffer::owned(data);
+        }
+        let Buffer::Owned(buf, _) = host else {
+            unreachable!();
+        };
+
+        return Arc::make_mut(buf).as_mut_slice();
+    }
+}
+
+impl<'a> IntoIterator for &'a Storage {
+    type Item = &'a f32;
+    type IntoIter = std::slice::Iter<'a, f32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Storage {
+    type Item = &'a mut f32;
+    type IntoIter = std::slice::IterMut<'a, f32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter_mut();
+    }
+}
+
+impl PartialEq for Storage {
+    fn eq(&self, other: &Storage) -> bool {
+        return self[..] == other[..];
+    }
+}
+
+impl fmt::Debug for Storage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return self[..].fmt(f);
+    }
+}
+
+// 设备缓冲区是 trait 对象，这些自动 trait 要靠 DeviceBuffer 的约束才能保留
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Tensor>();
+    const fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
+    assert_unwind_safe::<Tensor>();
+};
+
+impl Tensor {
+    pub(crate) fn from_storage(data: Storage, shape: Shape) -> Result<Tensor, String> {
+        let total_size = shape.numel();
+        if data.len() != total_size {
+            return Err(format!(
+                "数据长度 {} 与形状 {:?} 不匹配（总大小：{}）",
+                data.len(),
+                shape,
+                total_size,
+            ));
+        }
+        let strides = shape.strides();
+
+        return Ok(Tensor {
+            data: data,
+            shape: shape,
+            strides: strides,
+            dtype: DType::F32,
+            device: Device::Cpu,
+        });
+    }
+
+    // 不复制地借用 'static 内存（常量表、leak 出来的缓冲区、进程内常驻的 mmap）。
+    // 借来的数据只读：写入会先复制出自有的缓冲区，原内存永远不会被改写。
+    // 生命周期更短的内存用 TensorView 借用，无需 unsafe
+    pub fn from_slice(data: &'static [f32], shape: impl Into<Shape>) -> Result<Tensor, String> {
+        let shape: Shape = shape.into();
+        let storage = Storage::from_buffer(Buffer::Borrowed(data));
+
+        return Tensor::from_storage(storage, shape);
+    }
+
+    /// # Safety
+    ///
+    /// `len > 0` 时 `ptr` 必须非空、按 f32 对齐并指向 `len` 个已初始化的 f32。
+    /// 这段内存必须在返回的张量及其所有 clone（包括交给其他线程的句柄）都被 drop 之前
+    /// 保持有效，且期间不能被任何人改写。张量本身只读不写，也不会释放这段内存。
+    /// 调用方能给出 `&'a [f32]` 时应改用安全的 [`TensorView::new`](crate::TensorView::new)。
+    pub unsafe fn from_raw_parts(
+        ptr: *const f32,
+        len: usize,
+        shape: impl Into<Shape>,
+    ) -> Result<Tensor, String> {
+        let shape: Shape = shape.into();
+        if len > 0 && (ptr.is_null() || !ptr.is_aligned()) {
+            return Err(format!("from_raw_parts 收到空指针或未对齐的指针 {:?}", ptr));
+        }
+        let data: &'static [f32] = if len == 0 {
+            &[]
+        } else {
+            // SAFETY: 指针有效性与生命周期由调用方按上面的约定保证
+            unsafe { std::slice::from_raw_parts(ptr, len) }
+        };
+
+        return Tensor::from_slice(data, shape);
+    }
+
+    // clone() 与原张量共享缓冲区，deep_copy() 立即复制一份独立的缓冲区
+    pub fn deep_copy(&self) -> Result<Tensor, String> {
+        let mut data = crate::alloc::allocate(self.data.len());
+        data.extend_from_slice(&self.data);
+
+        let out = Tensor::new(data, self.shape.clone())?;
+
+        return Ok(self.placed(out).with_dtype(self.dtype));
+    }
+
+    pub fn storage_ptr(&self) -> *const f32 {
+        return self.data.as_ptr();
+    }
+
+    pub fn shares_storage(&self, other: &Tensor) -> bool {
+        return self.data.shares_with(&other.data);
+    }
+
+    // 冻结不可撤销，clone 出的句柄同样是冻结的；需要可写副本时用 deep_copy()
+    pub fn freeze(&mut self) {
+        self.data.frozen = true;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        return self.data.is_frozen();
+    }
+
+    pub(crate) fn check_writable(&self, op: &str) -> Result<(), String> {
+        if self.data.is_frozen() {
+            return Err(format!("{}：张量已冻结，不能修改", op));
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_until_written_and_deep_copies_never_do() {
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        let mut b = a.clone();
+        assert!(b.shares_storage(&a));
+        assert_eq!(b.storage_ptr(), a.storage_ptr());
+        assert!(a.reshaped(vec![4]).unwrap().shares_storage(&a));
+
+        // 第一次写入时才复制，原张量保持不变
+        b.set(&[0, 0], 9.0).unwrap();
+        assert!(!b.shares_storage(&a));
+        assert_eq!(a.data[0], 1.0);
+        assert_eq!(b.data[0], 9.0);
+
+        let c = a.deep_copy().unwrap();
+        assert!(!c.shares_storage(&a));
+        assert_ne!(c.storage_ptr(), a.storage_ptr());
+        assert_eq!(c, a);
+
+        // 独占缓冲区时写入不会复制
+        let mut d = c.clone();
+        drop(c);
+        let before = d.storage_ptr();
+        d.data_mut().unwrap()[1] = 0.0;
+        assert_eq!(d.storage_ptr(), before);
+    }
+
+    #[test]
+    fn frozen_tensors_reject_mutation() {
+        let mut w = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![2, 2]).unwrap();
+        w.freeze();
+        assert!(w.is_frozen());
+        assert!(w.data_mut().is_err());
+        assert!(w.set(&[0, 0], 5.0).is_err());
+        assert!(w.get_mut(&[1, 1]).is_err());
+        assert!(w.reshape(vec![4]).is_err());
+        assert!(w.row_mut(0).is_err());
+        assert!(w.fill_diagonal_(0.0).is_err());
+        assert!(w.axpy_(1.0, &w.clone()).is_err());
+        assert!(w.storage().is_frozen());
+
+        // 读取不受影响，句柄共享冻结状态，deep_copy 得到可写副本
+        assert_eq!(w.get(&[1, 0]).unwrap(), &3.0);
+        assert!(w.clone().is_frozen());
+        let mut copy = w.deep_copy().unwrap();
+        assert!(!copy.is_frozen());
+        copy.set(&[0, 0], 5.0).unwrap();
+        assert_eq!(w.data[0], 1.0);
+    }
+
+    static TABLE: [f32; 6] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+
+    #[test]
+    fn borrowed_tensors_read_in_place_and_copy_on_write() {
+        let t = Tensor::from_slice(&TABLE, vec![2, 3]).unwrap();
+        assert!(t.data.is_borrowed());
+        assert_eq!(t.storage_ptr(), TABLE.as_ptr());
+        assert_eq!(t.sum().unwrap(), 21.0);
+        assert!(t.clone().shares_storage(&t));
+        assert!(Tensor::from_slice(&TABLE, vec![4]).is_err());
+
+        let mut written = t.clone();
+        written.set(&[0, 0], 9.0).unwrap();
+        assert!(!written.data.is_borrowed());
+        assert_eq!(TABLE[0], 1.0);
+        assert_eq!(t.data[0], 1.0);
+
+        let raw = unsafe { Tensor::from_raw_parts(TABLE.as_ptr(), 6, vec![3, 2]) }.unwrap();
+        assert!(raw.shares_storage(&t));
+        assert_eq!(raw.get(&[2, 1]).unwrap(), &6.0);
+        let empty = unsafe { Tensor::from_raw_parts(std::ptr::null(), 0, vec![0]) }.unwrap();
+        assert_eq!(empty.shape, [0]);
+        assert!(unsafe { Tensor::from_raw_parts(std::ptr::null(), 2, vec![2]) }.is_err());
+    }
+
+    #[test]
+    fn weights_are_shared_across_threads_without_copying() {
+        let mut weights = Tensor::new((0..1024).map(|i| i as f32).collect(), vec![32, 32]).unwrap();
+        weights.freeze();
+
+        let sums: Vec<f32> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|_| {
+                    let handle = weights.clone();
+                    let origin = &weights;
+                    scope.spawn(move || {
+                        assert!(handle.shares_storage(origin));
+                        handle.data.iter().sum::<f32>()
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+        assert!(sums.iter().all(|&s| s == 523776.0));
+
+        // 未冻结的句柄在线程内写入只改自己的副本
+        let shared = Tensor::zeros(vec![4]).unwrap();
+        let mut local = shared.clone();
+        std::thread::spawn(move || local.set(&[0], 1.0).unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(shared.data.to_vec(), vec![0.0; 4]);
+    }
+}
@@ -0,0 +1,131 @@
+use super::Tensor;
+use crate::parallel;
+
+// 求和方式。Naive 在每个块内顺序累加，误差随块长线性增长；
+// Pairwise 递归二分后两两相加，误差随 log(n) 增长；
+// Kahan 用 Neumaier 补偿累加，误差与长度基本无关，代价约为四倍的加法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Summation {
+    #[default]
+    Naive,
+    Pairwise,
+    Kahan,
+}
+
+const PAIRWISE_BASE: usize = 8;
+
+fn naive_sum(lane: &[f32]) -> f32 {
+    return lane.iter().fold(0.0, |acc, &x| acc + x);
+}
+
+fn pairwise_sum(lane: &[f32]) -> f32 {
+    if lane.len() <= PAIRWISE_BASE {
+        return naive_sum(lane);
+    }
+    let (lo, hi) = lane.split_at(lane.len() / 2);
+
+    return pairwise_sum(lo) + pairwise_sum(hi);
+}
+
+// Neumaier 的 two-sum：返回 a + b 及其舍入误差
+fn two_sum(a: f32, b: f32) -> (f32, f32) {
+    let sum = a + b;
+    let err = if a.abs() >= b.abs() {
+        (a - sum) + b
+    } else {
+        (b - sum) + a
+    };
+
+    return (sum, err);
+}
+
+fn kahan_partial(lane: &[f32]) -> (f32, f32) {
+    let (mut sum, mut comp) = (0.0f32, 0.0f32);
+    for &x in lane {
+        let (s, err) = two_sum(sum, x);
+        sum = s;
+        comp += err;
+    }
+
+    return (sum, comp);
+}
+
+// 出现无穷或 NaN 时补偿项没有意义，直接返回累加结果
+fn kahan_finish((sum, comp): (f32, f32)) -> f32 {
+    if sum.is_finite() {
+        return sum + comp;
+    }
+
+    return sum;
+}
+
+// 空 lane 的和为 +0.0；Iterator::sum 对空的 f32 序列给出 -0.0
+pub(crate) fn sum_lane(lane: &[f32], mode: Summation) -> f32 {
+    return match mode {
+        Summation::Naive => naive_sum(lane),
+        Summation::Pairwise => pairwise_sum(lane),
+        Summation::Kahan => kahan_finish(kahan_partial(lane)),
+    };
+}
+
+// 整个张量的和：固定大小的块并行求和，再按固定的树两两合并
+pub(crate) fn sum_all(data: &[f32], mode: Summation) -> f32 {
+    if mode == Summation::Kahan {
+        let partial = parallel::reduce_tree(
+            data,
+            |_, chunk| kahan_partial(chunk),
+            |a, b| {
+                let (sum, err) = two_sum(a.0, b.0);
+                (sum, a.1 + b.1 + err)
+            },
+        );
+        return partial.map(kahan_finish).unwrap_or(0.0);
+    }
+
+    return parallel::reduce_tree(data, |_, chunk| sum_lane(chunk, mode), |a, b| a + b)
+        .unwrap_or(0.0);
+}
+
+impl Tensor {
+    // 不受 config::set_summation 影响的单次调用版本
+    pub fn sum_with(&self, mode: Summation) -> Result<f32, String> {
+        return Ok(sum_all(&self.data, mode));
+    }
+
+    pub fn mean_with(&self, mode: Summation) -> Result<f32, String> {
+        return Ok(sum_all(&self.data, mode) / self.data.len() as f32);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+
+    #[test]
+    fn compensated_modes_recover_lost_precision() {
+        // 顺序累加 1e6 个 0.1 会漂移到 100958 左右
+        let t = Tensor::full(vec![1_000_000], 0.1).unwrap();
+        let naive = t.sum_with(Summation::Naive).unwrap();
+        let pairwise = t.sum_with(Summation::Pairwise).unwrap();
+        let kahan = t.sum_with(Summation::Kahan).unwrap();
+        assert!((kahan - 100_000.0).abs() < 0.01);
+        assert!((pairwise - 100_000.0).abs() < 0.1);
+        assert!((kahan - 100_000.0).abs() < (naive - 100_000.0).abs());
+        assert!((t.mean_with(Summation::Kahan).unwrap() - 0.1).abs() < 1e-7);
+
+        // 大数吃掉小数的经典例子
+        let lane = [1e8, 1.0, -1e8, 1.0];
+        assert_eq!(sum_lane(&lane, Summation::Naive), 1.0);
+        assert_eq!(sum_lane(&lane, Summation::Kahan), 2.0);
+        assert_eq!(
+            sum_lane(&[1.0, f32::INFINITY], Summation::Kahan),
+            f32::INFINITY
+        );
+        assert_eq!(sum_lane(&[], Summation::Kahan).to_bits(), 0);
+
+        // sum 使用全局配置的方式；测试只读取配置，不改动它
+        let configured = t.sum_with(config::summation()).unwrap();
+        assert_eq!(t.sum().unwrap().to_bits(), configured.to_bits());
+    }
+}
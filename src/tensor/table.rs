@@ -0,0 +1,122 @@
+use super::Tensor;
+
+// 二维张量的表格文本：Markdown 便于贴进报告与 issue，CSV 便于导入其它工具
+impl Tensor {
+    fn table_headers(&self, op: &str, headers: Option<&[&str]>) -> Result<Vec<String>, String> {
+        let (_, cols) = self.matrix_dims(op)?;
+        let Some(headers) = headers else {
+            return Ok((0..cols).map(|j| j.to_string()).collect());
+        };
+        if headers.len() != cols {
+            return Err(format!("列名数量 {} 与列数 {} 不匹配", headers.len(), cols));
+        }
+
+        return Ok(headers.iter().map(|h| h.to_string()).collect());
+    }
+
+    // 超过 max_rows 的行省略为一行 "…"，并注明省略的行数
+    pub fn to_markdown_table(
+        &self,
+        max_rows: usize,
+        headers: Option<&[&str]>,
+    ) -> Result<String, String> {
+        let headers = self.table_headers("to_markdown_table", headers)?;
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+
+        let escape = |s: &str| s.replace('|', "\\|");
+        let mut out = String::new();
+        out.push_str(&format!(
+            "| {} |\n",
+            headers
+                .iter()
+                .map(|h| escape(h))
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ));
+        out.push_str(&format!("|{}\n", "---:|".repeat(cols)));
+        for i in 0..rows.min(max_rows) {
+            let cells: Vec<String> = self.data[i * cols..(i + 1) * cols]
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        if rows > max_rows {
+            out.push_str(&format!("| {} |\n", vec!["…"; cols].join(" | ")));
+            out.push_str(&format!(
+                "\n（共 {} 行，省略 {} 行）\n",
+                rows,
+                rows - max_rows
+            ));
+        }
+
+        return Ok(out);
+    }
+
+    pub fn to_csv_string(&self, headers: Option<&[&str]>) -> Result<String, String> {
+        let has_headers = headers.is_some();
+        let headers = self.table_headers("to_csv_string", headers)?;
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+
+        // 含逗号、引号或换行的列名按 RFC 4180 加引号
+        let quote = |s: &str| {
+            if s.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.to_string()
+            }
+        };
+        let mut out = String::new();
+        if has_headers {
+            out.push_str(
+                &headers
+                    .iter()
+                    .map(|h| quote(h))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+            out.push('\n');
+        }
+        for i in 0..rows {
+            let cells: Vec<String> = self.data[i * cols..(i + 1) * cols]
+                .iter()
+                .map(|v| v.to_string())
+                .collect();
+            out.push_str(&cells.join(","));
+            out.push('\n');
+        }
+
+        return Ok(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_and_csv_tables() {
+        let t = Tensor::new(vec![1.0, 2.5, -3.0, f32::NAN, 5.0, 6.0], vec![3, 2]).unwrap();
+        assert_eq!(
+            t.to_markdown_table(10, Some(&["a", "b|c"])).unwrap(),
+            "| a | b\\|c |\n|---:|---:|\n| 1 | 2.5 |\n| -3 | NaN |\n| 5 | 6 |\n"
+        );
+        let truncated = t.to_markdown_table(1, None).unwrap();
+        assert!(truncated.starts_with("| 0 | 1 |\n|---:|---:|\n| 1 | 2.5 |\n| … | … |\n"));
+        assert!(truncated.contains("省略 2 行"));
+
+        assert_eq!(t.to_csv_string(None).unwrap(), "1,2.5\n-3,NaN\n5,6\n");
+        assert_eq!(
+            t.to_csv_string(Some(&["x", "say \"hi\", y"])).unwrap(),
+            "x,\"say \"\"hi\"\", y\"\n1,2.5\n-3,NaN\n5,6\n"
+        );
+
+        assert!(t.to_csv_string(Some(&["x"])).is_err());
+        assert!(
+            Tensor::zeros(vec![3])
+                .unwrap()
+                .to_markdown_table(5, None)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use super::Tensor;
+use std::thread;
+
+use crate::{parallel, profile};
+
+impl Tensor {
+    pub fn split_gains(
+        grad: &Tensor,
+        hess: &Tensor,
+        lambda: f32,
+        gamma: f32,
+    ) -> Result<(Tensor, usize), String> {
+        if grad.shape.len() != 1 || grad.shape != hess.shape {
+            return Err(format!(
+                "梯度直方图 {:?} 与海森直方图 {:?} 必须是形状相同的一维张量",
+                grad.shape, hess.shape
+            ));
+        }
+        let bins = grad.shape[0];
+        if bins < 2 {
+            return Err(format!("至少需要 2 个分箱才能切分，实际为 {}", bins));
+        }
+        let _scope = profile::scope("split_gains", bins);
+
+        let lambda = lambda as f64;
+        let g_total: f64 = grad.data.iter().map(|&g| g as f64).sum();
+        let h_total: f64 = hess.data.iter().map(|&h| h as f64).sum();
+        let parent = g_total * g_total / (h_total + lambda);
+
+        let mut gains = Vec::with_capacity(bins - 1);
+        let mut best = 0;
+        let (mut g_left, mut h_left) = (0.0f64, 0.0f64);
+        for i in 0..bins - 1 {
+            g_left += grad.data[i] as f64;
+            h_left += hess.data[i] as f64;
+            let g_right = g_total - g_left;
+            let h_right = h_total - h_left;
+
+            let gain = 0.5
+                * (g_left * g_left / (h_left + lambda) + g_right * g_right / (h_right + lambda)
+                    - parent)
+                - gamma as f64;
+            gains.push(gain as f32);
+            if gains[i] > gains[best] {
+                best = i;
+            }
+        }
+
+        return Ok((grad.placed(Tensor::new(gains, vec![bins - 1])?), best));
+    }
+
+    pub fn argsort_columns(&self) -> Result<Tensor, String> {
+        if self.shape.len() != 2 {
+            return Err(format!(
+                "argsort_columns 需要二维特征矩阵，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+        let _scope = profile::scope("argsort_columns", self.data.len());
+
+        let sort_column = |j: usize| -> Vec<usize> {
+            let mut order: Vec<usize> = (0..rows).collect();
+            order.sort_by(|&a, &b| self.data[a * cols + j].total_cmp(&self.data[b * cols + j]));
+            order
+        };
+
+        let workers = parallel::workers_for(self.data.len()).min(cols.max(1));
+        let sorted: Vec<Vec<usize>> = if workers <= 1 {
+            (0..cols).map(sort_column).collect()
+        } else {
+            let per_worker = cols.div_ceil(workers);
+            thread::scope(|scope| {
+                let handles: Vec<_> = (0..workers)
+                    .map(|w| {
+                        let sort_column = &sort_column;
+                        let start = (w * per_worker).min(cols);
+                        let end = ((w + 1) * per_worker).min(cols);
+                        scope.spawn(move || (start..end).map(sort_column).collect::<Vec<_>>())
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .flat_map(|h| h.join().unwrap())
+                    .collect()
+            })
+        };
+
+        let mut data = crate::alloc::allocate(self.data.len());
+        data.resize(self.data.len(), 0.0);
+        for (j, order) in sorted.iter().enumerate() {
+            for (i, &row) in order.iter().enumerate() {
+                data[i * cols + j] = row as f32;
+            }
+        }
+
+        return Ok(self.placed(Tensor::new(data, self.shape.clone())?));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn argsort_columns_orders_each_column() {
+        let x = Tensor::new(
+            vec![3.0, 0.0, 1.0, 2.0, 2.0, 1.0, f32::NAN, -1.0],
+            vec![4, 2],
+        )
+        .unwrap();
+        let order = x.argsort_columns().unwrap();
+        assert_eq!(
+            order.data.to_vec(),
+            vec![1.0, 3.0, 2.0, 0.0, 0.0, 2.0, 3.0, 1.0]
+        );
+        assert!(Tensor::ones(vec![3]).unwrap().argsort_columns().is_err());
+        assert_eq!(
+            Tensor::zeros(vec![0, 3])
+                .unwrap()
+                .argsort_columns()
+                .unwrap()
+                .shape,
+            [0, 3]
+        );
+    }
+
+    #[test]
+    fn split_gains_picks_the_separating_bin() {
+        let grad = Tensor::new(vec![-2.0, -2.0, 2.0, 2.0], vec![4]).unwrap();
+        let hess = Tensor::ones(vec![4]).unwrap();
+        let (gains, best) = Tensor::split_gains(&grad, &hess, 0.0, 0.0).unwrap();
+        assert_eq!(gains.shape, [3]);
+        assert_eq!(best, 1);
+        assert!(Tensor::split_gains(&grad, &Tensor::ones(vec![3]).unwrap(), 0.0, 0.0).is_err());
+        assert!(Tensor::split_gains(&grad.reshaped(vec![4, 1]).unwrap(), &hess, 0.0, 0.0).is_err());
+    }
+}
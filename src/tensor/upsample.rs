@@ -0,0 +1,141 @@
+use super::Tensor;
+use crate::{check, parallel, profile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsampleMode {
+    Nearest,
+    Bilinear { align_corners: bool },
+}
+
+// 输出坐标映射回输入坐标，返回相邻两个采样点及第二个点的权重
+fn source_coords(len: usize, out: usize, mode: UpsampleMode) -> Vec<(usize, usize, f32)> {
+    return (0..out)
+        .map(|i| match mode {
+            UpsampleMode::Nearest => ((i * len / out).min(len - 1), 0, 0.0),
+            UpsampleMode::Bilinear { align_corners } => {
+                let src = if align_corners {
+                    if out > 1 {
+                        i as f32 * (len - 1) as f32 / (out - 1) as f32
+                    } else {
+                        0.0
+                    }
+                } else {
+                    ((i as f32 + 0.5) * len as f32 / out as f32 - 0.5).max(0.0)
+                };
+                let lo = (src.floor() as usize).min(len - 1);
+                let hi = (lo + 1).min(len - 1);
+                (lo, hi, src - lo as f32)
+            }
+        })
+        .collect();
+}
+
+impl Tensor {
+    pub(crate) fn resize2d(
+        &self,
+        (oh, ow): (usize, usize),
+        mode: UpsampleMode,
+    ) -> Result<Tensor, String> {
+        if self.shape.len() != 4 {
+            return Err(format!(
+                "upsample 需要 [N, C, H, W] 输入，实际形状为 {:?}",
+                self.shape
+            ));
+        }
+        let (n, c, h, w) = (self.shape[0], self.shape[1], self.shape[2], self.shape[3]);
+        if oh == 0 || ow == 0 || h == 0 || w == 0 {
+            return Err(format!(
+                "upsample 的输入尺寸 {}x{} 与输出尺寸 {}x{} 必须为正",
+                h, w, oh, ow
+            ));
+        }
+        let _scope = profile::scope("upsample", n * c * oh * ow);
+
+        let rows = source_coords(h, oh, mode);
+        let cols = source_coords(w, ow, mode);
+        let planes = parallel::map_range(n * c, n * c * oh * ow, |p| {
+            let plane = &self.data[p * h * w..(p + 1) * h * w];
+            let mut out = Vec::with_capacity(oh * ow);
+            for &(y0, y1, fy) in &rows {
+                for &(x0, x1, fx) in &cols {
+                    let top = plane[y0 * w + x0] * (1.0 - fx) + plane[y0 * w + x1] * fx;
+                    let bottom = plane[y1 * w + x0] * (1.0 - fx) + plane[y1 * w + x1] * fx;
+                    out.push(if fy == 0.0 {
+                        top
+                    } else {
+                        top * (1.0 - fy) + bottom * fy
+                    });
+                }
+            }
+            out
+        });
+
+        let mut data = crate::alloc::allocate(n * c * oh * ow);
+        for plane in planes {
+            data.extend(plane);
+        }
+
+        let out = self.placed(Tensor::new(data, vec![n, c, oh, ow])?);
+        check::inspect("upsample", &[&self.shape], &out)?;
+
+        return Ok(out);
+    }
+
+    // 输出尺寸为 floor(H * scale_factor) x floor(W * scale_factor)
+    pub fn upsample(&self, scale_factor: f32, mode: UpsampleMode) -> Result<Tensor, String> {
+        if !scale_factor.is_finite() || scale_factor <= 0.0 || self.shape.len() != 4 {
+            return Err(format!(
+                "upsample 需要正的缩放因子与 [N, C, H, W] 输入，实际为 {} 与 {:?}",
+                scale_factor, self.shape
+            ));
+        }
+        let size = (
+            (self.shape[2] as f32 * scale_factor).floor() as usize,
+            (self.shape[3] as f32 * scale_factor).floor() as usize,
+        );
+
+        return self.resize2d(size, mode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_and_bilinear_match_pytorch() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], vec![1, 1, 2, 2]).unwrap();
+        let nearest = x.upsample(2.0, UpsampleMode::Nearest).unwrap();
+        assert_eq!(nearest.shape, [1, 1, 4, 4]);
+        assert_eq!(nearest.data[..8], [1.0, 1.0, 2.0, 2.0, 1.0, 1.0, 2.0, 2.0]);
+
+        // F.interpolate(x, scale_factor=2, mode="bilinear", align_corners=False) 的首行
+        let half = x
+            .upsample(
+                2.0,
+                UpsampleMode::Bilinear {
+                    align_corners: false,
+                },
+            )
+            .unwrap();
+        assert_eq!(half.data[..4], [1.0, 1.25, 1.75, 2.0]);
+        assert_eq!(half.data[4..8], [1.5, 1.75, 2.25, 2.5]);
+
+        let corners = x
+            .upsample(
+                1.5,
+                UpsampleMode::Bilinear {
+                    align_corners: true,
+                },
+            )
+            .unwrap();
+        assert_eq!(corners.shape, [1, 1, 3, 3]);
+        assert_eq!(
+            corners.data.to_vec(),
+            vec![1.0, 1.5, 2.0, 2.0, 2.5, 3.0, 3.0, 3.5, 4.0]
+        );
+
+        assert!(x.upsample(0.0, UpsampleMode::Nearest).is_err());
+        assert!(x.upsample(0.4, UpsampleMode::Nearest).is_err());
+    }
+}
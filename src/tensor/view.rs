@@ -0,0 +1,136 @@
+use std::ops::Index;
+
+use super::{Shape, Tensor};
+
+// 借用任意生命周期内存的只读张量视图，不需要 unsafe：借用检查保证内存在视图存活期间有效。
+// Tensor 的句柄可以 clone 到任意地方，只能借用 'static 内存（from_slice）；
+// 非 'static 的缓冲区（栈上数组、调用方持有的 mmap）用 TensorView 读取，
+// 需要参与运算时用 to_tensor 复制出自有的张量
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TensorView<'a> {
+    data: &'a [f32],
+    shape: &'a [usize],
+}
+
+impl<'a> TensorView<'a> {
+    pub fn new(data: &'a [f32], shape: &'a [usize]) -> Result<TensorView<'a>, String> {
+        let total_size: usize = shape.iter().product();
+        if data.len() != total_size {
+            return Err(format!(
+                "数据长度 {} 与形状 {:?} 不匹配（总大小：{}）",
+                data.len(),
+                shape,
+                total_size,
+            ));
+        }
+
+        return Ok(TensorView {
+            data: data,
+            shape: shape,
+        });
+    }
+
+    // 本机字节序的 f32 字节；未按 4 对齐或长度不是 4 的倍数时返回 Err，可改用 Tensor::from_bytes 复制
+    pub fn from_bytes(bytes: &'a [u8], shape: &'a [usize]) -> Result<TensorView<'a>, String> {
+        // SAFETY: 任意位模式都是合法的 f32，align_to 只返回对齐的中段，生命周期沿用 bytes
+        let (head, data, tail) = unsafe { bytes.align_to::<f32>() };
+        if !head.is_empty() || !tail.is_empty() {
+            return Err(format!(
+                "字节切片（长度 {}）未按 f32 对齐或长度不是 4 的倍数，无法零拷贝借用",
+                bytes.len()
+            ));
+        }
+
+        return TensorView::new(data, shape);
+    }
+
+    pub fn shape(&self) -> &'a [usize] {
+        return self.shape;
+    }
+
+    pub fn data(&self) -> &'a [f32] {
+        return self.data;
+    }
+
+    pub fn get(&self, indices: &[usize]) -> Result<&'a f32, String> {
+        if indices.len() != self.shape.len() {
+            return Err(format!(
+                "索引维度 {} 与张量秩 {} 不匹配",
+                indices.len(),
+                self.shape.len()
+            ));
+        }
+        let mut index = 0;
+        for (dim, &idx) in indices.iter().enumerate() {
+            if idx >= self.shape[dim] {
+                return Err(format!(
+                    "索引 {} 超出张量维度 {} 的范围（大小：{}）",
+                    idx, dim, self.shape[dim]
+                ));
+            }
+            index = index * self.shape[dim] + idx;
+        }
+
+        return Ok(&self.data[index]);
+    }
+
+    pub fn to_tensor(&self) -> Result<Tensor, String> {
+        return Tensor::new(self.data.to_vec(), Shape::new(self.shape.to_vec()));
+    }
+}
+
+impl Index<&[usize]> for TensorView<'_> {
+    type Output = f32;
+
+    fn index(&self, indices: &[usize]) -> &Self::Output {
+        return self.get(indices).unwrap();
+    }
+}
+
+impl Tensor {
+    // 主机数据的只读视图；设备张量会先取回主机副本
+    pub fn as_view(&self) -> TensorView<'_> {
+        return TensorView {
+            data: &self.data,
+            shape: &self.shape,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DType, Endianness};
+
+    #[test]
+    fn views_borrow_non_static_buffers() {
+        let buffer: Vec<f32> = (0..6).map(|i| i as f32).collect();
+        let shape = [2, 3];
+        let view = TensorView::new(&buffer, &shape).unwrap();
+        assert_eq!(view.data().as_ptr(), buffer.as_ptr());
+        assert_eq!(view.get(&[1, 2]).unwrap(), &5.0);
+        assert_eq!(view[&[0, 1][..]], 1.0);
+        assert!(view.get(&[2, 0]).is_err());
+        assert!(view.get(&[0]).is_err());
+        assert!(TensorView::new(&buffer, &[4]).is_err());
+
+        let owned = view.to_tensor().unwrap();
+        assert_eq!(owned.shape, [2, 3]);
+        assert_eq!(owned.as_view(), view);
+        assert!(!owned.storage().is_borrowed());
+
+        let words = [1.5f32, -2.0, 0.25, 8.0];
+        let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_ne_bytes()).collect();
+        let aligned: &[u8] = unsafe { std::slice::from_raw_parts(words.as_ptr().cast(), 16) };
+        let view = TensorView::from_bytes(aligned, &[2, 2]).unwrap();
+        assert_eq!(view.data(), words);
+        assert_eq!(
+            Tensor::from_bytes(&bytes, vec![2, 2], DType::F32, Endianness::native())
+                .unwrap()
+                .as_view(),
+            view
+        );
+        assert!(TensorView::from_bytes(&aligned[1..13], &[3]).is_err());
+        assert!(TensorView::from_bytes(aligned, &[3]).is_err());
+    }
+}
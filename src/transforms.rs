@@ -0,0 +1,219 @@
+use crate::random::{self, Rng};
+use crate::{Tensor, UpsampleMode, profile};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transform {
+    Resize {
+        size: (usize, usize),
+        mode: UpsampleMode,
+    },
+    CenterCrop((usize, usize)),
+    RandomCrop((usize, usize)),
+    // 以给定概率逐张水平翻转
+    HorizontalFlip(f32),
+    Normalize {
+        mean: Vec<f32>,
+        std: Vec<f32>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    steps: Vec<Transform>,
+    rng: Rng,
+}
+
+fn image_dims(batch: &Tensor) -> Result<(usize, usize, usize, usize), String> {
+    if batch.shape.len() != 4 {
+        return Err(format!(
+            "图像变换需要 [N, C, H, W] 批次，实际形状为 {:?}",
+            batch.shape
+        ));
+    }
+
+    return Ok((
+        batch.shape[0],
+        batch.shape[1],
+        batch.shape[2],
+        batch.shape[3],
+    ));
+}
+
+// offsets 为每张图像左上角的 (y, x)
+fn crop(
+    batch: &Tensor,
+    (ch, cw): (usize, usize),
+    offsets: &[(usize, usize)],
+) -> Result<Tensor, String> {
+    let (n, c, h, w) = image_dims(batch)?;
+    let _scope = profile::scope("crop", n * c * ch * cw);
+    let mut data = crate::alloc::allocate(n * c * ch * cw);
+    for (i, &(y0, x0)) in offsets.iter().enumerate() {
+        for plane in 0..c {
+            let base = (i * c + plane) * h * w;
+            for y in y0..y0 + ch {
+                data.extend_from_slice(&batch.data[base + y * w + x0..base + y * w + x0 + cw]);
+            }
+        }
+    }
+
+    return Tensor::new(data, vec![n, c, ch, cw]);
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        let seed = random::derive_seed();
+
+        return Pipeline {
+            steps: Vec::new(),
+            rng: Rng::new(seed),
+        };
+    }
+
+    pub fn then(mut self, step: Transform) -> Self {
+        self.steps.push(step);
+        return self;
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        return self;
+    }
+
+    pub fn steps(&self) -> &[Transform] {
+        return &self.steps;
+    }
+
+    pub fn apply(&mut self, batch: &Tensor) -> Result<Tensor, String> {
+        let mut out = batch.clone();
+        for step in &self.steps {
+            let (n, c, h, w) = image_dims(&out)?;
+            out = match step {
+                Transform::Resize { size, mode } => out.resize2d(*size, *mode)?,
+                Transform::CenterCrop((ch, cw)) | Transform::RandomCrop((ch, cw))
+                    if *ch > h || *cw > w || *ch == 0 || *cw == 0 =>
+                {
+                    return Err(format!("裁剪尺寸 {}x{} 超出图像尺寸 {}x{}", ch, cw, h, w));
+                }
+                Transform::CenterCrop(size) => {
+                    let offset = ((h - size.0) / 2, (w - size.1) / 2);
+                    crop(&out, *size, &vec![offset; n])?
+                }
+                Transform::RandomCrop(size) => {
+                    let offsets: Vec<(usize, usize)> = (0..n)
+                        .map(|_| {
+                            (
+                                self.rng.below(h - size.0 + 1),
+                                self.rng.below(w - size.1 + 1),
+                            )
+                        })
+                        .collect();
+                    crop(&out, *size, &offsets)?
+                }
+                Transform::HorizontalFlip(p) => {
+                    // 输入批次可能是冻结的，此时翻转到一份可写的副本上
+                    let mut flipped = if out.is_frozen() {
+                        out.deep_copy()?
+                    } else {
+                        out
+                    };
+                    for image in flipped.data_mut()?.chunks_mut(c * h * w) {
+                        if self.rng.next_f32() < *p {
+                            image.chunks_mut(w).for_each(|row| row.reverse());
+                        }
+                    }
+                    flipped
+                }
+                Transform::Normalize { mean, std } => {
+                    if mean.len() != c || std.len() != c || std.iter().any(|&s| s <= 0.0) {
+                        return Err(format!(
+                            "归一化需要 {} 个通道均值与正的标准差，实际为 {:?} 与 {:?}",
+                            c, mean, std
+                        ));
+                    }
+                    let mean = Tensor::new(mean.clone(), vec![c, 1, 1])?;
+                    let std = Tensor::new(std.clone(), vec![c, 1, 1])?;
+                    out.sub(&mean)?.div(&std)?
+                }
+            };
+        }
+
+        return Ok(out);
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        return Pipeline::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(n: usize, c: usize, h: usize, w: usize) -> Tensor {
+        let len = n * c * h * w;
+
+        return Tensor::new((0..len).map(|i| i as f32).collect(), vec![n, c, h, w]).unwrap();
+    }
+
+    #[test]
+    fn pipeline_composes_resize_crop_and_normalize() {
+        let x = batch(2, 2, 4, 4);
+        let mut pipeline = Pipeline::new()
+            .then(Transform::Resize {
+                size: (8, 8),
+                mode: UpsampleMode::Nearest,
+            })
+            .then(Transform::CenterCrop((4, 6)))
+            .then(Transform::Normalize {
+                mean: vec![0.0, 16.0],
+                std: vec![1.0, 2.0],
+            });
+        let y = pipeline.apply(&x).unwrap();
+        assert_eq!(y.shape, [2, 2, 4, 6]);
+        // 中心裁剪从放大后的 (2, 1) 开始，对应原图 (1, 0)
+        assert_eq!(y.data[..6], [4.0, 5.0, 5.0, 6.0, 6.0, 7.0]);
+        assert_eq!(y.data[24], (20.0 - 16.0) / 2.0);
+
+        let mut bad = Pipeline::new().then(Transform::CenterCrop((5, 1)));
+        assert!(bad.apply(&x).is_err());
+        let mut bad = Pipeline::new().then(Transform::Normalize {
+            mean: vec![0.0],
+            std: vec![1.0],
+        });
+        assert!(bad.apply(&x).is_err());
+    }
+
+    #[test]
+    fn random_steps_are_reproducible_per_seed() {
+        let x = batch(8, 1, 5, 5);
+        let make = || {
+            Pipeline::new()
+                .then(Transform::RandomCrop((3, 3)))
+                .then(Transform::HorizontalFlip(0.5))
+                .seed(11)
+        };
+        let (mut a, mut b) = (make(), make());
+        let ya = a.apply(&x).unwrap();
+        assert_eq!(ya, b.apply(&x).unwrap());
+        assert_eq!(ya.shape, [8, 1, 3, 3]);
+
+        // 每行仍是原图某行中连续的 3 个元素（可能被翻转）
+        for row in ya.data.chunks(3) {
+            let lo = row.iter().cloned().fold(f32::INFINITY, f32::min);
+            assert_eq!(row.iter().sum::<f32>(), 3.0 * lo + 3.0);
+        }
+
+        let mut always = Pipeline::new().then(Transform::HorizontalFlip(1.0));
+        let flipped = always.apply(&x).unwrap();
+        assert_eq!(flipped.data[..5], [4.0, 3.0, 2.0, 1.0, 0.0]);
+
+        // 冻结的输入批次不被改写，也不会 panic
+        let mut frozen = x.clone();
+        frozen.freeze();
+        assert_eq!(always.apply(&frozen).unwrap(), flipped);
+        assert_eq!(frozen, x);
+    }
+}